@@ -3,17 +3,62 @@ use std::sync::Arc;
 use eyre::Result;
 use twilight_model::gateway::payload::incoming::ChannelDelete;
 
-use crate::structs::context::Context;
+use crate::{structs::context::Context, utilities::audit::audit};
 
 pub async fn run(context: Arc<Context>, payload: ChannelDelete) -> Result<()> {
     let channel_id = payload.0.id;
+    // A command that deleted the channel has audited it already.
+    let is_audited = context.cache.remove_deleted_channel(channel_id);
 
-    if context.cache.join_channel(channel_id).is_some() {
+    if let Some(join_channel) = context.cache.join_channel(channel_id) {
         context.database.remove_join_channel(channel_id).await?;
         context.cache.remove_join_channel(channel_id);
-    } else if context.cache.voice_channel(channel_id).is_some() {
+
+        if let Some(guild) = context
+            .cache
+            .guild(join_channel.guild_id)
+            .filter(|_| !is_audited)
+        {
+            audit(
+                &context,
+                &guild,
+                None,
+                Some(channel_id),
+                "join channel deleted",
+                format!("**{}** has been deleted.", join_channel.name.read()),
+            )
+            .await?;
+        }
+    } else if let Some(voice_channel) = context.cache.voice_channel(channel_id) {
         context.database.remove_voice_channel(channel_id).await?;
         context.cache.remove_voice_channel(channel_id);
+
+        let waiting_room_id = *voice_channel.waiting_room_id.read();
+
+        // A waiting room goes with its voice channel.
+        if let Some(waiting_room_id) = waiting_room_id {
+            _ = context.client.delete_channel(waiting_room_id).await;
+        }
+        if let Some(guild) = context
+            .cache
+            .guild(voice_channel.guild_id)
+            .filter(|_| !is_audited)
+        {
+            audit(
+                &context,
+                &guild,
+                None,
+                Some(channel_id),
+                "voice channel deleted",
+                format!("**{}** has been deleted.", voice_channel.name.read()),
+            )
+            .await?;
+        }
+    } else if let Some(voice_channel_id) = context.cache.remove_waiting_room(channel_id) {
+        context
+            .database
+            .update_voice_channel_waiting_room_id(voice_channel_id, None)
+            .await?;
     }
 
     Ok(())
@@ -1,15 +1,36 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::SystemTime};
 
 use eyre::Result;
 use twilight_model::gateway::payload::incoming::ChannelUpdate;
 
-use crate::structs::{
-    cache::{CachedJoinChannelUpdate, CachedVoiceChannelUpdate},
-    context::Context,
+use crate::{
+    interactions::commands::voice::{name::rename_retry_text, privacy::set_privacy},
+    structs::{
+        cache::{
+            CachedGuild, CachedJoinChannelUpdate, CachedVoiceChannel, CachedVoiceChannelUpdate,
+        },
+        context::Context,
+        database::{ChannelPrivacy, ExternalEditPolicy, VoiceChannelKind, VoiceChannelSettings},
+    },
+    utilities::{
+        audit::audit, constants::VOICE_REGION_OPTIONS, name_filter::name_violation,
+        permission_overwrites::overwrite_privacy, text::list_text,
+    },
 };
 
-pub fn run(context: Arc<Context>, payload: ChannelUpdate) -> Result<()> {
+/// A setting Voz saves for a voice channel, compared against each update to spot edits made
+/// outside of Voz.
+#[derive(Clone, Copy, Eq, PartialEq)]
+enum Setting {
+    Bitrate,
+    Name,
+    RtcRegion,
+    UserLimit,
+}
+
+pub async fn run(context: Arc<Context>, payload: ChannelUpdate) -> Result<()> {
     let channel_id = payload.0.id;
+    let edited_settings = VoiceChannelSettings::from(&payload.0);
     let name = payload.0.name;
     let permission_overwrites = payload.0.permission_overwrites.unwrap_or_default();
 
@@ -22,7 +43,23 @@ pub fn run(context: Arc<Context>, payload: ChannelUpdate) -> Result<()> {
                 ..Default::default()
             },
         );
-    } else if context.cache.voice_channel(channel_id).is_some() {
+    } else if let Some(voice_channel) = context.cache.voice_channel(channel_id) {
+        let previous_privacy = overwrite_privacy(
+            voice_channel.guild_id,
+            &voice_channel.permission_overwrites.read(),
+        );
+        let edited_privacy = overwrite_privacy(voice_channel.guild_id, &permission_overwrites);
+        let previous_settings = VoiceChannelSettings {
+            name: voice_channel.name.read().clone(),
+            bitrate: *voice_channel.bitrate.read(),
+            user_limit: voice_channel
+                .user_limit
+                .read()
+                .filter(|user_limit| user_limit.ne(&0)),
+            rtc_region: voice_channel.rtc_region.read().clone(),
+        };
+        let saved_settings = voice_channel.saved_settings.read().clone();
+
         context.cache.update_voice_channel(
             channel_id,
             CachedVoiceChannelUpdate {
@@ -36,7 +73,379 @@ pub fn run(context: Arc<Context>, payload: ChannelUpdate) -> Result<()> {
                 ..Default::default()
             },
         );
+
+        // Voz's own edits update the privacy level before they're sent, so overwrites that
+        // describe another one were edited by hand. Only the edit that changed them is handled,
+        // rather than every update after it.
+        if edited_privacy.ne(&previous_privacy) && voice_channel.privacy.read().ne(&edited_privacy)
+        {
+            if let Some(guild) = context.cache.guild(voice_channel.guild_id) {
+                handle_external_edit(&context, &guild, &voice_channel, edited_privacy).await?;
+            }
+        }
+
+        // The same goes for the saved settings, which Voz's own edits also update first.
+        if let Some(saved_settings) = saved_settings {
+            let edited = [
+                Setting::Name,
+                Setting::Bitrate,
+                Setting::UserLimit,
+                Setting::RtcRegion,
+            ]
+            .into_iter()
+            .filter(|setting| {
+                !is_same_setting(*setting, &edited_settings, &previous_settings)
+                    && !is_same_setting(*setting, &edited_settings, &saved_settings)
+            })
+            .collect::<Vec<Setting>>();
+
+            if !edited.is_empty() {
+                if let Some(guild) = context.cache.guild(voice_channel.guild_id) {
+                    handle_external_settings_edit(
+                        &context,
+                        &guild,
+                        &voice_channel,
+                        &edited,
+                        edited_settings,
+                        saved_settings,
+                    )
+                    .await?;
+                }
+            }
+        }
     }
 
     Ok(())
 }
+
+/// Applies the server's external edit policy to a voice channel whose overwrites now describe
+/// `edited_privacy` instead of its saved privacy level.
+async fn handle_external_edit(
+    context: &Context,
+    guild: &CachedGuild,
+    voice_channel: &CachedVoiceChannel,
+    edited_privacy: ChannelPrivacy,
+) -> Result<()> {
+    let privacy = voice_channel.privacy.read().clone();
+    let edited_privacy_text = privacy_text(&edited_privacy);
+    let privacy_text = privacy_text(&privacy);
+    // A stage channel's owner can edit its overwrites themselves, so its privacy level is always
+    // kept to the one set through Voz.
+    let external_edit_policy = if voice_channel.kind.eq(&VoiceChannelKind::Stage) {
+        ExternalEditPolicy::Revert
+    } else {
+        *guild.external_edit_policy.read()
+    };
+    let description = match external_edit_policy {
+        ExternalEditPolicy::Accept => {
+            context
+                .database
+                .update_voice_channel_privacy(voice_channel.id, edited_privacy.clone())
+                .await?;
+            context.cache.update_voice_channel(
+                voice_channel.id,
+                CachedVoiceChannelUpdate {
+                    privacy: Some(edited_privacy),
+                    ..Default::default()
+                },
+            );
+
+            return Ok(());
+        }
+        ExternalEditPolicy::Audit => format!(
+            "<#{}> was edited outside of Voz, so it's **{edited_privacy_text}** rather than **{privacy_text}**.",
+            voice_channel.id
+        ),
+        ExternalEditPolicy::Revert => {
+            if set_privacy(context, guild, voice_channel, privacy).await? {
+                format!(
+                    "<#{}> was edited outside of Voz to be **{edited_privacy_text}**, so I made it **{privacy_text}** again.",
+                    voice_channel.id
+                )
+            } else {
+                format!(
+                    "<#{}> was edited outside of Voz to be **{edited_privacy_text}**, and I'm unable to make it **{privacy_text}** again.",
+                    voice_channel.id
+                )
+            }
+        }
+    };
+
+    audit(
+        context,
+        guild,
+        None,
+        Some(voice_channel.id),
+        "external edit",
+        description,
+    )
+    .await
+}
+
+/// Applies the server's external edit policy to a voice channel whose `edited` settings no longer
+/// match the ones saved for it.
+async fn handle_external_settings_edit(
+    context: &Context,
+    guild: &CachedGuild,
+    voice_channel: &CachedVoiceChannel,
+    edited: &[Setting],
+    edited_settings: VoiceChannelSettings,
+    saved_settings: VoiceChannelSettings,
+) -> Result<()> {
+    let now = SystemTime::now();
+
+    // Discord's rename rate limit counts renames made outside of Voz too.
+    if edited.contains(&Setting::Name) {
+        context.cache.insert_rename_time(voice_channel.id, now);
+    }
+
+    let name_violation_reason = edited
+        .contains(&Setting::Name)
+        .then(|| name_violation(guild, &edited_settings.name))
+        .flatten();
+    let external_edit_policy = *guild.external_edit_policy.read();
+    let description = match external_edit_policy {
+        ExternalEditPolicy::Accept => {
+            // A name the name filter rejects is never accepted, whoever chose it.
+            let mut accepted_settings = saved_settings.clone();
+
+            for setting in edited {
+                match setting {
+                    Setting::Bitrate => accepted_settings.bitrate = edited_settings.bitrate,
+                    Setting::Name if name_violation_reason.is_none() => {
+                        accepted_settings.name = edited_settings.name.clone();
+                    }
+                    Setting::Name => {}
+                    Setting::RtcRegion => {
+                        accepted_settings.rtc_region = edited_settings.rtc_region.clone();
+                    }
+                    Setting::UserLimit => accepted_settings.user_limit = edited_settings.user_limit,
+                }
+            }
+
+            context
+                .database
+                .update_voice_channel_settings(voice_channel.id, &accepted_settings)
+                .await?;
+            context.cache.update_voice_channel(
+                voice_channel.id,
+                CachedVoiceChannelUpdate {
+                    saved_settings: Some(Some(accepted_settings)),
+                    ..Default::default()
+                },
+            );
+
+            let Some(name_violation_reason) = name_violation_reason else {
+                return Ok(());
+            };
+
+            format!(
+                "<#{}> was renamed outside of Voz to **{}**, which {name_violation_reason}. {}.",
+                voice_channel.id,
+                edited_settings.name,
+                change_name_back(context, voice_channel, &saved_settings.name, now).await
+            )
+        }
+        ExternalEditPolicy::Audit => {
+            let description = format!(
+                "<#{}> was edited outside of Voz, so {}.",
+                voice_channel.id,
+                list_text(edited.iter().map(|setting| {
+                    format!(
+                        "its {} is **{}** rather than **{}**",
+                        setting_label(*setting),
+                        setting_value(*setting, &edited_settings),
+                        setting_value(*setting, &saved_settings)
+                    )
+                }))
+            );
+
+            // A stage channel's owner can rename it themselves, so a name the name filter
+            // rejects doesn't stay up for the audit alone.
+            match name_violation_reason.filter(|_| voice_channel.kind.eq(&VoiceChannelKind::Stage))
+            {
+                Some(name_violation_reason) => format!(
+                    "{description} Its name {name_violation_reason}. {}.",
+                    change_name_back(context, voice_channel, &saved_settings.name, now).await
+                ),
+                None => description,
+            }
+        }
+        ExternalEditPolicy::Revert => {
+            // A name changed back too soon would go over Discord's rename rate limit, so it's
+            // queued instead.
+            let is_name_queued = context
+                .cache
+                .rename_retry_after(voice_channel.id, now)
+                .is_some();
+            let reverting = edited
+                .iter()
+                .copied()
+                .filter(|setting| setting.ne(&Setting::Name) || !is_name_queued)
+                .collect::<Vec<Setting>>();
+            let mut update_channel_request = context.client.update_channel(voice_channel.id);
+
+            for setting in &reverting {
+                update_channel_request = match setting {
+                    Setting::Bitrate => update_channel_request.bitrate(saved_settings.bitrate),
+                    Setting::Name => update_channel_request.name(&saved_settings.name),
+                    Setting::RtcRegion => {
+                        update_channel_request.rtc_region(saved_settings.rtc_region.as_deref())
+                    }
+                    Setting::UserLimit => update_channel_request
+                        .user_limit(saved_settings.user_limit.unwrap_or_default() as u16),
+                };
+            }
+
+            let (reverted, unchanged) =
+                if reverting.is_empty() || update_channel_request.await.is_ok() {
+                    (reverting, Vec::new())
+                } else {
+                    (Vec::new(), reverting)
+                };
+
+            if reverted.contains(&Setting::Name) {
+                context.cache.insert_rename_time(voice_channel.id, now);
+            }
+
+            let queued_text = if edited.contains(&Setting::Name) && is_name_queued {
+                Some(change_name_back(context, voice_channel, &saved_settings.name, now).await)
+            } else {
+                None
+            };
+            let changed_back_text = |settings: &[Setting]| {
+                list_text(settings.iter().map(|setting| {
+                    format!(
+                        "its {} back to **{}**",
+                        setting_label(*setting),
+                        setting_value(*setting, &saved_settings)
+                    )
+                }))
+            };
+
+            match (unchanged.is_empty(), queued_text) {
+                (true, None) => format!(
+                    "<#{}> was edited outside of Voz, so I changed {}.",
+                    voice_channel.id,
+                    changed_back_text(&reverted)
+                ),
+                (true, Some(queued_text)) if reverted.is_empty() => format!(
+                    "<#{}> was edited outside of Voz, so {queued_text}.",
+                    voice_channel.id
+                ),
+                (true, Some(queued_text)) => format!(
+                    "<#{}> was edited outside of Voz, so I changed {} and {queued_text}.",
+                    voice_channel.id,
+                    changed_back_text(&reverted)
+                ),
+                (false, None) => format!(
+                    "<#{}> was edited outside of Voz, and I'm unable to change {}.",
+                    voice_channel.id,
+                    changed_back_text(&unchanged)
+                ),
+                (false, Some(queued_text)) => format!(
+                    "<#{}> was edited outside of Voz, and I'm unable to change {}, but {queued_text}.",
+                    voice_channel.id,
+                    changed_back_text(&unchanged)
+                ),
+            }
+        }
+    };
+
+    audit(
+        context,
+        guild,
+        None,
+        Some(voice_channel.id),
+        "external edit",
+        description,
+    )
+    .await
+}
+
+/// Changes the voice channel's name back to `name`, or queues it while Discord won't allow
+/// another rename, returning what was done.
+async fn change_name_back(
+    context: &Context,
+    voice_channel: &CachedVoiceChannel,
+    name: &str,
+    now: SystemTime,
+) -> String {
+    if let Some(retry_after) = context.cache.rename_retry_after(voice_channel.id, now) {
+        context
+            .cache
+            .insert_queued_name(voice_channel.id, None, name.to_owned());
+
+        return format!(
+            "I'll change its name back to **{name}** in **{}**",
+            rename_retry_text(retry_after)
+        );
+    }
+
+    if context
+        .client
+        .update_channel(voice_channel.id)
+        .name(name)
+        .await
+        .is_err()
+    {
+        return format!("I'm unable to change its name back to **{name}**");
+    }
+
+    context.cache.insert_rename_time(voice_channel.id, now);
+
+    format!("I changed its name back to **{name}**")
+}
+
+fn is_same_setting(
+    setting: Setting,
+    settings: &VoiceChannelSettings,
+    other_settings: &VoiceChannelSettings,
+) -> bool {
+    match setting {
+        Setting::Bitrate => settings.bitrate.eq(&other_settings.bitrate),
+        Setting::Name => settings.name.eq(&other_settings.name),
+        Setting::RtcRegion => settings.rtc_region.eq(&other_settings.rtc_region),
+        Setting::UserLimit => settings.user_limit.eq(&other_settings.user_limit),
+    }
+}
+
+fn privacy_text(privacy: &ChannelPrivacy) -> &'static str {
+    match privacy {
+        ChannelPrivacy::Invisible => "invisible",
+        ChannelPrivacy::Locked => "locked (and visible)",
+        ChannelPrivacy::Unlocked => "unlocked (and visible)",
+    }
+}
+
+fn setting_label(setting: Setting) -> &'static str {
+    match setting {
+        Setting::Bitrate => "bitrate",
+        Setting::Name => "name",
+        Setting::RtcRegion => "voice region",
+        Setting::UserLimit => "user limit",
+    }
+}
+
+/// A setting as it's shown in audit events.
+fn setting_value(setting: Setting, settings: &VoiceChannelSettings) -> String {
+    match setting {
+        Setting::Bitrate => format!("{}kbps", settings.bitrate / 1_000),
+        Setting::Name => settings.name.clone(),
+        Setting::RtcRegion => settings.rtc_region.as_deref().map_or_else(
+            || "Automatic".to_owned(),
+            |rtc_region| {
+                VOICE_REGION_OPTIONS
+                    .into_iter()
+                    .find(|(_, value)| value.eq(&rtc_region))
+                    .map_or(rtc_region, |(rtc_region_text, _)| rtc_region_text)
+                    .to_owned()
+            },
+        ),
+        Setting::UserLimit => match settings.user_limit {
+            None => "no limit".to_owned(),
+            Some(1) => "1 user".to_owned(),
+            Some(user_limit) => format!("{user_limit} users"),
+        },
+    }
+}
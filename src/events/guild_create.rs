@@ -1,15 +1,33 @@
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+    time::SystemTime,
+};
 
 use eyre::Result;
 use twilight_model::{
     channel::{ChannelType, VideoQualityMode},
     gateway::payload::incoming::GuildCreate,
-    id::{marker::ChannelMarker, Id},
+    guild::Member,
+    id::{
+        marker::{ChannelMarker, UserMarker},
+        Id,
+    },
 };
 
-use crate::structs::context::Context;
+use super::voice_state_update::create_voice_channel;
+use crate::{
+    structs::{
+        cache::CachedGuildUpdate,
+        context::Context,
+        database::{NameFilterKind, VoiceChannelMember, VoiceChannelSettings},
+    },
+    utilities::name_filter::compile_pattern,
+};
 
 pub async fn run(context: Arc<Context>, payload: GuildCreate) -> Result<()> {
+    // The payload was sent before this point, so no channel in it is any newer.
+    let snapshot_time = SystemTime::now();
     let guild_id = payload.0.id;
     let Some(bot_role_id) = payload.0.roles.into_iter().find_map(|role| {
         if !role.managed {
@@ -22,7 +40,7 @@ pub async fn run(context: Arc<Context>, payload: GuildCreate) -> Result<()> {
 
         tags.bot_id
             .is_some_and(|bot_id| bot_id.eq(&context.application_id.cast()))
-            .then(|| role.id)
+            .then_some(role.id)
     }) else {
         context.client.leave_guild(guild_id).await?;
 
@@ -33,13 +51,57 @@ pub async fn run(context: Arc<Context>, payload: GuildCreate) -> Result<()> {
         .cache
         .insert_guild(bot_role_id, guild_id, payload.0.name);
 
+    let guild_settings = context.database.guild_settings(guild_id).await?;
+    let blocked_role_ids = context.database.guild_blocked_roles(guild_id).await?;
+    let blocked_user_ids = context.database.guild_blocked_members(guild_id).await?;
+    let mut name_filter_patterns = Vec::new();
+    let mut name_filter_words = HashSet::new();
+
+    for name_filter in context.database.guild_name_filters(guild_id).await? {
+        match name_filter.kind {
+            // Patterns are checked before they're saved, so one that no longer compiles is skipped
+            // rather than keeping the whole server from loading.
+            NameFilterKind::Pattern => {
+                if let Ok(pattern) = compile_pattern(&name_filter.value) {
+                    name_filter_patterns.push(pattern);
+                }
+            }
+            NameFilterKind::Word => {
+                name_filter_words.insert(name_filter.value);
+            }
+        }
+    }
+
+    context.cache.update_guild(
+        guild_id,
+        CachedGuildUpdate {
+            blocked_dms_enabled: Some(guild_settings.blocked_dms_enabled),
+            blocked_role_ids: Some(blocked_role_ids.into_iter().collect()),
+            blocked_user_ids: Some(blocked_user_ids.into_iter().collect()),
+            creation_cooldown: Some(guild_settings.creation_cooldown),
+            external_edit_policy: Some(guild_settings.external_edit_policy),
+            locale: Some(guild_settings.locale),
+            log_channel_id: Some(guild_settings.log_channel_id),
+            max_voice_channels: Some(guild_settings.max_voice_channels),
+            name_filter_patterns: Some(name_filter_patterns),
+            name_filter_words: Some(name_filter_words),
+            name_template: Some(guild_settings.name_template),
+            presets_enabled: Some(guild_settings.presets_enabled),
+            printable_names_only: Some(guild_settings.printable_names_only),
+            ..Default::default()
+        },
+    );
+
     let filtered_guild_channels =
         payload
             .0
             .channels
             .into_iter()
             .fold(HashMap::new(), |mut acc, channel| {
-                if channel.kind.eq(&ChannelType::GuildVoice) {
+                if matches!(
+                    channel.kind,
+                    ChannelType::GuildStageVoice | ChannelType::GuildVoice
+                ) {
                     acc.insert(
                         channel.id,
                         (
@@ -63,7 +125,7 @@ pub async fn run(context: Arc<Context>, payload: GuildCreate) -> Result<()> {
 
     context
         .database
-        .remove_unknown_channels(filtered_guild_channel_ids, guild_id)
+        .remove_unknown_channels(filtered_guild_channel_ids, guild_id, snapshot_time)
         .await?;
 
     for join_channel in context.database.guild_join_channels(guild_id).await? {
@@ -78,14 +140,21 @@ pub async fn run(context: Arc<Context>, payload: GuildCreate) -> Result<()> {
             };
 
         context.cache.insert_join_channel(
-            join_channel.access_role_id,
+            join_channel.allowed_role_ids.into_iter().collect(),
+            join_channel.denied_role_ids.into_iter().collect(),
             join_channel.id,
             join_channel.guild_id,
+            join_channel.kind,
             name,
+            join_channel.ownership_delay,
+            join_channel.ownership_policy,
             join_channel.parent_id,
             join_channel.permanence,
             permission_overwrites,
             join_channel.privacy,
+            join_channel.status_template,
+            join_channel.voice_channel_limit,
+            join_channel.waiting_room,
         );
     }
     for voice_channel in context.database.guild_voice_channels(guild_id).await? {
@@ -111,32 +180,146 @@ pub async fn run(context: Arc<Context>, payload: GuildCreate) -> Result<()> {
             )
         };
 
+        // Voice channels saved before their settings were take on whatever Discord has now.
+        let saved_settings = match voice_channel.settings {
+            None if filtered_guild_channels.contains_key(&voice_channel.id) => {
+                let saved_settings = VoiceChannelSettings {
+                    name: name.clone(),
+                    bitrate,
+                    user_limit: user_limit.filter(|user_limit| user_limit.ne(&0)),
+                    rtc_region: rtc_region.clone(),
+                };
+
+                context
+                    .database
+                    .update_voice_channel_settings(voice_channel.id, &saved_settings)
+                    .await?;
+
+                Some(saved_settings)
+            }
+            saved_settings => saved_settings,
+        };
+
         context.cache.insert_voice_channel(
             bitrate,
             voice_channel.id,
             voice_channel.guild_id,
+            voice_channel.join_channel_id,
+            voice_channel.kind,
             name,
             voice_channel.owner_id,
+            voice_channel.ownership_delay,
+            voice_channel.ownership_policy,
             voice_channel.permanence,
             permission_overwrites,
             voice_channel.privacy,
             rate_limit_per_user,
             rtc_region,
+            saved_settings,
+            voice_channel.status,
             user_limit,
             video_quality_mode,
         );
+
+        if let Some(waiting_room_id) = voice_channel.waiting_room_id {
+            context
+                .cache
+                .insert_waiting_room(voice_channel.id, waiting_room_id);
+        }
+    }
+    for voice_channel_manager in context
+        .database
+        .guild_voice_channel_managers(guild_id)
+        .await?
+    {
+        context.cache.insert_voice_channel_manager(
+            voice_channel_manager.channel_id,
+            voice_channel_manager.user_id,
+        );
     }
+
+    let members = payload
+        .0
+        .members
+        .into_iter()
+        .map(|member| (member.user.id, member))
+        .collect::<HashMap<Id<UserMarker>, Member>>();
+    let Some(guild) = context.cache.guild(guild_id) else {
+        return Ok(());
+    };
+
     for voice_state in payload.0.voice_states {
         let Some(channel_id) = voice_state.channel_id else {
             continue;
         };
+        let Some(member) = voice_state
+            .member
+            .or_else(|| members.get(&voice_state.user_id).cloned())
+        else {
+            continue;
+        };
 
-        if context.cache.voice_channel(channel_id).is_some() {
-            context
-                .cache
-                .insert_voice_state(guild_id, voice_state.user_id, channel_id);
+        if member.user.bot {
+            continue;
+        }
+
+        context.cache.insert_member_name(
+            guild_id,
+            voice_state.user_id,
+            member
+                .nick
+                .clone()
+                .or_else(|| member.user.global_name.clone())
+                .unwrap_or_else(|| member.user.name.clone()),
+        );
+        context
+            .cache
+            .insert_voice_state(guild_id, voice_state.user_id, channel_id);
+
+        // Members who connected to a join channel while I was offline are still waiting
+        // for their own voice channel.
+        if let Some(join_channel) = context.cache.join_channel(channel_id) {
+            create_voice_channel(&context, &guild, &join_channel, &member).await?;
         }
     }
 
+    // Join times survive restarts, but only for members who are still connected.
+    for voice_channel_member in context
+        .database
+        .guild_voice_channel_members(guild_id)
+        .await?
+    {
+        context.cache.insert_join_time(
+            voice_channel_member.channel_id,
+            voice_channel_member.user_id,
+            voice_channel_member.joined_at,
+        );
+    }
+
+    let voice_channel_members = guild
+        .voice_channel_ids
+        .read()
+        .clone()
+        .into_iter()
+        .filter_map(|channel_id| context.cache.voice_channel(channel_id))
+        .flat_map(|voice_channel| {
+            voice_channel
+                .join_times
+                .read()
+                .iter()
+                .map(|(user_id, joined_at)| VoiceChannelMember {
+                    channel_id: voice_channel.id,
+                    user_id: *user_id,
+                    joined_at: *joined_at,
+                })
+                .collect::<Vec<VoiceChannelMember>>()
+        })
+        .collect::<Vec<VoiceChannelMember>>();
+
+    context
+        .database
+        .replace_guild_voice_channel_members(guild_id, voice_channel_members)
+        .await?;
+
     Ok(())
 }
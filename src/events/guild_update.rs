@@ -16,6 +16,7 @@ pub fn run(context: Arc<Context>, payload: GuildUpdate) -> Result<()> {
             payload.0.id,
             CachedGuildUpdate {
                 name: Some(payload.0.name),
+                ..Default::default()
             },
         );
     }
@@ -10,8 +10,11 @@ use twilight_model::{
 use twilight_util::builder::{embed::EmbedBuilder, InteractionResponseDataBuilder};
 
 use crate::{
-    interactions::commands::*,
-    structs::{context::Context, interaction::ApplicationCommandInteraction},
+    interactions::{commands::*, components::*},
+    structs::{
+        context::Context,
+        interaction::{ApplicationCommandInteraction, MessageComponentInteraction},
+    },
 };
 
 pub async fn run(context: Arc<Context>, payload: InteractionCreate) -> Result<()> {
@@ -23,7 +26,7 @@ pub async fn run(context: Arc<Context>, payload: InteractionCreate) -> Result<()
             .and_then(|guild_id| context.cache.guild(guild_id)),
         interaction
             .member
-            .and_then(|member| member.user.and_then(|user| Some(user.id))),
+            .and_then(|member| member.user.map(|user| user.id)),
     ) else {
         let embed = EmbedBuilder::new()
             .color(0xF8F8FF)
@@ -59,6 +62,18 @@ pub async fn run(context: Arc<Context>, payload: InteractionCreate) -> Result<()
 
             handle_application_command(context, interaction).await?;
         }
+        Some(InteractionData::MessageComponent(data)) => {
+            let interaction = MessageComponentInteraction {
+                channel,
+                data,
+                guild,
+                id: interaction.id,
+                token: interaction.token,
+                user_id,
+            };
+
+            handle_message_component(context, interaction).await?;
+        }
         _ => {
             let embed = EmbedBuilder::new()
                 .color(0xF8F8FF)
@@ -91,7 +106,9 @@ async fn handle_application_command(
 
     match command_name {
         "join" => join::run(context, interaction).await?,
+        "settings" => settings::run(context, interaction).await?,
         "voice" => voice::run(context, interaction).await?,
+        "voice-admin" => voice_admin::run(context, interaction).await?,
         _ => {
             let embed = EmbedBuilder::new()
                 .color(0xF8F8FF)
@@ -117,3 +134,44 @@ async fn handle_application_command(
 
     Ok(())
 }
+
+/// Routes a button press by the prefix of its custom id, such as "knock" in "knock:accept:1".
+async fn handle_message_component(
+    context: Arc<Context>,
+    interaction: MessageComponentInteraction,
+) -> Result<()> {
+    let component_name = interaction
+        .data
+        .custom_id
+        .split(':')
+        .next()
+        .unwrap_or_default()
+        .to_owned();
+
+    match component_name.as_str() {
+        "audit" => audit::run(context, interaction).await?,
+        "knock" => knock::run(context, interaction).await?,
+        "members" => members::run(context, interaction).await?,
+        _ => {
+            let embed = EmbedBuilder::new()
+                .color(0xF8F8FF)
+                .description("I don't recognize this interaction.")
+                .build();
+            let interaction_response_data = InteractionResponseDataBuilder::new()
+                .embeds(vec![embed])
+                .flags(MessageFlags::EPHEMERAL)
+                .build();
+            let interaction_response = InteractionResponse {
+                data: Some(interaction_response_data),
+                kind: InteractionResponseType::ChannelMessageWithSource,
+            };
+
+            context
+                .interaction_client()
+                .create_response(interaction.id, &interaction.token, &interaction_response)
+                .await?;
+        }
+    }
+
+    Ok(())
+}
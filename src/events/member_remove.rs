@@ -10,22 +10,22 @@ pub async fn run(context: Arc<Context>, payload: MemberRemove) -> Result<()> {
         guild_id,
         user: User { id: user_id, .. },
     } = payload;
-    let Some(channel_id) = context.cache.voice_channel_owner(guild_id, user_id) else {
-        return Ok(());
-    };
-    let channel_id = *channel_id;
 
-    context
-        .database
-        .update_voice_channel_owner_id(channel_id, None)
-        .await?;
-    context.cache.update_voice_channel(
-        channel_id,
-        CachedVoiceChannelUpdate {
-            owner_id: Some(None),
-            ..Default::default()
-        },
-    );
+    context.cache.remove_member(guild_id, user_id);
+
+    for channel_id in context.cache.owned_voice_channels(guild_id, user_id) {
+        context
+            .database
+            .update_voice_channel_owner_id(channel_id, None)
+            .await?;
+        context.cache.update_voice_channel(
+            channel_id,
+            CachedVoiceChannelUpdate {
+                owner_id: Some(None),
+                ..Default::default()
+            },
+        );
+    }
 
     Ok(())
 }
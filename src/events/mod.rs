@@ -20,7 +20,7 @@ use crate::structs::context::Context;
 pub async fn handle_event(context: Arc<Context>, event: Event) -> Result<()> {
     match event {
         Event::ChannelDelete(payload) => channel_delete::run(context, *payload).await,
-        Event::ChannelUpdate(payload) => channel_update::run(context, *payload),
+        Event::ChannelUpdate(payload) => channel_update::run(context, *payload).await,
         Event::GuildCreate(payload) => guild_create::run(context, *payload).await,
         Event::GuildDelete(payload) => guild_delete::run(context, payload).await,
         Event::GuildUpdate(payload) => guild_update::run(context, *payload),
@@ -33,3 +33,649 @@ pub async fn handle_event(context: Arc<Context>, event: Event) -> Result<()> {
         _ => Ok(()),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::Arc,
+        time::{Duration, SystemTime},
+    };
+
+    use serde_json::{json, Value};
+    use twilight_model::id::{marker::UserMarker, Id};
+
+    use crate::{
+        structs::database::{ChannelPrivacy, VoiceChannelKind},
+        tasks::renames::apply_queued_names,
+        testing::{
+            self,
+            mock_discord::MockDiscord,
+            payloads::{channel_delete, channel_update, command, subcommand},
+            replay::replay,
+            seed_join_channel, VOICE_CHANNEL_ID,
+        },
+    };
+
+    /// A `CHANNEL_UPDATE` for the voice channel as the mock has it, with the `@everyone` overwrite
+    /// edited by hand to deny `deny`.
+    fn edit_everyone_overwrite(mock_discord: &MockDiscord, deny: &str) -> String {
+        let mut voice_channel = mock_discord.channel(5000).unwrap();
+
+        for permission_overwrite in voice_channel["permission_overwrites"]
+            .as_array_mut()
+            .unwrap()
+        {
+            if permission_overwrite["id"].eq("1001") {
+                permission_overwrite["deny"] = Value::from(deny);
+            }
+        }
+
+        channel_update(voice_channel)
+    }
+
+    fn external_edits_command(token: &str, policy: &str) -> String {
+        command(
+            token,
+            1001,
+            1,
+            subcommand(
+                "settings",
+                "set",
+                json!([{ "name": "external-edits", "type": 3, "value": policy }]),
+            ),
+        )
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a database, run with `cargo test -- --ignored`"]
+    async fn joining_creates_and_moves_into_a_voice_channel() {
+        let mock_discord = MockDiscord::start().await;
+        let (context, _guard) = testing::context(&mock_discord).await;
+        let guild_id = Id::new(1001);
+        let user_id = Id::<UserMarker>::new(2001);
+
+        seed_join_channel(&context, guild_id, Id::new(1101), ChannelPrivacy::Unlocked).await;
+        replay(&context, include_str!("../testing/recordings/create.jsonl"))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            mock_discord
+                .requests_to("POST", "/guilds/1001/channels")
+                .len(),
+            1
+        );
+        assert_eq!(
+            mock_discord
+                .requests_to("PATCH", "/guilds/1001/members/2001")
+                .len(),
+            1
+        );
+        assert_eq!(
+            mock_discord
+                .assert_requested("POST", "/guilds/1001/channels")
+                .body["name"],
+            "alex's voice"
+        );
+        assert_eq!(
+            mock_discord
+                .assert_requested("PATCH", "/guilds/1001/members/2001")
+                .body["channel_id"],
+            "5000"
+        );
+
+        let voice_channel = context.cache.voice_channel(VOICE_CHANNEL_ID).unwrap();
+
+        assert_eq!(*voice_channel.owner_id.read(), Some(user_id));
+        assert_eq!(voice_channel.name.read().as_str(), "alex's voice");
+        assert!(voice_channel.connected_user_ids.read().contains(&user_id));
+        assert_eq!(
+            context.cache.voice_channel_owner(guild_id, user_id),
+            Some(Arc::new(VOICE_CHANNEL_ID))
+        );
+        assert_eq!(
+            context
+                .database
+                .guild_voice_channels(guild_id)
+                .await
+                .unwrap()[0]
+                .owner_id,
+            Some(user_id)
+        );
+
+        context.database.remove_guild(guild_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a database, run with `cargo test -- --ignored`"]
+    async fn members_are_told_when_no_voice_channel_can_be_created() {
+        let mock_discord = MockDiscord::start().await;
+        let (context, _guard) = testing::context(&mock_discord).await;
+        let guild_id = Id::new(1001);
+
+        seed_join_channel(&context, guild_id, Id::new(1101), ChannelPrivacy::Unlocked).await;
+        mock_discord.fail("POST", "/guilds/1001/channels");
+        replay(&context, include_str!("../testing/recordings/create.jsonl"))
+            .await
+            .unwrap();
+
+        let recipient_id = mock_discord
+            .assert_requested("POST", "/users/@me/channels")
+            .body["recipient_id"]
+            .clone();
+
+        assert_eq!(recipient_id, "2001");
+        assert!(mock_discord
+            .assert_requested("PATCH", "/guilds/1001/members/2001")
+            .body["channel_id"]
+            .is_null());
+        assert_eq!(
+            mock_discord.requests_to("POST", "/channels/5000/messages")[0].body["embeds"][0]
+                ["description"],
+            "I'm unable to create a voice channel in **guild** right now. Try again in 10 minutes."
+        );
+        assert!(context.cache.voice_channel(VOICE_CHANNEL_ID).is_none());
+
+        context.database.remove_guild(guild_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a database, run with `cargo test -- --ignored`"]
+    async fn leaving_deletes_the_empty_voice_channel() {
+        let mock_discord = MockDiscord::start().await;
+        let (context, _guard) = testing::context(&mock_discord).await;
+        let guild_id = Id::new(1002);
+
+        seed_join_channel(&context, guild_id, Id::new(1102), ChannelPrivacy::Unlocked).await;
+        replay(&context, include_str!("../testing/recordings/leave.jsonl"))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            mock_discord.requests_to("DELETE", "/channels/5000").len(),
+            1
+        );
+        assert!(context.cache.voice_channel(VOICE_CHANNEL_ID).is_none());
+        assert!(context
+            .cache
+            .voice_channel_owner(guild_id, Id::new(2002))
+            .is_none());
+        assert!(context
+            .database
+            .guild_voice_channels(guild_id)
+            .await
+            .unwrap()
+            .is_empty());
+
+        context.database.remove_guild(guild_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a database, run with `cargo test -- --ignored`"]
+    async fn deleting_an_occupied_voice_channel_forgets_it() {
+        let mock_discord = MockDiscord::start().await;
+        let (context, _guard) = testing::context(&mock_discord).await;
+        let guild_id = Id::new(1003);
+
+        seed_join_channel(&context, guild_id, Id::new(1103), ChannelPrivacy::Unlocked).await;
+        replay(&context, include_str!("../testing/recordings/delete.jsonl"))
+            .await
+            .unwrap();
+
+        // The channel was deleted by someone else, so I never have to.
+        mock_discord.assert_not_requested("DELETE", "/channels/5000");
+        assert!(context.cache.voice_channel(VOICE_CHANNEL_ID).is_none());
+        assert!(context
+            .cache
+            .guild(guild_id)
+            .unwrap()
+            .voice_channel_ids
+            .read()
+            .is_empty());
+        assert!(context.cache.voice_state(guild_id, Id::new(2004)).is_none());
+        assert!(context
+            .database
+            .guild_voice_channels(guild_id)
+            .await
+            .unwrap()
+            .is_empty());
+
+        context.database.remove_guild(guild_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a database, run with `cargo test -- --ignored`"]
+    async fn deletions_through_commands_are_audited_once() {
+        let mock_discord = MockDiscord::start().await;
+        let (context, _guard) = testing::context(&mock_discord).await;
+        let guild_id = Id::new(1001);
+
+        seed_join_channel(&context, guild_id, Id::new(1101), ChannelPrivacy::Unlocked).await;
+        replay(&context, include_str!("../testing/recordings/create.jsonl"))
+            .await
+            .unwrap();
+
+        let voice_channel = mock_discord.channel(5000).unwrap();
+
+        replay(
+            &context,
+            &command(
+                "delete",
+                1001,
+                2001,
+                subcommand("voice", "delete", json!([])),
+            ),
+        )
+        .await
+        .unwrap();
+        replay(&context, &channel_delete(voice_channel))
+            .await
+            .unwrap();
+
+        let (audit_events, _) = context
+            .database
+            .guild_audit_events(guild_id, None, Some(VOICE_CHANNEL_ID), 10, 0)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            audit_events
+                .iter()
+                .map(|audit_event| audit_event.description.as_str())
+                .collect::<Vec<&str>>(),
+            [
+                "<@2001> deleted **alex's voice**.",
+                "<@2001> created <#5000> from <#1101>.",
+            ]
+        );
+        assert!(context.cache.voice_channel(VOICE_CHANNEL_ID).is_none());
+        assert!(context.cache.deleted_channel_ids.is_empty());
+
+        context.database.remove_guild(guild_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a database, run with `cargo test -- --ignored`"]
+    async fn reconnecting_serves_members_waiting_in_join_channels() {
+        let mock_discord = MockDiscord::start().await;
+        let (context, _guard) = testing::context(&mock_discord).await;
+        let guild_id = Id::new(1004);
+        let user_id = Id::<UserMarker>::new(2005);
+
+        seed_join_channel(&context, guild_id, Id::new(1104), ChannelPrivacy::Unlocked).await;
+        replay(
+            &context,
+            include_str!("../testing/recordings/reconnect.jsonl"),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            mock_discord
+                .requests_to("POST", "/guilds/1004/channels")
+                .len(),
+            1
+        );
+        assert_eq!(
+            mock_discord
+                .requests_to("PATCH", "/guilds/1004/members/2005")
+                .len(),
+            1
+        );
+        assert!(context.cache.join_channel(Id::new(1104)).is_some());
+        assert_eq!(
+            context.cache.voice_state(guild_id, user_id),
+            Some(Arc::new(VOICE_CHANNEL_ID))
+        );
+        assert_eq!(
+            context.cache.voice_channel_owner(guild_id, user_id),
+            Some(Arc::new(VOICE_CHANNEL_ID))
+        );
+
+        context.database.remove_guild(guild_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a database, run with `cargo test -- --ignored`"]
+    async fn external_edits_follow_the_server_policy() {
+        let mock_discord = MockDiscord::start().await;
+        let (context, _guard) = testing::context(&mock_discord).await;
+        let guild_id = Id::new(1001);
+
+        seed_join_channel(&context, guild_id, Id::new(1101), ChannelPrivacy::Locked).await;
+        replay(&context, include_str!("../testing/recordings/create.jsonl"))
+            .await
+            .unwrap();
+        replay(
+            &context,
+            &[
+                external_edits_command("revert", "revert"),
+                edit_everyone_overwrite(&mock_discord, "0"),
+            ]
+            .join("\n"),
+        )
+        .await
+        .unwrap();
+
+        let voice_channel = context.cache.voice_channel(VOICE_CHANNEL_ID).unwrap();
+        let revert_request = mock_discord.assert_requested("PATCH", "/channels/5000");
+
+        assert!(revert_request.body["permission_overwrites"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|permission_overwrite| permission_overwrite["id"].eq("1001")
+                && permission_overwrite["deny"].eq("1048576")));
+        assert_eq!(*voice_channel.privacy.read(), ChannelPrivacy::Locked);
+
+        // The reverted overwrites come back as an update of their own, which changes nothing.
+        replay(
+            &context,
+            &[
+                channel_update(mock_discord.channel(5000).unwrap()),
+                external_edits_command("accept", "accept"),
+                edit_everyone_overwrite(&mock_discord, "1049600"),
+            ]
+            .join("\n"),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(mock_discord.requests_to("PATCH", "/channels/5000").len(), 1);
+        assert_eq!(*voice_channel.privacy.read(), ChannelPrivacy::Invisible);
+        assert_eq!(
+            context
+                .database
+                .guild_voice_channels(guild_id)
+                .await
+                .unwrap()[0]
+                .privacy,
+            ChannelPrivacy::Invisible
+        );
+
+        replay(
+            &context,
+            &[
+                external_edits_command("audit", "audit"),
+                edit_everyone_overwrite(&mock_discord, "0"),
+            ]
+            .join("\n"),
+        )
+        .await
+        .unwrap();
+
+        let (audit_events, _) = context
+            .database
+            .guild_audit_events(guild_id, None, Some(VOICE_CHANNEL_ID), 10, 0)
+            .await
+            .unwrap();
+
+        assert_eq!(mock_discord.requests_to("PATCH", "/channels/5000").len(), 1);
+        assert_eq!(*voice_channel.privacy.read(), ChannelPrivacy::Invisible);
+        assert_eq!(
+            audit_events
+                .iter()
+                .map(|audit_event| audit_event.description.as_str())
+                .collect::<Vec<&str>>(),
+            [
+                "<#5000> was edited outside of Voz, so it's **unlocked (and visible)** rather than **invisible**.",
+                "<#5000> was edited outside of Voz to be **unlocked (and visible)**, so I made it **locked (and visible)** again.",
+                "<@2001> created <#5000> from <#1101>.",
+            ]
+        );
+
+        context.database.remove_guild(guild_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a database, run with `cargo test -- --ignored`"]
+    async fn stage_owners_are_held_to_the_name_filter_and_privacy() {
+        let mock_discord = MockDiscord::start().await;
+        let (context, _guard) = testing::context(&mock_discord).await;
+        let guild_id = Id::new(1001);
+
+        seed_join_channel(&context, guild_id, Id::new(1101), ChannelPrivacy::Locked).await;
+        context
+            .database
+            .remove_join_channel(Id::new(1101))
+            .await
+            .unwrap();
+        context
+            .database
+            .insert_join_channel(
+                Id::new(1101),
+                guild_id,
+                None,
+                false,
+                ChannelPrivacy::Locked,
+                VoiceChannelKind::Stage,
+            )
+            .await
+            .unwrap();
+        replay(&context, include_str!("../testing/recordings/create.jsonl"))
+            .await
+            .unwrap();
+        context
+            .cache
+            .guild(guild_id)
+            .unwrap()
+            .name_filter_words
+            .write()
+            .insert("heck".to_owned());
+
+        let mut edited_voice_channel = mock_discord.channel(5000).unwrap();
+
+        edited_voice_channel["name"] = "heck".into();
+        replay(
+            &context,
+            &[
+                external_edits_command("audit", "audit"),
+                channel_update(edited_voice_channel),
+            ]
+            .join("\n"),
+        )
+        .await
+        .unwrap();
+        replay(
+            &context,
+            &[
+                external_edits_command("accept", "accept"),
+                edit_everyone_overwrite(&mock_discord, "0"),
+            ]
+            .join("\n"),
+        )
+        .await
+        .unwrap();
+
+        let (audit_events, _) = context
+            .database
+            .guild_audit_events(guild_id, None, Some(VOICE_CHANNEL_ID), 2, 0)
+            .await
+            .unwrap();
+
+        assert_eq!(mock_discord.channel(5000).unwrap()["name"], "alex's voice");
+        assert_eq!(
+            *context
+                .cache
+                .voice_channel(VOICE_CHANNEL_ID)
+                .unwrap()
+                .privacy
+                .read(),
+            ChannelPrivacy::Locked
+        );
+        assert_eq!(
+            audit_events
+                .iter()
+                .map(|audit_event| audit_event.description.as_str())
+                .collect::<Vec<&str>>(),
+            [
+                "<#5000> was edited outside of Voz to be **unlocked (and visible)**, so I made it **locked (and visible)** again.",
+                "<#5000> was edited outside of Voz, so its name is **heck** rather than **alex's voice**. Its name contains a word that isn't allowed here. I changed its name back to **alex's voice**.",
+            ]
+        );
+
+        context.database.remove_guild(guild_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a database, run with `cargo test -- --ignored`"]
+    async fn external_setting_edits_follow_the_server_policy() {
+        let mock_discord = MockDiscord::start().await;
+        let (context, _guard) = testing::context(&mock_discord).await;
+        let guild_id = Id::new(1001);
+
+        seed_join_channel(&context, guild_id, Id::new(1101), ChannelPrivacy::Unlocked).await;
+        replay(&context, include_str!("../testing/recordings/create.jsonl"))
+            .await
+            .unwrap();
+
+        let mut edited_voice_channel = mock_discord.channel(5000).unwrap();
+
+        edited_voice_channel["name"] = "renamed".into();
+        edited_voice_channel["bitrate"] = 96_000.into();
+        replay(
+            &context,
+            &[
+                external_edits_command("revert", "revert"),
+                channel_update(edited_voice_channel),
+            ]
+            .join("\n"),
+        )
+        .await
+        .unwrap();
+
+        let revert_request = mock_discord.assert_requested("PATCH", "/channels/5000");
+
+        assert_eq!(revert_request.body["name"], "alex's voice");
+        assert_eq!(revert_request.body["bitrate"], 64_000);
+
+        // Neither the reverted settings nor Voz's own edits come back as edits made outside of
+        // Voz, and the two renames leave none for another revert.
+        replay(
+            &context,
+            &[
+                channel_update(mock_discord.channel(5000).unwrap()),
+                command(
+                    "bitrate",
+                    1001,
+                    2001,
+                    subcommand(
+                        "voice",
+                        "bitrate",
+                        json!([{ "name": "rate", "type": 4, "value": 80 }]),
+                    ),
+                ),
+            ]
+            .join("\n"),
+        )
+        .await
+        .unwrap();
+
+        let mut edited_voice_channel = mock_discord.channel(5000).unwrap();
+
+        replay(&context, &channel_update(edited_voice_channel.clone()))
+            .await
+            .unwrap();
+        edited_voice_channel["name"] = "renamed again".into();
+        replay(&context, &channel_update(edited_voice_channel))
+            .await
+            .unwrap();
+
+        assert_eq!(mock_discord.requests_to("PATCH", "/channels/5000").len(), 2);
+        assert_eq!(
+            context
+                .cache
+                .voice_channel(VOICE_CHANNEL_ID)
+                .unwrap()
+                .queued_name
+                .read()
+                .clone(),
+            Some((None, "alex's voice".to_owned()))
+        );
+
+        let mut edited_voice_channel = mock_discord.channel(5000).unwrap();
+
+        // Accepted edits still go through the name filter.
+        context
+            .cache
+            .guild(guild_id)
+            .unwrap()
+            .name_filter_words
+            .write()
+            .insert("heck".to_owned());
+        edited_voice_channel["name"] = "heck".into();
+        edited_voice_channel["user_limit"] = 5.into();
+        replay(
+            &context,
+            &[
+                external_edits_command("accept", "accept"),
+                channel_update(edited_voice_channel.clone()),
+            ]
+            .join("\n"),
+        )
+        .await
+        .unwrap();
+
+        let voice_channel = context.cache.voice_channel(VOICE_CHANNEL_ID).unwrap();
+        let saved_settings = context
+            .database
+            .guild_voice_channels(guild_id)
+            .await
+            .unwrap()
+            .remove(0)
+            .settings
+            .unwrap();
+
+        assert_eq!(mock_discord.requests_to("PATCH", "/channels/5000").len(), 2);
+        assert_eq!(saved_settings.name, "alex's voice");
+        assert_eq!(saved_settings.bitrate, 80_000);
+        assert_eq!(saved_settings.user_limit, Some(5));
+        assert_eq!(
+            voice_channel.saved_settings.read().as_ref(),
+            Some(&saved_settings)
+        );
+
+        edited_voice_channel["rtc_region"] = "japan".into();
+        replay(
+            &context,
+            &[
+                external_edits_command("audit", "audit"),
+                channel_update(edited_voice_channel),
+            ]
+            .join("\n"),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(mock_discord.requests_to("PATCH", "/channels/5000").len(), 2);
+        assert_eq!(
+            apply_queued_names(&context, SystemTime::now() + Duration::from_secs(600)).await,
+            1
+        );
+        assert_eq!(mock_discord.channel(5000).unwrap()["name"], "alex's voice");
+
+        let (audit_events, _) = context
+            .database
+            .guild_audit_events(guild_id, None, Some(VOICE_CHANNEL_ID), 10, 0)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            audit_events
+                .iter()
+                .map(|audit_event| audit_event.description.as_str())
+                .collect::<Vec<&str>>(),
+            [
+                "I changed the name of <#5000> back to **alex's voice**.",
+                "<#5000> was edited outside of Voz, so its voice region is **Japan** rather than **Automatic**.",
+                "<#5000> was renamed outside of Voz to **heck**, which contains a word that isn't allowed here. I'll change its name back to **alex's voice** in **10m**.",
+                "<#5000> was edited outside of Voz, so I'll change its name back to **alex's voice** in **10m**.",
+                "<@2001> set the bitrate of <#5000> to 80kbps.",
+                "<#5000> was edited outside of Voz, so I changed its name back to **alex's voice** and its bitrate back to **64kbps**.",
+                "<@2001> created <#5000> from <#1101>.",
+            ]
+        );
+
+        context.database.remove_guild(guild_id).await.unwrap();
+    }
+}
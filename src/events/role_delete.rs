@@ -3,36 +3,59 @@ use std::sync::Arc;
 use eyre::Result;
 use twilight_model::gateway::payload::incoming::RoleDelete;
 
-use crate::structs::{cache::CachedJoinChannelUpdate, context::Context};
+use crate::structs::{
+    cache::{CachedGuildUpdate, CachedJoinChannelUpdate},
+    context::Context,
+};
 
 pub async fn run(context: Arc<Context>, payload: RoleDelete) -> Result<()> {
     let RoleDelete { guild_id, role_id } = payload;
     let Some(guild) = context.cache.guild(guild_id) else {
         return Ok(());
     };
+    let mut blocked_role_ids = guild.blocked_role_ids.read().clone();
+
+    // A deleted role has no members left to turn away.
+    if blocked_role_ids.remove(&role_id) {
+        context
+            .database
+            .remove_blocked_role(guild_id, role_id)
+            .await?;
+        context.cache.update_guild(
+            guild_id,
+            CachedGuildUpdate {
+                blocked_role_ids: Some(blocked_role_ids),
+                ..Default::default()
+            },
+        );
+    }
+
     let guild_join_channel_ids = guild.join_channel_ids.read().clone();
 
     for channel_id in guild_join_channel_ids {
         let Some(join_channel) = context.cache.join_channel(channel_id) else {
             continue;
         };
-        let Some(access_role_id) = join_channel.access_role_id.read().clone() else {
-            continue;
-        };
+        let mut allowed_role_ids = join_channel.allowed_role_ids.read().clone();
+        let mut denied_role_ids = join_channel.denied_role_ids.read().clone();
 
-        if access_role_id.eq(&role_id) {
-            context
-                .database
-                .update_join_channel_access_role_id(channel_id, None)
-                .await?;
-            context.cache.update_join_channel(
-                channel_id,
-                CachedJoinChannelUpdate {
-                    access_role_id: Some(None),
-                    ..Default::default()
-                },
-            );
+        if !allowed_role_ids.remove(&role_id) && !denied_role_ids.remove(&role_id) {
+            continue;
         }
+
+        // Discord drops the role's overwrite along with the role.
+        context
+            .database
+            .remove_join_channel_role(channel_id, role_id)
+            .await?;
+        context.cache.update_join_channel(
+            channel_id,
+            CachedJoinChannelUpdate {
+                allowed_role_ids: Some(allowed_role_ids),
+                denied_role_ids: Some(denied_role_ids),
+                ..Default::default()
+            },
+        );
     }
 
     Ok(())
@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::SystemTime};
 
 use eyre::Result;
 use twilight_model::{
@@ -10,10 +10,32 @@ use twilight_model::{
         ChannelType, VideoQualityMode,
     },
     gateway::payload::incoming::VoiceStateUpdate,
-    guild::Permissions,
+    guild::{Member, Permissions},
+    id::{
+        marker::{GuildMarker, UserMarker},
+        Id,
+    },
 };
 
-use crate::structs::{context::Context, database::ChannelPrivacy};
+use twilight_util::builder::embed::EmbedBuilder;
+
+use crate::{
+    interactions::components::knock::{is_allowed, knock_retry_after, post_knock},
+    structs::{
+        cache::{CachedGuild, CachedJoinChannel, CachedVoiceChannel},
+        context::Context,
+        database::{ChannelPrivacy, RelationshipKind, VoiceChannelKind, VoiceChannelSettings},
+    },
+    utilities::{
+        audit::audit,
+        name_filter::name_violation,
+        permission_overwrites::{apply_relationships, owner_permissions, privacy_permissions},
+        template::{default_name, render, render_name},
+        time::humanize,
+        voice_status::set_voice_status,
+        waiting_room::create_waiting_room,
+    },
+};
 
 pub async fn run(context: Arc<Context>, payload: VoiceStateUpdate) -> Result<()> {
     let (Some(guild_id), Some(member)) = (payload.0.guild_id, payload.0.member) else {
@@ -28,126 +50,449 @@ pub async fn run(context: Arc<Context>, payload: VoiceStateUpdate) -> Result<()>
         return Ok(());
     };
     let user_id = member.user.id;
+    let channel_id = payload.0.channel_id;
 
-    if let Some(known_channel_id) = context.cache.voice_state(guild_id, user_id) {
-        let known_channel_id = *known_channel_id;
-
-        if context.cache.voice_channel(known_channel_id).is_none() {
-            return Ok(());
-        };
+    context.cache.insert_member_name(
+        guild_id,
+        user_id,
+        member
+            .nick
+            .clone()
+            .or_else(|| member.user.global_name.clone())
+            .unwrap_or_else(|| member.user.name.clone()),
+    );
 
-        context.cache.remove_voice_state(guild_id, user_id);
+    let previous_channel_id = context
+        .cache
+        .update_voice_state(guild_id, user_id, channel_id);
 
-        let Some(voice_channel) = context.cache.voice_channel(known_channel_id) else {
-            return Ok(());
-        };
+    // Mute, deafen, stream and video toggles don't move the member.
+    if previous_channel_id.eq(&channel_id) {
+        return Ok(());
+    }
+    if let Some(voice_channel) =
+        previous_channel_id.and_then(|channel_id| context.cache.voice_channel(channel_id))
+    {
+        context
+            .database
+            .remove_voice_channel_member(voice_channel.id, user_id)
+            .await?;
 
         if voice_channel.permanence.read().eq(&false)
             && voice_channel.connected_user_ids.read().is_empty()
         {
-            context.client.delete_channel(known_channel_id).await?;
+            context.client.delete_channel(voice_channel.id).await?;
         }
     }
-    if let Some(channel_id) = payload.0.channel_id {
-        if context.cache.voice_channel(channel_id).is_some() {
+    if let Some(voice_channel) =
+        channel_id.and_then(|channel_id| context.cache.voice_channel(channel_id))
+    {
+        let joined_at = voice_channel.join_times.read().get(&user_id).copied();
+
+        if let Some(joined_at) = joined_at {
             context
-                .cache
-                .insert_voice_state(guild_id, user_id, channel_id);
+                .database
+                .insert_voice_channel_member(voice_channel.id, user_id, joined_at)
+                .await?;
         }
+    }
+
+    if let Some(voice_channel) = channel_id
+        .and_then(|channel_id| context.cache.waiting_room(channel_id))
+        .and_then(|voice_channel_id| context.cache.voice_channel(*voice_channel_id))
+    {
+        return wait(&context, &voice_channel, user_id).await;
+    }
+
+    let Some(join_channel) =
+        channel_id.and_then(|channel_id| context.cache.join_channel(channel_id))
+    else {
+        return Ok(());
+    };
+
+    create_voice_channel(&context, &guild, &join_channel, &member).await
+}
+
+pub async fn create_voice_channel(
+    context: &Arc<Context>,
+    guild: &CachedGuild,
+    join_channel: &CachedJoinChannel,
+    member: &Member,
+) -> Result<()> {
+    let guild_id = guild.id;
+    let user_id = member.user.id;
+    let username = member.user.name.as_str();
+
+    if context.cache.is_blocked(guild_id, user_id, &member.roles) {
+        let description = guild.blocked_dms_enabled.read().then(|| {
+            format!(
+                "You may not create voice channels in **{}**.",
+                guild.name.read()
+            )
+        });
+
+        return turn_away(context, guild.id, user_id, description).await;
+    }
+    if !context.cache.can_own_voice_channel(guild_id, user_id) {
+        let description = format!(
+            "You already own as many voice channels as **{}** allows.",
+            guild.name.read()
+        );
+
+        return turn_away(context, guild.id, user_id, Some(description)).await;
+    }
+    if let Some(retry_after) = context.cache.creation_retry_after(guild_id, user_id) {
+        let description = format!(
+            "You may create another voice channel in **{}** in {}.",
+            guild.name.read(),
+            humanize(retry_after.as_secs().max(1))
+        );
+
+        return turn_away(context, guild.id, user_id, Some(description)).await;
+    }
 
-        if context
+    let voice_channel_limit = *join_channel.voice_channel_limit.read();
+
+    if voice_channel_limit.is_some_and(|voice_channel_limit| {
+        context
             .cache
-            .voice_channel_owner(guild_id, user_id)
-            .is_some()
-        {
-            return Ok(());
-        }
+            .join_channel_voice_channel_count(join_channel.id)
+            .ge(&usize::from(voice_channel_limit))
+    }) {
+        let description = format!(
+            "**{}** already has as many voice channels as it allows. Try again once one has closed.",
+            join_channel.name.read()
+        );
 
-        let Some(join_channel) = context.cache.join_channel(channel_id) else {
-            return Ok(());
-        };
-        let username = member.user.name;
-        let name = if username.ends_with("s") {
-            format!("{username}' voice")
-        } else {
-            format!("{username}'s voice")
-        };
-        let join_channel_privacy = join_channel.privacy.read().clone();
-        let join_channel_parent_id = join_channel.parent_id.read().clone();
-        let privacy_permissions = match join_channel_privacy {
-            ChannelPrivacy::Invisible => Permissions::VIEW_CHANNEL,
-            ChannelPrivacy::Locked => Permissions::CONNECT,
-            ChannelPrivacy::Unlocked => Permissions::empty(),
-        };
-        let permission_overwrites = &[
-            ChannelPermissionOverwrite {
-                allow: privacy_permissions,
-                deny: Permissions::empty(),
-                id: guild.bot_role_id.cast(),
-                kind: ChannelPermissionOverwriteType::Role,
-            },
-            ChannelPermissionOverwrite {
-                allow: Permissions::empty(),
-                deny: privacy_permissions,
-                id: guild.id.cast(),
-                kind: ChannelPermissionOverwriteType::Role,
-            },
-            ChannelPermissionOverwrite {
-                allow: privacy_permissions,
-                deny: Permissions::empty(),
-                id: user_id.cast(),
+        return turn_away(context, guild.id, user_id, Some(description)).await;
+    }
+
+    let user_preset = if *guild.presets_enabled.read() {
+        context.database.user_preset(guild_id, user_id).await?
+    } else {
+        None
+    };
+    let locale = guild.locale.read().clone();
+    let name = match (&user_preset, guild.name_template.read().as_deref()) {
+        (Some(user_preset), _) => user_preset.name.clone(),
+        (None, Some(name_template)) => render_name(name_template, username),
+        (None, None) => default_name(&locale, username),
+    };
+    // A preset or template the name filter rejects gives way to the default name.
+    let name = if name_violation(guild, &name).is_some() {
+        default_name(&locale, username)
+    } else {
+        name
+    };
+    let privacy = user_preset.as_ref().map_or_else(
+        || join_channel.privacy.read().clone(),
+        |user_preset| user_preset.privacy.clone(),
+    );
+    let join_channel_parent_id = *join_channel.parent_id.read();
+    let privacy_permissions = privacy_permissions(join_channel.kind, &privacy);
+    let mut permission_overwrites = vec![
+        ChannelPermissionOverwrite {
+            allow: privacy_permissions,
+            deny: Permissions::empty(),
+            id: guild.bot_role_id.cast(),
+            kind: ChannelPermissionOverwriteType::Role,
+        },
+        ChannelPermissionOverwrite {
+            allow: Permissions::empty(),
+            deny: privacy_permissions,
+            id: guild.id.cast(),
+            kind: ChannelPermissionOverwriteType::Role,
+        },
+        ChannelPermissionOverwrite {
+            allow: owner_permissions(join_channel.kind, &privacy),
+            deny: Permissions::empty(),
+            id: user_id.cast(),
+            kind: ChannelPermissionOverwriteType::Member,
+        },
+    ];
+
+    if let Some(user_preset) = &user_preset {
+        let allowed_user_ids = user_preset
+            .allowed_user_ids
+            .iter()
+            .map(|allowed_user_id| (*allowed_user_id, privacy_permissions, Permissions::empty()));
+        let denied_user_ids = user_preset.denied_user_ids.iter().map(|denied_user_id| {
+            (
+                *denied_user_id,
+                Permissions::empty(),
+                Permissions::VIEW_CHANNEL,
+            )
+        });
+
+        for (member_id, allow, deny) in allowed_user_ids.chain(denied_user_ids) {
+            if member_id.eq(&user_id) {
+                continue;
+            }
+
+            permission_overwrites.push(ChannelPermissionOverwrite {
+                allow,
+                deny,
+                id: member_id.cast(),
                 kind: ChannelPermissionOverwriteType::Member,
-            },
-        ];
+            });
+        }
+    }
+
+    let user_relationships = context
+        .database
+        .user_relationships(guild_id, user_id)
+        .await?;
+
+    apply_relationships(
+        &mut permission_overwrites,
+        &user_relationships,
+        privacy_permissions,
+        |member_id| member_id.eq(&user_id),
+    );
+
+    let voice_channel_kind = match join_channel.kind {
+        VoiceChannelKind::Stage => ChannelType::GuildStageVoice,
+        VoiceChannelKind::Voice => ChannelType::GuildVoice,
+    };
+    let voice_channel_request = |with_preset_settings: bool| {
         let mut voice_channel_request = context
             .client
             .create_guild_channel(guild_id, &name)
-            .kind(ChannelType::GuildVoice)
-            .permission_overwrites(permission_overwrites);
+            .kind(voice_channel_kind)
+            .permission_overwrites(&permission_overwrites);
 
         if let Some(parent_id) = join_channel_parent_id {
             voice_channel_request = voice_channel_request.parent_id(parent_id);
         }
+        if let Some(user_preset) = user_preset.as_ref().filter(|_| with_preset_settings) {
+            voice_channel_request = voice_channel_request
+                .bitrate(user_preset.bitrate)
+                .video_quality_mode(user_preset.video_quality_mode);
 
-        let Ok(voice_channel_response) = voice_channel_request.await else {
-            return Ok(());
-        };
-        let Ok(voice_channel) = voice_channel_response.model().await else {
-            return Ok(());
-        };
-        let join_channel_permanence = join_channel.permanence.read().clone();
+            if let Some(rate_limit_per_user) = user_preset.rate_limit_per_user {
+                voice_channel_request =
+                    voice_channel_request.rate_limit_per_user(rate_limit_per_user);
+            }
+            if let Some(rtc_region) = &user_preset.rtc_region {
+                voice_channel_request = voice_channel_request.rtc_region(rtc_region);
+            }
+            if let Some(user_limit) = user_preset.user_limit {
+                voice_channel_request = voice_channel_request.user_limit(user_limit as u16);
+            }
+        }
 
-        context
-            .database
-            .insert_voice_channel(
-                voice_channel.id,
-                guild_id,
-                Some(user_id),
-                join_channel_permanence,
-                join_channel_privacy.clone(),
-            )
-            .await?;
-        context.cache.insert_voice_channel(
-            voice_channel.bitrate.unwrap_or(64_000),
+        voice_channel_request
+    };
+    // Discord may refuse a preset's settings here, such as a bitrate above what a stage or the
+    // server's boost level allows, so the voice channel is then created without them.
+    let voice_channel_response = match voice_channel_request(true).await {
+        Err(_) if user_preset.is_some() => voice_channel_request(false).await,
+        voice_channel_response => voice_channel_response,
+    };
+    let Some(voice_channel) = (match voice_channel_response {
+        Ok(voice_channel_response) => voice_channel_response.model().await.ok(),
+        Err(_) => None,
+    }) else {
+        let description = format!(
+            "I'm unable to create a voice channel in **{}** right now. Try again in 10 minutes.",
+            guild.name.read()
+        );
+
+        return turn_away(context, guild.id, user_id, Some(description)).await;
+    };
+
+    context
+        .cache
+        .insert_creation_time(guild_id, user_id, SystemTime::now());
+
+    // Members may listen to a locked stage without being let in, so it has no waiting room.
+    let has_waiting_room = *join_channel.waiting_room.read()
+        && privacy.eq(&ChannelPrivacy::Locked)
+        && join_channel.kind.eq(&VoiceChannelKind::Voice);
+    let join_channel_ownership_delay = *join_channel.ownership_delay.read();
+    let join_channel_ownership_policy = *join_channel.ownership_policy.read();
+    let join_channel_permanence = *join_channel.permanence.read();
+    let status = join_channel
+        .status_template
+        .read()
+        .as_deref()
+        .map(|status_template| render(status_template, username));
+    let saved_settings = VoiceChannelSettings::from(&voice_channel);
+
+    context
+        .database
+        .insert_voice_channel(
             voice_channel.id,
             guild_id,
-            name,
             Some(user_id),
             join_channel_permanence,
-            voice_channel.permission_overwrites.unwrap_or_default(),
-            join_channel_privacy,
-            voice_channel.rate_limit_per_user,
-            voice_channel.rtc_region,
-            voice_channel.user_limit,
-            voice_channel
-                .video_quality_mode
-                .unwrap_or(VideoQualityMode::Auto),
-        );
+            privacy.clone(),
+            join_channel_ownership_policy,
+            join_channel_ownership_delay,
+            Some(join_channel.id),
+            join_channel.kind,
+            &saved_settings,
+        )
+        .await?;
+
+    // A status Discord refused is left off rather than stopping the member from being moved.
+    let status = match status {
+        Some(status) => set_voice_status(&context.client, voice_channel.id, Some(&status))
+            .await
+            .ok()
+            .map(|_| status),
+        None => None,
+    };
+
+    if status.is_some() {
+        context
+            .database
+            .update_voice_channel_status(voice_channel.id, status.as_deref())
+            .await?;
+    }
+
+    context.cache.insert_voice_channel(
+        voice_channel.bitrate.unwrap_or(64_000),
+        voice_channel.id,
+        guild_id,
+        Some(join_channel.id),
+        join_channel.kind,
+        name.clone(),
+        Some(user_id),
+        join_channel_ownership_delay,
+        join_channel_ownership_policy,
+        join_channel_permanence,
+        voice_channel.permission_overwrites.unwrap_or_default(),
+        privacy,
+        voice_channel.rate_limit_per_user,
+        voice_channel.rtc_region,
+        Some(saved_settings),
+        status,
+        voice_channel.user_limit,
+        voice_channel
+            .video_quality_mode
+            .unwrap_or(VideoQualityMode::Auto),
+    );
+
+    if has_waiting_room {
+        create_waiting_room(
+            context,
+            guild,
+            join_channel_parent_id,
+            voice_channel.id,
+            &name,
+        )
+        .await?;
+    }
+
+    context
+        .client
+        .update_guild_member(guild_id, user_id)
+        .channel_id(Some(voice_channel.id))
+        .await?;
+
+    audit(
+        context,
+        guild,
+        Some(user_id),
+        Some(voice_channel.id),
+        "voice channel created",
+        format!(
+            "<@{user_id}> created <#{}> from <#{}>.",
+            voice_channel.id, join_channel.id
+        ),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Disconnects a member the join channel won't create a voice channel for and, given a
+/// `description`, tells them why in a DM.
+async fn turn_away(
+    context: &Context,
+    guild_id: Id<GuildMarker>,
+    user_id: Id<UserMarker>,
+    description: Option<String>,
+) -> Result<()> {
+    _ = context
+        .client
+        .update_guild_member(guild_id, user_id)
+        .channel_id(None)
+        .await;
+
+    let Some(description) = description else {
+        return Ok(());
+    };
+
+    // Members may not accept DMs from this server, which isn't worth failing over.
+    let Ok(private_channel_response) = context.client.create_private_channel(user_id).await else {
+        return Ok(());
+    };
+    let Ok(private_channel) = private_channel_response.model().await else {
+        return Ok(());
+    };
+    let embed = EmbedBuilder::new()
+        .color(0xF8F8FF)
+        .description(description)
+        .build();
+
+    _ = context
+        .client
+        .create_message(private_channel.id)
+        .embeds(&[embed])
+        .await;
+
+    Ok(())
+}
+
+/// Knocks on the voice channel for a member who joined its waiting room. Members who may already
+/// join are moved in, and those the owner blocked are disconnected.
+async fn wait(
+    context: &Arc<Context>,
+    voice_channel: &CachedVoiceChannel,
+    user_id: Id<UserMarker>,
+) -> Result<()> {
+    let guild_id = voice_channel.guild_id;
+
+    if is_allowed(voice_channel, user_id) {
         context
             .client
             .update_guild_member(guild_id, user_id)
             .channel_id(Some(voice_channel.id))
             .await?;
+
+        return Ok(());
+    }
+
+    let owner_id = *voice_channel.owner_id.read();
+
+    if let Some(owner_id) = owner_id {
+        let is_blocked = context
+            .database
+            .user_relationships(guild_id, owner_id)
+            .await?
+            .into_iter()
+            .any(|user_relationship| {
+                user_relationship.member_id.eq(&user_id)
+                    && user_relationship.kind.eq(&RelationshipKind::Blocked)
+            });
+
+        if is_blocked {
+            context
+                .client
+                .update_guild_member(guild_id, user_id)
+                .channel_id(None)
+                .await?;
+
+            return Ok(());
+        }
+    }
+
+    // Members hopping in and out of the waiting room only knock once per rate limit, and stay
+    // there even if I can't post the knock.
+    if knock_retry_after(voice_channel, user_id).is_none() {
+        _ = post_knock(context, voice_channel, user_id).await;
     }
 
     Ok(())
@@ -0,0 +1,154 @@
+use std::sync::Arc;
+
+use eyre::Result;
+use twilight_model::application::interaction::application_command::CommandOptionValue;
+use twilight_util::builder::embed::EmbedBuilder;
+
+use super::{join_channel, save_roles};
+use crate::{
+    structs::{
+        context::Context, database::JoinChannelRoleKind, interaction::ApplicationCommandInteraction,
+    },
+    utilities::audit::audit,
+};
+
+pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteraction) -> Result<()> {
+    let Some(join_channel) = join_channel(&context, &interaction).await? else {
+        return Ok(());
+    };
+    let channel_id = join_channel.id;
+    let Some(CommandOptionValue::Role(role_id)) = interaction
+        .data
+        .options
+        .iter()
+        .find(|option| option.name.eq("role"))
+        .map(|option| option.value.clone())
+    else {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description("I could not find a valid **role** value.")
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    };
+    let kind = match interaction
+        .data
+        .options
+        .iter()
+        .find(|option| option.name.eq("kind"))
+        .map(|option| &option.value)
+    {
+        Some(CommandOptionValue::String(kind)) if kind.eq("deny") => JoinChannelRoleKind::Deny,
+        _ => JoinChannelRoleKind::Allow,
+    };
+
+    if role_id.eq(&interaction.guild.bot_role_id) || role_id.cast().eq(&interaction.guild.id) {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description("This role may not be used as an access role.")
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    }
+
+    let mut allowed_role_ids = join_channel.allowed_role_ids.read().clone();
+    let mut denied_role_ids = join_channel.denied_role_ids.read().clone();
+    let has_changed = match kind {
+        JoinChannelRoleKind::Allow => {
+            denied_role_ids.remove(&role_id);
+            allowed_role_ids.insert(role_id)
+        }
+        JoinChannelRoleKind::Deny => {
+            allowed_role_ids.remove(&role_id);
+            denied_role_ids.insert(role_id)
+        }
+    };
+
+    if !has_changed {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description("No changes have been made.")
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    }
+    if !save_roles(&context, &join_channel, allowed_role_ids, denied_role_ids).await? {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description(
+                "I'm unable to change the access roles of this join channel right now. Try again in 10 minutes.",
+            )
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    }
+
+    context
+        .database
+        .insert_join_channel_role(channel_id, role_id, kind)
+        .await?;
+
+    let (audit_description, description) = match kind {
+        JoinChannelRoleKind::Allow => (
+            format!(
+                "<@{}> let members with <@&{role_id}> into <#{channel_id}>.",
+                interaction.user_id
+            ),
+            format!("Members with <@&{role_id}> may now use <#{channel_id}>."),
+        ),
+        JoinChannelRoleKind::Deny => (
+            format!(
+                "<@{}> kept members with <@&{role_id}> out of <#{channel_id}>.",
+                interaction.user_id
+            ),
+            format!("Members with <@&{role_id}> may no longer use <#{channel_id}>."),
+        ),
+    };
+
+    audit(
+        &context,
+        &interaction.guild,
+        Some(interaction.user_id),
+        Some(channel_id),
+        "/join access-role add",
+        audit_description,
+    )
+    .await?;
+
+    let embed = EmbedBuilder::new()
+        .color(0xF8F8FF)
+        .description(description)
+        .build();
+
+    context
+        .interaction_client()
+        .update_response(&interaction.token)
+        .embeds(Some(&[embed]))
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,37 @@
+use std::sync::Arc;
+
+use eyre::Result;
+use twilight_util::builder::embed::EmbedBuilder;
+
+use super::{join_channel, role_mentions};
+use crate::structs::{context::Context, interaction::ApplicationCommandInteraction};
+
+pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteraction) -> Result<()> {
+    let Some(join_channel) = join_channel(&context, &interaction).await? else {
+        return Ok(());
+    };
+    let allowed_role_ids = join_channel.allowed_role_ids.read().clone();
+    let denied_role_ids = join_channel.denied_role_ids.read().clone();
+    let description = if allowed_role_ids.is_empty() && denied_role_ids.is_empty() {
+        format!("<#{}> has no access roles.", join_channel.id)
+    } else {
+        format!(
+            "**Allowed:** {}\n**Denied:** {}",
+            role_mentions(allowed_role_ids),
+            role_mentions(denied_role_ids)
+        )
+    };
+    let embed = EmbedBuilder::new()
+        .color(0xF8F8FF)
+        .description(description)
+        .title(format!("Access roles for \"{}\"", join_channel.name.read()))
+        .build();
+
+    context
+        .interaction_client()
+        .update_response(&interaction.token)
+        .embeds(Some(&[embed]))
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,431 @@
+mod add;
+mod list;
+mod remove;
+
+use std::{collections::HashSet, mem::replace, str::FromStr, sync::Arc};
+
+use eyre::Result;
+use twilight_model::{
+    application::{
+        command::{CommandOptionChoice, CommandOptionChoiceValue},
+        interaction::application_command::{CommandDataOption, CommandOptionValue},
+    },
+    channel::message::MessageFlags,
+    guild::Permissions,
+    http::interaction::{InteractionResponse, InteractionResponseType},
+    id::{
+        marker::{ChannelMarker, RoleMarker},
+        Id,
+    },
+};
+use twilight_util::builder::{embed::EmbedBuilder, InteractionResponseDataBuilder};
+
+use crate::{
+    structs::{
+        cache::{CachedJoinChannel, CachedJoinChannelUpdate},
+        context::Context,
+        database::ChannelPrivacy,
+        interaction::ApplicationCommandInteraction,
+    },
+    utilities::permission_overwrites::apply_join_channel_roles,
+};
+
+pub async fn run(
+    context: Arc<Context>,
+    mut interaction: ApplicationCommandInteraction,
+) -> Result<()> {
+    let Some(CommandDataOption {
+        name,
+        value: CommandOptionValue::SubCommand(options),
+    }) = interaction.data.options.clone().into_iter().next()
+    else {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description("I could not find a subcommand.")
+            .build();
+        let interaction_response_data = InteractionResponseDataBuilder::new()
+            .embeds(vec![embed])
+            .flags(MessageFlags::EPHEMERAL)
+            .build();
+        let interaction_response = InteractionResponse {
+            data: Some(interaction_response_data),
+            kind: InteractionResponseType::ChannelMessageWithSource,
+        };
+
+        context
+            .interaction_client()
+            .create_response(interaction.id, &interaction.token, &interaction_response)
+            .await?;
+
+        return Ok(());
+    };
+    let _ = replace(&mut interaction.data.options, options);
+
+    match name.as_str() {
+        "add" => add::run(context, interaction).await?,
+        "list" => list::run(context, interaction).await?,
+        "remove" => remove::run(context, interaction).await?,
+        _ => {
+            let embed = EmbedBuilder::new()
+                .color(0xF8F8FF)
+                .description(format!(
+                    "I don't have a subcommand with the name \"{name}\"."
+                ))
+                .build();
+            let interaction_response_data = InteractionResponseDataBuilder::new()
+                .embeds(vec![embed])
+                .flags(MessageFlags::EPHEMERAL)
+                .build();
+            let interaction_response = InteractionResponse {
+                data: Some(interaction_response_data),
+                kind: InteractionResponseType::ChannelMessageWithSource,
+            };
+
+            context
+                .interaction_client()
+                .create_response(interaction.id, &interaction.token, &interaction_response)
+                .await?;
+
+            return Ok(());
+        }
+    }
+
+    Ok(())
+}
+
+/// Answers the autocomplete of the `channel` option, or defers the reply and finds the join
+/// channel it names. `None` means there's nothing left to do.
+async fn join_channel(
+    context: &Context,
+    interaction: &ApplicationCommandInteraction,
+) -> Result<Option<Arc<CachedJoinChannel>>> {
+    let channel_value = match interaction
+        .data
+        .options
+        .iter()
+        .find(|&option| option.name.eq("channel"))
+        .cloned()
+        .map(|option| option.value)
+    {
+        Some(CommandOptionValue::Focused(value, _)) => {
+            let lowercased_value = value.to_ascii_lowercase();
+            let mut filtered_join_channels = interaction
+                .guild
+                .join_channel_ids
+                .read()
+                .clone()
+                .into_iter()
+                .filter_map(|channel_id| {
+                    let join_channel = context.cache.join_channel(channel_id)?;
+                    let name = join_channel.name.read().clone();
+
+                    if !name.contains(&lowercased_value) {
+                        return None;
+                    }
+
+                    Some((name, join_channel.id.to_string()))
+                })
+                .collect::<Vec<(String, String)>>();
+
+            filtered_join_channels.sort();
+
+            let choices = filtered_join_channels
+                .into_iter()
+                .map(|join_channel| CommandOptionChoice {
+                    name: join_channel.0,
+                    name_localizations: None,
+                    value: CommandOptionChoiceValue::String(join_channel.1),
+                })
+                .collect::<Vec<CommandOptionChoice>>();
+            let data = InteractionResponseDataBuilder::new()
+                .choices(choices)
+                .build();
+            let interaction_response = InteractionResponse {
+                data: Some(data),
+                kind: InteractionResponseType::ApplicationCommandAutocompleteResult,
+            };
+
+            context
+                .interaction_client()
+                .create_response(interaction.id, &interaction.token, &interaction_response)
+                .await?;
+
+            return Ok(None);
+        }
+        Some(CommandOptionValue::String(value)) => value,
+        _ => return Ok(None),
+    };
+    let interaction_response_data = InteractionResponseDataBuilder::new()
+        .flags(MessageFlags::EPHEMERAL)
+        .build();
+    let interaction_response = InteractionResponse {
+        data: Some(interaction_response_data),
+        kind: InteractionResponseType::DeferredChannelMessageWithSource,
+    };
+
+    context
+        .interaction_client()
+        .create_response(interaction.id, &interaction.token, &interaction_response)
+        .await?;
+
+    let Some(join_channel) = Id::<ChannelMarker>::from_str(&channel_value)
+        .ok()
+        .and_then(|channel_id| context.cache.join_channel(channel_id))
+    else {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description("I do not recognize this join channel.")
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(None);
+    };
+
+    Ok(Some(join_channel))
+}
+
+/// Rebuilds the join channel's role overwrites for the new allowed and denied roles, returning
+/// whether Discord took them.
+async fn save_roles(
+    context: &Context,
+    join_channel: &CachedJoinChannel,
+    allowed_role_ids: HashSet<Id<RoleMarker>>,
+    denied_role_ids: HashSet<Id<RoleMarker>>,
+) -> Result<bool> {
+    let previous_role_ids = join_channel
+        .allowed_role_ids
+        .read()
+        .union(&join_channel.denied_role_ids.read())
+        .copied()
+        .collect::<HashSet<Id<RoleMarker>>>();
+    let privacy_permissions = match join_channel.privacy.read().clone() {
+        ChannelPrivacy::Invisible => Permissions::VIEW_CHANNEL,
+        ChannelPrivacy::Locked => Permissions::CONNECT,
+        ChannelPrivacy::Unlocked => Permissions::empty(),
+    };
+    let mut permission_overwrites = join_channel.permission_overwrites.read().clone();
+
+    apply_join_channel_roles(
+        &mut permission_overwrites,
+        &previous_role_ids,
+        &allowed_role_ids,
+        &denied_role_ids,
+        privacy_permissions,
+    );
+
+    if context
+        .client
+        .update_channel(join_channel.id)
+        .permission_overwrites(&permission_overwrites)
+        .await
+        .is_err()
+    {
+        return Ok(false);
+    }
+
+    context.cache.update_join_channel(
+        join_channel.id,
+        CachedJoinChannelUpdate {
+            allowed_role_ids: Some(allowed_role_ids),
+            denied_role_ids: Some(denied_role_ids),
+            permission_overwrites: Some(permission_overwrites),
+            ..Default::default()
+        },
+    );
+
+    Ok(true)
+}
+
+/// The roles as mentions, lowest id first, or "None." when there are none.
+pub fn role_mentions(role_ids: impl IntoIterator<Item = Id<RoleMarker>>) -> String {
+    let mut role_ids = role_ids.into_iter().collect::<Vec<Id<RoleMarker>>>();
+
+    if role_ids.is_empty() {
+        return "None.".to_owned();
+    }
+
+    role_ids.sort();
+    role_ids
+        .into_iter()
+        .map(|role_id| format!("<@&{role_id}>"))
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+/// Every role mentioned or named by id in `value`, such as "<@&1> <@&2>", in order.
+pub fn parse_role_ids(value: &str) -> Vec<Id<RoleMarker>> {
+    let mut role_ids = Vec::new();
+
+    for role_id in value
+        .split(|character: char| character.is_whitespace() || character.eq(&','))
+        .filter_map(|word| {
+            word.trim_start_matches("<@&")
+                .trim_end_matches('>')
+                .parse::<u64>()
+                .ok()
+        })
+        .filter_map(Id::new_checked)
+    {
+        if !role_ids.contains(&role_id) {
+            role_ids.push(role_id);
+        }
+    }
+
+    role_ids
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use twilight_model::id::Id;
+
+    use super::parse_role_ids;
+    use crate::{
+        structs::database::ChannelPrivacy,
+        testing::{
+            self,
+            mock_discord::MockDiscord,
+            payloads::{command, subcommand_group},
+            replay::replay,
+            seed_join_channel,
+        },
+    };
+
+    #[test]
+    fn parses_role_mentions_and_ids() {
+        assert_eq!(
+            parse_role_ids("<@&3100> 3200, <@&3100> @Members 0"),
+            [Id::new(3100), Id::new(3200)]
+        );
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a database, run with `cargo test -- --ignored`"]
+    async fn allows_and_denies_roles() {
+        let mock_discord = MockDiscord::start().await;
+        let (context, _guard) = testing::context(&mock_discord).await;
+        let guild_id = Id::new(1001);
+        let (guild_create, _) = include_str!("../../../../testing/recordings/create.jsonl")
+            .split_once('\n')
+            .unwrap();
+
+        seed_join_channel(&context, guild_id, Id::new(1101), ChannelPrivacy::Locked).await;
+        mock_discord.insert_channel(json!({
+            "guild_id": "1001",
+            "id": "1101",
+            "name": "join",
+            "permission_overwrites": [],
+            "type": 2,
+        }));
+        replay(
+            &context,
+            &[
+                guild_create.to_owned(),
+                command(
+                    "allow",
+                    1001,
+                    1,
+                    subcommand_group(
+                        "join",
+                        "access-role",
+                        "add",
+                        json!([
+                            { "name": "channel", "type": 3, "value": "1101" },
+                            { "name": "role", "type": 8, "value": "3100" }
+                        ]),
+                    ),
+                ),
+                command(
+                    "deny",
+                    1001,
+                    1,
+                    subcommand_group(
+                        "join",
+                        "access-role",
+                        "add",
+                        json!([
+                            { "name": "channel", "type": 3, "value": "1101" },
+                            { "name": "role", "type": 8, "value": "3200" },
+                            { "name": "kind", "type": 3, "value": "deny" }
+                        ]),
+                    ),
+                ),
+                command(
+                    "list",
+                    1001,
+                    1,
+                    subcommand_group(
+                        "join",
+                        "access-role",
+                        "list",
+                        json!([{ "name": "channel", "type": 3, "value": "1101" }]),
+                    ),
+                ),
+                command(
+                    "remove",
+                    1001,
+                    1,
+                    subcommand_group(
+                        "join",
+                        "access-role",
+                        "remove",
+                        json!([
+                            { "name": "channel", "type": 3, "value": "1101" },
+                            { "name": "role", "type": 8, "value": "3100" }
+                        ]),
+                    ),
+                ),
+            ]
+            .join("\n"),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            mock_discord.response_descriptions("allow"),
+            ["Members with <@&3100> may now use <#1101>."]
+        );
+        assert_eq!(
+            mock_discord.response_descriptions("deny"),
+            ["Members with <@&3200> may no longer use <#1101>."]
+        );
+        assert_eq!(
+            mock_discord.response_descriptions("list"),
+            ["**Allowed:** <@&3100>\n**Denied:** <@&3200>"]
+        );
+        assert_eq!(
+            mock_discord.response_descriptions("remove"),
+            ["<@&3100> is no longer an access role for <#1101>."]
+        );
+
+        let overwrite_requests = mock_discord.requests_to("PATCH", "/channels/1101");
+        let overwrites = overwrite_requests.last().unwrap().body["permission_overwrites"]
+            .as_array()
+            .unwrap()
+            .clone();
+
+        assert!(overwrites
+            .iter()
+            .all(|overwrite| overwrite["id"].ne("3100")));
+        assert!(overwrites
+            .iter()
+            .any(|overwrite| overwrite["id"].eq("3200") && overwrite["deny"].eq("1048576")));
+
+        let join_channel = context
+            .database
+            .guild_join_channels(guild_id)
+            .await
+            .unwrap()
+            .remove(0);
+
+        assert!(join_channel.allowed_role_ids.is_empty());
+        assert_eq!(join_channel.denied_role_ids, [Id::new(3200)]);
+
+        context.database.remove_guild(guild_id).await.unwrap();
+    }
+}
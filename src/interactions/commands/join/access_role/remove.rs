@@ -0,0 +1,105 @@
+use std::sync::Arc;
+
+use eyre::Result;
+use twilight_model::application::interaction::application_command::CommandOptionValue;
+use twilight_util::builder::embed::EmbedBuilder;
+
+use super::{join_channel, save_roles};
+use crate::{
+    structs::{context::Context, interaction::ApplicationCommandInteraction},
+    utilities::audit::audit,
+};
+
+pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteraction) -> Result<()> {
+    let Some(join_channel) = join_channel(&context, &interaction).await? else {
+        return Ok(());
+    };
+    let channel_id = join_channel.id;
+    let Some(CommandOptionValue::Role(role_id)) = interaction
+        .data
+        .options
+        .iter()
+        .find(|option| option.name.eq("role"))
+        .map(|option| option.value.clone())
+    else {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description("I could not find a valid **role** value.")
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    };
+    let mut allowed_role_ids = join_channel.allowed_role_ids.read().clone();
+    let mut denied_role_ids = join_channel.denied_role_ids.read().clone();
+    let has_changed = allowed_role_ids.remove(&role_id) | denied_role_ids.remove(&role_id);
+
+    if !has_changed {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description("No changes have been made.")
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    }
+    if !save_roles(&context, &join_channel, allowed_role_ids, denied_role_ids).await? {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description(
+                "I'm unable to change the access roles of this join channel right now. Try again in 10 minutes.",
+            )
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    }
+
+    context
+        .database
+        .remove_join_channel_role(channel_id, role_id)
+        .await?;
+
+    audit(
+        &context,
+        &interaction.guild,
+        Some(interaction.user_id),
+        Some(channel_id),
+        "/join access-role remove",
+        format!(
+            "<@{}> removed <@&{role_id}> from the access roles of <#{channel_id}>.",
+            interaction.user_id
+        ),
+    )
+    .await?;
+
+    let embed = EmbedBuilder::new()
+        .color(0xF8F8FF)
+        .description(format!(
+            "<@&{role_id}> is no longer an access role for <#{channel_id}>."
+        ))
+        .build();
+
+    context
+        .interaction_client()
+        .update_response(&interaction.token)
+        .embeds(Some(&[embed]))
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,206 @@
+use std::sync::Arc;
+
+use eyre::Result;
+use twilight_model::{
+    application::interaction::application_command::CommandOptionValue,
+    channel::message::MessageFlags,
+    http::interaction::{InteractionResponse, InteractionResponseType},
+};
+use twilight_util::builder::InteractionResponseDataBuilder;
+
+use crate::{
+    interactions::components::audit::audit_page,
+    structs::{context::Context, interaction::ApplicationCommandInteraction},
+};
+
+/// Shows the server's audit events a page at a time. The Previous and Next buttons are handled by
+/// `components::audit`.
+pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteraction) -> Result<()> {
+    let interaction_response_data = InteractionResponseDataBuilder::new()
+        .flags(MessageFlags::EPHEMERAL)
+        .build();
+    let interaction_response = InteractionResponse {
+        data: Some(interaction_response_data),
+        kind: InteractionResponseType::DeferredChannelMessageWithSource,
+    };
+
+    context
+        .interaction_client()
+        .create_response(interaction.id, &interaction.token, &interaction_response)
+        .await?;
+
+    let mut user_id = None;
+    let mut channel_id = None;
+    let mut page = 1;
+
+    for option in interaction.data.options.iter() {
+        match &option.value {
+            CommandOptionValue::User(member_id) => user_id = Some(*member_id),
+            CommandOptionValue::Channel(option_channel_id) => channel_id = Some(*option_channel_id),
+            CommandOptionValue::Integer(option_page) => page = *option_page,
+            _ => {}
+        }
+    }
+
+    let (embed, components) =
+        audit_page(&context, interaction.guild.id, user_id, channel_id, page).await?;
+
+    context
+        .interaction_client()
+        .update_response(&interaction.token)
+        .components(Some(&components))
+        .embeds(Some(&[embed]))
+        .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use twilight_model::id::Id;
+
+    use crate::{
+        structs::database::ChannelPrivacy,
+        testing::{
+            self,
+            mock_discord::MockDiscord,
+            payloads::{command, component, subcommand, subcommand_group},
+            replay::replay,
+            seed_join_channel,
+        },
+    };
+
+    #[tokio::test]
+    #[ignore = "needs a database, run with `cargo test -- --ignored`"]
+    async fn lists_audit_events_newest_first() {
+        let mock_discord = MockDiscord::start().await;
+        let (context, _guard) = testing::context(&mock_discord).await;
+        let guild_id = Id::new(1001);
+
+        seed_join_channel(&context, guild_id, Id::new(1101), ChannelPrivacy::Unlocked).await;
+        replay(
+            &context,
+            include_str!("../../../testing/recordings/create.jsonl"),
+        )
+        .await
+        .unwrap();
+        replay(
+            &context,
+            &[
+                command(
+                    "status",
+                    1001,
+                    2001,
+                    subcommand_group(
+                        "voice",
+                        "status",
+                        "set",
+                        json!([{ "name": "text", "type": 3, "value": "lofi beats" }]),
+                    ),
+                ),
+                command(
+                    "member",
+                    1001,
+                    1,
+                    subcommand(
+                        "join",
+                        "audit",
+                        json!([{ "name": "member", "type": 6, "value": "2001" }]),
+                    ),
+                ),
+                command(
+                    "channel",
+                    1001,
+                    1,
+                    subcommand(
+                        "join",
+                        "audit",
+                        json!([{ "name": "channel", "type": 7, "value": "9999" }]),
+                    ),
+                ),
+                component("next", 1001, 1, 1, "audit:2:2001:0"),
+            ]
+            .join("\n"),
+        )
+        .await
+        .unwrap();
+
+        let descriptions = mock_discord.response_descriptions("member");
+        let lines = descriptions[0].lines().collect::<Vec<&str>>();
+
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0]
+            .ends_with("`/voice status set` <@2001> set the status of <#5000> to **lofi beats**."));
+        assert!(lines[1].ends_with("`voice channel created` <@2001> created <#5000> from <#1101>."));
+        assert_eq!(
+            mock_discord.response_descriptions("channel"),
+            ["No audit events have been recorded."]
+        );
+        // Pages past the last one show the last one.
+        assert_eq!(mock_discord.response_descriptions("next"), descriptions);
+        assert_eq!(
+            context
+                .database
+                .guild_audit_events(guild_id, None, Some(Id::new(5000)), 10, 0)
+                .await
+                .unwrap()
+                .1,
+            2
+        );
+
+        context.database.remove_guild(guild_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a database, run with `cargo test -- --ignored`"]
+    async fn long_audit_events_fit_on_a_page() {
+        let mock_discord = MockDiscord::start().await;
+        let (context, _guard) = testing::context(&mock_discord).await;
+        let guild_id = Id::new(1001);
+
+        seed_join_channel(&context, guild_id, Id::new(1101), ChannelPrivacy::Unlocked).await;
+        replay(
+            &context,
+            include_str!("../../../testing/recordings/create.jsonl"),
+        )
+        .await
+        .unwrap();
+
+        // As long as a status set through `/voice status set` can make one.
+        let description = format!(
+            "<@2001> set the status of <#5000> to **{}**.",
+            "a".repeat(500)
+        );
+
+        for _ in 0..10 {
+            context
+                .database
+                .insert_audit_event(
+                    guild_id,
+                    Some(Id::new(5000)),
+                    Some(Id::new(2001)),
+                    "/voice status set",
+                    &description,
+                )
+                .await
+                .unwrap();
+        }
+
+        replay(
+            &context,
+            &command("audit", 1001, 1, subcommand("join", "audit", json!([]))),
+        )
+        .await
+        .unwrap();
+
+        let descriptions = mock_discord.response_descriptions("audit");
+        let lines = descriptions[0].lines().collect::<Vec<&str>>();
+
+        assert!(descriptions[0].chars().count() <= 4_096);
+        assert_eq!(lines.len(), 10);
+        assert!(lines.iter().all(|line| line.ends_with('…')));
+
+        context.database.remove_guild(guild_id).await.unwrap();
+    }
+}
@@ -0,0 +1,71 @@
+use std::sync::Arc;
+
+use eyre::Result;
+use twilight_model::{
+    channel::message::MessageFlags,
+    http::interaction::{InteractionResponse, InteractionResponseType},
+};
+use twilight_util::builder::{embed::EmbedBuilder, InteractionResponseDataBuilder};
+
+use crate::structs::{context::Context, interaction::ApplicationCommandInteraction};
+
+pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteraction) -> Result<()> {
+    let interaction_response_data = InteractionResponseDataBuilder::new()
+        .flags(MessageFlags::EPHEMERAL)
+        .build();
+    let interaction_response = InteractionResponse {
+        data: Some(interaction_response_data),
+        kind: InteractionResponseType::DeferredChannelMessageWithSource,
+    };
+
+    context
+        .interaction_client()
+        .create_response(interaction.id, &interaction.token, &interaction_response)
+        .await?;
+
+    let mut blocked_user_ids = interaction
+        .guild
+        .blocked_user_ids
+        .read()
+        .iter()
+        .copied()
+        .collect::<Vec<_>>();
+    let mut blocked_role_ids = interaction
+        .guild
+        .blocked_role_ids
+        .read()
+        .iter()
+        .copied()
+        .collect::<Vec<_>>();
+
+    blocked_user_ids.sort();
+    blocked_role_ids.sort();
+
+    let lines = blocked_user_ids
+        .into_iter()
+        .map(|user_id| format!("- <@{user_id}>"))
+        .chain(
+            blocked_role_ids
+                .into_iter()
+                .map(|role_id| format!("- <@&{role_id}>")),
+        )
+        .collect::<Vec<String>>();
+    let description = if lines.is_empty() {
+        "No members or roles have been blocked.".to_owned()
+    } else {
+        lines.join("\n")
+    };
+    let embed = EmbedBuilder::new()
+        .color(0xF8F8FF)
+        .description(description)
+        .title("Blocked members and roles")
+        .build();
+
+    context
+        .interaction_client()
+        .update_response(&interaction.token)
+        .embeds(Some(&[embed]))
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,131 @@
+use std::sync::Arc;
+
+use eyre::Result;
+use twilight_model::{
+    application::interaction::application_command::CommandOptionValue,
+    channel::message::MessageFlags,
+    http::interaction::{InteractionResponse, InteractionResponseType},
+};
+use twilight_util::builder::{embed::EmbedBuilder, InteractionResponseDataBuilder};
+
+use crate::{
+    structs::{
+        cache::CachedGuildUpdate, context::Context, interaction::ApplicationCommandInteraction,
+    },
+    utilities::audit::audit,
+};
+
+pub async fn run(
+    context: Arc<Context>,
+    interaction: ApplicationCommandInteraction,
+    is_blocking: bool,
+) -> Result<()> {
+    let interaction_response_data = InteractionResponseDataBuilder::new()
+        .flags(MessageFlags::EPHEMERAL)
+        .build();
+    let interaction_response = InteractionResponse {
+        data: Some(interaction_response_data),
+        kind: InteractionResponseType::DeferredChannelMessageWithSource,
+    };
+
+    context
+        .interaction_client()
+        .create_response(interaction.id, &interaction.token, &interaction_response)
+        .await?;
+
+    let Some(CommandOptionValue::User(member_id)) = interaction
+        .data
+        .options
+        .iter()
+        .find(|option| option.name.eq("member"))
+        .map(|option| option.value.clone())
+    else {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description("I could not find a valid **member** value.")
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    };
+    let mut blocked_user_ids = interaction.guild.blocked_user_ids.read().clone();
+    let has_changed = if is_blocking {
+        blocked_user_ids.insert(member_id)
+    } else {
+        blocked_user_ids.remove(&member_id)
+    };
+
+    if !has_changed {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description("No changes have been made.")
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    }
+
+    let (action, verb, description) = if is_blocking {
+        context
+            .database
+            .insert_blocked_member(interaction.guild.id, member_id)
+            .await?;
+
+        (
+            "/join block member",
+            "blocked",
+            format!("<@{member_id}> may no longer create voice channels."),
+        )
+    } else {
+        context
+            .database
+            .remove_blocked_member(interaction.guild.id, member_id)
+            .await?;
+
+        (
+            "/join unblock member",
+            "unblocked",
+            format!("<@{member_id}> may create voice channels again."),
+        )
+    };
+
+    context.cache.update_guild(
+        interaction.guild.id,
+        CachedGuildUpdate {
+            blocked_user_ids: Some(blocked_user_ids),
+            ..Default::default()
+        },
+    );
+    audit(
+        &context,
+        &interaction.guild,
+        Some(interaction.user_id),
+        None,
+        action,
+        format!("<@{}> {verb} <@{member_id}>.", interaction.user_id),
+    )
+    .await?;
+
+    let embed = EmbedBuilder::new()
+        .color(0xF8F8FF)
+        .description(description)
+        .build();
+
+    context
+        .interaction_client()
+        .update_response(&interaction.token)
+        .embeds(Some(&[embed]))
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,259 @@
+mod list;
+mod member;
+mod role;
+
+use std::{mem::replace, sync::Arc};
+
+use eyre::Result;
+use twilight_model::{
+    application::interaction::application_command::{CommandDataOption, CommandOptionValue},
+    channel::message::MessageFlags,
+    http::interaction::{InteractionResponse, InteractionResponseType},
+};
+use twilight_util::builder::{embed::EmbedBuilder, InteractionResponseDataBuilder};
+
+use crate::structs::{context::Context, interaction::ApplicationCommandInteraction};
+
+/// Runs a `/join block` or `/join unblock` subcommand against the server's blocklist.
+pub async fn run(
+    context: Arc<Context>,
+    mut interaction: ApplicationCommandInteraction,
+    is_blocking: bool,
+) -> Result<()> {
+    let Some(CommandDataOption {
+        name,
+        value: CommandOptionValue::SubCommand(options),
+    }) = interaction.data.options.clone().into_iter().next()
+    else {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description("I could not find a subcommand.")
+            .build();
+        let interaction_response_data = InteractionResponseDataBuilder::new()
+            .embeds(vec![embed])
+            .flags(MessageFlags::EPHEMERAL)
+            .build();
+        let interaction_response = InteractionResponse {
+            data: Some(interaction_response_data),
+            kind: InteractionResponseType::ChannelMessageWithSource,
+        };
+
+        context
+            .interaction_client()
+            .create_response(interaction.id, &interaction.token, &interaction_response)
+            .await?;
+
+        return Ok(());
+    };
+    let _ = replace(&mut interaction.data.options, options);
+
+    match name.as_str() {
+        "list" => list::run(context, interaction).await?,
+        "member" => member::run(context, interaction, is_blocking).await?,
+        "role" => role::run(context, interaction, is_blocking).await?,
+        _ => {
+            let embed = EmbedBuilder::new()
+                .color(0xF8F8FF)
+                .description(format!(
+                    "I don't have a subcommand with the name \"{name}\"."
+                ))
+                .build();
+            let interaction_response_data = InteractionResponseDataBuilder::new()
+                .embeds(vec![embed])
+                .flags(MessageFlags::EPHEMERAL)
+                .build();
+            let interaction_response = InteractionResponse {
+                data: Some(interaction_response_data),
+                kind: InteractionResponseType::ChannelMessageWithSource,
+            };
+
+            context
+                .interaction_client()
+                .create_response(interaction.id, &interaction.token, &interaction_response)
+                .await?;
+
+            return Ok(());
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+    use twilight_model::id::Id;
+
+    use crate::{
+        structs::database::ChannelPrivacy,
+        testing::{
+            self,
+            mock_discord::MockDiscord,
+            payloads::{command, role_delete, subcommand, subcommand_group, voice_state_update},
+            replay::replay,
+            seed_join_channel,
+        },
+    };
+
+    #[tokio::test]
+    #[ignore = "needs a database, run with `cargo test -- --ignored`"]
+    async fn blocked_members_are_turned_away() {
+        let mock_discord = MockDiscord::start().await;
+        let (context, _guard) = testing::context(&mock_discord).await;
+        let guild_id = Id::new(1001);
+        let (guild_create, create) = include_str!("../../../../testing/recordings/create.jsonl")
+            .split_once('\n')
+            .unwrap();
+
+        seed_join_channel(&context, guild_id, Id::new(1101), ChannelPrivacy::Unlocked).await;
+        replay(
+            &context,
+            &[
+                guild_create.to_owned(),
+                command(
+                    "block",
+                    1001,
+                    1,
+                    subcommand_group(
+                        "join",
+                        "block",
+                        "member",
+                        json!([{ "name": "member", "type": 6, "value": "2001" }]),
+                    ),
+                ),
+                create.to_owned(),
+            ]
+            .join("\n"),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            mock_discord.response_descriptions("block"),
+            ["<@2001> may no longer create voice channels."]
+        );
+        assert!(mock_discord
+            .requests_to("POST", "/guilds/1001/channels")
+            .is_empty());
+        assert_eq!(
+            mock_discord
+                .assert_requested("PATCH", "/guilds/1001/members/2001")
+                .body["channel_id"],
+            Value::Null
+        );
+
+        let private_channel_request = mock_discord.assert_requested("POST", "/users/@me/channels");
+
+        assert_eq!(private_channel_request.body["recipient_id"], "2001");
+        assert_eq!(
+            mock_discord.requests_to("POST", "/channels/5000/messages")[0].body["embeds"][0]
+                ["description"],
+            "You may not create voice channels in **guild**."
+        );
+        assert_eq!(
+            context
+                .database
+                .guild_blocked_members(guild_id)
+                .await
+                .unwrap(),
+            [Id::new(2001)]
+        );
+
+        let mut blocked_role_member: Value =
+            serde_json::from_str(&voice_state_update(1001, 2002, "sam", Some(1101))).unwrap();
+
+        blocked_role_member["d"]["member"]["roles"] = json!(["3100"]);
+        replay(
+            &context,
+            &[
+                command(
+                    "unblock",
+                    1001,
+                    1,
+                    subcommand_group(
+                        "join",
+                        "unblock",
+                        "member",
+                        json!([{ "name": "member", "type": 6, "value": "2001" }]),
+                    ),
+                ),
+                command(
+                    "block-role",
+                    1001,
+                    1,
+                    subcommand_group(
+                        "join",
+                        "block",
+                        "role",
+                        json!([{ "name": "role", "type": 8, "value": "3100" }]),
+                    ),
+                ),
+                command(
+                    "dms",
+                    1001,
+                    1,
+                    subcommand(
+                        "settings",
+                        "set",
+                        json!([{ "name": "blocked-dms", "type": 5, "value": false }]),
+                    ),
+                ),
+                blocked_role_member.to_string(),
+                command(
+                    "list",
+                    1001,
+                    1,
+                    subcommand_group("join", "block", "list", json!([])),
+                ),
+            ]
+            .join("\n"),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            mock_discord.response_descriptions("unblock"),
+            ["<@2001> may create voice channels again."]
+        );
+        assert!(mock_discord
+            .requests_to("POST", "/guilds/1001/channels")
+            .is_empty());
+        assert_eq!(
+            mock_discord
+                .assert_requested("PATCH", "/guilds/1001/members/2002")
+                .body["channel_id"],
+            Value::Null
+        );
+        assert_eq!(
+            mock_discord
+                .requests_to("POST", "/users/@me/channels")
+                .len(),
+            1
+        );
+        assert_eq!(mock_discord.response_descriptions("list"), ["- <@&3100>"]);
+        assert!(context
+            .database
+            .guild_blocked_members(guild_id)
+            .await
+            .unwrap()
+            .is_empty());
+
+        replay(&context, &role_delete(1001, 3100)).await.unwrap();
+
+        assert!(context
+            .database
+            .guild_blocked_roles(guild_id)
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(context
+            .cache
+            .guild(guild_id)
+            .unwrap()
+            .blocked_role_ids
+            .read()
+            .is_empty());
+
+        context.database.remove_guild(guild_id).await.unwrap();
+    }
+}
@@ -0,0 +1,131 @@
+use std::sync::Arc;
+
+use eyre::Result;
+use twilight_model::{
+    application::interaction::application_command::CommandOptionValue,
+    channel::message::MessageFlags,
+    http::interaction::{InteractionResponse, InteractionResponseType},
+};
+use twilight_util::builder::{embed::EmbedBuilder, InteractionResponseDataBuilder};
+
+use crate::{
+    structs::{
+        cache::CachedGuildUpdate, context::Context, interaction::ApplicationCommandInteraction,
+    },
+    utilities::audit::audit,
+};
+
+pub async fn run(
+    context: Arc<Context>,
+    interaction: ApplicationCommandInteraction,
+    is_blocking: bool,
+) -> Result<()> {
+    let interaction_response_data = InteractionResponseDataBuilder::new()
+        .flags(MessageFlags::EPHEMERAL)
+        .build();
+    let interaction_response = InteractionResponse {
+        data: Some(interaction_response_data),
+        kind: InteractionResponseType::DeferredChannelMessageWithSource,
+    };
+
+    context
+        .interaction_client()
+        .create_response(interaction.id, &interaction.token, &interaction_response)
+        .await?;
+
+    let Some(CommandOptionValue::Role(role_id)) = interaction
+        .data
+        .options
+        .iter()
+        .find(|option| option.name.eq("role"))
+        .map(|option| option.value.clone())
+    else {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description("I could not find a valid **role** value.")
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    };
+    let mut blocked_role_ids = interaction.guild.blocked_role_ids.read().clone();
+    let has_changed = if is_blocking {
+        blocked_role_ids.insert(role_id)
+    } else {
+        blocked_role_ids.remove(&role_id)
+    };
+
+    if !has_changed {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description("No changes have been made.")
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    }
+
+    let (action, verb, description) = if is_blocking {
+        context
+            .database
+            .insert_blocked_role(interaction.guild.id, role_id)
+            .await?;
+
+        (
+            "/join block role",
+            "blocked",
+            format!("Members with <@&{role_id}> may no longer create voice channels."),
+        )
+    } else {
+        context
+            .database
+            .remove_blocked_role(interaction.guild.id, role_id)
+            .await?;
+
+        (
+            "/join unblock role",
+            "unblocked",
+            format!("Members with <@&{role_id}> may create voice channels again."),
+        )
+    };
+
+    context.cache.update_guild(
+        interaction.guild.id,
+        CachedGuildUpdate {
+            blocked_role_ids: Some(blocked_role_ids),
+            ..Default::default()
+        },
+    );
+    audit(
+        &context,
+        &interaction.guild,
+        Some(interaction.user_id),
+        None,
+        action,
+        format!("<@{}> {verb} <@&{role_id}>.", interaction.user_id),
+    )
+    .await?;
+
+    let embed = EmbedBuilder::new()
+        .color(0xF8F8FF)
+        .description(description)
+        .build();
+
+    context
+        .interaction_client()
+        .update_response(&interaction.token)
+        .embeds(Some(&[embed]))
+        .await?;
+
+    Ok(())
+}
@@ -12,8 +12,12 @@ use twilight_model::{
 };
 use twilight_util::builder::{embed::EmbedBuilder, InteractionResponseDataBuilder};
 
-use crate::structs::{
-    cache::CachedJoinChannelUpdate, context::Context, interaction::ApplicationCommandInteraction,
+use crate::{
+    structs::{
+        cache::CachedJoinChannelUpdate, context::Context,
+        interaction::ApplicationCommandInteraction,
+    },
+    utilities::audit::audit,
 };
 
 pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteraction) -> Result<()> {
@@ -34,9 +38,7 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
                 .clone()
                 .into_iter()
                 .filter_map(|channel_id| {
-                    let Some(join_channel) = context.cache.join_channel(channel_id) else {
-                        return None;
-                    };
+                    let join_channel = context.cache.join_channel(channel_id)?;
                     let name = join_channel.name.read().clone();
 
                     if !name.contains(&lowercased_value) {
@@ -156,6 +158,25 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
         },
     );
 
+    audit(
+        &context,
+        &interaction.guild,
+        Some(interaction.user_id),
+        Some(channel_id),
+        "/join category",
+        match parent_id {
+            Some(parent_id) => format!(
+                "<@{}> moved voice channels from <#{channel_id}> under <#{parent_id}>.",
+                interaction.user_id
+            ),
+            None => format!(
+                "<@{}> removed the category for voice channels from <#{channel_id}>.",
+                interaction.user_id
+            ),
+        },
+    )
+    .await?;
+
     let description = if let Some(parent_id) = parent_id {
         format!("Voice channels from <#{channel_id}> will now be created under <#{parent_id}>.")
     } else {
@@ -20,8 +20,17 @@ use twilight_model::{
 };
 use twilight_util::builder::{embed::EmbedBuilder, InteractionResponseDataBuilder};
 
-use crate::structs::{
-    context::Context, database::ChannelPrivacy, interaction::ApplicationCommandInteraction,
+use super::access_role::parse_role_ids;
+use crate::{
+    structs::{
+        context::Context,
+        database::{ChannelPrivacy, JoinChannelRoleKind, OwnershipPolicy, VoiceChannelKind},
+        interaction::ApplicationCommandInteraction,
+    },
+    utilities::{
+        audit::audit, constants::DEFAULT_OWNERSHIP_DELAY,
+        permission_overwrites::apply_join_channel_roles,
+    },
 };
 
 pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteraction) -> Result<()> {
@@ -58,8 +67,10 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
     }
 
     let mut name = format!("join-{}", join_channel_count + 1);
-    let mut access_role_id: Option<Id<RoleMarker>> = None;
+    let mut allowed_role_ids: HashSet<Id<RoleMarker>> = HashSet::new();
+    let mut denied_role_ids: HashSet<Id<RoleMarker>> = HashSet::new();
     let mut parent_id: Option<Id<ChannelMarker>> = None;
+    let mut kind = VoiceChannelKind::Voice;
     let mut permanence = false;
     let mut privacy = ChannelPrivacy::Unlocked;
     let bot_and_everyone_role_ids: HashSet<Id<GenericMarker>> = HashSet::from_iter(vec![
@@ -72,27 +83,20 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
             ("name", CommandOptionValue::String(value)) => {
                 name = value;
             }
-            ("access-role", CommandOptionValue::Role(value)) => {
-                if bot_and_everyone_role_ids.contains(&value.cast()) {
-                    let embed = EmbedBuilder::new()
-                        .color(0xF8F8FF)
-                        .description("This role may not be used as an access role.")
-                        .build();
-
-                    context
-                        .interaction_client()
-                        .update_response(&interaction.token)
-                        .embeds(Some(&[embed]))
-                        .await?;
-
-                    return Ok(());
-                }
-
-                access_role_id = Some(value);
+            ("access-roles", CommandOptionValue::String(value)) => {
+                allowed_role_ids.extend(parse_role_ids(&value));
+            }
+            ("deny-roles", CommandOptionValue::String(value)) => {
+                denied_role_ids.extend(parse_role_ids(&value));
             }
             ("category", CommandOptionValue::Channel(value)) => {
                 parent_id = Some(value);
             }
+            ("kind", CommandOptionValue::String(value)) => {
+                if value.eq("stage") {
+                    kind = VoiceChannelKind::Stage
+                }
+            }
             ("permanence", CommandOptionValue::Boolean(value)) => {
                 permanence = value;
             }
@@ -100,13 +104,46 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
                 if value.eq("invisible") {
                     privacy = ChannelPrivacy::Invisible
                 } else if value.eq("locked") {
-                    privacy = ChannelPrivacy::Invisible
+                    privacy = ChannelPrivacy::Locked
                 }
             }
             _ => continue,
         }
     }
 
+    if allowed_role_ids
+        .iter()
+        .chain(denied_role_ids.iter())
+        .any(|role_id| bot_and_everyone_role_ids.contains(&role_id.cast()))
+    {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description("This role may not be used as an access role.")
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    }
+    if let Some(role_id) = allowed_role_ids.intersection(&denied_role_ids).next() {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description(format!("<@&{role_id}> may not be both allowed and denied."))
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    }
+
     let common_permissions = match privacy {
         ChannelPrivacy::Invisible => Permissions::VIEW_CHANNEL,
         ChannelPrivacy::Locked => Permissions::CONNECT,
@@ -128,14 +165,13 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
         },
     ];
 
-    if let Some(access_role_id) = access_role_id {
-        permission_overwrites.push(ChannelPermissionOverwrite {
-            allow: common_permissions,
-            deny: Permissions::empty(),
-            id: access_role_id.cast(),
-            kind: ChannelPermissionOverwriteType::Role,
-        });
-    }
+    apply_join_channel_roles(
+        &mut permission_overwrites,
+        &HashSet::new(),
+        &allowed_role_ids,
+        &denied_role_ids,
+        common_permissions,
+    );
 
     let request = context
         .client
@@ -176,26 +212,67 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
         .insert_join_channel(
             join_channel.id,
             guild_id,
-            access_role_id,
             parent_id,
             permanence,
             privacy.clone(),
+            kind,
         )
         .await?;
+
+    for (role_ids, kind) in [
+        (&allowed_role_ids, JoinChannelRoleKind::Allow),
+        (&denied_role_ids, JoinChannelRoleKind::Deny),
+    ] {
+        for role_id in role_ids {
+            context
+                .database
+                .insert_join_channel_role(join_channel.id, *role_id, kind)
+                .await?;
+        }
+    }
+
     context.cache.insert_join_channel(
-        access_role_id,
+        allowed_role_ids,
+        denied_role_ids,
         join_channel.id,
         guild_id,
+        kind,
         name,
+        DEFAULT_OWNERSHIP_DELAY,
+        OwnershipPolicy::Keep,
         parent_id,
         permanence,
         join_channel.permission_overwrites.unwrap_or_default(),
         privacy,
+        None,
+        None,
+        false,
     );
 
+    audit(
+        &context,
+        &interaction.guild,
+        Some(interaction.user_id),
+        Some(join_channel.id),
+        "/join create",
+        format!(
+            "<@{}> created the join channel <#{}>.",
+            interaction.user_id, join_channel.id
+        ),
+    )
+    .await?;
+
+    let description = match kind {
+        // Moderating a stage takes Manage Channels, which lets its owner edit it by hand too.
+        VoiceChannelKind::Stage => format!(
+            "I have created <#{}>. Owners of its stage channels will have **Manage Channels** on them, as Discord requires it to moderate a stage, so they can edit them by hand. I'll still change back names the name filter rejects and privacy edits made outside of Voz.",
+            join_channel.id
+        ),
+        VoiceChannelKind::Voice => format!("I have created <#{}>.", join_channel.id),
+    };
     let embed = EmbedBuilder::new()
         .color(0xF8F8FF)
-        .description(format!("I have created <#{}>.", join_channel.id))
+        .description(description)
         .build();
 
     context
@@ -1,11 +1,17 @@
 mod access_role;
+mod audit;
+mod block;
 mod category;
 mod create;
 mod name;
+mod ownership;
 mod permanence;
 mod privacy;
 mod remove;
+mod status;
 mod view;
+mod voice_channel_limit;
+mod waiting_room;
 
 use std::{mem::replace, sync::Arc};
 
@@ -25,8 +31,9 @@ pub async fn run(
 ) -> Result<()> {
     let Some(CommandDataOption {
         name,
-        value: CommandOptionValue::SubCommand(options),
-    }) = interaction.data.options.clone().into_iter().nth(0)
+        value:
+            CommandOptionValue::SubCommand(options) | CommandOptionValue::SubCommandGroup(options),
+    }) = interaction.data.options.clone().into_iter().next()
     else {
         let embed = EmbedBuilder::new()
             .color(0xF8F8FF)
@@ -52,13 +59,20 @@ pub async fn run(
 
     match name.as_str() {
         "access-role" => access_role::run(context, interaction).await?,
+        "audit" => audit::run(context, interaction).await?,
+        "block" => block::run(context, interaction, true).await?,
         "category" => category::run(context, interaction).await?,
         "create" => create::run(context, interaction).await?,
         "name" => name::run(context, interaction).await?,
+        "ownership" => ownership::run(context, interaction).await?,
         "permanence" => permanence::run(context, interaction).await?,
         "privacy" => privacy::run(context, interaction).await?,
         "remove" => remove::run(context, interaction).await?,
+        "status" => status::run(context, interaction).await?,
+        "unblock" => block::run(context, interaction, false).await?,
         "view" => view::run(context, interaction).await?,
+        "voice-channel-limit" => voice_channel_limit::run(context, interaction).await?,
+        "waiting-room" => waiting_room::run(context, interaction).await?,
         _ => {
             let embed = EmbedBuilder::new()
                 .color(0xF8F8FF)
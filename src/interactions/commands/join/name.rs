@@ -12,7 +12,10 @@ use twilight_model::{
 };
 use twilight_util::builder::{embed::EmbedBuilder, InteractionResponseDataBuilder};
 
-use crate::structs::{context::Context, interaction::ApplicationCommandInteraction};
+use crate::{
+    structs::{context::Context, interaction::ApplicationCommandInteraction},
+    utilities::audit::audit,
+};
 
 pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteraction) -> Result<()> {
     let channel_value = match interaction
@@ -32,9 +35,7 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
                 .clone()
                 .into_iter()
                 .filter_map(|channel_id| {
-                    let Some(join_channel) = context.cache.join_channel(channel_id) else {
-                        return None;
-                    };
+                    let join_channel = context.cache.join_channel(channel_id)?;
                     let name = join_channel.name.read().clone();
 
                     if !name.contains(&lowercased_value) {
@@ -163,6 +164,19 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
         return Ok(());
     };
 
+    audit(
+        &context,
+        &interaction.guild,
+        Some(interaction.user_id),
+        Some(channel_id),
+        "/join name",
+        format!(
+            "<@{}> renamed <#{channel_id}> to **{name}**.",
+            interaction.user_id
+        ),
+    )
+    .await?;
+
     let embed = EmbedBuilder::new()
         .color(0xF8F8FF)
         .description(format!("I have renamed <#{channel_id}>."))
@@ -7,24 +7,21 @@ use twilight_model::{
         interaction::application_command::CommandOptionValue,
     },
     channel::message::MessageFlags,
-    guild::Permissions,
-    http::{
-        interaction::{InteractionResponse, InteractionResponseType},
-        permission_overwrite::{
-            PermissionOverwrite as HttpPermissionOverwrite,
-            PermissionOverwriteType as HttpPermissionOverwriteType,
-        },
-    },
-    id::{
-        marker::{ChannelMarker, RoleMarker},
-        Id,
-    },
+    http::interaction::{InteractionResponse, InteractionResponseType},
+    id::{marker::ChannelMarker, Id},
 };
 use twilight_util::builder::{embed::EmbedBuilder, InteractionResponseDataBuilder};
 
-use crate::structs::{
-    cache::CachedJoinChannelUpdate, context::Context, database::ChannelPrivacy,
-    interaction::ApplicationCommandInteraction,
+use crate::{
+    structs::{
+        cache::CachedJoinChannelUpdate, context::Context, database::OwnershipPolicy,
+        interaction::ApplicationCommandInteraction,
+    },
+    utilities::{
+        audit::audit,
+        constants::{DEFAULT_OWNERSHIP_DELAY, MAX_OWNERSHIP_DELAY},
+        time::{humanize, parse},
+    },
 };
 
 pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteraction) -> Result<()> {
@@ -45,9 +42,7 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
                 .clone()
                 .into_iter()
                 .filter_map(|channel_id| {
-                    let Some(join_channel) = context.cache.join_channel(channel_id) else {
-                        return None;
-                    };
+                    let join_channel = context.cache.join_channel(channel_id)?;
                     let name = join_channel.name.read().clone();
 
                     if !name.contains(&lowercased_value) {
@@ -113,7 +108,6 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
 
         return Ok(());
     };
-
     let Some(join_channel) = context.cache.join_channel(channel_id) else {
         let embed = EmbedBuilder::new()
             .color(0xF8F8FF)
@@ -128,26 +122,37 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
 
         return Ok(());
     };
-    let mut access_role_id: Option<Id<RoleMarker>> = None;
-
-    if let Some(role_option) = interaction
+    let ownership_policy = match interaction
         .data
         .options
         .iter()
-        .find(|&option| option.name.eq("role"))
-        .cloned()
+        .find(|&option| option.name.eq("policy"))
+        .map(|option| &option.value)
     {
-        if let CommandOptionValue::Role(value) = role_option.value {
-            access_role_id = Some(value);
+        Some(CommandOptionValue::String(value)) if value.eq("claimable") => {
+            OwnershipPolicy::Claimable
         }
+        Some(CommandOptionValue::String(value)) if value.eq("transfer") => {
+            OwnershipPolicy::Transfer
+        }
+        _ => OwnershipPolicy::Keep,
     };
-
-    let known_access_role_id = join_channel.access_role_id.read().clone();
-
-    if known_access_role_id.eq(&access_role_id) {
+    let ownership_delay = match interaction
+        .data
+        .options
+        .iter()
+        .find(|&option| option.name.eq("delay"))
+        .map(|option| &option.value)
+    {
+        Some(CommandOptionValue::String(value)) => parse(value)
+            .filter(|seconds| seconds.le(&MAX_OWNERSHIP_DELAY))
+            .map(|seconds| seconds as u32),
+        _ => Some(DEFAULT_OWNERSHIP_DELAY),
+    };
+    let Some(ownership_delay) = ownership_delay else {
         let embed = EmbedBuilder::new()
             .color(0xF8F8FF)
-            .description("No changes have been made.")
+            .description("I could not find a valid **delay** value.")
             .build();
 
         context
@@ -157,62 +162,84 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
             .await?;
 
         return Ok(());
-    }
-
-    let mut embed_builder = EmbedBuilder::new().color(0xF8F8FF);
-
-    if let Some(known_access_role_id) = known_access_role_id {
-        context
-            .client
-            .delete_channel_permission(channel_id)
-            .role(known_access_role_id)
-            .await?;
+    };
 
-        embed_builder = embed_builder.description(format!(
-            "The access role for <#{channel_id}> has been removed."
-        ));
-    } else {
-        let access_role_id = access_role_id.unwrap();
-        let access_role_permissions = match join_channel.privacy.read().clone() {
-            ChannelPrivacy::Invisible => Permissions::VIEW_CHANNEL,
-            ChannelPrivacy::Locked => Permissions::CONNECT,
-            ChannelPrivacy::Unlocked => Permissions::empty(),
-        };
+    if join_channel.ownership_policy.read().eq(&ownership_policy)
+        && join_channel.ownership_delay.read().eq(&ownership_delay)
+    {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description("No changes have been made.")
+            .build();
 
         context
-            .client
-            .update_channel_permission(
-                join_channel.id,
-                &HttpPermissionOverwrite {
-                    allow: Some(access_role_permissions),
-                    deny: None,
-                    id: access_role_id.cast(),
-                    kind: HttpPermissionOverwriteType::Role,
-                },
-            )
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
             .await?;
 
-        embed_builder = embed_builder.description(format!(
-            "The access role for <#{channel_id}> is now <@&{access_role_id}>."
-        ));
+        return Ok(());
     }
 
     context
         .database
-        .update_join_channel_access_role_id(channel_id, access_role_id)
+        .update_join_channel_ownership(channel_id, ownership_policy, ownership_delay)
         .await?;
     context.cache.update_join_channel(
         channel_id,
         CachedJoinChannelUpdate {
-            access_role_id: Some(access_role_id),
+            ownership_delay: Some(ownership_delay),
+            ownership_policy: Some(ownership_policy),
             ..Default::default()
         },
     );
+
+    audit(
+        &context,
+        &interaction.guild,
+        Some(interaction.user_id),
+        Some(channel_id),
+        "/join ownership",
+        format!(
+            "<@{}> changed the ownership policy of <#{channel_id}>, so **new** voice channels will now {}.",
+            interaction.user_id,
+            ownership_text(ownership_policy, ownership_delay)
+        ),
+    )
+    .await?;
+
+    let embed = EmbedBuilder::new()
+        .color(0xF8F8FF)
+        .description(format!(
+            "**New** voice channels from <#{channel_id}> will now {}.",
+            ownership_text(ownership_policy, ownership_delay)
+        ))
+        .build();
+
     context
         .interaction_client()
         .update_response(&interaction.token)
-        .embeds(Some(&[embed_builder.build()]))
+        .embeds(Some(&[embed]))
         .await?;
 
     Ok(())
 }
+
+/// Describes an ownership policy as the rest of a sentence about new voice channels.
+pub fn ownership_text(ownership_policy: OwnershipPolicy, ownership_delay: u32) -> String {
+    let delay_text = if ownership_delay.eq(&0) {
+        "immediately".to_owned()
+    } else {
+        format!("after **{}**", humanize(ownership_delay.into()))
+    };
+
+    match ownership_policy {
+        OwnershipPolicy::Claimable => {
+            format!("become claimable {delay_text} once their owner leaves")
+        }
+        OwnershipPolicy::Keep => "keep their owner when the owner leaves".to_owned(),
+        OwnershipPolicy::Transfer => {
+            format!("pass to the longest-connected member {delay_text} once their owner leaves")
+        }
+    }
+}
@@ -12,8 +12,12 @@ use twilight_model::{
 };
 use twilight_util::builder::{embed::EmbedBuilder, InteractionResponseDataBuilder};
 
-use crate::structs::{
-    cache::CachedJoinChannelUpdate, context::Context, interaction::ApplicationCommandInteraction,
+use crate::{
+    structs::{
+        cache::CachedJoinChannelUpdate, context::Context,
+        interaction::ApplicationCommandInteraction,
+    },
+    utilities::audit::audit,
 };
 
 pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteraction) -> Result<()> {
@@ -34,9 +38,7 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
                 .clone()
                 .into_iter()
                 .filter_map(|channel_id| {
-                    let Some(join_channel) = context.cache.join_channel(channel_id) else {
-                        return None;
-                    };
+                    let join_channel = context.cache.join_channel(channel_id)?;
                     let name = join_channel.name.read().clone();
 
                     if !name.contains(&lowercased_value) {
@@ -156,6 +158,19 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
         },
     );
 
+    audit(
+        &context,
+        &interaction.guild,
+        Some(interaction.user_id),
+        Some(channel_id),
+        "/join permanence",
+        format!(
+            "<@{}> set the default permanence value of <#{channel_id}> to **{permanence}**.",
+            interaction.user_id
+        ),
+    )
+    .await?;
+
     let embed = EmbedBuilder::new()
         .color(0xF8F8FF)
         .description(format!(
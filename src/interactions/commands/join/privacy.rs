@@ -16,9 +16,12 @@ use twilight_model::{
 };
 use twilight_util::builder::{embed::EmbedBuilder, InteractionResponseDataBuilder};
 
-use crate::structs::{
-    cache::CachedJoinChannelUpdate, context::Context, database::ChannelPrivacy,
-    interaction::ApplicationCommandInteraction,
+use crate::{
+    structs::{
+        cache::CachedJoinChannelUpdate, context::Context, database::ChannelPrivacy,
+        interaction::ApplicationCommandInteraction,
+    },
+    utilities::audit::audit,
 };
 
 pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteraction) -> Result<()> {
@@ -39,9 +42,7 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
                 .clone()
                 .into_iter()
                 .filter_map(|channel_id| {
-                    let Some(join_channel) = context.cache.join_channel(channel_id) else {
-                        return None;
-                    };
+                    let join_channel = context.cache.join_channel(channel_id)?;
                     let name = join_channel.name.read().clone();
 
                     if !name.contains(&lowercased_value) {
@@ -177,9 +178,10 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
             permission_overwrite.deny = common_permissions;
         }
         if join_channel
-            .access_role_id
+            .allowed_role_ids
             .read()
-            .is_some_and(|access_role_id| permission_overwrite.id.eq(&access_role_id.cast()))
+            .iter()
+            .any(|role_id| permission_overwrite.id.eq(&role_id.cast()))
         {
             permission_overwrite.allow = common_permissions;
         }
@@ -218,6 +220,19 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
         },
     );
 
+    audit(
+        &context,
+        &interaction.guild,
+        Some(interaction.user_id),
+        Some(channel_id),
+        "/join privacy",
+        format!(
+            "<@{}> set the default privacy level of <#{channel_id}> to **{privacy_text}**.",
+            interaction.user_id
+        ),
+    )
+    .await?;
+
     let embed = EmbedBuilder::new()
         .color(0xF8F8FF)
         .description(format!(
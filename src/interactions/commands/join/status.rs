@@ -0,0 +1,282 @@
+use std::{str::FromStr, sync::Arc};
+
+use eyre::Result;
+use twilight_model::{
+    application::{
+        command::{CommandOptionChoice, CommandOptionChoiceValue},
+        interaction::application_command::CommandOptionValue,
+    },
+    channel::message::MessageFlags,
+    http::interaction::{InteractionResponse, InteractionResponseType},
+    id::{marker::ChannelMarker, Id},
+};
+use twilight_util::builder::{embed::EmbedBuilder, InteractionResponseDataBuilder};
+
+use crate::{
+    structs::{
+        cache::CachedJoinChannelUpdate, context::Context,
+        interaction::ApplicationCommandInteraction,
+    },
+    utilities::audit::audit,
+};
+
+pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteraction) -> Result<()> {
+    let channel_value = match interaction
+        .data
+        .options
+        .iter()
+        .find(|&option| option.name.eq("channel"))
+        .cloned()
+        .map(|option| option.value)
+    {
+        Some(CommandOptionValue::Focused(value, _)) => {
+            let lowercased_value = value.to_ascii_lowercase();
+            let mut filtered_join_channels = interaction
+                .guild
+                .join_channel_ids
+                .read()
+                .clone()
+                .into_iter()
+                .filter_map(|channel_id| {
+                    let join_channel = context.cache.join_channel(channel_id)?;
+                    let name = join_channel.name.read().clone();
+
+                    if !name.contains(&lowercased_value) {
+                        return None;
+                    }
+
+                    Some((name, join_channel.id.to_string()))
+                })
+                .collect::<Vec<(String, String)>>();
+
+            filtered_join_channels.sort();
+
+            let choices = filtered_join_channels
+                .into_iter()
+                .map(|join_channel| CommandOptionChoice {
+                    name: join_channel.0,
+                    name_localizations: None,
+                    value: CommandOptionChoiceValue::String(join_channel.1),
+                })
+                .collect::<Vec<CommandOptionChoice>>();
+            let data = InteractionResponseDataBuilder::new()
+                .choices(choices)
+                .build();
+            let interaction_response = InteractionResponse {
+                data: Some(data),
+                kind: InteractionResponseType::ApplicationCommandAutocompleteResult,
+            };
+
+            context
+                .interaction_client()
+                .create_response(interaction.id, &interaction.token, &interaction_response)
+                .await?;
+
+            return Ok(());
+        }
+        Some(CommandOptionValue::String(value)) => value,
+        _ => return Ok(()),
+    };
+    let interaction_response_data = InteractionResponseDataBuilder::new()
+        .flags(MessageFlags::EPHEMERAL)
+        .build();
+    let interaction_response = InteractionResponse {
+        data: Some(interaction_response_data),
+        kind: InteractionResponseType::DeferredChannelMessageWithSource,
+    };
+
+    context
+        .interaction_client()
+        .create_response(interaction.id, &interaction.token, &interaction_response)
+        .await?;
+
+    let Ok(channel_id) = Id::<ChannelMarker>::from_str(&channel_value) else {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description("I could not find a valid **channel** value.")
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    };
+    let Some(join_channel) = context.cache.join_channel(channel_id) else {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description("I do not recognize this join channel.")
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    };
+    let template = interaction
+        .data
+        .options
+        .iter()
+        .find(|&option| option.name.eq("template"))
+        .and_then(|option| match &option.value {
+            CommandOptionValue::String(value) => Some(value.trim().to_owned()),
+            _ => None,
+        })
+        .filter(|value| !value.is_empty());
+
+    if join_channel.status_template.read().eq(&template) {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description("No changes have been made.")
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    }
+
+    context
+        .database
+        .update_join_channel_status_template(channel_id, template.as_deref())
+        .await?;
+    context.cache.update_join_channel(
+        channel_id,
+        CachedJoinChannelUpdate {
+            status_template: Some(template.clone()),
+            ..Default::default()
+        },
+    );
+
+    audit(
+        &context,
+        &interaction.guild,
+        Some(interaction.user_id),
+        Some(channel_id),
+        "/join status",
+        match &template {
+            Some(template) => format!(
+                "<@{}> set the default status of <#{channel_id}> to **{template}**.",
+                interaction.user_id
+            ),
+            None => format!(
+                "<@{}> removed the default status of <#{channel_id}>.",
+                interaction.user_id
+            ),
+        },
+    )
+    .await?;
+
+    let description = match template {
+        Some(template) => format!(
+            "**New** voice channels from <#{channel_id}> will now start with the status **{template}**."
+        ),
+        None => format!("**New** voice channels from <#{channel_id}> will no longer start with a status."),
+    };
+    let embed = EmbedBuilder::new()
+        .color(0xF8F8FF)
+        .description(description)
+        .build();
+
+    context
+        .interaction_client()
+        .update_response(&interaction.token)
+        .embeds(Some(&[embed]))
+        .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use twilight_model::id::Id;
+
+    use crate::{
+        structs::database::ChannelPrivacy,
+        testing::{
+            self,
+            mock_discord::MockDiscord,
+            payloads::{command, subcommand},
+            replay::replay,
+            seed_join_channel, VOICE_CHANNEL_ID,
+        },
+    };
+
+    #[tokio::test]
+    #[ignore = "needs a database, run with `cargo test -- --ignored`"]
+    async fn new_voice_channels_start_with_the_status_template() {
+        let mock_discord = MockDiscord::start().await;
+        let (context, _guard) = testing::context(&mock_discord).await;
+        let guild_id = Id::new(1001);
+        let (guild_create, create) = include_str!("../../../testing/recordings/create.jsonl")
+            .split_once('\n')
+            .unwrap();
+
+        seed_join_channel(&context, guild_id, Id::new(1101), ChannelPrivacy::Unlocked).await;
+        replay(
+            &context,
+            &[
+                guild_create.to_owned(),
+                command(
+                    "status",
+                    1001,
+                    2001,
+                    subcommand(
+                        "join",
+                        "status",
+                        json!([
+                            { "name": "channel", "type": 3, "value": "1101" },
+                            { "name": "template", "type": 3, "value": "{owner} is studying" },
+                        ]),
+                    ),
+                ),
+                create.to_owned(),
+            ]
+            .join("\n"),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            mock_discord.response_descriptions("status"),
+            ["**New** voice channels from <#1101> will now start with the status **{owner} is studying**."]
+        );
+        assert_eq!(
+            mock_discord
+                .assert_requested("PUT", "/channels/5000/voice-status")
+                .body["status"],
+            "alex is studying"
+        );
+        assert_eq!(
+            context
+                .cache
+                .voice_channel(VOICE_CHANNEL_ID)
+                .unwrap()
+                .status
+                .read()
+                .as_deref(),
+            Some("alex is studying")
+        );
+        assert_eq!(
+            context
+                .database
+                .guild_voice_channels(guild_id)
+                .await
+                .unwrap()[0]
+                .status
+                .as_deref(),
+            Some("alex is studying")
+        );
+
+        context.database.remove_guild(guild_id).await.unwrap();
+    }
+}
@@ -12,8 +12,11 @@ use twilight_model::{
 };
 use twilight_util::builder::{embed::EmbedBuilder, InteractionResponseDataBuilder};
 
+use super::{access_role::role_mentions, ownership::ownership_text};
 use crate::structs::{
-    context::Context, database::ChannelPrivacy, interaction::ApplicationCommandInteraction,
+    context::Context,
+    database::{ChannelPrivacy, VoiceChannelKind},
+    interaction::ApplicationCommandInteraction,
 };
 
 pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteraction) -> Result<()> {
@@ -34,9 +37,7 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
                 .clone()
                 .into_iter()
                 .filter_map(|channel_id| {
-                    let Some(join_channel) = context.cache.join_channel(channel_id) else {
-                        return None;
-                    };
+                    let join_channel = context.cache.join_channel(channel_id)?;
                     let name = join_channel.name.read().clone();
 
                     if !name.contains(&lowercased_value) {
@@ -117,19 +118,29 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
 
         return Ok(());
     };
-    let access_role_text = join_channel
-        .access_role_id
-        .read()
-        .map_or("No access role set.".to_owned(), |access_role_id| {
-            format!("<@&{access_role_id}>")
-        });
+    let allowed_roles_text = role_mentions(join_channel.allowed_role_ids.read().clone());
+    let denied_roles_text = role_mentions(join_channel.denied_role_ids.read().clone());
     let category_text = join_channel
         .parent_id
         .read()
         .map_or("No category set.".to_owned(), |parent_id| {
             format!("<#{parent_id}>")
         });
+    let kind_text = format!(
+        "<#{channel_id}> creates **{}**.",
+        match join_channel.kind {
+            VoiceChannelKind::Stage => "stage channels",
+            VoiceChannelKind::Voice => "voice channels",
+        }
+    );
     let permanence_text = format!("**New** voice channels from <#{channel_id}> will now have a default permanence value of **{}**.", join_channel.permanence.read());
+    let ownership_text = format!(
+        "**New** voice channels from <#{channel_id}> will now {}.",
+        ownership_text(
+            *join_channel.ownership_policy.read(),
+            *join_channel.ownership_delay.read()
+        )
+    );
     let privacy_text_clause = match join_channel.privacy.read().clone() {
         ChannelPrivacy::Invisible => "invisible",
         ChannelPrivacy::Locked => "locked (and visible)",
@@ -138,9 +149,29 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
     let privacy_text = format!(
         "**New** voice channels from <#{channel_id}> will now be **{privacy_text_clause}**."
     );
+    let status_text = match join_channel.status_template.read().as_deref() {
+        Some(status_template) => format!(
+            "**New** voice channels from <#{channel_id}> will now start with the status **{status_template}**."
+        ),
+        None => format!("**New** voice channels from <#{channel_id}> will not start with a status."),
+    };
+    let voice_channel_limit_text = match *join_channel.voice_channel_limit.read() {
+        Some(voice_channel_limit) => format!(
+            "<#{channel_id}> may have up to **{voice_channel_limit}** voice channels at once."
+        ),
+        None => format!("<#{channel_id}> may have any number of voice channels at once."),
+    };
+    let waiting_room_text = format!(
+        "**New** locked voice channels from <#{channel_id}> will {} a waiting room.",
+        if *join_channel.waiting_room.read() {
+            "now have"
+        } else {
+            "not have"
+        }
+    );
     let embed = EmbedBuilder::new()
         .color(0xF8F8FF)
-        .description(format!("**Access role:** {access_role_text}\n**Category:** {category_text}\n**Permanence:** {permanence_text}\n**Privacy:** {privacy_text}"))
+        .description(format!("**Allowed roles:** {allowed_roles_text}\n**Category:** {category_text}\n**Denied roles:** {denied_roles_text}\n**Kind:** {kind_text}\n**Ownership:** {ownership_text}\n**Permanence:** {permanence_text}\n**Privacy:** {privacy_text}\n**Status:** {status_text}\n**Voice channel limit:** {voice_channel_limit_text}\n**Waiting room:** {waiting_room_text}"))
         .title(join_channel.name.read().clone())
         .build();
 
@@ -0,0 +1,302 @@
+use std::{str::FromStr, sync::Arc};
+
+use eyre::Result;
+use twilight_model::{
+    application::{
+        command::{CommandOptionChoice, CommandOptionChoiceValue},
+        interaction::application_command::CommandOptionValue,
+    },
+    channel::message::MessageFlags,
+    http::interaction::{InteractionResponse, InteractionResponseType},
+    id::{marker::ChannelMarker, Id},
+};
+use twilight_util::builder::{embed::EmbedBuilder, InteractionResponseDataBuilder};
+
+use crate::{
+    structs::{
+        cache::CachedJoinChannelUpdate, context::Context,
+        interaction::ApplicationCommandInteraction,
+    },
+    utilities::audit::audit,
+};
+
+pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteraction) -> Result<()> {
+    let channel_value = match interaction
+        .data
+        .options
+        .iter()
+        .find(|&option| option.name.eq("channel"))
+        .cloned()
+        .map(|option| option.value)
+    {
+        Some(CommandOptionValue::Focused(value, _)) => {
+            let lowercased_value = value.to_ascii_lowercase();
+            let mut filtered_join_channels = interaction
+                .guild
+                .join_channel_ids
+                .read()
+                .clone()
+                .into_iter()
+                .filter_map(|channel_id| {
+                    let join_channel = context.cache.join_channel(channel_id)?;
+                    let name = join_channel.name.read().clone();
+
+                    if !name.contains(&lowercased_value) {
+                        return None;
+                    }
+
+                    Some((name, join_channel.id.to_string()))
+                })
+                .collect::<Vec<(String, String)>>();
+
+            filtered_join_channels.sort();
+
+            let choices = filtered_join_channels
+                .into_iter()
+                .map(|join_channel| CommandOptionChoice {
+                    name: join_channel.0,
+                    name_localizations: None,
+                    value: CommandOptionChoiceValue::String(join_channel.1),
+                })
+                .collect::<Vec<CommandOptionChoice>>();
+            let data = InteractionResponseDataBuilder::new()
+                .choices(choices)
+                .build();
+            let interaction_response = InteractionResponse {
+                data: Some(data),
+                kind: InteractionResponseType::ApplicationCommandAutocompleteResult,
+            };
+
+            context
+                .interaction_client()
+                .create_response(interaction.id, &interaction.token, &interaction_response)
+                .await?;
+
+            return Ok(());
+        }
+        Some(CommandOptionValue::String(value)) => value,
+        _ => return Ok(()),
+    };
+    let interaction_response_data = InteractionResponseDataBuilder::new()
+        .flags(MessageFlags::EPHEMERAL)
+        .build();
+    let interaction_response = InteractionResponse {
+        data: Some(interaction_response_data),
+        kind: InteractionResponseType::DeferredChannelMessageWithSource,
+    };
+
+    context
+        .interaction_client()
+        .create_response(interaction.id, &interaction.token, &interaction_response)
+        .await?;
+
+    let Ok(channel_id) = Id::<ChannelMarker>::from_str(&channel_value) else {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description("I could not find a valid **channel** value.")
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    };
+    let Some(join_channel) = context.cache.join_channel(channel_id) else {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description("I do not recognize this join channel.")
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    };
+    let voice_channel_limit = interaction
+        .data
+        .options
+        .iter()
+        .find(|&option| option.name.eq("limit"))
+        .and_then(|option| match option.value {
+            CommandOptionValue::Integer(limit) => u16::try_from(limit).ok(),
+            _ => None,
+        });
+
+    if join_channel
+        .voice_channel_limit
+        .read()
+        .eq(&voice_channel_limit)
+    {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description("No changes have been made.")
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    }
+
+    context
+        .database
+        .update_join_channel_voice_channel_limit(channel_id, voice_channel_limit)
+        .await?;
+    context.cache.update_join_channel(
+        channel_id,
+        CachedJoinChannelUpdate {
+            voice_channel_limit: Some(voice_channel_limit),
+            ..Default::default()
+        },
+    );
+
+    let (audit_description, description) = match voice_channel_limit {
+        Some(voice_channel_limit) => (
+            format!(
+                "<@{}> limited <#{channel_id}> to **{voice_channel_limit}** voice channels at once.",
+                interaction.user_id
+            ),
+            format!(
+                "<#{channel_id}> may now have up to **{voice_channel_limit}** voice channels at once."
+            ),
+        ),
+        None => (
+            format!(
+                "<@{}> removed the voice channel limit of <#{channel_id}>.",
+                interaction.user_id
+            ),
+            format!("<#{channel_id}> may now have any number of voice channels at once."),
+        ),
+    };
+
+    audit(
+        &context,
+        &interaction.guild,
+        Some(interaction.user_id),
+        Some(channel_id),
+        "/join voice-channel-limit",
+        audit_description,
+    )
+    .await?;
+
+    let embed = EmbedBuilder::new()
+        .color(0xF8F8FF)
+        .description(description)
+        .build();
+
+    context
+        .interaction_client()
+        .update_response(&interaction.token)
+        .embeds(Some(&[embed]))
+        .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+    use twilight_model::id::Id;
+
+    use crate::{
+        structs::database::ChannelPrivacy,
+        testing::{
+            self,
+            mock_discord::MockDiscord,
+            payloads::{command, subcommand, voice_state_update},
+            replay::replay,
+            seed_join_channel,
+        },
+    };
+
+    #[tokio::test]
+    #[ignore = "needs a database, run with `cargo test -- --ignored`"]
+    async fn full_join_channels_turn_members_away() {
+        let mock_discord = MockDiscord::start().await;
+        let (context, _guard) = testing::context(&mock_discord).await;
+        let guild_id = Id::new(1001);
+        let (guild_create, create) = include_str!("../../../testing/recordings/create.jsonl")
+            .split_once('\n')
+            .unwrap();
+        let limit = json!([
+            { "name": "channel", "type": 3, "value": "1101" },
+            { "name": "limit", "type": 4, "value": 1 },
+        ]);
+
+        seed_join_channel(&context, guild_id, Id::new(1101), ChannelPrivacy::Unlocked).await;
+        replay(
+            &context,
+            &[
+                guild_create.to_owned(),
+                command(
+                    "limit",
+                    1001,
+                    1,
+                    subcommand("join", "voice-channel-limit", limit.clone()),
+                ),
+                command(
+                    "limit-again",
+                    1001,
+                    1,
+                    subcommand("join", "voice-channel-limit", limit),
+                ),
+                create.to_owned(),
+                voice_state_update(1001, 2002, "sam", Some(1101)),
+            ]
+            .join("\n"),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            mock_discord.response_descriptions("limit"),
+            ["<#1101> may now have up to **1** voice channels at once."]
+        );
+        assert_eq!(
+            mock_discord.response_descriptions("limit-again"),
+            ["No changes have been made."]
+        );
+        assert!(mock_discord.channel(5000).is_some());
+        assert_eq!(
+            mock_discord
+                .assert_requested("PATCH", "/guilds/1001/members/2002")
+                .body["channel_id"],
+            Value::Null
+        );
+        // The DM channel takes the next id.
+        assert_eq!(
+            mock_discord.requests_to("POST", "/channels/5001/messages")[0].body["embeds"][0]
+                ["description"],
+            "**join** already has as many voice channels as it allows. Try again once one has closed."
+        );
+
+        let voice_channels = context
+            .database
+            .guild_voice_channels(guild_id)
+            .await
+            .unwrap();
+
+        assert_eq!(voice_channels.len(), 1);
+        assert_eq!(voice_channels[0].join_channel_id, Some(Id::new(1101)));
+        assert_eq!(
+            context
+                .database
+                .guild_join_channels(guild_id)
+                .await
+                .unwrap()[0]
+                .voice_channel_limit,
+            Some(1)
+        );
+
+        context.database.remove_guild(guild_id).await.unwrap();
+    }
+}
@@ -0,0 +1,387 @@
+use std::{str::FromStr, sync::Arc};
+
+use eyre::Result;
+use twilight_model::{
+    application::{
+        command::{CommandOptionChoice, CommandOptionChoiceValue},
+        interaction::application_command::CommandOptionValue,
+    },
+    channel::message::MessageFlags,
+    http::interaction::{InteractionResponse, InteractionResponseType},
+    id::{marker::ChannelMarker, Id},
+};
+use twilight_util::builder::{embed::EmbedBuilder, InteractionResponseDataBuilder};
+
+use crate::{
+    structs::{
+        cache::CachedJoinChannelUpdate, context::Context,
+        interaction::ApplicationCommandInteraction,
+    },
+    utilities::audit::audit,
+};
+
+pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteraction) -> Result<()> {
+    let channel_value = match interaction
+        .data
+        .options
+        .iter()
+        .find(|&option| option.name.eq("channel"))
+        .cloned()
+        .map(|option| option.value)
+    {
+        Some(CommandOptionValue::Focused(value, _)) => {
+            let lowercased_value = value.to_ascii_lowercase();
+            let mut filtered_join_channels = interaction
+                .guild
+                .join_channel_ids
+                .read()
+                .clone()
+                .into_iter()
+                .filter_map(|channel_id| {
+                    let join_channel = context.cache.join_channel(channel_id)?;
+                    let name = join_channel.name.read().clone();
+
+                    if !name.contains(&lowercased_value) {
+                        return None;
+                    }
+
+                    Some((name, join_channel.id.to_string()))
+                })
+                .collect::<Vec<(String, String)>>();
+
+            filtered_join_channels.sort();
+
+            let choices = filtered_join_channels
+                .into_iter()
+                .map(|join_channel| CommandOptionChoice {
+                    name: join_channel.0,
+                    name_localizations: None,
+                    value: CommandOptionChoiceValue::String(join_channel.1),
+                })
+                .collect::<Vec<CommandOptionChoice>>();
+            let data = InteractionResponseDataBuilder::new()
+                .choices(choices)
+                .build();
+            let interaction_response = InteractionResponse {
+                data: Some(data),
+                kind: InteractionResponseType::ApplicationCommandAutocompleteResult,
+            };
+
+            context
+                .interaction_client()
+                .create_response(interaction.id, &interaction.token, &interaction_response)
+                .await?;
+
+            return Ok(());
+        }
+        Some(CommandOptionValue::String(value)) => value,
+        _ => return Ok(()),
+    };
+    let interaction_response_data = InteractionResponseDataBuilder::new()
+        .flags(MessageFlags::EPHEMERAL)
+        .build();
+    let interaction_response = InteractionResponse {
+        data: Some(interaction_response_data),
+        kind: InteractionResponseType::DeferredChannelMessageWithSource,
+    };
+
+    context
+        .interaction_client()
+        .create_response(interaction.id, &interaction.token, &interaction_response)
+        .await?;
+
+    let Ok(channel_id) = Id::<ChannelMarker>::from_str(&channel_value) else {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description("I could not find a valid **channel** value.")
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    };
+    let Some(join_channel) = context.cache.join_channel(channel_id) else {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description("I do not recognize this join channel.")
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    };
+    let mut waiting_room = false;
+
+    if let Some(enabled_option) = interaction
+        .data
+        .options
+        .iter()
+        .find(|&option| option.name.eq("enabled"))
+        .cloned()
+    {
+        if let CommandOptionValue::Boolean(enabled) = enabled_option.value {
+            waiting_room = enabled;
+        }
+    };
+    if join_channel.waiting_room.read().eq(&waiting_room) {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description("No changes have been made.")
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    }
+
+    context
+        .database
+        .update_join_channel_waiting_room(channel_id, waiting_room)
+        .await?;
+    context.cache.update_join_channel(
+        channel_id,
+        CachedJoinChannelUpdate {
+            waiting_room: Some(waiting_room),
+            ..Default::default()
+        },
+    );
+
+    audit(
+        &context,
+        &interaction.guild,
+        Some(interaction.user_id),
+        Some(channel_id),
+        "/join waiting-room",
+        format!(
+            "<@{}> turned waiting rooms {} for <#{channel_id}>.",
+            interaction.user_id,
+            if waiting_room { "on" } else { "off" }
+        ),
+    )
+    .await?;
+
+    let description = if waiting_room {
+        format!("**New** locked voice channels from <#{channel_id}> will now have a waiting room.")
+    } else {
+        format!(
+            "**New** locked voice channels from <#{channel_id}> will no longer have a waiting room."
+        )
+    };
+    let embed = EmbedBuilder::new()
+        .color(0xF8F8FF)
+        .description(description)
+        .build();
+
+    context
+        .interaction_client()
+        .update_response(&interaction.token)
+        .embeds(Some(&[embed]))
+        .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use twilight_model::id::Id;
+
+    use crate::{
+        structs::database::ChannelPrivacy,
+        testing::{
+            self,
+            mock_discord::MockDiscord,
+            payloads::{channel_delete, command, component, subcommand, voice_state_update},
+            replay::replay,
+            seed_join_channel,
+        },
+    };
+
+    #[tokio::test]
+    #[ignore = "needs a database, run with `cargo test -- --ignored`"]
+    async fn waiting_room_knocks_and_goes_with_its_voice_channel() {
+        let mock_discord = MockDiscord::start().await;
+        let (context, _guard) = testing::context(&mock_discord).await;
+        let guild_id = Id::new(1001);
+        let (guild_create, create) = include_str!("../../../testing/recordings/create.jsonl")
+            .split_once('\n')
+            .unwrap();
+
+        seed_join_channel(&context, guild_id, Id::new(1101), ChannelPrivacy::Locked).await;
+        replay(
+            &context,
+            &[
+                guild_create.to_owned(),
+                command(
+                    "waiting-room",
+                    1001,
+                    2001,
+                    subcommand(
+                        "join",
+                        "waiting-room",
+                        json!([
+                            { "name": "channel", "type": 3, "value": "1101" },
+                            { "name": "enabled", "type": 5, "value": true },
+                        ]),
+                    ),
+                ),
+                create.to_owned(),
+            ]
+            .join("\n"),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            mock_discord.response_descriptions("waiting-room"),
+            ["**New** locked voice channels from <#1101> will now have a waiting room."]
+        );
+
+        let waiting_room = mock_discord.channel(5001).unwrap();
+
+        assert_eq!(waiting_room["name"], "alex's voice (waiting)");
+        assert_eq!(
+            context
+                .cache
+                .voice_channel(Id::new(5000))
+                .unwrap()
+                .waiting_room_id
+                .read()
+                .map(Id::get),
+            Some(5001)
+        );
+
+        replay(
+            &context,
+            &[
+                voice_state_update(1001, 2002, "sam", Some(5001)),
+                component("accept", 1001, 2001, 5000, "knock:accept:2002"),
+            ]
+            .join("\n"),
+        )
+        .await
+        .unwrap();
+
+        mock_discord.assert_requested("POST", "/channels/5000/messages");
+        assert_eq!(
+            mock_discord
+                .assert_requested("PATCH", "/guilds/1001/members/2002")
+                .body["channel_id"],
+            "5000"
+        );
+
+        replay(
+            &context,
+            &channel_delete(mock_discord.channel(5000).unwrap()),
+        )
+        .await
+        .unwrap();
+
+        mock_discord.assert_requested("DELETE", "/channels/5001");
+
+        context.database.remove_guild(guild_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a database, run with `cargo test -- --ignored`"]
+    async fn waiting_rooms_follow_the_privacy_level() {
+        let mock_discord = MockDiscord::start().await;
+        let (context, _guard) = testing::context(&mock_discord).await;
+        let guild_id = Id::new(1001);
+        let (guild_create, create) = include_str!("../../../testing/recordings/create.jsonl")
+            .split_once('\n')
+            .unwrap();
+        let privacy_command = |token: &str, level: &str| {
+            command(
+                token,
+                1001,
+                2001,
+                subcommand(
+                    "voice",
+                    "privacy",
+                    json!([{ "name": "level", "type": 3, "value": level }]),
+                ),
+            )
+        };
+        let waiting_room_id = || {
+            *context
+                .cache
+                .voice_channel(Id::new(5000))
+                .unwrap()
+                .waiting_room_id
+                .read()
+        };
+
+        seed_join_channel(&context, guild_id, Id::new(1101), ChannelPrivacy::Unlocked).await;
+        replay(
+            &context,
+            &[
+                guild_create.to_owned(),
+                command(
+                    "waiting-room",
+                    1001,
+                    2001,
+                    subcommand(
+                        "join",
+                        "waiting-room",
+                        json!([
+                            { "name": "channel", "type": 3, "value": "1101" },
+                            { "name": "enabled", "type": 5, "value": true },
+                        ]),
+                    ),
+                ),
+                create.to_owned(),
+            ]
+            .join("\n"),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(waiting_room_id(), None);
+
+        replay(&context, &privacy_command("lock", "locked"))
+            .await
+            .unwrap();
+
+        assert_eq!(waiting_room_id(), Some(Id::new(5001)));
+        assert_eq!(
+            mock_discord.channel(5001).unwrap()["name"],
+            "alex's voice (waiting)"
+        );
+
+        replay(
+            &context,
+            &[
+                voice_state_update(1001, 2002, "sam", Some(5001)),
+                privacy_command("unlock", "unlocked"),
+            ]
+            .join("\n"),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(waiting_room_id(), None);
+        assert!(context.cache.waiting_room(Id::new(5001)).is_none());
+        mock_discord.assert_requested("DELETE", "/channels/5001");
+        assert_eq!(
+            mock_discord
+                .assert_requested("PATCH", "/guilds/1001/members/2002")
+                .body["channel_id"],
+            "5000"
+        );
+
+        context.database.remove_guild(guild_id).await.unwrap();
+    }
+}
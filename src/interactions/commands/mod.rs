@@ -1,2 +1,4 @@
 pub mod join;
+pub mod settings;
 pub mod voice;
+pub mod voice_admin;
@@ -0,0 +1,478 @@
+mod name_filter;
+mod reset;
+mod set;
+mod view;
+
+use std::{mem::replace, sync::Arc};
+
+use eyre::Result;
+use twilight_model::{
+    application::interaction::application_command::{CommandDataOption, CommandOptionValue},
+    channel::message::MessageFlags,
+    http::interaction::{InteractionResponse, InteractionResponseType},
+};
+use twilight_util::builder::{embed::EmbedBuilder, InteractionResponseDataBuilder};
+
+use crate::{
+    structs::{
+        cache::{CachedGuild, CachedGuildUpdate},
+        context::Context,
+        database::{ExternalEditPolicy, GuildSettings},
+        interaction::ApplicationCommandInteraction,
+    },
+    utilities::{constants::LOCALE_OPTIONS, time::humanize},
+};
+
+pub async fn run(
+    context: Arc<Context>,
+    mut interaction: ApplicationCommandInteraction,
+) -> Result<()> {
+    let Some(CommandDataOption {
+        name,
+        value:
+            CommandOptionValue::SubCommand(options) | CommandOptionValue::SubCommandGroup(options),
+    }) = interaction.data.options.clone().into_iter().next()
+    else {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description("I could not find a subcommand.")
+            .build();
+        let interaction_response_data = InteractionResponseDataBuilder::new()
+            .embeds(vec![embed])
+            .flags(MessageFlags::EPHEMERAL)
+            .build();
+        let interaction_response = InteractionResponse {
+            data: Some(interaction_response_data),
+            kind: InteractionResponseType::ChannelMessageWithSource,
+        };
+
+        context
+            .interaction_client()
+            .create_response(interaction.id, &interaction.token, &interaction_response)
+            .await?;
+
+        return Ok(());
+    };
+    let _ = replace(&mut interaction.data.options, options);
+
+    match name.as_str() {
+        "name-filter" => name_filter::run(context, interaction).await?,
+        "reset" => reset::run(context, interaction).await?,
+        "set" => set::run(context, interaction).await?,
+        "view" => view::run(context, interaction).await?,
+        _ => {
+            let embed = EmbedBuilder::new()
+                .color(0xF8F8FF)
+                .description(format!(
+                    "I don't have a subcommand with the name \"{name}\"."
+                ))
+                .build();
+            let interaction_response_data = InteractionResponseDataBuilder::new()
+                .embeds(vec![embed])
+                .flags(MessageFlags::EPHEMERAL)
+                .build();
+            let interaction_response = InteractionResponse {
+                data: Some(interaction_response_data),
+                kind: InteractionResponseType::ChannelMessageWithSource,
+            };
+
+            context
+                .interaction_client()
+                .create_response(interaction.id, &interaction.token, &interaction_response)
+                .await?;
+
+            return Ok(());
+        }
+    }
+
+    Ok(())
+}
+
+/// The settings a server has now, as the cache knows them.
+fn current_settings(guild: &CachedGuild) -> GuildSettings {
+    GuildSettings {
+        blocked_dms_enabled: *guild.blocked_dms_enabled.read(),
+        creation_cooldown: *guild.creation_cooldown.read(),
+        external_edit_policy: *guild.external_edit_policy.read(),
+        locale: guild.locale.read().clone(),
+        log_channel_id: *guild.log_channel_id.read(),
+        max_voice_channels: *guild.max_voice_channels.read(),
+        name_template: guild.name_template.read().clone(),
+        presets_enabled: *guild.presets_enabled.read(),
+        printable_names_only: *guild.printable_names_only.read(),
+    }
+}
+
+/// The name of an external edit policy, as offered by `/settings set`.
+fn external_edit_policy_name(external_edit_policy: ExternalEditPolicy) -> &'static str {
+    match external_edit_policy {
+        ExternalEditPolicy::Accept => "Accept",
+        ExternalEditPolicy::Audit => "Audit only",
+        ExternalEditPolicy::Revert => "Revert",
+    }
+}
+
+/// The name of a locale, as offered by `/settings set`.
+fn locale_name(locale: &str) -> &str {
+    LOCALE_OPTIONS
+        .iter()
+        .find(|(_, value)| value.eq(&locale))
+        .map_or(locale, |(name, _)| name)
+}
+
+async fn save_settings(
+    context: &Arc<Context>,
+    guild: &CachedGuild,
+    guild_settings: GuildSettings,
+) -> Result<()> {
+    context
+        .database
+        .update_guild_settings(guild.id, &guild_settings)
+        .await?;
+    context.cache.update_guild(
+        guild.id,
+        CachedGuildUpdate {
+            blocked_dms_enabled: Some(guild_settings.blocked_dms_enabled),
+            creation_cooldown: Some(guild_settings.creation_cooldown),
+            external_edit_policy: Some(guild_settings.external_edit_policy),
+            locale: Some(guild_settings.locale),
+            log_channel_id: Some(guild_settings.log_channel_id),
+            max_voice_channels: Some(guild_settings.max_voice_channels),
+            name_template: Some(guild_settings.name_template),
+            presets_enabled: Some(guild_settings.presets_enabled),
+            printable_names_only: Some(guild_settings.printable_names_only),
+            ..Default::default()
+        },
+    );
+
+    Ok(())
+}
+
+/// One line for every setting that differs between `before` and `after`.
+fn describe_changes(before: &GuildSettings, after: &GuildSettings) -> Vec<String> {
+    let mut lines = Vec::new();
+
+    if before.blocked_dms_enabled.ne(&after.blocked_dms_enabled) {
+        lines.push(if after.blocked_dms_enabled {
+            "Blocked members will now be told why in a DM.".to_owned()
+        } else {
+            "Blocked members will no longer be told why in a DM.".to_owned()
+        });
+    }
+    if before.creation_cooldown.ne(&after.creation_cooldown) {
+        lines.push(match after.creation_cooldown {
+            0 => "Members may now create voice channels back to back.".to_owned(),
+            creation_cooldown => format!(
+                "Members must now wait **{}** between creating voice channels.",
+                humanize(u64::from(creation_cooldown))
+            ),
+        });
+    }
+    if before.external_edit_policy.ne(&after.external_edit_policy) {
+        lines.push(
+            match after.external_edit_policy {
+                ExternalEditPolicy::Accept => {
+                    "Voice channels edited outside of Voz will now keep their edited privacy level and settings."
+                }
+                ExternalEditPolicy::Audit => {
+                    "Voice channels edited outside of Voz will now be kept as they are, but audited."
+                }
+                ExternalEditPolicy::Revert => {
+                    "Voice channels edited outside of Voz will now have their privacy level and settings put back."
+                }
+            }
+            .to_owned(),
+        );
+    }
+    if before.locale.ne(&after.locale) {
+        lines.push(format!(
+            "Default voice channel names are now written in **{}**.",
+            locale_name(&after.locale)
+        ));
+    }
+    if before.log_channel_id.ne(&after.log_channel_id) {
+        lines.push(match after.log_channel_id {
+            Some(log_channel_id) => {
+                format!("I'll now log voice channel activity in <#{log_channel_id}>.")
+            }
+            None => "I'll no longer log voice channel activity.".to_owned(),
+        });
+    }
+    if before.max_voice_channels.ne(&after.max_voice_channels) {
+        lines.push(match after.max_voice_channels {
+            1 => "Members may now own **1** voice channel at once.".to_owned(),
+            max_voice_channels => {
+                format!(
+                    "Members may now own up to **{max_voice_channels}** voice channels at once."
+                )
+            }
+        });
+    }
+    if before.name_template.ne(&after.name_template) {
+        lines.push(match &after.name_template {
+            Some(name_template) => {
+                format!("**New** voice channels will now be named **{name_template}**.")
+            }
+            None => "**New** voice channels will now be named after their owner.".to_owned(),
+        });
+    }
+    if before.presets_enabled.ne(&after.presets_enabled) {
+        lines.push(if after.presets_enabled {
+            "Members may now save presets, which **new** voice channels will start with.".to_owned()
+        } else {
+            "Presets are now turned off. **New** voice channels will ignore saved presets."
+                .to_owned()
+        });
+    }
+    if before.printable_names_only.ne(&after.printable_names_only) {
+        lines.push(if after.printable_names_only {
+            "Voice channel names may now only contain printable characters.".to_owned()
+        } else {
+            "Voice channel names may now contain any characters.".to_owned()
+        });
+    }
+
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use twilight_model::id::Id;
+
+    use crate::{
+        structs::database::ChannelPrivacy,
+        testing::{
+            self,
+            mock_discord::MockDiscord,
+            payloads::{channel_delete, command, subcommand, voice_state_update},
+            replay::replay,
+            seed_join_channel,
+        },
+    };
+
+    #[tokio::test]
+    #[ignore = "needs a database, run with `cargo test -- --ignored`"]
+    async fn settings_shape_new_voice_channels() {
+        let mock_discord = MockDiscord::start().await;
+        let (context, _guard) = testing::context(&mock_discord).await;
+        let guild_id = Id::new(1001);
+        let (guild_create, create) = include_str!("../../../testing/recordings/create.jsonl")
+            .split_once('\n')
+            .unwrap();
+
+        seed_join_channel(&context, guild_id, Id::new(1101), ChannelPrivacy::Unlocked).await;
+        replay(
+            &context,
+            &[
+                guild_create.to_owned(),
+                command(
+                    "set",
+                    1001,
+                    1,
+                    subcommand(
+                        "settings",
+                        "set",
+                        json!([
+                            { "name": "log-channel", "type": 7, "value": "3000" },
+                            { "name": "max-voice-channels", "type": 4, "value": 2 },
+                            { "name": "name-template", "type": 3, "value": "{owner}'s room" },
+                        ]),
+                    ),
+                ),
+                create.to_owned(),
+                // Someone stays behind, so alex's first voice channel isn't deleted when empty.
+                voice_state_update(1001, 2002, "sam", Some(5001)),
+                voice_state_update(1001, 2001, "alex", Some(1101)),
+                voice_state_update(1001, 2001, "alex", None),
+                voice_state_update(1001, 2001, "alex", Some(1101)),
+                command("view", 1001, 1, subcommand("settings", "view", json!([]))),
+            ]
+            .join("\n"),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            mock_discord.response_descriptions("set"),
+            ["I'll now log voice channel activity in <#3000>.\nMembers may now own up to **2** voice channels at once.\n**New** voice channels will now be named **{owner}'s room**."]
+        );
+        // Log messages take ids too, so the voice channels are 5001 and 5003.
+        assert_eq!(mock_discord.channel(5001).unwrap()["name"], "alex's room");
+        assert_eq!(mock_discord.channel(5003).unwrap()["name"], "alex's room");
+        assert!(mock_discord.channel(5005).is_none());
+        assert_eq!(
+            context.cache.owned_voice_channels(guild_id, Id::new(2001)),
+            [Id::new(5001), Id::new(5003)]
+        );
+        // The third time, alex is told why instead (in a DM, which takes the next id).
+        assert_eq!(
+            mock_discord.requests_to("POST", "/channels/5005/messages")[0].body["embeds"][0]
+                ["description"],
+            "You already own as many voice channels as **guild** allows."
+        );
+        assert_eq!(
+            mock_discord
+                .requests_to("POST", "/channels/3000/messages")
+                .len(),
+            3
+        );
+        assert_eq!(
+            mock_discord.response_descriptions("view"),
+            ["**Blocked DMs:** On\n**Creation cooldown:** Off\n**External edits:** Accept\n**Locale:** English (US)\n**Log channel:** <#3000>\n**Maximum voice channels:** 2\n**Naming template:** **{owner}'s room**\n**Presets:** On\n**Printable names only:** Off"]
+        );
+
+        let guild_settings = context.database.guild_settings(guild_id).await.unwrap();
+
+        assert_eq!(guild_settings.log_channel_id, Some(Id::new(3000)));
+        assert_eq!(guild_settings.max_voice_channels, 2);
+
+        context.database.remove_guild(guild_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a database, run with `cargo test -- --ignored`"]
+    async fn reset_restores_the_default_name() {
+        let mock_discord = MockDiscord::start().await;
+        let (context, _guard) = testing::context(&mock_discord).await;
+        let guild_id = Id::new(1001);
+        let (guild_create, create) = include_str!("../../../testing/recordings/create.jsonl")
+            .split_once('\n')
+            .unwrap();
+
+        seed_join_channel(&context, guild_id, Id::new(1101), ChannelPrivacy::Unlocked).await;
+        replay(
+            &context,
+            &[
+                guild_create.to_owned(),
+                command(
+                    "set",
+                    1001,
+                    1,
+                    subcommand(
+                        "settings",
+                        "set",
+                        json!([
+                            { "name": "locale", "type": 3, "value": "fr" },
+                            { "name": "name-template", "type": 3, "value": "{owner}'s room" },
+                        ]),
+                    ),
+                ),
+                command(
+                    "reset",
+                    1001,
+                    1,
+                    subcommand(
+                        "settings",
+                        "reset",
+                        json!([{ "name": "setting", "type": 3, "value": "name-template" }]),
+                    ),
+                ),
+                command(
+                    "reset-again",
+                    1001,
+                    1,
+                    subcommand(
+                        "settings",
+                        "reset",
+                        json!([{ "name": "setting", "type": 3, "value": "name-template" }]),
+                    ),
+                ),
+                create.to_owned(),
+            ]
+            .join("\n"),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            mock_discord.response_descriptions("reset"),
+            ["**New** voice channels will now be named after their owner."]
+        );
+        assert_eq!(
+            mock_discord.response_descriptions("reset-again"),
+            ["No changes have been made."]
+        );
+        assert_eq!(mock_discord.channel(5000).unwrap()["name"], "Salon de alex");
+
+        context.database.remove_guild(guild_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a database, run with `cargo test -- --ignored`"]
+    async fn creation_cooldown_turns_members_away() {
+        let mock_discord = MockDiscord::start().await;
+        let (context, _guard) = testing::context(&mock_discord).await;
+        let guild_id = Id::new(1001);
+        let (guild_create, create) = include_str!("../../../testing/recordings/create.jsonl")
+            .split_once('\n')
+            .unwrap();
+
+        seed_join_channel(&context, guild_id, Id::new(1101), ChannelPrivacy::Unlocked).await;
+        replay(
+            &context,
+            &[
+                guild_create.to_owned(),
+                command(
+                    "set",
+                    1001,
+                    1,
+                    subcommand(
+                        "settings",
+                        "set",
+                        json!([{ "name": "creation-cooldown", "type": 3, "value": "10m" }]),
+                    ),
+                ),
+                create.to_owned(),
+            ]
+            .join("\n"),
+        )
+        .await
+        .unwrap();
+
+        let voice_channel = mock_discord.channel(5000).unwrap();
+
+        replay(
+            &context,
+            &[
+                voice_state_update(1001, 2001, "alex", None),
+                channel_delete(voice_channel),
+                voice_state_update(1001, 2001, "alex", Some(1101)),
+            ]
+            .join("\n"),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            mock_discord.response_descriptions("set"),
+            ["Members must now wait **10m** between creating voice channels."]
+        );
+        // The first voice channel is deleted once alex leaves it, and no other is created.
+        assert_eq!(
+            mock_discord
+                .requests_to("POST", "/guilds/1001/channels")
+                .len(),
+            1
+        );
+
+        let description = mock_discord.requests_to("POST", "/channels/5001/messages")[0].body
+            ["embeds"][0]["description"]
+            .as_str()
+            .unwrap()
+            .to_owned();
+
+        assert!(description.starts_with("You may create another voice channel in **guild** in 9m"));
+        assert_eq!(
+            context
+                .database
+                .guild_settings(guild_id)
+                .await
+                .unwrap()
+                .creation_cooldown,
+            600
+        );
+
+        context.database.remove_guild(guild_id).await.unwrap();
+    }
+}
@@ -0,0 +1,173 @@
+use std::sync::Arc;
+
+use eyre::Result;
+use twilight_model::{
+    application::interaction::application_command::CommandOptionValue,
+    channel::message::MessageFlags,
+    http::interaction::{InteractionResponse, InteractionResponseType},
+};
+use twilight_util::builder::{embed::EmbedBuilder, InteractionResponseDataBuilder};
+
+use super::{name_filter_kind, name_filter_text};
+use crate::{
+    structs::{
+        cache::CachedGuildUpdate, context::Context, database::NameFilterKind,
+        interaction::ApplicationCommandInteraction,
+    },
+    utilities::{audit::audit, name_filter::compile_pattern},
+};
+
+pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteraction) -> Result<()> {
+    let interaction_response_data = InteractionResponseDataBuilder::new()
+        .flags(MessageFlags::EPHEMERAL)
+        .build();
+    let interaction_response = InteractionResponse {
+        data: Some(interaction_response_data),
+        kind: InteractionResponseType::DeferredChannelMessageWithSource,
+    };
+
+    context
+        .interaction_client()
+        .create_response(interaction.id, &interaction.token, &interaction_response)
+        .await?;
+
+    let Some(kind) = name_filter_kind(&interaction) else {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description("I could not find a valid **kind** value.")
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    };
+    let Some(value) = interaction.data.options.iter().find_map(|option| {
+        match (option.name.as_str(), &option.value) {
+            ("value", CommandOptionValue::String(value)) if !value.trim().is_empty() => {
+                Some(value.trim().to_owned())
+            }
+            _ => None,
+        }
+    }) else {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description("I could not find a valid **value** value.")
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    };
+    let (value, has_changed, update) = match kind {
+        NameFilterKind::Pattern => {
+            let Ok(pattern) = compile_pattern(&value) else {
+                let embed = EmbedBuilder::new()
+                    .color(0xF8F8FF)
+                    .description(format!("`{value}` is not a valid pattern."))
+                    .build();
+
+                context
+                    .interaction_client()
+                    .update_response(&interaction.token)
+                    .embeds(Some(&[embed]))
+                    .await?;
+
+                return Ok(());
+            };
+            let mut name_filter_patterns = interaction.guild.name_filter_patterns.read().clone();
+            let has_changed = !name_filter_patterns
+                .iter()
+                .any(|name_filter_pattern| name_filter_pattern.as_str().eq(&value));
+
+            name_filter_patterns.push(pattern);
+
+            (
+                value,
+                has_changed,
+                CachedGuildUpdate {
+                    name_filter_patterns: Some(name_filter_patterns),
+                    ..Default::default()
+                },
+            )
+        }
+        NameFilterKind::Word => {
+            // Words match regardless of case, so they're kept lowercase.
+            let value = value.to_lowercase();
+            let mut name_filter_words = interaction.guild.name_filter_words.read().clone();
+            let has_changed = name_filter_words.insert(value.clone());
+
+            (
+                value,
+                has_changed,
+                CachedGuildUpdate {
+                    name_filter_words: Some(name_filter_words),
+                    ..Default::default()
+                },
+            )
+        }
+    };
+
+    if !has_changed {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description("No changes have been made.")
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    }
+
+    context
+        .database
+        .insert_name_filter(interaction.guild.id, kind, &value)
+        .await?;
+    context.cache.update_guild(interaction.guild.id, update);
+
+    let name_filter_text = name_filter_text(kind, &value);
+
+    audit(
+        &context,
+        &interaction.guild,
+        Some(interaction.user_id),
+        None,
+        "/settings name-filter add",
+        format!(
+            "<@{}> added {name_filter_text} to the name filter.",
+            interaction.user_id
+        ),
+    )
+    .await?;
+
+    let embed = EmbedBuilder::new()
+        .color(0xF8F8FF)
+        .description(match kind {
+            NameFilterKind::Pattern => {
+                format!("Voice channel names may no longer match {name_filter_text}.")
+            }
+            NameFilterKind::Word => {
+                format!("Voice channel names may no longer contain {name_filter_text}.")
+            }
+        })
+        .build();
+
+    context
+        .interaction_client()
+        .update_response(&interaction.token)
+        .embeds(Some(&[embed]))
+        .await?;
+
+    Ok(())
+}
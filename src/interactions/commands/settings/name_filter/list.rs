@@ -0,0 +1,73 @@
+use std::sync::Arc;
+
+use eyre::Result;
+use twilight_model::{
+    channel::message::MessageFlags,
+    http::interaction::{InteractionResponse, InteractionResponseType},
+};
+use twilight_util::builder::{embed::EmbedBuilder, InteractionResponseDataBuilder};
+
+use super::name_filter_text;
+use crate::structs::{
+    context::Context, database::NameFilterKind, interaction::ApplicationCommandInteraction,
+};
+
+pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteraction) -> Result<()> {
+    let interaction_response_data = InteractionResponseDataBuilder::new()
+        .flags(MessageFlags::EPHEMERAL)
+        .build();
+    let interaction_response = InteractionResponse {
+        data: Some(interaction_response_data),
+        kind: InteractionResponseType::DeferredChannelMessageWithSource,
+    };
+
+    context
+        .interaction_client()
+        .create_response(interaction.id, &interaction.token, &interaction_response)
+        .await?;
+
+    let mut name_filter_words = interaction
+        .guild
+        .name_filter_words
+        .read()
+        .iter()
+        .cloned()
+        .collect::<Vec<String>>();
+    let mut name_filter_patterns = interaction
+        .guild
+        .name_filter_patterns
+        .read()
+        .iter()
+        .map(|pattern| pattern.as_str().to_owned())
+        .collect::<Vec<String>>();
+
+    name_filter_words.sort();
+    name_filter_patterns.sort();
+
+    let lines =
+        name_filter_words
+            .into_iter()
+            .map(|word| format!("- {}", name_filter_text(NameFilterKind::Word, &word)))
+            .chain(name_filter_patterns.into_iter().map(|pattern| {
+                format!("- {}", name_filter_text(NameFilterKind::Pattern, &pattern))
+            }))
+            .collect::<Vec<String>>();
+    let description = if lines.is_empty() {
+        "No words or patterns have been filtered.".to_owned()
+    } else {
+        lines.join("\n")
+    };
+    let embed = EmbedBuilder::new()
+        .color(0xF8F8FF)
+        .description(description)
+        .title("Name filter")
+        .build();
+
+    context
+        .interaction_client()
+        .update_response(&interaction.token)
+        .embeds(Some(&[embed]))
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,288 @@
+mod add;
+mod list;
+mod remove;
+
+use std::{mem::replace, sync::Arc};
+
+use eyre::Result;
+use twilight_model::{
+    application::interaction::application_command::{CommandDataOption, CommandOptionValue},
+    channel::message::MessageFlags,
+    http::interaction::{InteractionResponse, InteractionResponseType},
+};
+use twilight_util::builder::{embed::EmbedBuilder, InteractionResponseDataBuilder};
+
+use crate::structs::{
+    context::Context, database::NameFilterKind, interaction::ApplicationCommandInteraction,
+};
+
+pub async fn run(
+    context: Arc<Context>,
+    mut interaction: ApplicationCommandInteraction,
+) -> Result<()> {
+    let Some(CommandDataOption {
+        name,
+        value: CommandOptionValue::SubCommand(options),
+    }) = interaction.data.options.clone().into_iter().next()
+    else {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description("I could not find a subcommand.")
+            .build();
+        let interaction_response_data = InteractionResponseDataBuilder::new()
+            .embeds(vec![embed])
+            .flags(MessageFlags::EPHEMERAL)
+            .build();
+        let interaction_response = InteractionResponse {
+            data: Some(interaction_response_data),
+            kind: InteractionResponseType::ChannelMessageWithSource,
+        };
+
+        context
+            .interaction_client()
+            .create_response(interaction.id, &interaction.token, &interaction_response)
+            .await?;
+
+        return Ok(());
+    };
+    let _ = replace(&mut interaction.data.options, options);
+
+    match name.as_str() {
+        "add" => add::run(context, interaction).await?,
+        "list" => list::run(context, interaction).await?,
+        "remove" => remove::run(context, interaction).await?,
+        _ => {
+            let embed = EmbedBuilder::new()
+                .color(0xF8F8FF)
+                .description(format!(
+                    "I don't have a subcommand with the name \"{name}\"."
+                ))
+                .build();
+            let interaction_response_data = InteractionResponseDataBuilder::new()
+                .embeds(vec![embed])
+                .flags(MessageFlags::EPHEMERAL)
+                .build();
+            let interaction_response = InteractionResponse {
+                data: Some(interaction_response_data),
+                kind: InteractionResponseType::ChannelMessageWithSource,
+            };
+
+            context
+                .interaction_client()
+                .create_response(interaction.id, &interaction.token, &interaction_response)
+                .await?;
+
+            return Ok(());
+        }
+    }
+
+    Ok(())
+}
+
+/// The `kind` option, which says whether the value is a word or a pattern.
+fn name_filter_kind(interaction: &ApplicationCommandInteraction) -> Option<NameFilterKind> {
+    match interaction
+        .data
+        .options
+        .iter()
+        .find(|&option| option.name.eq("kind"))
+        .map(|option| &option.value)
+    {
+        Some(CommandOptionValue::String(kind)) if kind.eq("pattern") => {
+            Some(NameFilterKind::Pattern)
+        }
+        Some(CommandOptionValue::String(kind)) if kind.eq("word") => Some(NameFilterKind::Word),
+        _ => None,
+    }
+}
+
+/// A name filter entry as it's shown in replies, with patterns kept as code.
+fn name_filter_text(kind: NameFilterKind, value: &str) -> String {
+    match kind {
+        NameFilterKind::Pattern => format!("`{value}`"),
+        NameFilterKind::Word => format!("**{value}**"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+    use twilight_model::id::Id;
+
+    use crate::{
+        structs::database::ChannelPrivacy,
+        testing::{
+            self,
+            mock_discord::MockDiscord,
+            payloads::{command, subcommand, subcommand_group},
+            replay::replay,
+            seed_join_channel,
+        },
+    };
+
+    fn name_filter_command(token: &str, subcommand_name: &str, options: Value) -> String {
+        command(
+            token,
+            1001,
+            1,
+            subcommand_group("settings", "name-filter", subcommand_name, options),
+        )
+    }
+
+    fn name_command(token: &str, name: &str) -> String {
+        command(
+            token,
+            1001,
+            2001,
+            subcommand(
+                "voice",
+                "name",
+                json!([{ "name": "name", "type": 3, "value": name }]),
+            ),
+        )
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a database, run with `cargo test -- --ignored`"]
+    async fn filtered_names_are_rejected_or_replaced() {
+        let mock_discord = MockDiscord::start().await;
+        let (context, _guard) = testing::context(&mock_discord).await;
+        let guild_id = Id::new(1001);
+        let (guild_create, create) = include_str!("../../../../testing/recordings/create.jsonl")
+            .split_once('\n')
+            .unwrap();
+        let word = json!({ "name": "kind", "type": 3, "value": "word" });
+        let pattern = json!({ "name": "kind", "type": 3, "value": "pattern" });
+
+        seed_join_channel(&context, guild_id, Id::new(1101), ChannelPrivacy::Unlocked).await;
+        replay(
+            &context,
+            &[
+                guild_create.to_owned(),
+                command(
+                    "set",
+                    1001,
+                    1,
+                    subcommand(
+                        "settings",
+                        "set",
+                        json!([
+                            { "name": "name-template", "type": 3, "value": "heck yeah {owner}" },
+                            { "name": "printable-names", "type": 5, "value": true },
+                        ]),
+                    ),
+                ),
+                name_filter_command(
+                    "add-word",
+                    "add",
+                    json!([word, { "name": "value", "type": 3, "value": "HECK" }]),
+                ),
+                name_filter_command(
+                    "add-pattern",
+                    "add",
+                    json!([pattern, { "name": "value", "type": 3, "value": r"^free\s+nitro" }]),
+                ),
+                name_filter_command(
+                    "add-invalid",
+                    "add",
+                    json!([pattern, { "name": "value", "type": 3, "value": "(" }]),
+                ),
+                command(
+                    "set-filtered",
+                    1001,
+                    1,
+                    subcommand(
+                        "settings",
+                        "set",
+                        json!([{ "name": "name-template", "type": 3, "value": "Heck {owner}" }]),
+                    ),
+                ),
+                create.to_owned(),
+                name_command("rename-pattern", "Free nitro here"),
+                name_command("rename-unprintable", "Z\u{0335}algo"),
+                name_filter_command("list", "list", json!([])),
+                name_filter_command(
+                    "suggest",
+                    "remove",
+                    json!([word, { "focused": true, "name": "value", "type": 3, "value": "he" }]),
+                ),
+                name_filter_command(
+                    "remove",
+                    "remove",
+                    json!([word, { "name": "value", "type": 3, "value": "heck" }]),
+                ),
+                name_filter_command(
+                    "remove-again",
+                    "remove",
+                    json!([word, { "name": "value", "type": 3, "value": "heck" }]),
+                ),
+                name_command("rename", "heck yeah"),
+            ]
+            .join("\n"),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            mock_discord.response_descriptions("add-word"),
+            ["Voice channel names may no longer contain **heck**."]
+        );
+        assert_eq!(
+            mock_discord.response_descriptions("add-pattern"),
+            ["Voice channel names may no longer match `^free\\s+nitro`."]
+        );
+        assert_eq!(
+            mock_discord.response_descriptions("add-invalid"),
+            ["`(` is not a valid pattern."]
+        );
+        assert_eq!(
+            mock_discord.response_descriptions("set-filtered"),
+            ["This name template contains a word that isn't allowed here."]
+        );
+        // The template was saved before its word was filtered, so the voice channel falls back to the default.
+        assert_eq!(
+            mock_discord
+                .assert_requested("POST", "/guilds/1001/channels")
+                .body["name"],
+            "alex's voice"
+        );
+        assert_eq!(
+            mock_discord.response_descriptions("rename-pattern"),
+            ["This name matches a pattern that isn't allowed here."]
+        );
+        assert_eq!(
+            mock_discord.response_descriptions("rename-unprintable"),
+            ["This name contains characters that aren't printable."]
+        );
+        assert_eq!(
+            mock_discord.response_descriptions("list"),
+            ["- **heck**\n- `^free\\s+nitro`"]
+        );
+        assert_eq!(
+            mock_discord.autocomplete_choices("suggest"),
+            [("heck".to_owned(), "heck".to_owned())]
+        );
+        assert_eq!(
+            mock_discord.response_descriptions("remove"),
+            ["Voice channel names may contain **heck** again."]
+        );
+        assert_eq!(
+            mock_discord.response_descriptions("remove-again"),
+            ["**heck** is not in the name filter."]
+        );
+        assert_eq!(mock_discord.channel(5000).unwrap()["name"], "heck yeah");
+        assert_eq!(
+            context
+                .database
+                .guild_name_filters(guild_id)
+                .await
+                .unwrap()
+                .into_iter()
+                .map(|name_filter| name_filter.value)
+                .collect::<Vec<String>>(),
+            [r"^free\s+nitro"]
+        );
+
+        context.database.remove_guild(guild_id).await.unwrap();
+    }
+}
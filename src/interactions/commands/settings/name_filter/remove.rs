@@ -0,0 +1,191 @@
+use std::sync::Arc;
+
+use eyre::Result;
+use twilight_model::{
+    application::{
+        command::{CommandOptionChoice, CommandOptionChoiceValue},
+        interaction::application_command::CommandOptionValue,
+    },
+    channel::message::MessageFlags,
+    http::interaction::{InteractionResponse, InteractionResponseType},
+};
+use twilight_util::builder::{embed::EmbedBuilder, InteractionResponseDataBuilder};
+
+use super::{name_filter_kind, name_filter_text};
+use crate::{
+    structs::{
+        cache::CachedGuildUpdate, context::Context, database::NameFilterKind,
+        interaction::ApplicationCommandInteraction,
+    },
+    utilities::audit::audit,
+};
+
+pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteraction) -> Result<()> {
+    let kind = name_filter_kind(&interaction);
+    let value = match interaction
+        .data
+        .options
+        .iter()
+        .find(|&option| option.name.eq("value"))
+        .cloned()
+        .map(|option| option.value)
+    {
+        Some(CommandOptionValue::Focused(value, _)) => {
+            let lowercased_value = value.to_lowercase();
+            let mut values = match kind {
+                Some(NameFilterKind::Pattern) => interaction
+                    .guild
+                    .name_filter_patterns
+                    .read()
+                    .iter()
+                    .map(|pattern| pattern.as_str().to_owned())
+                    .collect::<Vec<String>>(),
+                Some(NameFilterKind::Word) => interaction
+                    .guild
+                    .name_filter_words
+                    .read()
+                    .iter()
+                    .cloned()
+                    .collect::<Vec<String>>(),
+                None => Vec::new(),
+            };
+
+            values.retain(|value| value.to_lowercase().contains(&lowercased_value));
+            values.sort();
+
+            let choices = values
+                .into_iter()
+                .take(25)
+                .map(|value| CommandOptionChoice {
+                    name: value.clone(),
+                    name_localizations: None,
+                    value: CommandOptionChoiceValue::String(value),
+                })
+                .collect::<Vec<CommandOptionChoice>>();
+            let data = InteractionResponseDataBuilder::new()
+                .choices(choices)
+                .build();
+            let interaction_response = InteractionResponse {
+                data: Some(data),
+                kind: InteractionResponseType::ApplicationCommandAutocompleteResult,
+            };
+
+            context
+                .interaction_client()
+                .create_response(interaction.id, &interaction.token, &interaction_response)
+                .await?;
+
+            return Ok(());
+        }
+        Some(CommandOptionValue::String(value)) => value.trim().to_owned(),
+        _ => return Ok(()),
+    };
+    let interaction_response_data = InteractionResponseDataBuilder::new()
+        .flags(MessageFlags::EPHEMERAL)
+        .build();
+    let interaction_response = InteractionResponse {
+        data: Some(interaction_response_data),
+        kind: InteractionResponseType::DeferredChannelMessageWithSource,
+    };
+
+    context
+        .interaction_client()
+        .create_response(interaction.id, &interaction.token, &interaction_response)
+        .await?;
+
+    let Some(kind) = kind else {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description("I could not find a valid **kind** value.")
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    };
+    let value = match kind {
+        NameFilterKind::Pattern => value,
+        NameFilterKind::Word => value.to_lowercase(),
+    };
+    let name_filter_text = name_filter_text(kind, &value);
+
+    if !context
+        .database
+        .remove_name_filter(interaction.guild.id, kind, &value)
+        .await?
+    {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description(format!("{name_filter_text} is not in the name filter."))
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    }
+
+    let update = match kind {
+        NameFilterKind::Pattern => {
+            let mut name_filter_patterns = interaction.guild.name_filter_patterns.read().clone();
+
+            name_filter_patterns.retain(|pattern| pattern.as_str().ne(&value));
+
+            CachedGuildUpdate {
+                name_filter_patterns: Some(name_filter_patterns),
+                ..Default::default()
+            }
+        }
+        NameFilterKind::Word => {
+            let mut name_filter_words = interaction.guild.name_filter_words.read().clone();
+
+            name_filter_words.remove(&value);
+
+            CachedGuildUpdate {
+                name_filter_words: Some(name_filter_words),
+                ..Default::default()
+            }
+        }
+    };
+
+    context.cache.update_guild(interaction.guild.id, update);
+    audit(
+        &context,
+        &interaction.guild,
+        Some(interaction.user_id),
+        None,
+        "/settings name-filter remove",
+        format!(
+            "<@{}> removed {name_filter_text} from the name filter.",
+            interaction.user_id
+        ),
+    )
+    .await?;
+
+    let embed = EmbedBuilder::new()
+        .color(0xF8F8FF)
+        .description(match kind {
+            NameFilterKind::Pattern => {
+                format!("Voice channel names may match {name_filter_text} again.")
+            }
+            NameFilterKind::Word => {
+                format!("Voice channel names may contain {name_filter_text} again.")
+            }
+        })
+        .build();
+
+    context
+        .interaction_client()
+        .update_response(&interaction.token)
+        .embeds(Some(&[embed]))
+        .await?;
+
+    Ok(())
+}
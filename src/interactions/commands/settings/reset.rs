@@ -0,0 +1,132 @@
+use std::sync::Arc;
+
+use eyre::Result;
+use twilight_model::{
+    application::interaction::application_command::CommandOptionValue,
+    channel::message::MessageFlags,
+    http::interaction::{InteractionResponse, InteractionResponseType},
+};
+use twilight_util::builder::{embed::EmbedBuilder, InteractionResponseDataBuilder};
+
+use super::{current_settings, describe_changes, save_settings};
+use crate::{
+    structs::{
+        context::Context, database::GuildSettings, interaction::ApplicationCommandInteraction,
+    },
+    utilities::audit::audit,
+};
+
+pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteraction) -> Result<()> {
+    let interaction_response_data = InteractionResponseDataBuilder::new()
+        .flags(MessageFlags::EPHEMERAL)
+        .build();
+    let interaction_response = InteractionResponse {
+        data: Some(interaction_response_data),
+        kind: InteractionResponseType::DeferredChannelMessageWithSource,
+    };
+
+    context
+        .interaction_client()
+        .create_response(interaction.id, &interaction.token, &interaction_response)
+        .await?;
+
+    let before = current_settings(&interaction.guild);
+    let mut after = current_settings(&interaction.guild);
+    let defaults = GuildSettings::default();
+
+    match interaction
+        .data
+        .options
+        .iter()
+        .find(|&option| option.name.eq("setting"))
+        .map(|option| &option.value)
+    {
+        Some(CommandOptionValue::String(setting)) if setting.eq("blocked-dms") => {
+            after.blocked_dms_enabled = defaults.blocked_dms_enabled;
+        }
+        Some(CommandOptionValue::String(setting)) if setting.eq("creation-cooldown") => {
+            after.creation_cooldown = defaults.creation_cooldown;
+        }
+        Some(CommandOptionValue::String(setting)) if setting.eq("external-edits") => {
+            after.external_edit_policy = defaults.external_edit_policy;
+        }
+        Some(CommandOptionValue::String(setting)) if setting.eq("locale") => {
+            after.locale = defaults.locale;
+        }
+        Some(CommandOptionValue::String(setting)) if setting.eq("log-channel") => {
+            after.log_channel_id = defaults.log_channel_id;
+        }
+        Some(CommandOptionValue::String(setting)) if setting.eq("max-voice-channels") => {
+            after.max_voice_channels = defaults.max_voice_channels;
+        }
+        Some(CommandOptionValue::String(setting)) if setting.eq("name-template") => {
+            after.name_template = defaults.name_template;
+        }
+        Some(CommandOptionValue::String(setting)) if setting.eq("presets") => {
+            after.presets_enabled = defaults.presets_enabled;
+        }
+        Some(CommandOptionValue::String(setting)) if setting.eq("printable-names") => {
+            after.printable_names_only = defaults.printable_names_only;
+        }
+        _ => {
+            let embed = EmbedBuilder::new()
+                .color(0xF8F8FF)
+                .description("I could not find a valid **setting** value.")
+                .build();
+
+            context
+                .interaction_client()
+                .update_response(&interaction.token)
+                .embeds(Some(&[embed]))
+                .await?;
+
+            return Ok(());
+        }
+    }
+
+    let lines = describe_changes(&before, &after);
+
+    if lines.is_empty() {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description("No changes have been made.")
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    }
+
+    save_settings(&context, &interaction.guild, after).await?;
+
+    audit(
+        &context,
+        &interaction.guild,
+        Some(interaction.user_id),
+        None,
+        "/settings reset",
+        format!(
+            "<@{}> changed the settings. {}",
+            interaction.user_id,
+            lines.join(" ")
+        ),
+    )
+    .await?;
+
+    let embed = EmbedBuilder::new()
+        .color(0xF8F8FF)
+        .description(lines.join("\n"))
+        .build();
+
+    context
+        .interaction_client()
+        .update_response(&interaction.token)
+        .embeds(Some(&[embed]))
+        .await?;
+
+    Ok(())
+}
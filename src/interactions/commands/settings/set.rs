@@ -0,0 +1,177 @@
+use std::sync::Arc;
+
+use eyre::Result;
+use twilight_model::{
+    application::interaction::application_command::CommandOptionValue,
+    channel::message::MessageFlags,
+    http::interaction::{InteractionResponse, InteractionResponseType},
+};
+use twilight_util::builder::{embed::EmbedBuilder, InteractionResponseDataBuilder};
+
+use super::{current_settings, describe_changes, save_settings};
+use crate::{
+    structs::{
+        context::Context, database::ExternalEditPolicy, interaction::ApplicationCommandInteraction,
+    },
+    utilities::{
+        audit::audit,
+        constants::{LOCALE_OPTIONS, MAX_CREATION_COOLDOWN, MAX_VOICE_CHANNELS},
+        name_filter::name_violation,
+        template::render,
+        time::parse,
+    },
+};
+
+pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteraction) -> Result<()> {
+    let interaction_response_data = InteractionResponseDataBuilder::new()
+        .flags(MessageFlags::EPHEMERAL)
+        .build();
+    let interaction_response = InteractionResponse {
+        data: Some(interaction_response_data),
+        kind: InteractionResponseType::DeferredChannelMessageWithSource,
+    };
+
+    context
+        .interaction_client()
+        .create_response(interaction.id, &interaction.token, &interaction_response)
+        .await?;
+
+    let before = current_settings(&interaction.guild);
+    let mut after = current_settings(&interaction.guild);
+    let mut invalid_option_name = None;
+
+    for option in interaction.data.options.iter() {
+        match (option.name.as_str(), &option.value) {
+            ("blocked-dms", CommandOptionValue::Boolean(blocked_dms_enabled)) => {
+                after.blocked_dms_enabled = *blocked_dms_enabled;
+            }
+            ("creation-cooldown", CommandOptionValue::String(creation_cooldown))
+                if parse(creation_cooldown)
+                    .is_some_and(|seconds| seconds.le(&MAX_CREATION_COOLDOWN)) =>
+            {
+                after.creation_cooldown = parse(creation_cooldown).unwrap_or_default() as u32;
+            }
+            ("external-edits", CommandOptionValue::String(policy)) if policy.eq("accept") => {
+                after.external_edit_policy = ExternalEditPolicy::Accept;
+            }
+            ("external-edits", CommandOptionValue::String(policy)) if policy.eq("audit") => {
+                after.external_edit_policy = ExternalEditPolicy::Audit;
+            }
+            ("external-edits", CommandOptionValue::String(policy)) if policy.eq("revert") => {
+                after.external_edit_policy = ExternalEditPolicy::Revert;
+            }
+            ("locale", CommandOptionValue::String(locale))
+                if LOCALE_OPTIONS.iter().any(|(_, value)| value.eq(locale)) =>
+            {
+                after.locale = locale.clone();
+            }
+            ("log-channel", CommandOptionValue::Channel(log_channel_id)) => {
+                after.log_channel_id = Some(*log_channel_id);
+            }
+            ("max-voice-channels", CommandOptionValue::Integer(max_voice_channels))
+                if (1..=MAX_VOICE_CHANNELS).contains(max_voice_channels) =>
+            {
+                after.max_voice_channels = *max_voice_channels as u8;
+            }
+            ("name-template", CommandOptionValue::String(name_template))
+                if !name_template.trim().is_empty() =>
+            {
+                after.name_template = Some(name_template.trim().to_owned());
+            }
+            ("presets", CommandOptionValue::Boolean(presets_enabled)) => {
+                after.presets_enabled = *presets_enabled;
+            }
+            ("printable-names", CommandOptionValue::Boolean(printable_names_only)) => {
+                after.printable_names_only = *printable_names_only;
+            }
+            (name, _) => {
+                invalid_option_name = Some(name.to_owned());
+
+                break;
+            }
+        }
+    }
+
+    if let Some(name) = invalid_option_name {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description(format!("I could not find a valid **{name}** value."))
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    }
+
+    // Only the owner's name is left out, as the name filter catches it when a channel is created.
+    let template_violation = after
+        .name_template
+        .as_deref()
+        .filter(|name_template| before.name_template.as_deref().ne(&Some(*name_template)))
+        .and_then(|name_template| name_violation(&interaction.guild, &render(name_template, "")));
+
+    if let Some(reason) = template_violation {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description(format!("This name template {reason}."))
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    }
+
+    let lines = describe_changes(&before, &after);
+
+    if lines.is_empty() {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description("No changes have been made.")
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    }
+
+    save_settings(&context, &interaction.guild, after).await?;
+
+    audit(
+        &context,
+        &interaction.guild,
+        Some(interaction.user_id),
+        None,
+        "/settings set",
+        format!(
+            "<@{}> changed the settings. {}",
+            interaction.user_id,
+            lines.join(" ")
+        ),
+    )
+    .await?;
+
+    let embed = EmbedBuilder::new()
+        .color(0xF8F8FF)
+        .description(lines.join("\n"))
+        .build();
+
+    context
+        .interaction_client()
+        .update_response(&interaction.token)
+        .embeds(Some(&[embed]))
+        .await?;
+
+    Ok(())
+}
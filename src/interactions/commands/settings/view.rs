@@ -0,0 +1,96 @@
+use std::sync::Arc;
+
+use eyre::Result;
+use twilight_model::{
+    channel::message::MessageFlags,
+    http::interaction::{InteractionResponse, InteractionResponseType},
+};
+use twilight_util::builder::{embed::EmbedBuilder, InteractionResponseDataBuilder};
+
+use super::{current_settings, external_edit_policy_name, locale_name};
+use crate::{
+    structs::{context::Context, interaction::ApplicationCommandInteraction},
+    utilities::{template::default_name, time::humanize},
+};
+
+pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteraction) -> Result<()> {
+    let interaction_response_data = InteractionResponseDataBuilder::new()
+        .flags(MessageFlags::EPHEMERAL)
+        .build();
+    let interaction_response = InteractionResponse {
+        data: Some(interaction_response_data),
+        kind: InteractionResponseType::DeferredChannelMessageWithSource,
+    };
+
+    context
+        .interaction_client()
+        .create_response(interaction.id, &interaction.token, &interaction_response)
+        .await?;
+
+    let guild_settings = current_settings(&interaction.guild);
+    let log_channel_text = guild_settings
+        .log_channel_id
+        .map_or("No log channel set.".to_owned(), |log_channel_id| {
+            format!("<#{log_channel_id}>")
+        });
+    let name_template_text = match &guild_settings.name_template {
+        Some(name_template) => format!("**{name_template}**"),
+        None => format!(
+            "**{}** (default)",
+            default_name(&guild_settings.locale, "{owner}")
+        ),
+    };
+    let blocked_dms_text = if guild_settings.blocked_dms_enabled {
+        "On"
+    } else {
+        "Off"
+    };
+    let creation_cooldown_text = match guild_settings.creation_cooldown {
+        0 => "Off".to_owned(),
+        creation_cooldown => humanize(u64::from(creation_cooldown)),
+    };
+    let presets_text = if guild_settings.presets_enabled {
+        "On"
+    } else {
+        "Off"
+    };
+    let printable_names_text = if guild_settings.printable_names_only {
+        "On"
+    } else {
+        "Off"
+    };
+    let description = [
+        format!("**Blocked DMs:** {blocked_dms_text}"),
+        format!("**Creation cooldown:** {creation_cooldown_text}"),
+        format!(
+            "**External edits:** {}",
+            external_edit_policy_name(guild_settings.external_edit_policy)
+        ),
+        format!("**Locale:** {}", locale_name(&guild_settings.locale)),
+        format!("**Log channel:** {log_channel_text}"),
+        format!(
+            "**Maximum voice channels:** {}",
+            guild_settings.max_voice_channels
+        ),
+        format!("**Naming template:** {name_template_text}"),
+        format!("**Presets:** {presets_text}"),
+        format!("**Printable names only:** {printable_names_text}"),
+    ]
+    .join("\n");
+    let embed = EmbedBuilder::new()
+        .color(0xF8F8FF)
+        .description(description)
+        .title(format!(
+            "Settings for \"{}\"",
+            interaction.guild.name.read()
+        ))
+        .build();
+
+    context
+        .interaction_client()
+        .update_response(&interaction.token)
+        .embeds(Some(&[embed]))
+        .await?;
+
+    Ok(())
+}
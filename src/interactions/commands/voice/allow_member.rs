@@ -1,4 +1,7 @@
-use std::sync::Arc;
+use std::{
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
 
 use eyre::Result;
 use twilight_model::{
@@ -18,11 +21,51 @@ use twilight_model::{
 };
 use twilight_util::builder::{embed::EmbedBuilder, InteractionResponseDataBuilder};
 
-use crate::structs::{
-    context::Context, database::ChannelPrivacy, interaction::ApplicationCommandInteraction,
+use super::{member_choices, parse_member_id};
+use crate::{
+    structs::{
+        context::Context, database::TimedPermissionKind, interaction::ApplicationCommandInteraction,
+    },
+    utilities::{
+        audit::audit,
+        constants::MAX_TIMED_PERMISSION,
+        permission_overwrites::privacy_permissions,
+        time::{humanize, parse},
+    },
 };
 
 pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteraction) -> Result<()> {
+    let member_value = match interaction
+        .data
+        .options
+        .iter()
+        .find(|&option| option.name.eq("member"))
+        .cloned()
+        .map(|option| option.value)
+    {
+        Some(CommandOptionValue::Focused(value, _)) => {
+            let user_ids = context
+                .cache
+                .recent_contacts(interaction.guild.id, interaction.user_id);
+            let choices = member_choices(&context, interaction.guild.id, user_ids, &value);
+            let data = InteractionResponseDataBuilder::new()
+                .choices(choices)
+                .build();
+            let interaction_response = InteractionResponse {
+                data: Some(data),
+                kind: InteractionResponseType::ApplicationCommandAutocompleteResult,
+            };
+
+            context
+                .interaction_client()
+                .create_response(interaction.id, &interaction.token, &interaction_response)
+                .await?;
+
+            return Ok(());
+        }
+        Some(CommandOptionValue::String(value)) => value,
+        _ => return Ok(()),
+    };
     let interaction_response_data = InteractionResponseDataBuilder::new()
         .flags(MessageFlags::EPHEMERAL)
         .build();
@@ -36,13 +79,7 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
         .create_response(interaction.id, &interaction.token, &interaction_response)
         .await?;
 
-    let Some(CommandOptionValue::User(member_id)) = interaction
-        .data
-        .options
-        .into_iter()
-        .find(|option| option.name.eq("member"))
-        .map(|option| option.value)
-    else {
+    let Some(member_id) = parse_member_id(&member_value) else {
         let embed = EmbedBuilder::new()
             .color(0xF8F8FF)
             .description("I could not find a valid **member** value.")
@@ -56,13 +93,42 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
 
         return Ok(());
     };
+    let duration = match interaction
+        .data
+        .options
+        .iter()
+        .find(|&option| option.name.eq("duration"))
+        .map(|option| option.value.clone())
+    {
+        Some(CommandOptionValue::String(value)) => {
+            let Some(duration) =
+                parse(&value).filter(|seconds| (1..=MAX_TIMED_PERMISSION).contains(seconds))
+            else {
+                let embed = EmbedBuilder::new()
+                    .color(0xF8F8FF)
+                    .description("I could not find a valid **duration** value.")
+                    .build();
+
+                context
+                    .interaction_client()
+                    .update_response(&interaction.token)
+                    .embeds(Some(&[embed]))
+                    .await?;
+
+                return Ok(());
+            };
+
+            Some(duration)
+        }
+        _ => None,
+    };
     let Some(voice_channel_id) = context
         .cache
-        .voice_channel_owner(interaction.guild.id, interaction.user_id)
+        .managed_voice_channel(interaction.guild.id, interaction.user_id)
     else {
         let embed = EmbedBuilder::new()
             .color(0xF8F8FF)
-            .description("You do not own a voice channel.")
+            .description("You do not own or manage a voice channel.")
             .build();
 
         context
@@ -125,13 +191,27 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
             (Permissions::empty(), Permissions::empty()),
             |permission_overwrite| (permission_overwrite.allow, permission_overwrite.deny),
         );
-    let permissions = match voice_channel.privacy.read().clone() {
-        ChannelPrivacy::Invisible => Permissions::VIEW_CHANNEL,
-        ChannelPrivacy::Locked => Permissions::CONNECT,
-        ChannelPrivacy::Unlocked => Permissions::empty(),
+    let permissions = privacy_permissions(voice_channel.kind, &voice_channel.privacy.read());
+
+    // Without a duration, any timed permission gives way to a permanent one. With one, only a
+    // timed permission is extended, as its expiry would take a permanent one with it.
+    let had_timed_permission = match duration {
+        Some(_) => context
+            .database
+            .voice_channel_timed_permissions(voice_channel.id)
+            .await?
+            .iter()
+            .any(|timed_permission| timed_permission.member_id.eq(&member_id)),
+        None => {
+            context
+                .database
+                .remove_timed_permission(voice_channel.id, member_id)
+                .await?
+        }
     };
+    let is_allowed = member_allow.contains(permissions);
 
-    if member_allow.contains(permissions) {
+    if is_allowed && (permissions.is_empty() || !had_timed_permission) {
         let embed = EmbedBuilder::new()
             .color(0xF8F8FF)
             .description(format!(
@@ -148,28 +228,67 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
         return Ok(());
     }
 
-    member_allow.remove(Permissions::CONNECT | Permissions::VIEW_CHANNEL);
-    member_deny.remove(Permissions::CONNECT | Permissions::VIEW_CHANNEL);
-    member_allow = member_allow.union(permissions);
+    if !is_allowed {
+        member_allow.remove(Permissions::CONNECT | Permissions::VIEW_CHANNEL);
+        member_deny.remove(Permissions::CONNECT | Permissions::VIEW_CHANNEL);
+        member_allow = member_allow.union(permissions);
 
-    context
-        .client
-        .update_channel_permission(
-            voice_channel.id,
-            &HttpPermissionOverwrite {
-                allow: Some(member_allow),
-                deny: Some(member_deny),
-                id: member_id.cast(),
-                kind: HttpPermissionOverwriteType::Member,
-            },
-        )
-        .await?;
+        context
+            .client
+            .update_channel_permission(
+                voice_channel.id,
+                &HttpPermissionOverwrite {
+                    allow: Some(member_allow),
+                    deny: Some(member_deny),
+                    id: member_id.cast(),
+                    kind: HttpPermissionOverwriteType::Member,
+                },
+            )
+            .await?;
+    }
+    if let Some(duration) = duration {
+        context
+            .database
+            .insert_timed_permission(
+                voice_channel.id,
+                member_id,
+                TimedPermissionKind::Allow,
+                SystemTime::now() + Duration::from_secs(duration),
+            )
+            .await?;
+    }
 
+    audit(
+        &context,
+        &interaction.guild,
+        Some(interaction.user_id),
+        Some(voice_channel.id),
+        "/voice allow-member",
+        match duration {
+            Some(duration) => format!(
+                "<@{}> allowed <@{member_id}> into <#{}> for **{}**.",
+                interaction.user_id,
+                voice_channel.id,
+                humanize(duration)
+            ),
+            None => format!(
+                "<@{}> allowed <@{member_id}> into <#{}>.",
+                interaction.user_id, voice_channel.id
+            ),
+        },
+    )
+    .await?;
+
+    let description = match duration {
+        Some(duration) => format!(
+            "<@{member_id}> is now allowed permission in this voice channel for **{}**.",
+            humanize(duration)
+        ),
+        None => format!("<@{member_id}> is now allowed permission in this voice channel."),
+    };
     let embed = EmbedBuilder::new()
         .color(0xF8F8FF)
-        .description(format!(
-            "<@{member_id}> is now allowed permission in this voice channel."
-        ))
+        .description(description)
         .build();
 
     context
@@ -180,3 +299,109 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use twilight_model::id::Id;
+
+    use crate::{
+        structs::database::ChannelPrivacy,
+        testing::{
+            self,
+            mock_discord::MockDiscord,
+            payloads::{command, subcommand, voice_state_update},
+            replay::replay,
+            seed_join_channel,
+        },
+    };
+
+    #[tokio::test]
+    #[ignore = "needs a database, run with `cargo test -- --ignored`"]
+    async fn allows_a_member_to_connect_to_a_locked_channel() {
+        let mock_discord = MockDiscord::start().await;
+        let (context, _guard) = testing::context(&mock_discord).await;
+        let guild_id = Id::new(1001);
+
+        seed_join_channel(&context, guild_id, Id::new(1101), ChannelPrivacy::Locked).await;
+        replay(
+            &context,
+            include_str!("../../../testing/recordings/create.jsonl"),
+        )
+        .await
+        .unwrap();
+        replay(
+            &context,
+            &command(
+                "allow",
+                1001,
+                2001,
+                subcommand(
+                    "voice",
+                    "allow-member",
+                    json!([{ "name": "member", "type": 3, "value": "2002" }]),
+                ),
+            ),
+        )
+        .await
+        .unwrap();
+
+        let request = mock_discord.assert_requested("PUT", "/channels/5000/permissions/2002");
+
+        assert_eq!(request.body["allow"], "1048576");
+        assert!(mock_discord.channel(5000).unwrap()["permission_overwrites"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|permission_overwrite| permission_overwrite["id"].eq("2002")));
+        assert_eq!(
+            mock_discord.response_descriptions("allow"),
+            ["<@2002> is now allowed permission in this voice channel."]
+        );
+
+        context.database.remove_guild(guild_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a database, run with `cargo test -- --ignored`"]
+    async fn suggests_recent_contacts() {
+        let mock_discord = MockDiscord::start().await;
+        let (context, _guard) = testing::context(&mock_discord).await;
+        let guild_id = Id::new(1001);
+
+        seed_join_channel(&context, guild_id, Id::new(1101), ChannelPrivacy::Locked).await;
+        replay(
+            &context,
+            include_str!("../../../testing/recordings/create.jsonl"),
+        )
+        .await
+        .unwrap();
+        replay(
+            &context,
+            &[
+                voice_state_update(1001, 2002, "sam", Some(5000)),
+                voice_state_update(1001, 2002, "sam", None),
+                command(
+                    "suggest",
+                    1001,
+                    2001,
+                    subcommand(
+                        "voice",
+                        "allow-member",
+                        json!([{ "focused": true, "name": "member", "type": 3, "value": "" }]),
+                    ),
+                ),
+            ]
+            .join("\n"),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            mock_discord.autocomplete_choices("suggest"),
+            [("sam".to_owned(), "2002".to_owned())]
+        );
+
+        context.database.remove_guild(guild_id).await.unwrap();
+    }
+}
@@ -8,7 +8,13 @@ use twilight_model::{
 };
 use twilight_util::builder::{embed::EmbedBuilder, InteractionResponseDataBuilder};
 
-use crate::structs::{context::Context, interaction::ApplicationCommandInteraction};
+use crate::{
+    structs::{
+        cache::CachedVoiceChannelUpdate, context::Context,
+        interaction::ApplicationCommandInteraction,
+    },
+    utilities::audit::audit,
+};
 
 pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteraction) -> Result<()> {
     let interaction_response_data = InteractionResponseDataBuilder::new()
@@ -46,11 +52,11 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
     };
     let Some(voice_channel_id) = context
         .cache
-        .voice_channel_owner(interaction.guild.id, interaction.user_id)
+        .managed_voice_channel(interaction.guild.id, interaction.user_id)
     else {
         let embed = EmbedBuilder::new()
             .color(0xF8F8FF)
-            .description("You do not own a voice channel.")
+            .description("You do not own or manage a voice channel.")
             .build();
 
         context
@@ -92,6 +98,12 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
         return Ok(());
     }
 
+    let saved_settings = context
+        .cache
+        .update_saved_settings(voice_channel.id, |saved_settings| {
+            saved_settings.bitrate = formatted_bitrate;
+        });
+
     if context
         .client
         .update_channel(voice_channel.id)
@@ -99,6 +111,14 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
         .await
         .is_err()
     {
+        context.cache.update_voice_channel(
+            voice_channel.id,
+            CachedVoiceChannelUpdate {
+                saved_settings: Some(saved_settings),
+                ..Default::default()
+            },
+        );
+
         let embed = EmbedBuilder::new()
             .color(0xF8F8FF)
             .description("I'm unable to set the bitrate right now. Try again in 10 minutes.")
@@ -113,6 +133,23 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
         return Ok(());
     };
 
+    context
+        .database
+        .update_voice_channel_bitrate(voice_channel.id, formatted_bitrate)
+        .await?;
+    audit(
+        &context,
+        &interaction.guild,
+        Some(interaction.user_id),
+        Some(voice_channel.id),
+        "/voice bitrate",
+        format!(
+            "<@{}> set the bitrate of <#{voice_channel_id}> to {rate}kbps.",
+            interaction.user_id
+        ),
+    )
+    .await?;
+
     let embed = EmbedBuilder::new()
         .color(0xF8F8FF)
         .description(format!(
@@ -1,9 +1,8 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::SystemTime};
 
 use eyre::Result;
 use twilight_model::{
     channel::message::MessageFlags,
-    guild::Permissions,
     http::{
         interaction::{InteractionResponse, InteractionResponseType},
         permission_overwrite::{
@@ -14,9 +13,18 @@ use twilight_model::{
 };
 use twilight_util::builder::{embed::EmbedBuilder, InteractionResponseDataBuilder};
 
-use crate::structs::{
-    cache::CachedVoiceChannelUpdate, context::Context, database::ChannelPrivacy,
-    interaction::ApplicationCommandInteraction,
+use crate::{
+    structs::{
+        cache::CachedVoiceChannelUpdate,
+        context::Context,
+        database::{OwnershipPolicy, VoiceChannelKind},
+        interaction::ApplicationCommandInteraction,
+    },
+    utilities::{
+        audit::audit,
+        permission_overwrites::{owner_permissions, privacy_permissions},
+        time::humanize,
+    },
 };
 
 pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteraction) -> Result<()> {
@@ -33,14 +41,13 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
         .create_response(interaction.id, &interaction.token, &interaction_response)
         .await?;
 
-    if context
+    if !context
         .cache
-        .voice_channel_owner(interaction.guild.id, interaction.user_id)
-        .is_some()
+        .can_own_voice_channel(interaction.guild.id, interaction.user_id)
     {
         let embed = EmbedBuilder::new()
             .color(0xF8F8FF)
-            .description("You already own a voice channel.")
+            .description("You already own as many voice channels as this server allows.")
             .build();
 
         context
@@ -84,10 +91,34 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
         return Ok(());
     };
 
-    if voice_channel.owner_id.read().is_some() {
+    // A claimable voice channel may be claimed as soon as its owner has been gone long enough,
+    // even if the hand-off task hasn't released it yet.
+    let claimable_in = match (
+        voice_channel.ownership_policy,
+        *voice_channel.owner_left_at.read(),
+    ) {
+        (OwnershipPolicy::Claimable, Some(owner_left_at)) => Some(
+            u64::from(voice_channel.ownership_delay).saturating_sub(
+                SystemTime::now()
+                    .duration_since(owner_left_at)
+                    .unwrap_or_default()
+                    .as_secs(),
+            ),
+        ),
+        _ => None,
+    };
+
+    if voice_channel.owner_id.read().is_some() && claimable_in.ne(&Some(0)) {
+        let description = match claimable_in {
+            Some(claimable_in) => format!(
+                "This voice channel's owner has left. You may claim it in {}.",
+                humanize(claimable_in)
+            ),
+            None => "This voice channel already has an owner.".to_owned(),
+        };
         let embed = EmbedBuilder::new()
             .color(0xF8F8FF)
-            .description("This voice channel already has an owner.")
+            .description(description)
             .build();
 
         context
@@ -99,18 +130,15 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
         return Ok(());
     };
 
-    let privacy_permissions = match voice_channel.privacy.read().clone() {
-        ChannelPrivacy::Invisible => Permissions::VIEW_CHANNEL,
-        ChannelPrivacy::Locked => Permissions::CONNECT,
-        ChannelPrivacy::Unlocked => Permissions::empty(),
-    };
+    let privacy = voice_channel.privacy.read().clone();
+    let previous_owner_id = *voice_channel.owner_id.read();
 
     context
         .client
         .update_channel_permission(
             voice_channel.id,
             &HttpPermissionOverwrite {
-                allow: Some(privacy_permissions),
+                allow: Some(owner_permissions(voice_channel.kind, &privacy)),
                 deny: None,
                 id: interaction.user_id.cast(),
                 kind: HttpPermissionOverwriteType::Member,
@@ -118,10 +146,35 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
         )
         .await?;
 
+    // The previous owner of a stage stops moderating it, but may still join as before.
+    if let Some(previous_owner_id) =
+        previous_owner_id.filter(|_| voice_channel.kind.eq(&VoiceChannelKind::Stage))
+    {
+        context
+            .client
+            .update_channel_permission(
+                voice_channel.id,
+                &HttpPermissionOverwrite {
+                    allow: Some(privacy_permissions(voice_channel.kind, &privacy)),
+                    deny: None,
+                    id: previous_owner_id.cast(),
+                    kind: HttpPermissionOverwriteType::Member,
+                },
+            )
+            .await?;
+    }
+
     context
         .database
         .update_voice_channel_owner_id(voice_channel.id, Some(interaction.user_id))
         .await?;
+    context
+        .database
+        .remove_voice_channel_manager(voice_channel.id, interaction.user_id)
+        .await?;
+    context
+        .cache
+        .remove_voice_channel_manager(voice_channel.id, interaction.user_id);
     context.cache.update_voice_channel(
         voice_channel.id,
         CachedVoiceChannelUpdate {
@@ -130,6 +183,16 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
         },
     );
 
+    audit(
+        &context,
+        &interaction.guild,
+        Some(interaction.user_id),
+        Some(voice_channel.id),
+        "/voice claim",
+        format!("<@{}> claimed <#{channel_id}>.", interaction.user_id),
+    )
+    .await?;
+
     let embed = EmbedBuilder::new()
         .color(0xF8F8FF)
         .description(format!("You are now the owner of <#{channel_id}>."))
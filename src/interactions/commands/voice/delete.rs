@@ -7,7 +7,10 @@ use twilight_model::{
 };
 use twilight_util::builder::{embed::EmbedBuilder, InteractionResponseDataBuilder};
 
-use crate::structs::{context::Context, interaction::ApplicationCommandInteraction};
+use crate::{
+    structs::{context::Context, interaction::ApplicationCommandInteraction},
+    utilities::audit::audit,
+};
 
 pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteraction) -> Result<()> {
     let interaction_response_data = InteractionResponseDataBuilder::new()
@@ -55,7 +58,27 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
         return Ok(());
     };
 
-    context.client.delete_channel(voice_channel.id).await?;
+    context.cache.insert_deleted_channel(voice_channel.id);
+
+    if let Err(error) = context.client.delete_channel(voice_channel.id).await {
+        context.cache.remove_deleted_channel(voice_channel.id);
+
+        return Err(error.into());
+    }
+
+    audit(
+        &context,
+        &interaction.guild,
+        Some(interaction.user_id),
+        Some(voice_channel.id),
+        "/voice delete",
+        format!(
+            "<@{}> deleted **{}**.",
+            interaction.user_id,
+            voice_channel.name.read()
+        ),
+    )
+    .await?;
 
     if voice_channel.id.ne(&interaction.channel.id) {
         let embed = EmbedBuilder::new()
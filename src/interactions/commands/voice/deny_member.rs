@@ -1,4 +1,7 @@
-use std::sync::Arc;
+use std::{
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
 
 use eyre::Result;
 use twilight_model::{
@@ -18,7 +21,16 @@ use twilight_model::{
 };
 use twilight_util::builder::{embed::EmbedBuilder, InteractionResponseDataBuilder};
 
-use crate::structs::{context::Context, interaction::ApplicationCommandInteraction};
+use crate::{
+    structs::{
+        context::Context, database::TimedPermissionKind, interaction::ApplicationCommandInteraction,
+    },
+    utilities::{
+        audit::audit,
+        constants::MAX_TIMED_PERMISSION,
+        time::{humanize, parse},
+    },
+};
 
 pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteraction) -> Result<()> {
     let interaction_response_data = InteractionResponseDataBuilder::new()
@@ -37,9 +49,9 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
     let Some(CommandOptionValue::User(member_id)) = interaction
         .data
         .options
-        .into_iter()
-        .find(|option| option.name.eq("member"))
-        .map(|option| option.value)
+        .iter()
+        .find(|&option| option.name.eq("member"))
+        .map(|option| option.value.clone())
     else {
         let embed = EmbedBuilder::new()
             .color(0xF8F8FF)
@@ -54,13 +66,42 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
 
         return Ok(());
     };
+    let duration = match interaction
+        .data
+        .options
+        .iter()
+        .find(|&option| option.name.eq("duration"))
+        .map(|option| option.value.clone())
+    {
+        Some(CommandOptionValue::String(value)) => {
+            let Some(duration) =
+                parse(&value).filter(|seconds| (1..=MAX_TIMED_PERMISSION).contains(seconds))
+            else {
+                let embed = EmbedBuilder::new()
+                    .color(0xF8F8FF)
+                    .description("I could not find a valid **duration** value.")
+                    .build();
+
+                context
+                    .interaction_client()
+                    .update_response(&interaction.token)
+                    .embeds(Some(&[embed]))
+                    .await?;
+
+                return Ok(());
+            };
+
+            Some(duration)
+        }
+        _ => None,
+    };
     let Some(voice_channel_id) = context
         .cache
-        .voice_channel_owner(interaction.guild.id, interaction.user_id)
+        .managed_voice_channel(interaction.guild.id, interaction.user_id)
     else {
         let embed = EmbedBuilder::new()
             .color(0xF8F8FF)
-            .description("You do not own a voice channel.")
+            .description("You do not own or manage a voice channel.")
             .build();
 
         context
@@ -91,6 +132,7 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
             .owner_id
             .read()
             .is_some_and(|owner_id| owner_id.eq(&member_id))
+        || voice_channel.manager_ids.read().contains(&member_id)
     {
         let embed = EmbedBuilder::new()
             .color(0xF8F8FF)
@@ -124,7 +166,25 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
             |permission_overwrite| (permission_overwrite.allow, permission_overwrite.deny),
         );
 
-    if member_deny.contains(Permissions::VIEW_CHANNEL) {
+    // Without a duration, any timed permission gives way to a permanent one. With one, only a
+    // timed permission is extended, as its expiry would take a permanent one with it.
+    let had_timed_permission = match duration {
+        Some(_) => context
+            .database
+            .voice_channel_timed_permissions(voice_channel.id)
+            .await?
+            .iter()
+            .any(|timed_permission| timed_permission.member_id.eq(&member_id)),
+        None => {
+            context
+                .database
+                .remove_timed_permission(voice_channel.id, member_id)
+                .await?
+        }
+    };
+    let is_denied = member_deny.contains(Permissions::VIEW_CHANNEL);
+
+    if is_denied && !had_timed_permission {
         let embed = EmbedBuilder::new()
             .color(0xF8F8FF)
             .description(format!(
@@ -141,28 +201,67 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
         return Ok(());
     }
 
-    member_allow.remove(Permissions::CONNECT);
-    member_deny.remove(Permissions::CONNECT);
-    member_deny = member_deny.union(Permissions::VIEW_CHANNEL);
+    if !is_denied {
+        member_allow.remove(Permissions::CONNECT);
+        member_deny.remove(Permissions::CONNECT);
+        member_deny = member_deny.union(Permissions::VIEW_CHANNEL);
 
-    context
-        .client
-        .update_channel_permission(
-            voice_channel.id,
-            &HttpPermissionOverwrite {
-                allow: Some(member_allow),
-                deny: Some(member_deny),
-                id: member_id.cast(),
-                kind: HttpPermissionOverwriteType::Member,
-            },
-        )
-        .await?;
+        context
+            .client
+            .update_channel_permission(
+                voice_channel.id,
+                &HttpPermissionOverwrite {
+                    allow: Some(member_allow),
+                    deny: Some(member_deny),
+                    id: member_id.cast(),
+                    kind: HttpPermissionOverwriteType::Member,
+                },
+            )
+            .await?;
+    }
+    if let Some(duration) = duration {
+        context
+            .database
+            .insert_timed_permission(
+                voice_channel.id,
+                member_id,
+                TimedPermissionKind::Deny,
+                SystemTime::now() + Duration::from_secs(duration),
+            )
+            .await?;
+    }
 
+    audit(
+        &context,
+        &interaction.guild,
+        Some(interaction.user_id),
+        Some(voice_channel.id),
+        "/voice deny-member",
+        match duration {
+            Some(duration) => format!(
+                "<@{}> denied <@{member_id}> from <#{}> for **{}**.",
+                interaction.user_id,
+                voice_channel.id,
+                humanize(duration)
+            ),
+            None => format!(
+                "<@{}> denied <@{member_id}> from <#{}>.",
+                interaction.user_id, voice_channel.id
+            ),
+        },
+    )
+    .await?;
+
+    let description = match duration {
+        Some(duration) => format!(
+            "<@{member_id}> is now denied permission in this voice channel for **{}**.",
+            humanize(duration)
+        ),
+        None => format!("<@{member_id}> is now denied permission in this voice channel."),
+    };
     let embed = EmbedBuilder::new()
         .color(0xF8F8FF)
-        .description(format!(
-            "<@{member_id}> is now denied permission in this voice channel."
-        ))
+        .description(description)
         .build();
 
     context
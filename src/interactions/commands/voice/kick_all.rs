@@ -0,0 +1,192 @@
+use std::sync::Arc;
+
+use eyre::Result;
+use twilight_model::{
+    channel::message::MessageFlags,
+    http::interaction::{InteractionResponse, InteractionResponseType},
+};
+use twilight_util::builder::{embed::EmbedBuilder, InteractionResponseDataBuilder};
+
+use crate::{
+    structs::{context::Context, interaction::ApplicationCommandInteraction},
+    utilities::{
+        audit::audit,
+        bulk::{mentions, run_bulk},
+    },
+};
+
+pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteraction) -> Result<()> {
+    let interaction_response_data = InteractionResponseDataBuilder::new()
+        .flags(MessageFlags::EPHEMERAL)
+        .build();
+    let interaction_response = InteractionResponse {
+        data: Some(interaction_response_data),
+        kind: InteractionResponseType::DeferredChannelMessageWithSource,
+    };
+
+    context
+        .interaction_client()
+        .create_response(interaction.id, &interaction.token, &interaction_response)
+        .await?;
+
+    let Some(voice_channel_id) = context
+        .cache
+        .managed_voice_channel(interaction.guild.id, interaction.user_id)
+    else {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description("You do not own or manage a voice channel.")
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    };
+    let Some(voice_channel) = context.cache.voice_channel(*voice_channel_id) else {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description("I could not find your voice channel.")
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    };
+    let owner_id = *voice_channel.owner_id.read();
+    let manager_ids = voice_channel.manager_ids.read().clone();
+    let mut member_ids = voice_channel
+        .connected_user_ids
+        .read()
+        .iter()
+        .copied()
+        .filter(|user_id| {
+            owner_id.ne(&Some(*user_id))
+                && !manager_ids.contains(user_id)
+                && context.application_id.ne(&user_id.cast())
+        })
+        .collect::<Vec<_>>();
+
+    member_ids.sort();
+
+    let outcome = run_bulk(member_ids, |member_id| {
+        let context = &context;
+        let guild_id = interaction.guild.id;
+
+        async move {
+            context
+                .client
+                .update_guild_member(guild_id, member_id)
+                .channel_id(None)
+                .await?;
+
+            Ok(true)
+        }
+    })
+    .await;
+
+    if !outcome.changed.is_empty() {
+        audit(
+            &context,
+            &interaction.guild,
+            Some(interaction.user_id),
+            Some(voice_channel.id),
+            "/voice kick-all",
+            format!(
+                "<@{}> disconnected {} from <#{voice_channel_id}>.",
+                interaction.user_id,
+                mentions(&outcome.changed)
+            ),
+        )
+        .await?;
+    }
+
+    let embed = EmbedBuilder::new()
+        .color(0xF8F8FF)
+        .description(outcome.describe(
+            |mentions| format!("I've disconnected {mentions} from <#{voice_channel_id}>."),
+            |mentions| {
+                format!("I'm unable to disconnect {mentions} right now. Try again in 10 minutes.")
+            },
+        ))
+        .build();
+
+    context
+        .interaction_client()
+        .update_response(&interaction.token)
+        .embeds(Some(&[embed]))
+        .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use twilight_model::id::Id;
+
+    use crate::{
+        structs::database::ChannelPrivacy,
+        testing::{
+            self,
+            mock_discord::MockDiscord,
+            payloads::{command, subcommand, voice_state_update},
+            replay::replay,
+            seed_join_channel,
+        },
+    };
+
+    #[tokio::test]
+    #[ignore = "needs a database, run with `cargo test -- --ignored`"]
+    async fn reports_members_it_could_not_disconnect() {
+        let mock_discord = MockDiscord::start().await;
+        let (context, _guard) = testing::context(&mock_discord).await;
+        let guild_id = Id::new(1001);
+
+        seed_join_channel(&context, guild_id, Id::new(1101), ChannelPrivacy::Unlocked).await;
+        replay(
+            &context,
+            include_str!("../../../testing/recordings/create.jsonl"),
+        )
+        .await
+        .unwrap();
+        mock_discord.fail("PATCH", "/guilds/1001/members/2003");
+        replay(
+            &context,
+            &[
+                voice_state_update(1001, 2002, "sam", Some(5000)),
+                voice_state_update(1001, 2003, "kim", Some(5000)),
+                voice_state_update(1001, 2004, "lee", Some(5000)),
+                command(
+                    "kick-all",
+                    1001,
+                    2001,
+                    subcommand("voice", "kick-all", json!([])),
+                ),
+            ]
+            .join("\n"),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            mock_discord.response_descriptions("kick-all"),
+            ["I've disconnected <@2002> and <@2004> from <#5000>.\nI'm unable to disconnect <@2003> right now. Try again in 10 minutes."]
+        );
+        assert_eq!(
+            mock_discord
+                .requests_to("PATCH", "/guilds/1001/members/2001")
+                .len(),
+            1
+        );
+
+        context.database.remove_guild(guild_id).await.unwrap();
+    }
+}
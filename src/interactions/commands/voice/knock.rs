@@ -0,0 +1,167 @@
+use std::sync::Arc;
+
+use eyre::Result;
+use twilight_model::{
+    application::interaction::application_command::CommandOptionValue,
+    channel::message::MessageFlags,
+    http::interaction::{InteractionResponse, InteractionResponseType},
+};
+use twilight_util::builder::{embed::EmbedBuilder, InteractionResponseDataBuilder};
+
+use crate::{
+    interactions::components::knock::{is_allowed, knock_retry_after, post_knock},
+    structs::{
+        context::Context, database::RelationshipKind, interaction::ApplicationCommandInteraction,
+    },
+    utilities::time::humanize,
+};
+
+pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteraction) -> Result<()> {
+    let interaction_response_data = InteractionResponseDataBuilder::new()
+        .flags(MessageFlags::EPHEMERAL)
+        .build();
+    let interaction_response = InteractionResponse {
+        data: Some(interaction_response_data),
+        kind: InteractionResponseType::DeferredChannelMessageWithSource,
+    };
+
+    context
+        .interaction_client()
+        .create_response(interaction.id, &interaction.token, &interaction_response)
+        .await?;
+
+    let Some(CommandOptionValue::User(owner_id)) = interaction
+        .data
+        .options
+        .into_iter()
+        .find(|option| option.name.eq("owner"))
+        .map(|option| option.value)
+    else {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description("I could not find a valid **owner** value.")
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    };
+    let Some(voice_channel) = context
+        .cache
+        .voice_channel_owner(interaction.guild.id, owner_id)
+        .and_then(|voice_channel_id| context.cache.voice_channel(*voice_channel_id))
+    else {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description(format!("<@{owner_id}> does not own a voice channel."))
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    };
+    let voice_channel_id = voice_channel.id;
+
+    if is_allowed(&voice_channel, interaction.user_id) {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description(format!(
+                "You may already join <#{voice_channel_id}>, so there's no need to knock."
+            ))
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    }
+
+    let is_blocked = context
+        .database
+        .user_relationships(interaction.guild.id, owner_id)
+        .await?
+        .into_iter()
+        .any(|user_relationship| {
+            user_relationship.member_id.eq(&interaction.user_id)
+                && user_relationship.kind.eq(&RelationshipKind::Blocked)
+        });
+
+    if is_blocked {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description(format!("You may not knock on <#{voice_channel_id}>."))
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    }
+
+    if let Some(retry_after) = knock_retry_after(&voice_channel, interaction.user_id) {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description(format!(
+                "You knocked on <#{voice_channel_id}> recently. You may knock again in {}.",
+                humanize(retry_after.as_secs().max(1))
+            ))
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    }
+
+    if post_knock(&context, &voice_channel, interaction.user_id)
+        .await
+        .is_err()
+    {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description(format!(
+                "I'm unable to knock on <#{voice_channel_id}> right now. Try again in 10 minutes."
+            ))
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    }
+
+    let embed = EmbedBuilder::new()
+        .color(0xF8F8FF)
+        .description(format!(
+            "I've knocked on <#{voice_channel_id}>. If you're waiting in a voice channel, I'll move you in once you're let in."
+        ))
+        .build();
+
+    context
+        .interaction_client()
+        .update_response(&interaction.token)
+        .embeds(Some(&[embed]))
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,88 @@
+use std::sync::Arc;
+
+use eyre::Result;
+use twilight_model::{
+    channel::message::MessageFlags,
+    http::interaction::{InteractionResponse, InteractionResponseType},
+};
+use twilight_util::builder::{embed::EmbedBuilder, InteractionResponseDataBuilder};
+
+use crate::structs::{context::Context, interaction::ApplicationCommandInteraction};
+
+pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteraction) -> Result<()> {
+    let interaction_response_data = InteractionResponseDataBuilder::new()
+        .flags(MessageFlags::EPHEMERAL)
+        .build();
+    let interaction_response = InteractionResponse {
+        data: Some(interaction_response_data),
+        kind: InteractionResponseType::DeferredChannelMessageWithSource,
+    };
+
+    context
+        .interaction_client()
+        .create_response(interaction.id, &interaction.token, &interaction_response)
+        .await?;
+
+    let Some(voice_channel_id) = context
+        .cache
+        .managed_voice_channel(interaction.guild.id, interaction.user_id)
+    else {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description("You do not own or manage a voice channel.")
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    };
+    let Some(voice_channel) = context.cache.voice_channel(*voice_channel_id) else {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description("I could not find your voice channel.")
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    };
+    let mut manager_ids = voice_channel
+        .manager_ids
+        .read()
+        .iter()
+        .copied()
+        .collect::<Vec<_>>();
+
+    manager_ids.sort();
+
+    let description = if manager_ids.is_empty() {
+        "No manager has been added.".to_owned()
+    } else {
+        manager_ids
+            .into_iter()
+            .map(|manager_id| format!("- <@{manager_id}>"))
+            .collect::<Vec<String>>()
+            .join("\n")
+    };
+    let embed = EmbedBuilder::new()
+        .color(0xF8F8FF)
+        .description(description)
+        .title(format!("Managers for \"{}\"", voice_channel.name.read()))
+        .build();
+
+    context
+        .interaction_client()
+        .update_response(&interaction.token)
+        .embeds(Some(&[embed]))
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,270 @@
+mod add;
+mod list;
+mod remove;
+
+use std::{mem::replace, sync::Arc};
+
+use eyre::Result;
+use twilight_model::{
+    application::interaction::application_command::{CommandDataOption, CommandOptionValue},
+    channel::message::MessageFlags,
+    http::interaction::{InteractionResponse, InteractionResponseType},
+};
+use twilight_util::builder::{embed::EmbedBuilder, InteractionResponseDataBuilder};
+
+use crate::structs::{context::Context, interaction::ApplicationCommandInteraction};
+
+pub async fn run(
+    context: Arc<Context>,
+    mut interaction: ApplicationCommandInteraction,
+) -> Result<()> {
+    let Some(CommandDataOption {
+        name,
+        value: CommandOptionValue::SubCommand(options),
+    }) = interaction.data.options.clone().into_iter().next()
+    else {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description("I could not find a subcommand.")
+            .build();
+        let interaction_response_data = InteractionResponseDataBuilder::new()
+            .embeds(vec![embed])
+            .flags(MessageFlags::EPHEMERAL)
+            .build();
+        let interaction_response = InteractionResponse {
+            data: Some(interaction_response_data),
+            kind: InteractionResponseType::ChannelMessageWithSource,
+        };
+
+        context
+            .interaction_client()
+            .create_response(interaction.id, &interaction.token, &interaction_response)
+            .await?;
+
+        return Ok(());
+    };
+    let _ = replace(&mut interaction.data.options, options);
+
+    match name.as_str() {
+        "add" => add::run(context, interaction).await?,
+        "list" => list::run(context, interaction).await?,
+        "remove" => remove::run(context, interaction).await?,
+        _ => {
+            let embed = EmbedBuilder::new()
+                .color(0xF8F8FF)
+                .description(format!(
+                    "I don't have a subcommand with the name \"{name}\"."
+                ))
+                .build();
+            let interaction_response_data = InteractionResponseDataBuilder::new()
+                .embeds(vec![embed])
+                .flags(MessageFlags::EPHEMERAL)
+                .build();
+            let interaction_response = InteractionResponse {
+                data: Some(interaction_response_data),
+                kind: InteractionResponseType::ChannelMessageWithSource,
+            };
+
+            context
+                .interaction_client()
+                .create_response(interaction.id, &interaction.token, &interaction_response)
+                .await?;
+
+            return Ok(());
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use twilight_model::id::Id;
+
+    use crate::{
+        structs::database::ChannelPrivacy,
+        testing::{
+            self,
+            mock_discord::MockDiscord,
+            payloads::{command, subcommand, subcommand_group},
+            replay::replay,
+            seed_join_channel, VOICE_CHANNEL_ID,
+        },
+    };
+
+    fn add_command(token: &str, user_id: u64, member_id: u64) -> String {
+        command(
+            token,
+            1001,
+            user_id,
+            subcommand_group(
+                "voice",
+                "managers",
+                "add",
+                json!([{ "name": "member", "type": 6, "value": member_id.to_string() }]),
+            ),
+        )
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a database, run with `cargo test -- --ignored`"]
+    async fn managers_share_everything_but_ownership() {
+        let mock_discord = MockDiscord::start().await;
+        let (context, _guard) = testing::context(&mock_discord).await;
+        let guild_id = Id::new(1001);
+
+        seed_join_channel(&context, guild_id, Id::new(1101), ChannelPrivacy::Locked).await;
+        replay(
+            &context,
+            include_str!("../../../../testing/recordings/create.jsonl"),
+        )
+        .await
+        .unwrap();
+        replay(&context, &add_command("add", 2001, 2002))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            mock_discord.response_descriptions("add"),
+            ["<@2002> now manages <#5000>."]
+        );
+        assert_eq!(
+            mock_discord
+                .assert_requested("PUT", "/channels/5000/permissions/2002")
+                .body["allow"],
+            "1048576"
+        );
+        assert!(context
+            .cache
+            .voice_channel(VOICE_CHANNEL_ID)
+            .unwrap()
+            .manager_ids
+            .read()
+            .contains(&Id::new(2002)));
+        assert_eq!(
+            context
+                .database
+                .guild_voice_channel_managers(guild_id)
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+
+        replay(
+            &context,
+            &command(
+                "rename",
+                1001,
+                2002,
+                subcommand(
+                    "voice",
+                    "name",
+                    json!([{ "name": "name", "type": 3, "value": "study hall" }]),
+                ),
+            ),
+        )
+        .await
+        .unwrap();
+        replay(
+            &context,
+            &command(
+                "transfer",
+                1001,
+                2002,
+                subcommand(
+                    "voice",
+                    "transfer",
+                    json!([{ "name": "member", "type": 3, "value": "2002" }]),
+                ),
+            ),
+        )
+        .await
+        .unwrap();
+        replay(&context, &add_command("add-again", 2002, 2003))
+            .await
+            .unwrap();
+
+        assert_eq!(mock_discord.channel(5000).unwrap()["name"], "study hall");
+        assert_eq!(
+            mock_discord.response_descriptions("transfer"),
+            ["You do not own a voice channel."]
+        );
+        assert_eq!(
+            mock_discord.response_descriptions("add-again"),
+            ["You do not own a voice channel."]
+        );
+
+        context.database.remove_guild(guild_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a database, run with `cargo test -- --ignored`"]
+    async fn removes_and_lists_managers() {
+        let mock_discord = MockDiscord::start().await;
+        let (context, _guard) = testing::context(&mock_discord).await;
+        let guild_id = Id::new(1001);
+
+        seed_join_channel(&context, guild_id, Id::new(1101), ChannelPrivacy::Unlocked).await;
+        replay(
+            &context,
+            include_str!("../../../../testing/recordings/create.jsonl"),
+        )
+        .await
+        .unwrap();
+        replay(&context, &add_command("add", 2001, 2002))
+            .await
+            .unwrap();
+        replay(
+            &context,
+            &command(
+                "list",
+                1001,
+                2002,
+                subcommand_group("voice", "managers", "list", json!([])),
+            ),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(mock_discord.response_descriptions("list"), ["- <@2002>"]);
+
+        replay(
+            &context,
+            &command(
+                "remove",
+                1001,
+                2001,
+                subcommand_group(
+                    "voice",
+                    "managers",
+                    "remove",
+                    json!([{ "name": "member", "type": 3, "value": "2002" }]),
+                ),
+            ),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            mock_discord.response_descriptions("remove"),
+            ["<@2002> no longer manages <#5000>, but may still join it."]
+        );
+        assert!(context
+            .cache
+            .voice_channel(VOICE_CHANNEL_ID)
+            .unwrap()
+            .manager_ids
+            .read()
+            .is_empty());
+        assert!(context
+            .database
+            .guild_voice_channel_managers(guild_id)
+            .await
+            .unwrap()
+            .is_empty());
+
+        context.database.remove_guild(guild_id).await.unwrap();
+    }
+}
@@ -0,0 +1,166 @@
+use std::sync::Arc;
+
+use eyre::Result;
+use twilight_model::{
+    application::interaction::application_command::CommandOptionValue,
+    channel::message::MessageFlags,
+    http::interaction::{InteractionResponse, InteractionResponseType},
+};
+use twilight_util::builder::{embed::EmbedBuilder, InteractionResponseDataBuilder};
+
+use super::super::{member_choices, parse_member_id};
+use crate::{
+    structs::{context::Context, interaction::ApplicationCommandInteraction},
+    utilities::audit::audit,
+};
+
+pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteraction) -> Result<()> {
+    let member_value = match interaction
+        .data
+        .options
+        .iter()
+        .find(|&option| option.name.eq("member"))
+        .cloned()
+        .map(|option| option.value)
+    {
+        Some(CommandOptionValue::Focused(value, _)) => {
+            let user_ids = context
+                .cache
+                .voice_channel_owner(interaction.guild.id, interaction.user_id)
+                .and_then(|voice_channel_id| context.cache.voice_channel(*voice_channel_id))
+                .map(|voice_channel| voice_channel.manager_ids.read().clone())
+                .unwrap_or_default();
+            let mut choices = member_choices(&context, interaction.guild.id, user_ids, &value);
+
+            choices.sort_by(|a, b| a.name.cmp(&b.name));
+
+            let data = InteractionResponseDataBuilder::new()
+                .choices(choices)
+                .build();
+            let interaction_response = InteractionResponse {
+                data: Some(data),
+                kind: InteractionResponseType::ApplicationCommandAutocompleteResult,
+            };
+
+            context
+                .interaction_client()
+                .create_response(interaction.id, &interaction.token, &interaction_response)
+                .await?;
+
+            return Ok(());
+        }
+        Some(CommandOptionValue::String(value)) => value,
+        _ => return Ok(()),
+    };
+    let interaction_response_data = InteractionResponseDataBuilder::new()
+        .flags(MessageFlags::EPHEMERAL)
+        .build();
+    let interaction_response = InteractionResponse {
+        data: Some(interaction_response_data),
+        kind: InteractionResponseType::DeferredChannelMessageWithSource,
+    };
+
+    context
+        .interaction_client()
+        .create_response(interaction.id, &interaction.token, &interaction_response)
+        .await?;
+
+    let Some(member_id) = parse_member_id(&member_value) else {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description("I could not find a valid **member** value.")
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    };
+    let Some(voice_channel_id) = context
+        .cache
+        .voice_channel_owner(interaction.guild.id, interaction.user_id)
+    else {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description("You do not own a voice channel.")
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    };
+    let Some(voice_channel) = context.cache.voice_channel(*voice_channel_id) else {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description("I could not find your voice channel.")
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    };
+
+    if !voice_channel.manager_ids.read().contains(&member_id) {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description(format!(
+                "<@{member_id}> does not manage this voice channel."
+            ))
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    }
+
+    context
+        .database
+        .remove_voice_channel_manager(voice_channel.id, member_id)
+        .await?;
+    context
+        .cache
+        .remove_voice_channel_manager(voice_channel.id, member_id);
+
+    audit(
+        &context,
+        &interaction.guild,
+        Some(interaction.user_id),
+        Some(voice_channel.id),
+        "/voice managers remove",
+        format!(
+            "<@{}> removed <@{member_id}> as a manager of <#{voice_channel_id}>.",
+            interaction.user_id
+        ),
+    )
+    .await?;
+
+    let embed = EmbedBuilder::new()
+        .color(0xF8F8FF)
+        .description(format!(
+            "<@{member_id}> no longer manages <#{voice_channel_id}>, but may still join it."
+        ))
+        .build();
+
+    context
+        .interaction_client()
+        .update_response(&interaction.token)
+        .embeds(Some(&[embed]))
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,101 @@
+use std::sync::Arc;
+
+use eyre::Result;
+use twilight_model::{
+    application::interaction::application_command::{CommandDataOption, CommandOptionValue},
+    channel::message::{
+        component::{ActionRow, Component, SelectMenu, SelectMenuType},
+        MessageFlags,
+    },
+    http::interaction::{InteractionResponse, InteractionResponseType},
+};
+use twilight_util::builder::{embed::EmbedBuilder, InteractionResponseDataBuilder};
+
+use crate::structs::{context::Context, interaction::ApplicationCommandInteraction};
+
+/// Runs a `/voice members` subcommand, which replies with a member select menu. The members
+/// picked are handled by `components::members`.
+pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteraction) -> Result<()> {
+    let Some(CommandDataOption {
+        name,
+        value: CommandOptionValue::SubCommand(_),
+    }) = interaction.data.options.first()
+    else {
+        return respond(
+            &context,
+            &interaction,
+            "I could not find a subcommand.",
+            None,
+        )
+        .await;
+    };
+    let Some(voice_channel_id) = context
+        .cache
+        .managed_voice_channel(interaction.guild.id, interaction.user_id)
+    else {
+        return respond(
+            &context,
+            &interaction,
+            "You do not own or manage a voice channel.",
+            None,
+        )
+        .await;
+    };
+    let description = match name.as_str() {
+        "allow" => format!("Pick the members to allow into <#{voice_channel_id}>."),
+        "deny" => format!("Pick the members to deny from <#{voice_channel_id}>."),
+        "remove" => format!("Pick the members to remove from <#{voice_channel_id}>."),
+        _ => {
+            return respond(
+                &context,
+                &interaction,
+                &format!("I don't have a subcommand with the name \"{name}\"."),
+                None,
+            )
+            .await
+        }
+    };
+    let component = Component::ActionRow(ActionRow {
+        components: vec![Component::SelectMenu(SelectMenu {
+            channel_types: None,
+            custom_id: format!("members:{name}"),
+            default_values: None,
+            disabled: false,
+            kind: SelectMenuType::User,
+            max_values: Some(25),
+            min_values: Some(1),
+            options: None,
+            placeholder: Some("Members".to_owned()),
+        })],
+    });
+
+    respond(&context, &interaction, &description, Some(component)).await
+}
+
+async fn respond(
+    context: &Context,
+    interaction: &ApplicationCommandInteraction,
+    description: &str,
+    component: Option<Component>,
+) -> Result<()> {
+    let embed = EmbedBuilder::new()
+        .color(0xF8F8FF)
+        .description(description)
+        .build();
+    let interaction_response_data = InteractionResponseDataBuilder::new()
+        .components(component)
+        .embeds(vec![embed])
+        .flags(MessageFlags::EPHEMERAL)
+        .build();
+    let interaction_response = InteractionResponse {
+        data: Some(interaction_response_data),
+        kind: InteractionResponseType::ChannelMessageWithSource,
+    };
+
+    context
+        .interaction_client()
+        .create_response(interaction.id, &interaction.token, &interaction_response)
+        .await?;
+
+    Ok(())
+}
@@ -3,28 +3,46 @@ mod bitrate;
 mod claim;
 mod delete;
 mod deny_member;
-mod name;
+mod kick_all;
+mod knock;
+mod managers;
+mod members;
+pub mod name;
 mod permanence;
-mod privacy;
+mod preset;
+pub mod privacy;
+mod relationship;
 mod remove_member;
+mod remove_role;
 mod slow_mode;
+mod stage;
+mod status;
 mod transfer;
 mod user_limit;
 mod video_quality_mode;
 mod view;
 mod voice_region;
 
-use std::{mem::replace, sync::Arc};
+use std::{mem::replace, str::FromStr, sync::Arc};
 
 use eyre::Result;
 use twilight_model::{
-    application::interaction::application_command::{CommandDataOption, CommandOptionValue},
+    application::{
+        command::{CommandOptionChoice, CommandOptionChoiceValue},
+        interaction::application_command::{CommandDataOption, CommandOptionValue},
+    },
     channel::message::MessageFlags,
     http::interaction::{InteractionResponse, InteractionResponseType},
+    id::{
+        marker::{GuildMarker, UserMarker},
+        Id,
+    },
 };
 use twilight_util::builder::{embed::EmbedBuilder, InteractionResponseDataBuilder};
 
-use crate::structs::{context::Context, interaction::ApplicationCommandInteraction};
+use crate::structs::{
+    context::Context, database::RelationshipKind, interaction::ApplicationCommandInteraction,
+};
 
 pub async fn run(
     context: Arc<Context>,
@@ -32,8 +50,9 @@ pub async fn run(
 ) -> Result<()> {
     let Some(CommandDataOption {
         name,
-        value: CommandOptionValue::SubCommand(options),
-    }) = interaction.data.options.clone().into_iter().nth(0)
+        value:
+            CommandOptionValue::SubCommand(options) | CommandOptionValue::SubCommandGroup(options),
+    }) = interaction.data.options.clone().into_iter().next()
     else {
         let embed = EmbedBuilder::new()
             .color(0xF8F8FF)
@@ -60,15 +79,25 @@ pub async fn run(
     match name.as_str() {
         "allow-member" => allow_member::run(context, interaction).await?,
         "bitrate" => bitrate::run(context, interaction).await?,
+        "block" => relationship::run(context, interaction, RelationshipKind::Blocked).await?,
         "claim" => claim::run(context, interaction).await?,
         "delete" => delete::run(context, interaction).await?,
         "deny-member" => deny_member::run(context, interaction).await?,
+        "kick-all" => kick_all::run(context, interaction).await?,
+        "knock" => knock::run(context, interaction).await?,
+        "managers" => managers::run(context, interaction).await?,
+        "members" => members::run(context, interaction).await?,
         "name" => name::run(context, interaction).await?,
         "permanence" => permanence::run(context, interaction).await?,
+        "preset" => preset::run(context, interaction).await?,
         "privacy" => privacy::run(context, interaction).await?,
         "remove-member" => remove_member::run(context, interaction).await?,
+        "remove-role" => remove_role::run(context, interaction).await?,
         "slow-mode" => slow_mode::run(context, interaction).await?,
+        "stage" => stage::run(context, interaction).await?,
+        "status" => status::run(context, interaction).await?,
         "transfer" => transfer::run(context, interaction).await?,
+        "trust" => relationship::run(context, interaction, RelationshipKind::Trusted).await?,
         "user-limit" => user_limit::run(context, interaction).await?,
         "video-quality-mode" => video_quality_mode::run(context, interaction).await?,
         "view" => view::run(context, interaction).await?,
@@ -100,3 +129,49 @@ pub async fn run(
 
     Ok(())
 }
+
+/// Autocomplete choices for a member option, in the order given and labelled with each member's
+/// display name. The value is the member's ID.
+fn member_choices(
+    context: &Context,
+    guild_id: Id<GuildMarker>,
+    user_ids: impl IntoIterator<Item = Id<UserMarker>>,
+    value: &str,
+) -> Vec<CommandOptionChoice> {
+    let lowercased_value = value.to_lowercase();
+
+    user_ids
+        .into_iter()
+        .filter_map(|user_id| {
+            let name = context
+                .cache
+                .member_name(guild_id, user_id)
+                .map_or_else(|| user_id.to_string(), |name| name.to_string());
+
+            if !name.to_lowercase().contains(&lowercased_value)
+                && !user_id.to_string().contains(&lowercased_value)
+            {
+                return None;
+            }
+
+            Some(CommandOptionChoice {
+                name,
+                name_localizations: None,
+                value: CommandOptionChoiceValue::String(user_id.to_string()),
+            })
+        })
+        .take(25)
+        .collect()
+}
+
+/// Reads a member option, which holds an ID when picked from suggestions but may also be a
+/// typed ID or mention.
+fn parse_member_id(value: &str) -> Option<Id<UserMarker>> {
+    let value = value.trim();
+    let value = value
+        .strip_prefix("<@")
+        .and_then(|value| value.strip_suffix('>'))
+        .map_or(value, |value| value.trim_start_matches('!'));
+
+    Id::from_str(value).ok()
+}
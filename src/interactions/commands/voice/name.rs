@@ -1,4 +1,7 @@
-use std::sync::Arc;
+use std::{
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
 
 use eyre::Result;
 use twilight_model::{
@@ -8,7 +11,13 @@ use twilight_model::{
 };
 use twilight_util::builder::{embed::EmbedBuilder, InteractionResponseDataBuilder};
 
-use crate::structs::{context::Context, interaction::ApplicationCommandInteraction};
+use crate::{
+    structs::{
+        cache::CachedVoiceChannelUpdate, context::Context,
+        interaction::ApplicationCommandInteraction,
+    },
+    utilities::{audit::audit, name_filter::name_violation, time::humanize},
+};
 
 pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteraction) -> Result<()> {
     let interaction_response_data = InteractionResponseDataBuilder::new()
@@ -24,6 +33,9 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
         .create_response(interaction.id, &interaction.token, &interaction_response)
         .await?;
 
+    let is_queued = interaction.data.options.iter().any(|option| {
+        option.name.eq("queue") && option.value.eq(&CommandOptionValue::Boolean(true))
+    });
     let Some(CommandOptionValue::String(name)) = interaction
         .data
         .options
@@ -46,11 +58,11 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
     };
     let Some(voice_channel_id) = context
         .cache
-        .voice_channel_owner(interaction.guild.id, interaction.user_id)
+        .managed_voice_channel(interaction.guild.id, interaction.user_id)
     else {
         let embed = EmbedBuilder::new()
             .color(0xF8F8FF)
-            .description("You do not own a voice channel.")
+            .description("You do not own or manage a voice channel.")
             .build();
 
         context
@@ -91,6 +103,60 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
         return Ok(());
     }
 
+    if let Some(reason) = name_violation(&interaction.guild, &name) {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description(format!("This name {reason}."))
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    }
+
+    let now = SystemTime::now();
+
+    if let Some(retry_after) = context.cache.rename_retry_after(voice_channel.id, now) {
+        let retry_text = rename_retry_text(retry_after);
+        let description = if is_queued {
+            context.cache.insert_queued_name(
+                voice_channel.id,
+                Some(interaction.user_id),
+                name.clone(),
+            );
+
+            format!(
+                "You can rename <#{voice_channel_id}> again in **{retry_text}**, so I'll name it **{name}** then."
+            )
+        } else {
+            format!(
+                "You can rename <#{voice_channel_id}> again in **{retry_text}**. Use the **queue** option to have me rename it then."
+            )
+        };
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description(description)
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    }
+
+    let saved_settings = context
+        .cache
+        .update_saved_settings(voice_channel.id, |saved_settings| {
+            saved_settings.name = name.clone();
+        });
+
     if context
         .client
         .update_channel(voice_channel.id)
@@ -98,6 +164,14 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
         .await
         .is_err()
     {
+        context.cache.update_voice_channel(
+            voice_channel.id,
+            CachedVoiceChannelUpdate {
+                saved_settings: Some(saved_settings),
+                ..Default::default()
+            },
+        );
+
         let embed = EmbedBuilder::new()
             .color(0xF8F8FF)
             .description("I'm unable to set the name right now. Try again in 10 minutes.")
@@ -112,6 +186,26 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
         return Ok(());
     };
 
+    context
+        .database
+        .update_voice_channel_name(voice_channel.id, &name)
+        .await?;
+    context.cache.insert_rename_time(voice_channel.id, now);
+    // A name set directly replaces whatever was waiting for the rate limit to pass.
+    context.cache.remove_queued_name(voice_channel.id);
+    audit(
+        &context,
+        &interaction.guild,
+        Some(interaction.user_id),
+        Some(voice_channel.id),
+        "/voice name",
+        format!(
+            "<@{}> renamed <#{voice_channel_id}> to **{name}**.",
+            interaction.user_id
+        ),
+    )
+    .await?;
+
     let embed = EmbedBuilder::new()
         .color(0xF8F8FF)
         .description(format!("I've set the name for <#{voice_channel_id}>.",))
@@ -125,3 +219,126 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
 
     Ok(())
 }
+
+/// How long until a voice channel may be renamed, rounded up to the minute.
+pub fn rename_retry_text(retry_after: Duration) -> String {
+    humanize(retry_after.as_secs().div_ceil(60) * 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use twilight_model::id::Id;
+
+    use crate::{
+        structs::database::ChannelPrivacy,
+        testing::{
+            self,
+            mock_discord::MockDiscord,
+            payloads::{command, subcommand},
+            replay::replay,
+            seed_join_channel,
+        },
+    };
+
+    fn name_command(token: &str, user_id: u64, name: &str) -> String {
+        command(
+            token,
+            1001,
+            user_id,
+            subcommand(
+                "voice",
+                "name",
+                json!([{ "name": "name", "type": 3, "value": name }]),
+            ),
+        )
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a database, run with `cargo test -- --ignored`"]
+    async fn renames_the_owned_voice_channel() {
+        let mock_discord = MockDiscord::start().await;
+        let (context, _guard) = testing::context(&mock_discord).await;
+        let guild_id = Id::new(1001);
+
+        seed_join_channel(&context, guild_id, Id::new(1101), ChannelPrivacy::Unlocked).await;
+        replay(
+            &context,
+            include_str!("../../../testing/recordings/create.jsonl"),
+        )
+        .await
+        .unwrap();
+        replay(&context, &name_command("rename", 2001, "study hall"))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            mock_discord
+                .assert_requested("PATCH", "/channels/5000")
+                .body["name"],
+            "study hall"
+        );
+        assert_eq!(mock_discord.channel(5000).unwrap()["name"], "study hall");
+        assert_eq!(
+            mock_discord.response_descriptions("rename"),
+            ["I've set the name for <#5000>."]
+        );
+
+        context.database.remove_guild(guild_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a database, run with `cargo test -- --ignored`"]
+    async fn reports_a_rejected_name() {
+        let mock_discord = MockDiscord::start().await;
+        let (context, _guard) = testing::context(&mock_discord).await;
+        let guild_id = Id::new(1001);
+
+        seed_join_channel(&context, guild_id, Id::new(1101), ChannelPrivacy::Unlocked).await;
+        replay(
+            &context,
+            include_str!("../../../testing/recordings/create.jsonl"),
+        )
+        .await
+        .unwrap();
+        mock_discord.fail("PATCH", "/channels/5000");
+        replay(&context, &name_command("rename", 2001, "study hall"))
+            .await
+            .unwrap();
+
+        assert_eq!(mock_discord.channel(5000).unwrap()["name"], "alex's voice");
+        assert_eq!(
+            mock_discord.response_descriptions("rename"),
+            ["I'm unable to set the name right now. Try again in 10 minutes."]
+        );
+
+        context.database.remove_guild(guild_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a database, run with `cargo test -- --ignored`"]
+    async fn refuses_members_without_a_voice_channel() {
+        let mock_discord = MockDiscord::start().await;
+        let (context, _guard) = testing::context(&mock_discord).await;
+        let guild_id = Id::new(1001);
+
+        seed_join_channel(&context, guild_id, Id::new(1101), ChannelPrivacy::Unlocked).await;
+        replay(
+            &context,
+            include_str!("../../../testing/recordings/create.jsonl"),
+        )
+        .await
+        .unwrap();
+        replay(&context, &name_command("rename", 2002, "study hall"))
+            .await
+            .unwrap();
+
+        mock_discord.assert_not_requested("PATCH", "/channels/5000");
+        assert_eq!(
+            mock_discord.response_descriptions("rename"),
+            ["You do not own or manage a voice channel."]
+        );
+
+        context.database.remove_guild(guild_id).await.unwrap();
+    }
+}
@@ -8,8 +8,12 @@ use twilight_model::{
 };
 use twilight_util::builder::{embed::EmbedBuilder, InteractionResponseDataBuilder};
 
-use crate::structs::{
-    cache::CachedVoiceChannelUpdate, context::Context, interaction::ApplicationCommandInteraction,
+use crate::{
+    structs::{
+        cache::CachedVoiceChannelUpdate, context::Context,
+        interaction::ApplicationCommandInteraction,
+    },
+    utilities::audit::audit,
 };
 
 pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteraction) -> Result<()> {
@@ -48,11 +52,11 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
     };
     let Some(voice_channel_id) = context
         .cache
-        .voice_channel_owner(interaction.guild.id, interaction.user_id)
+        .managed_voice_channel(interaction.guild.id, interaction.user_id)
     else {
         let embed = EmbedBuilder::new()
             .color(0xF8F8FF)
-            .description("You do not own a voice channel.")
+            .description("You do not own or manage a voice channel.")
             .build();
 
         context
@@ -105,6 +109,19 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
         },
     );
 
+    audit(
+        &context,
+        &interaction.guild,
+        Some(interaction.user_id),
+        Some(voice_channel.id),
+        "/voice permanence",
+        format!(
+            "<@{}> set the permanence value of <#{}> to **{value}**.",
+            interaction.user_id, voice_channel.id
+        ),
+    )
+    .await?;
+
     let permanence_text = if value {
         format!(
             "When empty, <#{}> **will not be deleted**.",
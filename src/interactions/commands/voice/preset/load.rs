@@ -0,0 +1,306 @@
+use std::{sync::Arc, time::SystemTime};
+
+use eyre::Result;
+use twilight_model::{
+    channel::{
+        message::MessageFlags,
+        permission_overwrite::{
+            PermissionOverwrite as ChannelPermissionOverwrite,
+            PermissionOverwriteType as ChannelPermissionOverwriteType,
+        },
+    },
+    guild::Permissions,
+    http::interaction::{InteractionResponse, InteractionResponseType},
+};
+use twilight_util::builder::{embed::EmbedBuilder, InteractionResponseDataBuilder};
+
+use crate::{
+    interactions::commands::voice::name::rename_retry_text,
+    structs::{
+        cache::CachedVoiceChannelUpdate, context::Context,
+        interaction::ApplicationCommandInteraction,
+    },
+    utilities::{
+        audit::audit,
+        name_filter::name_violation,
+        permission_overwrites::{apply_relationships, privacy_permissions},
+    },
+};
+
+pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteraction) -> Result<()> {
+    let interaction_response_data = InteractionResponseDataBuilder::new()
+        .flags(MessageFlags::EPHEMERAL)
+        .build();
+    let interaction_response = InteractionResponse {
+        data: Some(interaction_response_data),
+        kind: InteractionResponseType::DeferredChannelMessageWithSource,
+    };
+
+    context
+        .interaction_client()
+        .create_response(interaction.id, &interaction.token, &interaction_response)
+        .await?;
+
+    if !*interaction.guild.presets_enabled.read() {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description("Presets have been turned off in this server.")
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    }
+
+    let Some(voice_channel_id) = context
+        .cache
+        .voice_channel_owner(interaction.guild.id, interaction.user_id)
+    else {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description("You do not own a voice channel.")
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    };
+    let Some(voice_channel) = context.cache.voice_channel(*voice_channel_id) else {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description("I could not find your voice channel.")
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    };
+    let Some(user_preset) = context
+        .database
+        .user_preset(interaction.guild.id, interaction.user_id)
+        .await?
+    else {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description("You have not saved a preset.")
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    };
+    let privacy_permissions = privacy_permissions(voice_channel.kind, &user_preset.privacy);
+    let owner_id = *voice_channel.owner_id.read();
+    let manager_ids = voice_channel.manager_ids.read().clone();
+    let is_kept = |member_id| owner_id.eq(&Some(member_id)) || manager_ids.contains(&member_id);
+    let mut permission_overwrites = voice_channel.permission_overwrites.read().clone();
+
+    // The preset's allow and deny lists replace the current ones, but the owner and managers
+    // keep their access.
+    permission_overwrites.retain(|permission_overwrite| {
+        permission_overwrite
+            .kind
+            .ne(&ChannelPermissionOverwriteType::Member)
+            || is_kept(permission_overwrite.id.cast())
+    });
+
+    for permission_overwrite in permission_overwrites.iter_mut() {
+        if permission_overwrite
+            .kind
+            .eq(&ChannelPermissionOverwriteType::Member)
+            || permission_overwrite
+                .id
+                .eq(&interaction.guild.bot_role_id.cast())
+        {
+            permission_overwrite.allow = privacy_permissions;
+        } else if permission_overwrite.id.eq(&interaction.guild.id.cast()) {
+            permission_overwrite.deny = privacy_permissions;
+        }
+    }
+
+    let allowed_user_ids = user_preset
+        .allowed_user_ids
+        .iter()
+        .map(|allowed_user_id| (*allowed_user_id, privacy_permissions, Permissions::empty()));
+    let denied_user_ids = user_preset.denied_user_ids.iter().map(|denied_user_id| {
+        (
+            *denied_user_id,
+            Permissions::empty(),
+            Permissions::VIEW_CHANNEL,
+        )
+    });
+
+    for (member_id, allow, deny) in allowed_user_ids.chain(denied_user_ids) {
+        if is_kept(member_id) {
+            continue;
+        }
+
+        permission_overwrites.push(ChannelPermissionOverwrite {
+            allow,
+            deny,
+            id: member_id.cast(),
+            kind: ChannelPermissionOverwriteType::Member,
+        });
+    }
+
+    let user_relationships = context
+        .database
+        .user_relationships(interaction.guild.id, interaction.user_id)
+        .await?;
+
+    apply_relationships(
+        &mut permission_overwrites,
+        &user_relationships,
+        privacy_permissions,
+        is_kept,
+    );
+
+    let now = SystemTime::now();
+    let is_renamed = user_preset.name.ne(&*voice_channel.name.read());
+    // The current name stays if the preset's was caught by the name filter after it was saved,
+    // or if Discord won't allow another rename yet.
+    let kept_name_reason = if !is_renamed {
+        None
+    } else if let Some(reason) = name_violation(&interaction.guild, &user_preset.name) {
+        Some(reason.to_owned())
+    } else {
+        context
+            .cache
+            .rename_retry_after(voice_channel.id, now)
+            .map(|retry_after| {
+                format!(
+                    "you can change again in **{}**",
+                    rename_retry_text(retry_after)
+                )
+            })
+    };
+    let is_renamed = is_renamed && kept_name_reason.is_none();
+
+    // As with `/voice privacy`, the cache takes the preset's privacy level and settings before the
+    // edit is sent.
+    let previous_privacy = voice_channel.privacy.read().clone();
+    let previous_saved_settings =
+        context
+            .cache
+            .update_saved_settings(voice_channel.id, |saved_settings| {
+                if is_renamed {
+                    saved_settings.name = user_preset.name.clone();
+                }
+
+                saved_settings.bitrate = user_preset.bitrate;
+                saved_settings.rtc_region = user_preset.rtc_region.clone();
+                saved_settings.user_limit = user_preset
+                    .user_limit
+                    .filter(|user_limit| user_limit.ne(&0));
+            });
+
+    context.cache.update_voice_channel(
+        voice_channel.id,
+        CachedVoiceChannelUpdate {
+            privacy: Some(user_preset.privacy.clone()),
+            ..Default::default()
+        },
+    );
+
+    let mut update_channel_request = context
+        .client
+        .update_channel(voice_channel.id)
+        .bitrate(user_preset.bitrate)
+        .permission_overwrites(&permission_overwrites)
+        .rate_limit_per_user(user_preset.rate_limit_per_user.unwrap_or_default())
+        .rtc_region(user_preset.rtc_region.as_deref())
+        .user_limit(user_preset.user_limit.unwrap_or_default() as u16)
+        .video_quality_mode(user_preset.video_quality_mode);
+
+    if is_renamed {
+        update_channel_request = update_channel_request.name(&user_preset.name);
+    }
+    if update_channel_request.await.is_err() {
+        context.cache.update_voice_channel(
+            voice_channel.id,
+            CachedVoiceChannelUpdate {
+                privacy: Some(previous_privacy),
+                saved_settings: Some(previous_saved_settings),
+                ..Default::default()
+            },
+        );
+
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description("I'm unable to load your preset right now. Try again in 10 minutes.")
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    };
+
+    context
+        .database
+        .update_voice_channel_privacy(voice_channel.id, user_preset.privacy)
+        .await?;
+
+    let saved_settings = voice_channel.saved_settings.read().clone();
+
+    if let Some(saved_settings) = saved_settings {
+        context
+            .database
+            .update_voice_channel_settings(voice_channel.id, &saved_settings)
+            .await?;
+    }
+
+    if is_renamed {
+        context.cache.insert_rename_time(voice_channel.id, now);
+    }
+
+    audit(
+        &context,
+        &interaction.guild,
+        Some(interaction.user_id),
+        Some(voice_channel.id),
+        "/voice preset load",
+        format!(
+            "<@{}> loaded their preset into <#{voice_channel_id}>.",
+            interaction.user_id
+        ),
+    )
+    .await?;
+
+    let embed = EmbedBuilder::new()
+        .color(0xF8F8FF)
+        .description(match kept_name_reason {
+            Some(reason) => format!(
+                "I've loaded your preset into <#{voice_channel_id}>, apart from its name, which {reason}."
+            ),
+            None => format!("I've loaded your preset into <#{voice_channel_id}>."),
+        })
+        .build();
+
+    context
+        .interaction_client()
+        .update_response(&interaction.token)
+        .embeds(Some(&[embed]))
+        .await?;
+
+    Ok(())
+}
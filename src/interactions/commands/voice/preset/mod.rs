@@ -0,0 +1,522 @@
+mod clear;
+mod load;
+mod save;
+
+use std::{mem::replace, sync::Arc};
+
+use eyre::Result;
+use twilight_model::{
+    application::interaction::application_command::{CommandDataOption, CommandOptionValue},
+    channel::message::MessageFlags,
+    http::interaction::{InteractionResponse, InteractionResponseType},
+};
+use twilight_util::builder::{embed::EmbedBuilder, InteractionResponseDataBuilder};
+
+use crate::structs::{context::Context, interaction::ApplicationCommandInteraction};
+
+pub async fn run(
+    context: Arc<Context>,
+    mut interaction: ApplicationCommandInteraction,
+) -> Result<()> {
+    let Some(CommandDataOption {
+        name,
+        value: CommandOptionValue::SubCommand(options),
+    }) = interaction.data.options.clone().into_iter().next()
+    else {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description("I could not find a subcommand.")
+            .build();
+        let interaction_response_data = InteractionResponseDataBuilder::new()
+            .embeds(vec![embed])
+            .flags(MessageFlags::EPHEMERAL)
+            .build();
+        let interaction_response = InteractionResponse {
+            data: Some(interaction_response_data),
+            kind: InteractionResponseType::ChannelMessageWithSource,
+        };
+
+        context
+            .interaction_client()
+            .create_response(interaction.id, &interaction.token, &interaction_response)
+            .await?;
+
+        return Ok(());
+    };
+    let _ = replace(&mut interaction.data.options, options);
+
+    match name.as_str() {
+        "clear" => clear::run(context, interaction).await?,
+        "load" => load::run(context, interaction).await?,
+        "save" => save::run(context, interaction).await?,
+        _ => {
+            let embed = EmbedBuilder::new()
+                .color(0xF8F8FF)
+                .description(format!(
+                    "I don't have a subcommand with the name \"{name}\"."
+                ))
+                .build();
+            let interaction_response_data = InteractionResponseDataBuilder::new()
+                .embeds(vec![embed])
+                .flags(MessageFlags::EPHEMERAL)
+                .build();
+            let interaction_response = InteractionResponse {
+                data: Some(interaction_response_data),
+                kind: InteractionResponseType::ChannelMessageWithSource,
+            };
+
+            context
+                .interaction_client()
+                .create_response(interaction.id, &interaction.token, &interaction_response)
+                .await?;
+
+            return Ok(());
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use twilight_model::{channel::VideoQualityMode, id::Id};
+
+    use crate::{
+        structs::database::{ChannelPrivacy, UserPreset, VoiceChannelKind},
+        testing::{
+            self,
+            mock_discord::MockDiscord,
+            payloads::{
+                channel_delete, channel_update, command, subcommand, subcommand_group,
+                voice_state_update,
+            },
+            replay::replay,
+            seed_join_channel,
+        },
+    };
+
+    fn preset_command(token: &str, name: &str) -> String {
+        command(
+            token,
+            1001,
+            2001,
+            subcommand_group("voice", "preset", name, json!([])),
+        )
+    }
+
+    fn rename_command(token: &str, name: &str) -> String {
+        command(
+            token,
+            1001,
+            2001,
+            subcommand(
+                "voice",
+                "name",
+                json!([{ "name": "name", "type": 3, "value": name }]),
+            ),
+        )
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a database, run with `cargo test -- --ignored`"]
+    async fn new_voice_channels_start_with_the_preset() {
+        let mock_discord = MockDiscord::start().await;
+        let (context, _guard) = testing::context(&mock_discord).await;
+        let guild_id = Id::new(1001);
+
+        seed_join_channel(&context, guild_id, Id::new(1101), ChannelPrivacy::Unlocked).await;
+        replay(
+            &context,
+            include_str!("../../../../testing/recordings/create.jsonl"),
+        )
+        .await
+        .unwrap();
+        replay(
+            &context,
+            &[
+                rename_command("rename", "study hall"),
+                command(
+                    "deny",
+                    1001,
+                    2001,
+                    subcommand(
+                        "voice",
+                        "deny-member",
+                        json!([{ "name": "member", "type": 6, "value": "2003" }]),
+                    ),
+                ),
+            ]
+            .join("\n"),
+        )
+        .await
+        .unwrap();
+
+        let voice_channel = mock_discord.channel(5000).unwrap();
+
+        replay(
+            &context,
+            &[
+                channel_update(voice_channel.clone()),
+                preset_command("save", "save"),
+                voice_state_update(1001, 2001, "alex", None),
+                channel_delete(voice_channel),
+                voice_state_update(1001, 2001, "alex", Some(1101)),
+            ]
+            .join("\n"),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            mock_discord.response_descriptions("save"),
+            ["I've saved the settings of <#5000> as your preset. Voice channels you create will start with them."]
+        );
+
+        let voice_channel = mock_discord.channel(5001).unwrap();
+
+        assert_eq!(voice_channel["name"], "study hall");
+        assert!(voice_channel["permission_overwrites"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|permission_overwrite| permission_overwrite["id"].eq("2003")
+                && permission_overwrite["deny"].eq("1024")));
+
+        replay(&context, &preset_command("clear", "clear"))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            mock_discord.response_descriptions("clear"),
+            ["I've cleared your preset."]
+        );
+        assert!(context
+            .database
+            .user_preset(guild_id, Id::new(2001))
+            .await
+            .unwrap()
+            .is_none());
+
+        context.database.remove_guild(guild_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a database, run with `cargo test -- --ignored`"]
+    async fn loading_restores_the_preset() {
+        let mock_discord = MockDiscord::start().await;
+        let (context, _guard) = testing::context(&mock_discord).await;
+        let guild_id = Id::new(1001);
+
+        seed_join_channel(&context, guild_id, Id::new(1101), ChannelPrivacy::Unlocked).await;
+        replay(
+            &context,
+            include_str!("../../../../testing/recordings/create.jsonl"),
+        )
+        .await
+        .unwrap();
+        replay(
+            &context,
+            &[
+                preset_command("load-early", "load"),
+                rename_command("rename", "study hall"),
+            ]
+            .join("\n"),
+        )
+        .await
+        .unwrap();
+        replay(
+            &context,
+            &channel_update(mock_discord.channel(5000).unwrap()),
+        )
+        .await
+        .unwrap();
+        replay(
+            &context,
+            &[
+                preset_command("save", "save"),
+                rename_command("rename-again", "lounge"),
+            ]
+            .join("\n"),
+        )
+        .await
+        .unwrap();
+        replay(
+            &context,
+            &channel_update(mock_discord.channel(5000).unwrap()),
+        )
+        .await
+        .unwrap();
+        // Both renames count towards Discord's rename rate limit, which has passed by now.
+        context
+            .cache
+            .voice_channel(Id::new(5000))
+            .unwrap()
+            .rename_times
+            .write()
+            .clear();
+        replay(&context, &preset_command("load", "load"))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            mock_discord.response_descriptions("load-early"),
+            ["You have not saved a preset."]
+        );
+        assert_eq!(
+            mock_discord.response_descriptions("load"),
+            ["I've loaded your preset into <#5000>."]
+        );
+        assert_eq!(mock_discord.channel(5000).unwrap()["name"], "study hall");
+
+        // Loading and renaming again uses up Discord's rename rate limit, so the next load keeps
+        // the current name.
+        replay(
+            &context,
+            &channel_update(mock_discord.channel(5000).unwrap()),
+        )
+        .await
+        .unwrap();
+        replay(&context, &rename_command("rename-last", "lounge"))
+            .await
+            .unwrap();
+        replay(
+            &context,
+            &channel_update(mock_discord.channel(5000).unwrap()),
+        )
+        .await
+        .unwrap();
+        replay(&context, &preset_command("load-limited", "load"))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            mock_discord.response_descriptions("load-limited"),
+            ["I've loaded your preset into <#5000>, apart from its name, which you can change again in **10m**."]
+        );
+        assert_eq!(mock_discord.channel(5000).unwrap()["name"], "lounge");
+
+        context.database.remove_guild(guild_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a database, run with `cargo test -- --ignored`"]
+    async fn presets_can_be_turned_off() {
+        let mock_discord = MockDiscord::start().await;
+        let (context, _guard) = testing::context(&mock_discord).await;
+        let guild_id = Id::new(1001);
+
+        seed_join_channel(&context, guild_id, Id::new(1101), ChannelPrivacy::Unlocked).await;
+        replay(
+            &context,
+            include_str!("../../../../testing/recordings/create.jsonl"),
+        )
+        .await
+        .unwrap();
+        replay(
+            &context,
+            &[
+                command(
+                    "presets",
+                    1001,
+                    1,
+                    subcommand(
+                        "settings",
+                        "set",
+                        json!([{ "name": "presets", "type": 5, "value": false }]),
+                    ),
+                ),
+                preset_command("save", "save"),
+            ]
+            .join("\n"),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            mock_discord.response_descriptions("save"),
+            ["Presets have been turned off in this server."]
+        );
+        assert!(
+            !context
+                .database
+                .guild_settings(guild_id)
+                .await
+                .unwrap()
+                .presets_enabled
+        );
+
+        context.database.remove_guild(guild_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a database, run with `cargo test -- --ignored`"]
+    async fn refused_preset_settings_are_left_out() {
+        let mock_discord = MockDiscord::start().await;
+        let (context, _guard) = testing::context(&mock_discord).await;
+        let guild_id = Id::new(1001);
+
+        seed_join_channel(&context, guild_id, Id::new(1101), ChannelPrivacy::Unlocked).await;
+        context
+            .database
+            .remove_join_channel(Id::new(1101))
+            .await
+            .unwrap();
+        context
+            .database
+            .insert_join_channel(
+                Id::new(1101),
+                guild_id,
+                None,
+                false,
+                ChannelPrivacy::Unlocked,
+                VoiceChannelKind::Stage,
+            )
+            .await
+            .unwrap();
+        // More than a stage allows.
+        context
+            .database
+            .insert_user_preset(&UserPreset {
+                guild_id,
+                user_id: Id::new(2001),
+                name: "study hall".to_owned(),
+                bitrate: 96_000,
+                user_limit: None,
+                rate_limit_per_user: None,
+                rtc_region: None,
+                video_quality_mode: VideoQualityMode::Auto,
+                privacy: ChannelPrivacy::Unlocked,
+                allowed_user_ids: Vec::new(),
+                denied_user_ids: Vec::new(),
+            })
+            .await
+            .unwrap();
+        replay(
+            &context,
+            include_str!("../../../../testing/recordings/create.jsonl"),
+        )
+        .await
+        .unwrap();
+
+        let create_requests = mock_discord.requests_to("POST", "/guilds/1001/channels");
+
+        assert_eq!(create_requests.len(), 2);
+        assert_eq!(create_requests[0].body["bitrate"], 96_000);
+        assert!(create_requests[1].body.get("bitrate").is_none());
+        assert_eq!(mock_discord.channel(5000).unwrap()["name"], "study hall");
+        assert_eq!(
+            mock_discord
+                .assert_requested("PATCH", "/guilds/1001/members/2001")
+                .body["channel_id"],
+            "5000"
+        );
+
+        context.database.remove_guild(guild_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a database, run with `cargo test -- --ignored`"]
+    async fn only_members_let_in_are_saved_as_allowed() {
+        let mock_discord = MockDiscord::start().await;
+        let (context, _guard) = testing::context(&mock_discord).await;
+        let guild_id = Id::new(1001);
+
+        seed_join_channel(&context, guild_id, Id::new(1101), ChannelPrivacy::Unlocked).await;
+        replay(
+            &context,
+            include_str!("../../../../testing/recordings/create.jsonl"),
+        )
+        .await
+        .unwrap();
+        replay(
+            &context,
+            &command(
+                "add",
+                1001,
+                2001,
+                subcommand_group(
+                    "voice",
+                    "managers",
+                    "add",
+                    json!([{ "name": "member", "type": 6, "value": "2002" }]),
+                ),
+            ),
+        )
+        .await
+        .unwrap();
+        replay(
+            &context,
+            &[
+                channel_update(mock_discord.channel(5000).unwrap()),
+                preset_command("save-unlocked", "save"),
+            ]
+            .join("\n"),
+        )
+        .await
+        .unwrap();
+
+        // The manager's overwrite allows nothing while the voice channel is unlocked.
+        assert!(context
+            .database
+            .user_preset(guild_id, Id::new(2001))
+            .await
+            .unwrap()
+            .unwrap()
+            .allowed_user_ids
+            .is_empty());
+
+        replay(
+            &context,
+            &[
+                command(
+                    "lock",
+                    1001,
+                    2001,
+                    subcommand(
+                        "voice",
+                        "privacy",
+                        json!([{ "name": "level", "type": 3, "value": "locked" }]),
+                    ),
+                ),
+                command(
+                    "allow",
+                    1001,
+                    2001,
+                    subcommand(
+                        "voice",
+                        "allow-member",
+                        json!([{ "name": "member", "type": 3, "value": "2003" }]),
+                    ),
+                ),
+            ]
+            .join("\n"),
+        )
+        .await
+        .unwrap();
+        replay(
+            &context,
+            &[
+                channel_update(mock_discord.channel(5000).unwrap()),
+                preset_command("save-locked", "save"),
+            ]
+            .join("\n"),
+        )
+        .await
+        .unwrap();
+
+        // Managing comes with its own access, which the preset leaves out.
+        assert_eq!(
+            context
+                .database
+                .user_preset(guild_id, Id::new(2001))
+                .await
+                .unwrap()
+                .unwrap()
+                .allowed_user_ids,
+            [Id::new(2003)]
+        );
+
+        context.database.remove_guild(guild_id).await.unwrap();
+    }
+}
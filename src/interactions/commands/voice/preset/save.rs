@@ -0,0 +1,139 @@
+use std::sync::Arc;
+
+use eyre::Result;
+use twilight_model::{
+    channel::{
+        message::MessageFlags,
+        permission_overwrite::PermissionOverwriteType as ChannelPermissionOverwriteType,
+    },
+    guild::Permissions,
+    http::interaction::{InteractionResponse, InteractionResponseType},
+    id::{marker::UserMarker, Id},
+};
+use twilight_util::builder::{embed::EmbedBuilder, InteractionResponseDataBuilder};
+
+use crate::structs::{
+    context::Context, database::UserPreset, interaction::ApplicationCommandInteraction,
+};
+
+pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteraction) -> Result<()> {
+    let interaction_response_data = InteractionResponseDataBuilder::new()
+        .flags(MessageFlags::EPHEMERAL)
+        .build();
+    let interaction_response = InteractionResponse {
+        data: Some(interaction_response_data),
+        kind: InteractionResponseType::DeferredChannelMessageWithSource,
+    };
+
+    context
+        .interaction_client()
+        .create_response(interaction.id, &interaction.token, &interaction_response)
+        .await?;
+
+    if !*interaction.guild.presets_enabled.read() {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description("Presets have been turned off in this server.")
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    }
+
+    let Some(voice_channel_id) = context
+        .cache
+        .voice_channel_owner(interaction.guild.id, interaction.user_id)
+    else {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description("You do not own a voice channel.")
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    };
+    let Some(voice_channel) = context.cache.voice_channel(*voice_channel_id) else {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description("I could not find your voice channel.")
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    };
+    let privacy = voice_channel.privacy.read().clone();
+    let manager_ids = voice_channel.manager_ids.read().clone();
+    let mut allowed_user_ids: Vec<Id<UserMarker>> = Vec::new();
+    let mut denied_user_ids: Vec<Id<UserMarker>> = Vec::new();
+
+    for permission_overwrite in voice_channel.permission_overwrites.read().iter() {
+        if permission_overwrite
+            .kind
+            .ne(&ChannelPermissionOverwriteType::Member)
+            || permission_overwrite.id.eq(&interaction.user_id.cast())
+            || manager_ids.contains(&permission_overwrite.id.cast())
+        {
+            continue;
+        }
+
+        // Only an overwrite that lets the member in counts as allowing them, since an unlocked
+        // voice channel's privacy permissions are empty and any overwrite would contain them.
+        if permission_overwrite
+            .deny
+            .contains(Permissions::VIEW_CHANNEL)
+        {
+            denied_user_ids.push(permission_overwrite.id.cast());
+        } else if permission_overwrite.allow.intersects(
+            Permissions::CONNECT | Permissions::REQUEST_TO_SPEAK | Permissions::VIEW_CHANNEL,
+        ) && permission_overwrite.deny.is_empty()
+        {
+            allowed_user_ids.push(permission_overwrite.id.cast());
+        }
+    }
+
+    let user_preset = UserPreset {
+        guild_id: interaction.guild.id,
+        user_id: interaction.user_id,
+        name: voice_channel.name.read().clone(),
+        bitrate: *voice_channel.bitrate.read(),
+        user_limit: *voice_channel.user_limit.read(),
+        rate_limit_per_user: *voice_channel.rate_limit_per_user.read(),
+        rtc_region: voice_channel.rtc_region.read().clone(),
+        video_quality_mode: *voice_channel.video_quality_mode.read(),
+        privacy,
+        allowed_user_ids,
+        denied_user_ids,
+    };
+
+    context.database.insert_user_preset(&user_preset).await?;
+
+    let embed = EmbedBuilder::new()
+        .color(0xF8F8FF)
+        .description(format!(
+            "I've saved the settings of <#{voice_channel_id}> as your preset. Voice channels you create will start with them."
+        ))
+        .build();
+
+    context
+        .interaction_client()
+        .update_response(&interaction.token)
+        .embeds(Some(&[embed]))
+        .await?;
+
+    Ok(())
+}
@@ -5,16 +5,28 @@ use twilight_model::{
     application::interaction::application_command::CommandOptionValue,
     channel::{
         message::MessageFlags,
-        permission_overwrite::PermissionOverwriteType as ChannelPermissionOverwriteType,
+        permission_overwrite::{
+            PermissionOverwrite as ChannelPermissionOverwrite,
+            PermissionOverwriteType as ChannelPermissionOverwriteType,
+        },
     },
     guild::Permissions,
     http::interaction::{InteractionResponse, InteractionResponseType},
 };
 use twilight_util::builder::{embed::EmbedBuilder, InteractionResponseDataBuilder};
 
-use crate::structs::{
-    cache::CachedVoiceChannelUpdate, context::Context, database::ChannelPrivacy,
-    interaction::ApplicationCommandInteraction,
+use crate::{
+    structs::{
+        cache::{CachedGuild, CachedVoiceChannel, CachedVoiceChannelUpdate},
+        context::Context,
+        database::{ChannelPrivacy, VoiceChannelKind},
+        interaction::ApplicationCommandInteraction,
+    },
+    utilities::{
+        audit::audit,
+        permission_overwrites::{apply_relationships, owner_permissions, privacy_permissions},
+        waiting_room::{create_waiting_room, delete_waiting_room},
+    },
 };
 
 pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteraction) -> Result<()> {
@@ -53,11 +65,11 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
     };
     let Some(voice_channel_id) = context
         .cache
-        .voice_channel_owner(interaction.guild.id, interaction.user_id)
+        .managed_voice_channel(interaction.guild.id, interaction.user_id)
     else {
         let embed = EmbedBuilder::new()
             .color(0xF8F8FF)
-            .description("You do not own a voice channel.")
+            .description("You do not own or manage a voice channel.")
             .build();
 
         context
@@ -82,22 +94,10 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
 
         return Ok(());
     };
-    let (common_permissions, formatted_privacy, privacy_text) = match level.as_str() {
-        "invisible" => (
-            Permissions::VIEW_CHANNEL,
-            ChannelPrivacy::Invisible,
-            "invisible",
-        ),
-        "locked" => (
-            Permissions::CONNECT,
-            ChannelPrivacy::Locked,
-            "locked (and visible)",
-        ),
-        _ => (
-            Permissions::empty(),
-            ChannelPrivacy::Unlocked,
-            "unlocked (and visible)",
-        ),
+    let (formatted_privacy, privacy_text) = match level.as_str() {
+        "invisible" => (ChannelPrivacy::Invisible, "invisible"),
+        "locked" => (ChannelPrivacy::Locked, "locked (and visible)"),
+        _ => (ChannelPrivacy::Unlocked, "unlocked (and visible)"),
     };
 
     if voice_channel.privacy.read().eq(&formatted_privacy) {
@@ -114,7 +114,65 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
 
         return Ok(());
     }
+    if !set_privacy(
+        &context,
+        &interaction.guild,
+        &voice_channel,
+        formatted_privacy,
+    )
+    .await?
+    {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description("I'm unable to set the privacy level right now. Try again in 10 minutes.")
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    }
+
+    audit(
+        &context,
+        &interaction.guild,
+        Some(interaction.user_id),
+        Some(voice_channel.id),
+        "/voice privacy",
+        format!(
+            "<@{}> made <#{voice_channel_id}> **{privacy_text}**.",
+            interaction.user_id
+        ),
+    )
+    .await?;
 
+    let embed = EmbedBuilder::new()
+        .color(0xF8F8FF)
+        .description(format!("<#{voice_channel_id}> is now **{privacy_text}**."))
+        .build();
+
+    context
+        .interaction_client()
+        .update_response(&interaction.token)
+        .embeds(Some(&[embed]))
+        .await?;
+
+    Ok(())
+}
+
+/// Rebuilds the voice channel's permission overwrites for `privacy` and saves it, returning
+/// whether Discord took them.
+pub async fn set_privacy(
+    context: &Context,
+    guild: &CachedGuild,
+    voice_channel: &CachedVoiceChannel,
+    privacy: ChannelPrivacy,
+) -> Result<bool> {
+    let common_permissions = privacy_permissions(voice_channel.kind, &privacy);
+    let owner_permissions = owner_permissions(voice_channel.kind, &privacy);
     let mut permission_overwrites = voice_channel.permission_overwrites.read().clone();
 
     for permission_overwrite in permission_overwrites.iter_mut() {
@@ -126,6 +184,15 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
                 .owner_id
                 .read()
                 .is_some_and(|owner_id| permission_overwrite.id.eq(&owner_id.cast()))
+            {
+                permission_overwrite.allow = owner_permissions;
+
+                continue;
+            }
+            if voice_channel
+                .manager_ids
+                .read()
+                .contains(&permission_overwrite.id.cast())
             {
                 permission_overwrite.allow = common_permissions;
             }
@@ -141,18 +208,70 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
 
             permission_overwrite.allow = common_permissions;
         } else {
-            if permission_overwrite
-                .id
-                .eq(&interaction.guild.bot_role_id.cast())
-            {
+            if permission_overwrite.id.eq(&guild.bot_role_id.cast()) {
                 permission_overwrite.allow = common_permissions;
             }
-            if permission_overwrite.id.eq(&interaction.guild.id.cast()) {
+            if permission_overwrite.id.eq(&guild.id.cast()) {
                 permission_overwrite.deny = common_permissions;
             }
         }
     }
 
+    // Either role overwrite may have been removed by hand, which would leave the privacy level
+    // with nothing to enforce it.
+    for (role_id, allow, deny) in [
+        (guild.bot_role_id, common_permissions, Permissions::empty()),
+        (guild.id.cast(), Permissions::empty(), common_permissions),
+    ] {
+        if common_permissions.is_empty()
+            || permission_overwrites.iter().any(|permission_overwrite| {
+                permission_overwrite
+                    .kind
+                    .eq(&ChannelPermissionOverwriteType::Role)
+                    && permission_overwrite.id.eq(&role_id.cast())
+            })
+        {
+            continue;
+        }
+
+        permission_overwrites.push(ChannelPermissionOverwrite {
+            allow,
+            deny,
+            id: role_id.cast(),
+            kind: ChannelPermissionOverwriteType::Role,
+        });
+    }
+
+    let owner_id = *voice_channel.owner_id.read();
+
+    // The owner's trusted and blocked lists follow the voice channel into every privacy level.
+    if let Some(owner_id) = owner_id {
+        let user_relationships = context
+            .database
+            .user_relationships(guild.id, owner_id)
+            .await?;
+        let manager_ids = voice_channel.manager_ids.read().clone();
+
+        apply_relationships(
+            &mut permission_overwrites,
+            &user_relationships,
+            common_permissions,
+            |member_id| member_id.eq(&owner_id) || manager_ids.contains(&member_id),
+        );
+    }
+
+    // The cache takes the new privacy level first, so the `CHANNEL_UPDATE` this causes isn't
+    // mistaken for an edit made outside of Voz.
+    let previous_privacy = voice_channel.privacy.read().clone();
+
+    context.cache.update_voice_channel(
+        voice_channel.id,
+        CachedVoiceChannelUpdate {
+            privacy: Some(privacy.clone()),
+            ..Default::default()
+        },
+    );
+
     if context
         .client
         .update_channel(voice_channel.id)
@@ -160,42 +279,42 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
         .await
         .is_err()
     {
-        let embed = EmbedBuilder::new()
-            .color(0xF8F8FF)
-            .description("I'm unable to set the privacy level right now. Try again in 10 minutes.")
-            .build();
+        context.cache.update_voice_channel(
+            voice_channel.id,
+            CachedVoiceChannelUpdate {
+                privacy: Some(previous_privacy),
+                ..Default::default()
+            },
+        );
 
-        context
-            .interaction_client()
-            .update_response(&interaction.token)
-            .embeds(Some(&[embed]))
-            .await?;
+        return Ok(false);
+    }
 
-        return Ok(());
-    };
+    let is_locked = privacy.eq(&ChannelPrivacy::Locked);
 
     context
         .database
-        .update_voice_channel_privacy(voice_channel.id, formatted_privacy.clone())
+        .update_voice_channel_privacy(voice_channel.id, privacy)
         .await?;
-    context.cache.update_voice_channel(
-        voice_channel.id,
-        CachedVoiceChannelUpdate {
-            privacy: Some(formatted_privacy),
-            ..Default::default()
-        },
-    );
 
-    let embed = EmbedBuilder::new()
-        .color(0xF8F8FF)
-        .description(format!("<#{voice_channel_id}> is now **{privacy_text}**."))
-        .build();
+    // Only a locked voice channel has a waiting room, and only if its join channel gives it one.
+    // Members may listen to a locked stage without being let in, so it has none.
+    let has_waiting_room = voice_channel.waiting_room_id.read().is_some();
 
-    context
-        .interaction_client()
-        .update_response(&interaction.token)
-        .embeds(Some(&[embed]))
-        .await?;
+    if is_locked && !has_waiting_room && voice_channel.kind.eq(&VoiceChannelKind::Voice) {
+        if let Some(join_channel) = voice_channel
+            .join_channel_id
+            .and_then(|join_channel_id| context.cache.join_channel(join_channel_id))
+            .filter(|join_channel| *join_channel.waiting_room.read())
+        {
+            let parent_id = *join_channel.parent_id.read();
+            let name = voice_channel.name.read().clone();
 
-    Ok(())
+            create_waiting_room(context, guild, parent_id, voice_channel.id, &name).await?;
+        }
+    } else if !is_locked && has_waiting_room {
+        delete_waiting_room(context, voice_channel).await?;
+    }
+
+    Ok(true)
 }
@@ -0,0 +1,203 @@
+use std::sync::Arc;
+
+use eyre::Result;
+use twilight_model::{
+    application::interaction::application_command::CommandOptionValue,
+    channel::{
+        message::MessageFlags,
+        permission_overwrite::PermissionOverwriteType as ChannelPermissionOverwriteType,
+    },
+    guild::Permissions,
+    http::{
+        interaction::{InteractionResponse, InteractionResponseType},
+        permission_overwrite::{
+            PermissionOverwrite as HttpPermissionOverwrite,
+            PermissionOverwriteType as HttpPermissionOverwriteType,
+        },
+    },
+};
+use twilight_util::builder::{embed::EmbedBuilder, InteractionResponseDataBuilder};
+
+use super::list_name;
+use crate::{
+    structs::{
+        context::Context, database::RelationshipKind, interaction::ApplicationCommandInteraction,
+    },
+    utilities::{audit::audit, permission_overwrites::privacy_permissions},
+};
+
+pub async fn run(
+    context: Arc<Context>,
+    interaction: ApplicationCommandInteraction,
+    kind: RelationshipKind,
+) -> Result<()> {
+    let interaction_response_data = InteractionResponseDataBuilder::new()
+        .flags(MessageFlags::EPHEMERAL)
+        .build();
+    let interaction_response = InteractionResponse {
+        data: Some(interaction_response_data),
+        kind: InteractionResponseType::DeferredChannelMessageWithSource,
+    };
+
+    context
+        .interaction_client()
+        .create_response(interaction.id, &interaction.token, &interaction_response)
+        .await?;
+
+    let Some(CommandOptionValue::User(member_id)) = interaction
+        .data
+        .options
+        .into_iter()
+        .find(|option| option.name.eq("member"))
+        .map(|option| option.value)
+    else {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description("I could not find a valid **member** value.")
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    };
+    let list_name = list_name(kind);
+
+    if context.application_id.eq(&member_id.cast()) || interaction.user_id.eq(&member_id) {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description(format!("<@{member_id}> may not be {list_name}."))
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    }
+
+    let is_listed = context
+        .database
+        .user_relationships(interaction.guild.id, interaction.user_id)
+        .await?
+        .into_iter()
+        .any(|user_relationship| {
+            user_relationship.member_id.eq(&member_id) && user_relationship.kind.eq(&kind)
+        });
+
+    if is_listed {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description(format!("<@{member_id}> is already {list_name}."))
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    }
+
+    context
+        .database
+        .insert_user_relationship(interaction.guild.id, interaction.user_id, member_id, kind)
+        .await?;
+
+    // The voice channel the member owns right now shouldn't wait for its next privacy change.
+    if let Some(voice_channel) = context
+        .cache
+        .voice_channel_owner(interaction.guild.id, interaction.user_id)
+        .and_then(|voice_channel_id| context.cache.voice_channel(*voice_channel_id))
+        .filter(|voice_channel| !voice_channel.manager_ids.read().contains(&member_id))
+    {
+        let (mut member_allow, mut member_deny) = voice_channel
+            .permission_overwrites
+            .read()
+            .clone()
+            .into_iter()
+            .find(|permission_overwrite| {
+                permission_overwrite
+                    .kind
+                    .eq(&ChannelPermissionOverwriteType::Member)
+                    && permission_overwrite.id.eq(&member_id.cast())
+            })
+            .map_or(
+                (Permissions::empty(), Permissions::empty()),
+                |permission_overwrite| (permission_overwrite.allow, permission_overwrite.deny),
+            );
+
+        member_allow.remove(Permissions::CONNECT | Permissions::VIEW_CHANNEL);
+        member_deny.remove(Permissions::CONNECT | Permissions::VIEW_CHANNEL);
+
+        match kind {
+            RelationshipKind::Blocked => member_deny = member_deny.union(Permissions::VIEW_CHANNEL),
+            RelationshipKind::Trusted => {
+                let permissions =
+                    privacy_permissions(voice_channel.kind, &voice_channel.privacy.read());
+
+                member_allow = member_allow.union(permissions);
+            }
+        }
+
+        context
+            .client
+            .update_channel_permission(
+                voice_channel.id,
+                &HttpPermissionOverwrite {
+                    allow: Some(member_allow),
+                    deny: Some(member_deny),
+                    id: member_id.cast(),
+                    kind: HttpPermissionOverwriteType::Member,
+                },
+            )
+            .await?;
+    }
+
+    audit(
+        &context,
+        &interaction.guild,
+        Some(interaction.user_id),
+        None,
+        match kind {
+            RelationshipKind::Blocked => "/voice block add",
+            RelationshipKind::Trusted => "/voice trust add",
+        },
+        format!(
+            "<@{}> added <@{member_id}> to their {} list.",
+            interaction.user_id,
+            match kind {
+                RelationshipKind::Blocked => "block",
+                RelationshipKind::Trusted => "trust",
+            }
+        ),
+    )
+    .await?;
+
+    let description = match kind {
+        RelationshipKind::Blocked => {
+            format!("<@{member_id}> is now blocked from every voice channel you own.")
+        }
+        RelationshipKind::Trusted => {
+            format!("<@{member_id}> is now trusted in every voice channel you own.")
+        }
+    };
+    let embed = EmbedBuilder::new()
+        .color(0xF8F8FF)
+        .description(description)
+        .build();
+
+    context
+        .interaction_client()
+        .update_response(&interaction.token)
+        .embeds(Some(&[embed]))
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,68 @@
+use std::sync::Arc;
+
+use eyre::Result;
+use twilight_model::{
+    channel::message::MessageFlags,
+    http::interaction::{InteractionResponse, InteractionResponseType},
+};
+use twilight_util::builder::{embed::EmbedBuilder, InteractionResponseDataBuilder};
+
+use super::list_name;
+use crate::structs::{
+    context::Context, database::RelationshipKind, interaction::ApplicationCommandInteraction,
+};
+
+pub async fn run(
+    context: Arc<Context>,
+    interaction: ApplicationCommandInteraction,
+    kind: RelationshipKind,
+) -> Result<()> {
+    let interaction_response_data = InteractionResponseDataBuilder::new()
+        .flags(MessageFlags::EPHEMERAL)
+        .build();
+    let interaction_response = InteractionResponse {
+        data: Some(interaction_response_data),
+        kind: InteractionResponseType::DeferredChannelMessageWithSource,
+    };
+
+    context
+        .interaction_client()
+        .create_response(interaction.id, &interaction.token, &interaction_response)
+        .await?;
+
+    let list_name = list_name(kind);
+    let member_ids = context
+        .database
+        .user_relationships(interaction.guild.id, interaction.user_id)
+        .await?
+        .into_iter()
+        .filter(|user_relationship| user_relationship.kind.eq(&kind))
+        .map(|user_relationship| user_relationship.member_id)
+        .collect::<Vec<_>>();
+    let description = if member_ids.is_empty() {
+        format!("You have not {list_name} a member.")
+    } else {
+        member_ids
+            .into_iter()
+            .map(|member_id| format!("- <@{member_id}>"))
+            .collect::<Vec<String>>()
+            .join("\n")
+    };
+    let title = match kind {
+        RelationshipKind::Blocked => "Blocked members",
+        RelationshipKind::Trusted => "Trusted members",
+    };
+    let embed = EmbedBuilder::new()
+        .color(0xF8F8FF)
+        .description(description)
+        .title(title)
+        .build();
+
+    context
+        .interaction_client()
+        .update_response(&interaction.token)
+        .embeds(Some(&[embed]))
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,277 @@
+mod add;
+mod list;
+mod remove;
+
+use std::{mem::replace, sync::Arc};
+
+use eyre::Result;
+use twilight_model::{
+    application::interaction::application_command::{CommandDataOption, CommandOptionValue},
+    channel::message::MessageFlags,
+    http::interaction::{InteractionResponse, InteractionResponseType},
+};
+use twilight_util::builder::{embed::EmbedBuilder, InteractionResponseDataBuilder};
+
+use crate::structs::{
+    context::Context, database::RelationshipKind, interaction::ApplicationCommandInteraction,
+};
+
+/// Runs a `/voice trust` or `/voice block` subcommand against the member's `kind` list.
+pub async fn run(
+    context: Arc<Context>,
+    mut interaction: ApplicationCommandInteraction,
+    kind: RelationshipKind,
+) -> Result<()> {
+    let Some(CommandDataOption {
+        name,
+        value: CommandOptionValue::SubCommand(options),
+    }) = interaction.data.options.clone().into_iter().next()
+    else {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description("I could not find a subcommand.")
+            .build();
+        let interaction_response_data = InteractionResponseDataBuilder::new()
+            .embeds(vec![embed])
+            .flags(MessageFlags::EPHEMERAL)
+            .build();
+        let interaction_response = InteractionResponse {
+            data: Some(interaction_response_data),
+            kind: InteractionResponseType::ChannelMessageWithSource,
+        };
+
+        context
+            .interaction_client()
+            .create_response(interaction.id, &interaction.token, &interaction_response)
+            .await?;
+
+        return Ok(());
+    };
+    let _ = replace(&mut interaction.data.options, options);
+
+    match name.as_str() {
+        "add" => add::run(context, interaction, kind).await?,
+        "list" => list::run(context, interaction, kind).await?,
+        "remove" => remove::run(context, interaction, kind).await?,
+        _ => {
+            let embed = EmbedBuilder::new()
+                .color(0xF8F8FF)
+                .description(format!(
+                    "I don't have a subcommand with the name \"{name}\"."
+                ))
+                .build();
+            let interaction_response_data = InteractionResponseDataBuilder::new()
+                .embeds(vec![embed])
+                .flags(MessageFlags::EPHEMERAL)
+                .build();
+            let interaction_response = InteractionResponse {
+                data: Some(interaction_response_data),
+                kind: InteractionResponseType::ChannelMessageWithSource,
+            };
+
+            context
+                .interaction_client()
+                .create_response(interaction.id, &interaction.token, &interaction_response)
+                .await?;
+
+            return Ok(());
+        }
+    }
+
+    Ok(())
+}
+
+/// How the member's `kind` list reads in replies, such as "trusted".
+fn list_name(kind: RelationshipKind) -> &'static str {
+    match kind {
+        RelationshipKind::Blocked => "blocked",
+        RelationshipKind::Trusted => "trusted",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+    use twilight_model::id::Id;
+
+    use crate::{
+        structs::database::ChannelPrivacy,
+        testing::{
+            self,
+            mock_discord::MockDiscord,
+            payloads::{command, subcommand, subcommand_group},
+            replay::replay,
+            seed_join_channel,
+        },
+    };
+
+    fn relationship_command(token: &str, group: &str, name: &str, member_id: u64) -> String {
+        // `add` takes a user option, while `remove` autocompletes a string.
+        let kind = if name.eq("add") { 6 } else { 3 };
+
+        command(
+            token,
+            1001,
+            2001,
+            subcommand_group(
+                "voice",
+                group,
+                name,
+                json!([{ "name": "member", "type": kind, "value": member_id.to_string() }]),
+            ),
+        )
+    }
+
+    fn member_overwrite(permission_overwrites: &Value, member_id: &str) -> Option<Value> {
+        permission_overwrites
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|permission_overwrite| permission_overwrite["id"].eq(member_id))
+            .cloned()
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a database, run with `cargo test -- --ignored`"]
+    async fn new_voice_channels_apply_the_lists() {
+        let mock_discord = MockDiscord::start().await;
+        let (context, _guard) = testing::context(&mock_discord).await;
+        let guild_id = Id::new(1001);
+        let (guild_create, voice_state_updates) =
+            include_str!("../../../../testing/recordings/create.jsonl")
+                .split_once('\n')
+                .unwrap();
+
+        seed_join_channel(&context, guild_id, Id::new(1101), ChannelPrivacy::Locked).await;
+
+        replay(
+            &context,
+            &[
+                guild_create,
+                &relationship_command("trust", "trust", "add", 2002),
+                &relationship_command("block", "block", "add", 2003),
+                &relationship_command("block-again", "block", "add", 2003),
+                &relationship_command("self", "trust", "add", 2001),
+                voice_state_updates,
+            ]
+            .join("\n"),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            mock_discord.response_descriptions("trust"),
+            ["<@2002> is now trusted in every voice channel you own."]
+        );
+        assert_eq!(
+            mock_discord.response_descriptions("block-again"),
+            ["<@2003> is already blocked."]
+        );
+        assert_eq!(
+            mock_discord.response_descriptions("self"),
+            ["<@2001> may not be trusted."]
+        );
+
+        let permission_overwrites = mock_discord
+            .assert_requested("POST", "/guilds/1001/channels")
+            .body["permission_overwrites"]
+            .clone();
+
+        assert_eq!(
+            member_overwrite(&permission_overwrites, "2002").unwrap()["allow"],
+            "1048576"
+        );
+        assert_eq!(
+            member_overwrite(&permission_overwrites, "2003").unwrap()["deny"],
+            "1024"
+        );
+
+        replay(
+            &context,
+            &[
+                relationship_command("unblock", "block", "remove", 2003),
+                relationship_command("unblock-again", "block", "remove", 2003),
+                command(
+                    "list",
+                    1001,
+                    2001,
+                    subcommand_group("voice", "trust", "list", json!([])),
+                ),
+            ]
+            .join("\n"),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            mock_discord.response_descriptions("unblock"),
+            ["<@2003> is no longer blocked."]
+        );
+        assert_eq!(
+            mock_discord.response_descriptions("unblock-again"),
+            ["<@2003> is not blocked."]
+        );
+        assert_eq!(mock_discord.response_descriptions("list"), ["- <@2002>"]);
+
+        context.database.remove_guild(guild_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a database, run with `cargo test -- --ignored`"]
+    async fn privacy_changes_keep_the_lists() {
+        let mock_discord = MockDiscord::start().await;
+        let (context, _guard) = testing::context(&mock_discord).await;
+        let guild_id = Id::new(1001);
+
+        seed_join_channel(&context, guild_id, Id::new(1101), ChannelPrivacy::Unlocked).await;
+        replay(
+            &context,
+            include_str!("../../../../testing/recordings/create.jsonl"),
+        )
+        .await
+        .unwrap();
+        replay(
+            &context,
+            &[
+                relationship_command("trust", "trust", "add", 2002),
+                relationship_command("block", "block", "add", 2003),
+                command(
+                    "privacy",
+                    1001,
+                    2001,
+                    subcommand(
+                        "voice",
+                        "privacy",
+                        json!([{ "name": "level", "type": 3, "value": "invisible" }]),
+                    ),
+                ),
+            ]
+            .join("\n"),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            mock_discord
+                .assert_requested("PUT", "/channels/5000/permissions/2003")
+                .body["deny"],
+            "1024"
+        );
+
+        let permission_overwrites = mock_discord
+            .assert_requested("PATCH", "/channels/5000")
+            .body["permission_overwrites"]
+            .clone();
+
+        assert_eq!(
+            member_overwrite(&permission_overwrites, "2002").unwrap()["allow"],
+            "1024"
+        );
+        assert_eq!(
+            member_overwrite(&permission_overwrites, "2003").unwrap()["deny"],
+            "1024"
+        );
+
+        context.database.remove_guild(guild_id).await.unwrap();
+    }
+}
@@ -0,0 +1,177 @@
+use std::sync::Arc;
+
+use eyre::Result;
+use twilight_model::{
+    application::interaction::application_command::CommandOptionValue,
+    channel::{
+        message::MessageFlags,
+        permission_overwrite::PermissionOverwriteType as ChannelPermissionOverwriteType,
+    },
+    http::interaction::{InteractionResponse, InteractionResponseType},
+};
+use twilight_util::builder::{embed::EmbedBuilder, InteractionResponseDataBuilder};
+
+use super::{
+    super::{member_choices, parse_member_id},
+    list_name,
+};
+use crate::{
+    structs::{
+        context::Context, database::RelationshipKind, interaction::ApplicationCommandInteraction,
+    },
+    utilities::audit::audit,
+};
+
+pub async fn run(
+    context: Arc<Context>,
+    interaction: ApplicationCommandInteraction,
+    kind: RelationshipKind,
+) -> Result<()> {
+    let member_value = match interaction
+        .data
+        .options
+        .iter()
+        .find(|&option| option.name.eq("member"))
+        .cloned()
+        .map(|option| option.value)
+    {
+        Some(CommandOptionValue::Focused(value, _)) => {
+            let user_ids = context
+                .database
+                .user_relationships(interaction.guild.id, interaction.user_id)
+                .await?
+                .into_iter()
+                .filter(|user_relationship| user_relationship.kind.eq(&kind))
+                .map(|user_relationship| user_relationship.member_id)
+                .collect::<Vec<_>>();
+            let mut choices = member_choices(&context, interaction.guild.id, user_ids, &value);
+
+            choices.sort_by(|a, b| a.name.cmp(&b.name));
+
+            let data = InteractionResponseDataBuilder::new()
+                .choices(choices)
+                .build();
+            let interaction_response = InteractionResponse {
+                data: Some(data),
+                kind: InteractionResponseType::ApplicationCommandAutocompleteResult,
+            };
+
+            context
+                .interaction_client()
+                .create_response(interaction.id, &interaction.token, &interaction_response)
+                .await?;
+
+            return Ok(());
+        }
+        Some(CommandOptionValue::String(value)) => value,
+        _ => return Ok(()),
+    };
+    let interaction_response_data = InteractionResponseDataBuilder::new()
+        .flags(MessageFlags::EPHEMERAL)
+        .build();
+    let interaction_response = InteractionResponse {
+        data: Some(interaction_response_data),
+        kind: InteractionResponseType::DeferredChannelMessageWithSource,
+    };
+
+    context
+        .interaction_client()
+        .create_response(interaction.id, &interaction.token, &interaction_response)
+        .await?;
+
+    let Some(member_id) = parse_member_id(&member_value) else {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description("I could not find a valid **member** value.")
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    };
+    let list_name = list_name(kind);
+
+    if !context
+        .database
+        .remove_user_relationship(interaction.guild.id, interaction.user_id, member_id, kind)
+        .await?
+    {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description(format!("<@{member_id}> is not {list_name}."))
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    }
+
+    // Managers keep the overwrite they were given when they were added.
+    if let Some(voice_channel) = context
+        .cache
+        .voice_channel_owner(interaction.guild.id, interaction.user_id)
+        .and_then(|voice_channel_id| context.cache.voice_channel(*voice_channel_id))
+        .filter(|voice_channel| !voice_channel.manager_ids.read().contains(&member_id))
+    {
+        let does_user_permission_exist =
+            voice_channel
+                .permission_overwrites
+                .read()
+                .iter()
+                .any(|permission_overwrite| {
+                    permission_overwrite
+                        .kind
+                        .eq(&ChannelPermissionOverwriteType::Member)
+                        && permission_overwrite.id.eq(&member_id.cast())
+                });
+
+        if does_user_permission_exist {
+            context
+                .client
+                .delete_channel_permission(voice_channel.id)
+                .member(member_id)
+                .await?;
+        }
+    }
+
+    audit(
+        &context,
+        &interaction.guild,
+        Some(interaction.user_id),
+        None,
+        match kind {
+            RelationshipKind::Blocked => "/voice block remove",
+            RelationshipKind::Trusted => "/voice trust remove",
+        },
+        format!(
+            "<@{}> removed <@{member_id}> from their {} list.",
+            interaction.user_id,
+            match kind {
+                RelationshipKind::Blocked => "block",
+                RelationshipKind::Trusted => "trust",
+            }
+        ),
+    )
+    .await?;
+
+    let embed = EmbedBuilder::new()
+        .color(0xF8F8FF)
+        .description(format!("<@{member_id}> is no longer {list_name}."))
+        .build();
+
+    context
+        .interaction_client()
+        .update_response(&interaction.token)
+        .embeds(Some(&[embed]))
+        .await?;
+
+    Ok(())
+}
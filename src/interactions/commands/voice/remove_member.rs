@@ -11,9 +11,52 @@ use twilight_model::{
 };
 use twilight_util::builder::{embed::EmbedBuilder, InteractionResponseDataBuilder};
 
-use crate::structs::{context::Context, interaction::ApplicationCommandInteraction};
+use super::{member_choices, parse_member_id};
+use crate::{
+    structs::{context::Context, interaction::ApplicationCommandInteraction},
+    utilities::audit::audit,
+};
 
 pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteraction) -> Result<()> {
+    let member_value = match interaction
+        .data
+        .options
+        .iter()
+        .find(|&option| option.name.eq("member"))
+        .cloned()
+        .map(|option| option.value)
+    {
+        Some(CommandOptionValue::Focused(value, _)) => {
+            let user_ids = context
+                .cache
+                .managed_voice_channel(interaction.guild.id, interaction.user_id)
+                .and_then(|voice_channel_id| context.cache.voice_channel(*voice_channel_id))
+                .map(|voice_channel| voice_channel.connected_user_ids.read().clone())
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|user_id| user_id.ne(&interaction.user_id));
+            let mut choices = member_choices(&context, interaction.guild.id, user_ids, &value);
+
+            choices.sort_by(|a, b| a.name.cmp(&b.name));
+
+            let data = InteractionResponseDataBuilder::new()
+                .choices(choices)
+                .build();
+            let interaction_response = InteractionResponse {
+                data: Some(data),
+                kind: InteractionResponseType::ApplicationCommandAutocompleteResult,
+            };
+
+            context
+                .interaction_client()
+                .create_response(interaction.id, &interaction.token, &interaction_response)
+                .await?;
+
+            return Ok(());
+        }
+        Some(CommandOptionValue::String(value)) => value,
+        _ => return Ok(()),
+    };
     let interaction_response_data = InteractionResponseDataBuilder::new()
         .flags(MessageFlags::EPHEMERAL)
         .build();
@@ -27,13 +70,7 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
         .create_response(interaction.id, &interaction.token, &interaction_response)
         .await?;
 
-    let Some(CommandOptionValue::User(member_id)) = interaction
-        .data
-        .options
-        .into_iter()
-        .find(|option| option.name.eq("member"))
-        .map(|option| option.value)
-    else {
+    let Some(member_id) = parse_member_id(&member_value) else {
         let embed = EmbedBuilder::new()
             .color(0xF8F8FF)
             .description("I could not find a valid **member** value.")
@@ -49,11 +86,11 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
     };
     let Some(voice_channel_id) = context
         .cache
-        .voice_channel_owner(interaction.guild.id, interaction.user_id)
+        .managed_voice_channel(interaction.guild.id, interaction.user_id)
     else {
         let embed = EmbedBuilder::new()
             .color(0xF8F8FF)
-            .description("You do not own a voice channel.")
+            .description("You do not own or manage a voice channel.")
             .build();
 
         context
@@ -84,6 +121,7 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
             .owner_id
             .read()
             .is_some_and(|owner_id| owner_id.eq(&member_id))
+        || voice_channel.manager_ids.read().contains(&member_id)
     {
         let embed = EmbedBuilder::new()
             .color(0xF8F8FF)
@@ -123,6 +161,13 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
 
         has_changed = true;
     }
+    if context
+        .database
+        .remove_timed_permission(voice_channel.id, member_id)
+        .await?
+    {
+        has_changed = true;
+    }
     if let Some(channel_id) = context.cache.voice_state(interaction.guild.id, member_id) {
         if voice_channel.id.eq(&*channel_id) {
             context
@@ -135,6 +180,21 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
         }
     }
 
+    if has_changed {
+        audit(
+            &context,
+            &interaction.guild,
+            Some(interaction.user_id),
+            Some(voice_channel.id),
+            "/voice remove-member",
+            format!(
+                "<@{}> removed <@{member_id}> from <#{}>.",
+                interaction.user_id, voice_channel.id
+            ),
+        )
+        .await?;
+    }
+
     let description = if has_changed {
         format!("<@{member_id}> has been removed of this voice channel.")
     } else {
@@ -153,3 +213,76 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use twilight_model::id::Id;
+
+    use crate::{
+        structs::database::ChannelPrivacy,
+        testing::{
+            self,
+            mock_discord::MockDiscord,
+            payloads::{command, subcommand, voice_state_update},
+            replay::replay,
+            seed_join_channel,
+        },
+    };
+
+    fn autocomplete(token: &str, value: &str) -> String {
+        command(
+            token,
+            1001,
+            2001,
+            subcommand(
+                "voice",
+                "remove-member",
+                json!([{ "focused": true, "name": "member", "type": 3, "value": value }]),
+            ),
+        )
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a database, run with `cargo test -- --ignored`"]
+    async fn suggests_members_connected_to_the_voice_channel() {
+        let mock_discord = MockDiscord::start().await;
+        let (context, _guard) = testing::context(&mock_discord).await;
+        let guild_id = Id::new(1001);
+
+        seed_join_channel(&context, guild_id, Id::new(1101), ChannelPrivacy::Unlocked).await;
+        replay(
+            &context,
+            include_str!("../../../testing/recordings/create.jsonl"),
+        )
+        .await
+        .unwrap();
+        replay(
+            &context,
+            &[
+                voice_state_update(1001, 2002, "sam", Some(5000)),
+                voice_state_update(1001, 2003, "kim", Some(5000)),
+                voice_state_update(1001, 2004, "lee", Some(1101)),
+                autocomplete("everyone", ""),
+                autocomplete("filtered", "SA"),
+            ]
+            .join("\n"),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            mock_discord.autocomplete_choices("everyone"),
+            [
+                ("kim".to_owned(), "2003".to_owned()),
+                ("sam".to_owned(), "2002".to_owned())
+            ]
+        );
+        assert_eq!(
+            mock_discord.autocomplete_choices("filtered"),
+            [("sam".to_owned(), "2002".to_owned())]
+        );
+
+        context.database.remove_guild(guild_id).await.unwrap();
+    }
+}
@@ -0,0 +1,350 @@
+use std::sync::Arc;
+
+use eyre::Result;
+use twilight_model::{
+    application::interaction::application_command::CommandOptionValue,
+    channel::{
+        message::MessageFlags,
+        permission_overwrite::PermissionOverwriteType as ChannelPermissionOverwriteType,
+    },
+    http::interaction::{InteractionResponse, InteractionResponseType},
+};
+use twilight_util::builder::{embed::EmbedBuilder, InteractionResponseDataBuilder};
+
+use crate::{
+    structs::{context::Context, interaction::ApplicationCommandInteraction},
+    utilities::{audit::audit, bulk::run_bulk},
+};
+
+pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteraction) -> Result<()> {
+    let interaction_response_data = InteractionResponseDataBuilder::new()
+        .flags(MessageFlags::EPHEMERAL)
+        .build();
+    let interaction_response = InteractionResponse {
+        data: Some(interaction_response_data),
+        kind: InteractionResponseType::DeferredChannelMessageWithSource,
+    };
+
+    context
+        .interaction_client()
+        .create_response(interaction.id, &interaction.token, &interaction_response)
+        .await?;
+
+    let Some(CommandOptionValue::Role(role_id)) = interaction
+        .data
+        .options
+        .iter()
+        .find(|&option| option.name.eq("role"))
+        .map(|option| option.value.clone())
+    else {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description("I could not find a valid **role** value.")
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    };
+    let Some(voice_channel_id) = context
+        .cache
+        .managed_voice_channel(interaction.guild.id, interaction.user_id)
+    else {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description("You do not own or manage a voice channel.")
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    };
+    let Some(voice_channel) = context.cache.voice_channel(*voice_channel_id) else {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description("I could not find your voice channel.")
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    };
+
+    // Removing @everyone would disconnect everyone, and my own role keeps me in the voice channel.
+    if interaction.guild.id.cast().eq(&role_id) || interaction.guild.bot_role_id.eq(&role_id) {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description(format!(
+                "<@&{role_id}> may not be removed from this voice channel."
+            ))
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    }
+
+    let mut lines = Vec::new();
+    let does_role_permission_exist =
+        voice_channel
+            .permission_overwrites
+            .read()
+            .iter()
+            .any(|permission_overwrite| {
+                permission_overwrite
+                    .kind
+                    .eq(&ChannelPermissionOverwriteType::Role)
+                    && permission_overwrite.id.eq(&role_id.cast())
+            });
+
+    // Like a member who can't be disconnected, a role that can't be removed is reported rather
+    // than leaving the members with it connected.
+    let is_role_permission_removed = does_role_permission_exist
+        && context
+            .client
+            .delete_channel_permission(voice_channel.id)
+            .role(role_id)
+            .await
+            .is_ok();
+
+    if is_role_permission_removed {
+        lines.push(format!(
+            "<@&{role_id}> has been removed from this voice channel."
+        ));
+    } else if does_role_permission_exist {
+        lines.push(format!(
+            "I'm unable to remove <@&{role_id}> from this voice channel right now. Try again in 10 minutes."
+        ));
+    }
+
+    let owner_id = *voice_channel.owner_id.read();
+    let manager_ids = voice_channel.manager_ids.read().clone();
+    let mut member_ids = voice_channel
+        .connected_user_ids
+        .read()
+        .iter()
+        .copied()
+        .filter(|user_id| {
+            owner_id.ne(&Some(*user_id))
+                && !manager_ids.contains(user_id)
+                && context.application_id.ne(&user_id.cast())
+        })
+        .collect::<Vec<_>>();
+
+    member_ids.sort();
+
+    // I don't keep track of roles, so each connected member is looked up before being
+    // disconnected.
+    let outcome = run_bulk(member_ids, |member_id| {
+        let context = &context;
+        let guild_id = interaction.guild.id;
+
+        async move {
+            let member = context
+                .client
+                .guild_member(guild_id, member_id)
+                .await?
+                .model()
+                .await?;
+
+            if !member.roles.contains(&role_id) {
+                return Ok(false);
+            }
+
+            context
+                .client
+                .update_guild_member(guild_id, member_id)
+                .channel_id(None)
+                .await?;
+
+            Ok(true)
+        }
+    })
+    .await;
+
+    if is_role_permission_removed || !outcome.changed.is_empty() {
+        audit(
+            &context,
+            &interaction.guild,
+            Some(interaction.user_id),
+            Some(voice_channel.id),
+            "/voice remove-role",
+            format!(
+                "<@{}> removed <@&{role_id}> from <#{voice_channel_id}>.",
+                interaction.user_id
+            ),
+        )
+        .await?;
+    }
+    if !outcome.changed.is_empty() || !outcome.failed.is_empty() || lines.is_empty() {
+        lines.push(outcome.describe(
+            |mentions| format!("I've disconnected {mentions} from <#{voice_channel_id}>."),
+            |mentions| {
+                format!("I'm unable to disconnect {mentions} right now. Try again in 10 minutes.")
+            },
+        ));
+    }
+
+    let embed = EmbedBuilder::new()
+        .color(0xF8F8FF)
+        .description(lines.join("\n"))
+        .build();
+
+    context
+        .interaction_client()
+        .update_response(&interaction.token)
+        .embeds(Some(&[embed]))
+        .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use twilight_model::id::Id;
+
+    use crate::{
+        structs::database::ChannelPrivacy,
+        testing::{
+            self,
+            mock_discord::MockDiscord,
+            payloads::{channel_update, command, subcommand, voice_state_update},
+            replay::replay,
+            seed_join_channel,
+        },
+    };
+
+    #[tokio::test]
+    #[ignore = "needs a database, run with `cargo test -- --ignored`"]
+    async fn disconnects_members_with_the_role() {
+        let mock_discord = MockDiscord::start().await;
+        let (context, _guard) = testing::context(&mock_discord).await;
+        let guild_id = Id::new(1001);
+
+        seed_join_channel(&context, guild_id, Id::new(1101), ChannelPrivacy::Unlocked).await;
+        replay(
+            &context,
+            include_str!("../../../testing/recordings/create.jsonl"),
+        )
+        .await
+        .unwrap();
+        mock_discord.insert_member_roles(2002, &[3001]);
+        replay(
+            &context,
+            &[
+                voice_state_update(1001, 2002, "sam", Some(5000)),
+                voice_state_update(1001, 2003, "kim", Some(5000)),
+                command(
+                    "remove-role",
+                    1001,
+                    2001,
+                    subcommand(
+                        "voice",
+                        "remove-role",
+                        json!([{ "name": "role", "type": 8, "value": "3001" }]),
+                    ),
+                ),
+                command(
+                    "everyone",
+                    1001,
+                    2001,
+                    subcommand(
+                        "voice",
+                        "remove-role",
+                        json!([{ "name": "role", "type": 8, "value": "1001" }]),
+                    ),
+                ),
+            ]
+            .join("\n"),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            mock_discord.response_descriptions("remove-role"),
+            ["I've disconnected <@2002> from <#5000>."]
+        );
+        assert_eq!(
+            mock_discord
+                .assert_requested("PATCH", "/guilds/1001/members/2002")
+                .body["channel_id"],
+            json!(null)
+        );
+        mock_discord.assert_not_requested("PATCH", "/guilds/1001/members/2003");
+        assert_eq!(
+            mock_discord.response_descriptions("everyone"),
+            ["<@&1001> may not be removed from this voice channel."]
+        );
+
+        context.database.remove_guild(guild_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a database, run with `cargo test -- --ignored`"]
+    async fn disconnects_members_when_the_role_cant_be_removed() {
+        let mock_discord = MockDiscord::start().await;
+        let (context, _guard) = testing::context(&mock_discord).await;
+        let guild_id = Id::new(1001);
+
+        seed_join_channel(&context, guild_id, Id::new(1101), ChannelPrivacy::Unlocked).await;
+        replay(
+            &context,
+            include_str!("../../../testing/recordings/create.jsonl"),
+        )
+        .await
+        .unwrap();
+
+        let mut voice_channel = mock_discord.channel(5000).unwrap();
+
+        voice_channel["permission_overwrites"]
+            .as_array_mut()
+            .unwrap()
+            .push(json!({ "id": "3001", "type": 0, "allow": "1048576", "deny": "0" }));
+        mock_discord.insert_member_roles(2002, &[3001]);
+        mock_discord.fail("DELETE", "/channels/5000/permissions/3001");
+        replay(
+            &context,
+            &[
+                channel_update(voice_channel),
+                voice_state_update(1001, 2002, "sam", Some(5000)),
+                command(
+                    "remove-role",
+                    1001,
+                    2001,
+                    subcommand(
+                        "voice",
+                        "remove-role",
+                        json!([{ "name": "role", "type": 8, "value": "3001" }]),
+                    ),
+                ),
+            ]
+            .join("\n"),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            mock_discord.response_descriptions("remove-role"),
+            ["I'm unable to remove <@&3001> from this voice channel right now. Try again in 10 minutes.\nI've disconnected <@2002> from <#5000>."]
+        );
+
+        context.database.remove_guild(guild_id).await.unwrap();
+    }
+}
@@ -14,11 +14,15 @@ use twilight_util::builder::{embed::EmbedBuilder, InteractionResponseDataBuilder
 
 use crate::{
     structs::{context::Context, interaction::ApplicationCommandInteraction},
-    utilities::constants::SLOW_MODE_OPTIONS,
+    utilities::{
+        audit::audit,
+        constants::{MAX_SLOW_MODE, SLOW_MODE_OPTIONS},
+        time::{humanize, parse},
+    },
 };
 
 pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteraction) -> Result<()> {
-    let slow_mode = match interaction
+    let duration = match interaction
         .data
         .options
         .iter()
@@ -30,17 +34,39 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
             let lowercased_value = value.to_ascii_lowercase();
             let mut choices: Vec<CommandOptionChoice> = Vec::with_capacity(5);
 
+            // Typed durations like "90s" or "2m" are offered first, as they're exactly what
+            // the member asked for.
+            if let Some(seconds) = parse(&value).filter(|seconds| seconds.le(&MAX_SLOW_MODE)) {
+                let name = if seconds.eq(&0) {
+                    "Off".to_owned()
+                } else {
+                    humanize(seconds)
+                };
+
+                choices.push(CommandOptionChoice {
+                    name,
+                    name_localizations: None,
+                    value: CommandOptionChoiceValue::String(seconds.to_string()),
+                })
+            }
+
             for [name, value] in SLOW_MODE_OPTIONS.clone().into_iter() {
-                if name.to_ascii_lowercase().contains(&lowercased_value) {
+                if choices.len().ge(&5) {
+                    break;
+                }
+                if name.to_ascii_lowercase().contains(&lowercased_value)
+                    && !choices.iter().any(|choice| {
+                        choice
+                            .value
+                            .eq(&CommandOptionChoiceValue::String(value.clone()))
+                    })
+                {
                     choices.push(CommandOptionChoice {
                         name,
                         name_localizations: None,
                         value: CommandOptionChoiceValue::String(value),
                     })
                 }
-                if choices.len().ge(&5) {
-                    break;
-                }
             }
 
             let data = InteractionResponseDataBuilder::new()
@@ -58,7 +84,7 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
 
             return Ok(());
         }
-        Some(CommandOptionValue::String(duration)) => duration.parse::<u16>()?,
+        Some(CommandOptionValue::String(duration)) => duration,
         _ => return Ok(()),
     };
     let interaction_response_data = InteractionResponseDataBuilder::new()
@@ -74,13 +100,30 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
         .create_response(interaction.id, &interaction.token, &interaction_response)
         .await?;
 
+    let Some(slow_mode) = parse(&duration)
+        .filter(|seconds| seconds.le(&MAX_SLOW_MODE))
+        .map(|seconds| seconds as u16)
+    else {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description("I could not find a valid **duration** value.")
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    };
     let Some(voice_channel_id) = context
         .cache
-        .voice_channel_owner(interaction.guild.id, interaction.user_id)
+        .managed_voice_channel(interaction.guild.id, interaction.user_id)
     else {
         let embed = EmbedBuilder::new()
             .color(0xF8F8FF)
-            .description("You do not own a voice channel.")
+            .description("You do not own or manage a voice channel.")
             .build();
 
         context
@@ -147,6 +190,20 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
         return Ok(());
     };
 
+    audit(
+        &context,
+        &interaction.guild,
+        Some(interaction.user_id),
+        Some(voice_channel.id),
+        "/voice slow-mode",
+        format!(
+            "<@{}> set the slow mode of <#{voice_channel_id}> to {} second(s).",
+            interaction.user_id,
+            slow_mode.separate_with_commas()
+        ),
+    )
+    .await?;
+
     let embed = EmbedBuilder::new()
         .color(0xF8F8FF)
         .description(format!(
@@ -163,3 +220,77 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use twilight_model::id::Id;
+
+    use crate::{
+        structs::database::ChannelPrivacy,
+        testing::{
+            self,
+            mock_discord::MockDiscord,
+            payloads::{command, subcommand},
+            replay::replay,
+            seed_join_channel,
+        },
+    };
+
+    fn slow_mode_command(token: &str, value: &str, focused: bool) -> String {
+        command(
+            token,
+            1001,
+            2001,
+            subcommand(
+                "voice",
+                "slow-mode",
+                json!([{ "focused": focused, "name": "duration", "type": 3, "value": value }]),
+            ),
+        )
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a database, run with `cargo test -- --ignored`"]
+    async fn understands_typed_durations() {
+        let mock_discord = MockDiscord::start().await;
+        let (context, _guard) = testing::context(&mock_discord).await;
+        let guild_id = Id::new(1001);
+
+        seed_join_channel(&context, guild_id, Id::new(1101), ChannelPrivacy::Unlocked).await;
+        replay(
+            &context,
+            include_str!("../../../testing/recordings/create.jsonl"),
+        )
+        .await
+        .unwrap();
+        replay(
+            &context,
+            &[
+                slow_mode_command("suggest", "90s", true),
+                slow_mode_command("submit", "2m", false),
+                slow_mode_command("invalid", "soon", false),
+            ]
+            .join("\n"),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            mock_discord.autocomplete_choices("suggest"),
+            [("1m 30s".to_owned(), "90".to_owned())]
+        );
+        assert_eq!(
+            mock_discord
+                .assert_requested("PATCH", "/channels/5000")
+                .body["rate_limit_per_user"],
+            120
+        );
+        assert_eq!(
+            mock_discord.response_descriptions("invalid"),
+            ["I could not find a valid **duration** value."]
+        );
+
+        context.database.remove_guild(guild_id).await.unwrap();
+    }
+}
@@ -0,0 +1,202 @@
+use std::sync::Arc;
+
+use eyre::Result;
+use twilight_model::{
+    application::interaction::application_command::CommandOptionValue,
+    channel::message::MessageFlags,
+    http::interaction::{InteractionResponse, InteractionResponseType},
+};
+use twilight_util::builder::{embed::EmbedBuilder, InteractionResponseDataBuilder};
+
+use super::super::{member_choices, parse_member_id};
+use crate::{
+    structs::{
+        context::Context, database::VoiceChannelKind, interaction::ApplicationCommandInteraction,
+    },
+    utilities::{audit::audit, stage::invite_to_speak},
+};
+
+pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteraction) -> Result<()> {
+    let member_value = match interaction
+        .data
+        .options
+        .iter()
+        .find(|&option| option.name.eq("member"))
+        .cloned()
+        .map(|option| option.value)
+    {
+        Some(CommandOptionValue::Focused(value, _)) => {
+            let user_ids = context
+                .cache
+                .managed_voice_channel(interaction.guild.id, interaction.user_id)
+                .and_then(|voice_channel_id| context.cache.voice_channel(*voice_channel_id))
+                .map(|voice_channel| voice_channel.connected_user_ids.read().clone())
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|user_id| user_id.ne(&interaction.user_id));
+            let mut choices = member_choices(&context, interaction.guild.id, user_ids, &value);
+
+            choices.sort_by(|a, b| a.name.cmp(&b.name));
+
+            let data = InteractionResponseDataBuilder::new()
+                .choices(choices)
+                .build();
+            let interaction_response = InteractionResponse {
+                data: Some(data),
+                kind: InteractionResponseType::ApplicationCommandAutocompleteResult,
+            };
+
+            context
+                .interaction_client()
+                .create_response(interaction.id, &interaction.token, &interaction_response)
+                .await?;
+
+            return Ok(());
+        }
+        Some(CommandOptionValue::String(value)) => value,
+        _ => return Ok(()),
+    };
+    let interaction_response_data = InteractionResponseDataBuilder::new()
+        .flags(MessageFlags::EPHEMERAL)
+        .build();
+    let interaction_response = InteractionResponse {
+        data: Some(interaction_response_data),
+        kind: InteractionResponseType::DeferredChannelMessageWithSource,
+    };
+
+    context
+        .interaction_client()
+        .create_response(interaction.id, &interaction.token, &interaction_response)
+        .await?;
+
+    let Some(member_id) = parse_member_id(&member_value) else {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description("I could not find a valid **member** value.")
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    };
+    let Some(voice_channel_id) = context
+        .cache
+        .managed_voice_channel(interaction.guild.id, interaction.user_id)
+    else {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description("You do not own or manage a voice channel.")
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    };
+    let Some(voice_channel) = context.cache.voice_channel(*voice_channel_id) else {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description("I could not find your voice channel.")
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    };
+
+    if voice_channel.kind.ne(&VoiceChannelKind::Stage) {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description(format!("<#{voice_channel_id}> is not a stage channel."))
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    }
+
+    if !voice_channel.connected_user_ids.read().contains(&member_id) {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description(format!(
+                "<@{member_id}> is not connected to <#{voice_channel_id}>."
+            ))
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    }
+
+    if invite_to_speak(
+        &context.client,
+        interaction.guild.id,
+        member_id,
+        voice_channel.id,
+    )
+    .await
+    .is_err()
+    {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description(format!(
+                "I'm unable to invite <@{member_id}> to speak right now. Try again in 10 minutes."
+            ))
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    }
+
+    audit(
+        &context,
+        &interaction.guild,
+        Some(interaction.user_id),
+        Some(voice_channel.id),
+        "/voice stage invite",
+        format!(
+            "<@{}> invited <@{member_id}> to speak in <#{voice_channel_id}>.",
+            interaction.user_id
+        ),
+    )
+    .await?;
+
+    let embed = EmbedBuilder::new()
+        .color(0xF8F8FF)
+        .description(format!(
+            "I've invited <@{member_id}> to speak in <#{voice_channel_id}>."
+        ))
+        .build();
+
+    context
+        .interaction_client()
+        .update_response(&interaction.token)
+        .embeds(Some(&[embed]))
+        .await?;
+
+    Ok(())
+}
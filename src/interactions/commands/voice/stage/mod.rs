@@ -0,0 +1,244 @@
+mod invite;
+mod topic;
+
+use std::{mem::replace, sync::Arc};
+
+use eyre::Result;
+use twilight_model::{
+    application::interaction::application_command::{CommandDataOption, CommandOptionValue},
+    channel::message::MessageFlags,
+    http::interaction::{InteractionResponse, InteractionResponseType},
+};
+use twilight_util::builder::{embed::EmbedBuilder, InteractionResponseDataBuilder};
+
+use crate::structs::{context::Context, interaction::ApplicationCommandInteraction};
+
+pub async fn run(
+    context: Arc<Context>,
+    mut interaction: ApplicationCommandInteraction,
+) -> Result<()> {
+    let Some(CommandDataOption {
+        name,
+        value: CommandOptionValue::SubCommand(options),
+    }) = interaction.data.options.clone().into_iter().next()
+    else {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description("I could not find a subcommand.")
+            .build();
+        let interaction_response_data = InteractionResponseDataBuilder::new()
+            .embeds(vec![embed])
+            .flags(MessageFlags::EPHEMERAL)
+            .build();
+        let interaction_response = InteractionResponse {
+            data: Some(interaction_response_data),
+            kind: InteractionResponseType::ChannelMessageWithSource,
+        };
+
+        context
+            .interaction_client()
+            .create_response(interaction.id, &interaction.token, &interaction_response)
+            .await?;
+
+        return Ok(());
+    };
+    let _ = replace(&mut interaction.data.options, options);
+
+    match name.as_str() {
+        "invite" => invite::run(context, interaction).await?,
+        "topic" => topic::run(context, interaction).await?,
+        _ => {
+            let embed = EmbedBuilder::new()
+                .color(0xF8F8FF)
+                .description(format!(
+                    "I don't have a subcommand with the name \"{name}\"."
+                ))
+                .build();
+            let interaction_response_data = InteractionResponseDataBuilder::new()
+                .embeds(vec![embed])
+                .flags(MessageFlags::EPHEMERAL)
+                .build();
+            let interaction_response = InteractionResponse {
+                data: Some(interaction_response_data),
+                kind: InteractionResponseType::ChannelMessageWithSource,
+            };
+
+            context
+                .interaction_client()
+                .create_response(interaction.id, &interaction.token, &interaction_response)
+                .await?;
+
+            return Ok(());
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use twilight_model::{guild::Permissions, id::Id};
+
+    use crate::{
+        structs::database::{ChannelPrivacy, VoiceChannelKind},
+        testing::{
+            self,
+            mock_discord::MockDiscord,
+            payloads::{channel_update, command, subcommand, subcommand_group, voice_state_update},
+            replay::replay,
+            seed_join_channel,
+        },
+        utilities::constants::STAGE_MODERATOR_PERMISSIONS,
+    };
+
+    fn overwrite_permissions(overwrite: &serde_json::Value, key: &str) -> Permissions {
+        Permissions::from_bits_truncate(overwrite[key].as_str().unwrap().parse().unwrap())
+    }
+
+    fn topic_command(token: &str, topic: &str) -> String {
+        command(
+            token,
+            1001,
+            2001,
+            subcommand_group(
+                "voice",
+                "stage",
+                "topic",
+                json!([{ "name": "text", "type": 3, "value": topic }]),
+            ),
+        )
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a database, run with `cargo test -- --ignored`"]
+    async fn runs_a_locked_stage() {
+        let mock_discord = MockDiscord::start().await;
+        let (context, _guard) = testing::context(&mock_discord).await;
+        let guild_id = Id::new(1001);
+
+        seed_join_channel(&context, guild_id, Id::new(1101), ChannelPrivacy::Locked).await;
+        context
+            .database
+            .remove_join_channel(Id::new(1101))
+            .await
+            .unwrap();
+        context
+            .database
+            .insert_join_channel(
+                Id::new(1101),
+                guild_id,
+                None,
+                false,
+                ChannelPrivacy::Locked,
+                VoiceChannelKind::Stage,
+            )
+            .await
+            .unwrap();
+        replay(
+            &context,
+            include_str!("../../../../testing/recordings/create.jsonl"),
+        )
+        .await
+        .unwrap();
+
+        let create_request = mock_discord.assert_requested("POST", "/guilds/1001/channels");
+        let permission_overwrites = create_request.body["permission_overwrites"]
+            .as_array()
+            .unwrap();
+        let owner_overwrite = permission_overwrites
+            .iter()
+            .find(|permission_overwrite| permission_overwrite["id"].eq("2001"))
+            .unwrap();
+        let everyone_overwrite = permission_overwrites
+            .iter()
+            .find(|permission_overwrite| permission_overwrite["id"].eq("1001"))
+            .unwrap();
+
+        assert_eq!(create_request.body["type"], 13);
+        assert!(overwrite_permissions(owner_overwrite, "allow")
+            .contains(STAGE_MODERATOR_PERMISSIONS | Permissions::REQUEST_TO_SPEAK));
+        assert!(overwrite_permissions(everyone_overwrite, "deny")
+            .contains(Permissions::REQUEST_TO_SPEAK));
+        assert!(!overwrite_permissions(everyone_overwrite, "deny").contains(Permissions::CONNECT));
+
+        replay(
+            &context,
+            &[
+                topic_command("start", "office hours"),
+                topic_command("change", "q&a"),
+                voice_state_update(1001, 2002, "blake", Some(5000)),
+                command(
+                    "invite",
+                    1001,
+                    2001,
+                    subcommand_group(
+                        "voice",
+                        "stage",
+                        "invite",
+                        json!([{ "name": "member", "type": 3, "value": "2002" }]),
+                    ),
+                ),
+            ]
+            .join("\n"),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            mock_discord.response_descriptions("start"),
+            ["The topic of <#5000> is now **office hours**."]
+        );
+        assert_eq!(
+            mock_discord.response_descriptions("change"),
+            ["The topic of <#5000> is now **q&a**."]
+        );
+        assert_eq!(
+            mock_discord.requests_to("POST", "/stage-instances").len(),
+            1
+        );
+        assert_eq!(mock_discord.stage_instance(5000).unwrap()["topic"], "q&a");
+        assert_eq!(
+            mock_discord.response_descriptions("invite"),
+            ["I've invited <@2002> to speak in <#5000>."]
+        );
+        assert_eq!(
+            mock_discord
+                .assert_requested("PATCH", "/guilds/1001/voice-states/2002")
+                .body,
+            json!({ "channel_id": "5000", "suppress": false })
+        );
+
+        // A member let into the stage may ask to speak, which is what `/voice view` looks for.
+        replay(
+            &context,
+            &command(
+                "allow",
+                1001,
+                2001,
+                subcommand(
+                    "voice",
+                    "allow-member",
+                    json!([{ "name": "member", "type": 3, "value": "2003" }]),
+                ),
+            ),
+        )
+        .await
+        .unwrap();
+        replay(
+            &context,
+            &[
+                channel_update(mock_discord.channel(5000).unwrap()),
+                command("view", 1001, 2001, subcommand("voice", "view", json!([]))),
+            ]
+            .join("\n"),
+        )
+        .await
+        .unwrap();
+
+        assert!(mock_discord.response_descriptions("view")[0]
+            .contains("**Allow list:** 1 user has been allowed."));
+
+        context.database.remove_guild(guild_id).await.unwrap();
+    }
+}
@@ -0,0 +1,186 @@
+mod clear;
+mod set;
+
+use std::{mem::replace, sync::Arc};
+
+use eyre::Result;
+use twilight_model::{
+    application::interaction::application_command::{CommandDataOption, CommandOptionValue},
+    channel::message::MessageFlags,
+    http::interaction::{InteractionResponse, InteractionResponseType},
+};
+use twilight_util::builder::{embed::EmbedBuilder, InteractionResponseDataBuilder};
+
+use crate::structs::{context::Context, interaction::ApplicationCommandInteraction};
+
+pub async fn run(
+    context: Arc<Context>,
+    mut interaction: ApplicationCommandInteraction,
+) -> Result<()> {
+    let Some(CommandDataOption {
+        name,
+        value: CommandOptionValue::SubCommand(options),
+    }) = interaction.data.options.clone().into_iter().next()
+    else {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description("I could not find a subcommand.")
+            .build();
+        let interaction_response_data = InteractionResponseDataBuilder::new()
+            .embeds(vec![embed])
+            .flags(MessageFlags::EPHEMERAL)
+            .build();
+        let interaction_response = InteractionResponse {
+            data: Some(interaction_response_data),
+            kind: InteractionResponseType::ChannelMessageWithSource,
+        };
+
+        context
+            .interaction_client()
+            .create_response(interaction.id, &interaction.token, &interaction_response)
+            .await?;
+
+        return Ok(());
+    };
+    let _ = replace(&mut interaction.data.options, options);
+
+    match name.as_str() {
+        "clear" => clear::run(context, interaction).await?,
+        "set" => set::run(context, interaction).await?,
+        _ => {
+            let embed = EmbedBuilder::new()
+                .color(0xF8F8FF)
+                .description(format!(
+                    "I don't have a subcommand with the name \"{name}\"."
+                ))
+                .build();
+            let interaction_response_data = InteractionResponseDataBuilder::new()
+                .embeds(vec![embed])
+                .flags(MessageFlags::EPHEMERAL)
+                .build();
+            let interaction_response = InteractionResponse {
+                data: Some(interaction_response_data),
+                kind: InteractionResponseType::ChannelMessageWithSource,
+            };
+
+            context
+                .interaction_client()
+                .create_response(interaction.id, &interaction.token, &interaction_response)
+                .await?;
+
+            return Ok(());
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use twilight_model::id::Id;
+
+    use crate::{
+        structs::database::ChannelPrivacy,
+        testing::{
+            self,
+            mock_discord::MockDiscord,
+            payloads::{command, subcommand_group},
+            replay::replay,
+            seed_join_channel, VOICE_CHANNEL_ID,
+        },
+    };
+
+    #[tokio::test]
+    #[ignore = "needs a database, run with `cargo test -- --ignored`"]
+    async fn sets_and_clears_the_status() {
+        let mock_discord = MockDiscord::start().await;
+        let (context, _guard) = testing::context(&mock_discord).await;
+        let guild_id = Id::new(1001);
+
+        seed_join_channel(&context, guild_id, Id::new(1101), ChannelPrivacy::Unlocked).await;
+        replay(
+            &context,
+            include_str!("../../../../testing/recordings/create.jsonl"),
+        )
+        .await
+        .unwrap();
+        replay(
+            &context,
+            &[
+                command(
+                    "set",
+                    1001,
+                    2001,
+                    subcommand_group(
+                        "voice",
+                        "status",
+                        "set",
+                        json!([{ "name": "text", "type": 3, "value": "lofi beats" }]),
+                    ),
+                ),
+                command(
+                    "set-again",
+                    1001,
+                    2001,
+                    subcommand_group(
+                        "voice",
+                        "status",
+                        "set",
+                        json!([{ "name": "text", "type": 3, "value": "lofi beats" }]),
+                    ),
+                ),
+            ]
+            .join("\n"),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            mock_discord.response_descriptions("set"),
+            ["The status of <#5000> is now **lofi beats**."]
+        );
+        assert_eq!(
+            mock_discord.response_descriptions("set-again"),
+            ["No change has been made."]
+        );
+        assert_eq!(mock_discord.channel(5000).unwrap()["status"], "lofi beats");
+        assert_eq!(
+            context
+                .database
+                .guild_voice_channels(guild_id)
+                .await
+                .unwrap()[0]
+                .status
+                .as_deref(),
+            Some("lofi beats")
+        );
+
+        replay(
+            &context,
+            &command(
+                "clear",
+                1001,
+                2001,
+                subcommand_group("voice", "status", "clear", json!([])),
+            ),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            mock_discord.response_descriptions("clear"),
+            ["I've cleared the status of <#5000>."]
+        );
+        assert!(mock_discord.channel(5000).unwrap()["status"].is_null());
+        assert!(context
+            .cache
+            .voice_channel(VOICE_CHANNEL_ID)
+            .unwrap()
+            .status
+            .read()
+            .is_none());
+
+        context.database.remove_guild(guild_id).await.unwrap();
+    }
+}
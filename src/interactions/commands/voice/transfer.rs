@@ -3,19 +3,58 @@ use std::sync::Arc;
 use eyre::Result;
 use twilight_model::{
     application::interaction::application_command::CommandOptionValue,
-    channel::{
-        message::MessageFlags,
-        permission_overwrite::PermissionOverwriteType as ChannelPermissionOverwriteType,
-    },
+    channel::message::MessageFlags,
     http::interaction::{InteractionResponse, InteractionResponseType},
 };
 use twilight_util::builder::{embed::EmbedBuilder, InteractionResponseDataBuilder};
 
-use crate::structs::{
-    cache::CachedVoiceChannelUpdate, context::Context, interaction::ApplicationCommandInteraction,
+use super::{member_choices, parse_member_id};
+use crate::{
+    structs::{context::Context, interaction::ApplicationCommandInteraction},
+    tasks::ownership::transfer_ownership,
+    utilities::audit::audit,
 };
 
 pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteraction) -> Result<()> {
+    let member_value = match interaction
+        .data
+        .options
+        .iter()
+        .find(|&option| option.name.eq("member"))
+        .cloned()
+        .map(|option| option.value)
+    {
+        Some(CommandOptionValue::Focused(value, _)) => {
+            let user_ids = context
+                .cache
+                .voice_channel_owner(interaction.guild.id, interaction.user_id)
+                .and_then(|voice_channel_id| context.cache.voice_channel(*voice_channel_id))
+                .map(|voice_channel| voice_channel.connected_user_ids.read().clone())
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|user_id| user_id.ne(&interaction.user_id));
+            let mut choices = member_choices(&context, interaction.guild.id, user_ids, &value);
+
+            choices.sort_by(|a, b| a.name.cmp(&b.name));
+
+            let data = InteractionResponseDataBuilder::new()
+                .choices(choices)
+                .build();
+            let interaction_response = InteractionResponse {
+                data: Some(data),
+                kind: InteractionResponseType::ApplicationCommandAutocompleteResult,
+            };
+
+            context
+                .interaction_client()
+                .create_response(interaction.id, &interaction.token, &interaction_response)
+                .await?;
+
+            return Ok(());
+        }
+        Some(CommandOptionValue::String(value)) => value,
+        _ => return Ok(()),
+    };
     let interaction_response_data = InteractionResponseDataBuilder::new()
         .flags(MessageFlags::EPHEMERAL)
         .build();
@@ -29,13 +68,7 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
         .create_response(interaction.id, &interaction.token, &interaction_response)
         .await?;
 
-    let Some(CommandOptionValue::User(member_id)) = interaction
-        .data
-        .options
-        .into_iter()
-        .find(|option| option.name.eq("member"))
-        .map(|option| option.value)
-    else {
+    let Some(member_id) = parse_member_id(&member_value) else {
         let embed = EmbedBuilder::new()
             .color(0xF8F8FF)
             .description("I could not find a valid **member** value.")
@@ -103,22 +136,27 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
         return Ok(());
     }
 
-    let mut permission_overwrites = voice_channel.permission_overwrites.read().clone();
+    if !context
+        .cache
+        .can_own_voice_channel(interaction.guild.id, member_id)
+    {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description(format!(
+                "<@{member_id}> already owns as many voice channels as this server allows."
+            ))
+            .build();
 
-    for permission_overwrite in permission_overwrites.iter_mut() {
-        if permission_overwrite.id.eq(&interaction.user_id.cast())
-            && permission_overwrite
-                .kind
-                .eq(&ChannelPermissionOverwriteType::Member)
-        {
-            permission_overwrite.id = member_id.cast();
-        }
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
     }
 
-    if context
-        .client
-        .update_channel(voice_channel.id)
-        .permission_overwrites(&permission_overwrites)
+    if transfer_ownership(&context, &voice_channel, member_id)
         .await
         .is_err()
     {
@@ -138,17 +176,18 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
         return Ok(());
     };
 
-    context
-        .database
-        .update_voice_channel_owner_id(voice_channel.id, Some(member_id))
-        .await?;
-    context.cache.update_voice_channel(
-        voice_channel.id,
-        CachedVoiceChannelUpdate {
-            owner_id: Some(Some(member_id)),
-            ..Default::default()
-        },
-    );
+    audit(
+        &context,
+        &interaction.guild,
+        Some(interaction.user_id),
+        Some(voice_channel.id),
+        "/voice transfer",
+        format!(
+            "<@{}> transferred <#{voice_channel_id}> to <@{member_id}>.",
+            interaction.user_id
+        ),
+    )
+    .await?;
 
     let embed = EmbedBuilder::new()
         .color(0xF8F8FF)
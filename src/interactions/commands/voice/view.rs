@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::SystemTime};
 
 use eyre::Result;
 use twilight_model::{
@@ -7,7 +7,6 @@ use twilight_model::{
         permission_overwrite::PermissionOverwriteType as ChannelPermissionOverwriteType,
         VideoQualityMode,
     },
-    guild::Permissions,
     http::interaction::{InteractionResponse, InteractionResponseType},
     id::{marker::UserMarker, Id},
 };
@@ -15,9 +14,11 @@ use twilight_util::builder::{embed::EmbedBuilder, InteractionResponseDataBuilder
 
 use crate::{
     structs::{
-        context::Context, database::ChannelPrivacy, interaction::ApplicationCommandInteraction,
+        context::Context,
+        database::{ChannelPrivacy, TimedPermissionKind},
+        interaction::ApplicationCommandInteraction,
     },
-    utilities::time::humanize,
+    utilities::{permission_overwrites::privacy_permissions, time::humanize},
 };
 
 pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteraction) -> Result<()> {
@@ -36,11 +37,11 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
 
     let Some(voice_channel_id) = context
         .cache
-        .voice_channel_owner(interaction.guild.id, interaction.user_id)
+        .managed_voice_channel(interaction.guild.id, interaction.user_id)
     else {
         let embed = EmbedBuilder::new()
             .color(0xF8F8FF)
-            .description("You do not own a voice channel.")
+            .description("You do not own or manage a voice channel.")
             .build();
 
         context
@@ -65,12 +66,21 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
 
         return Ok(());
     };
-    let (privacy_permissions, privacy_text) = match voice_channel.privacy.read().clone() {
-        ChannelPrivacy::Invisible => (Permissions::VIEW_CHANNEL, "Invisible"),
-        ChannelPrivacy::Locked => (Permissions::CONNECT, "Locked (and visible)"),
-        ChannelPrivacy::Unlocked => (Permissions::empty(), "Unlocked (and visible)"),
+    let privacy = voice_channel.privacy.read().clone();
+    let privacy_permissions = privacy_permissions(voice_channel.kind, &privacy);
+    let privacy_text = match privacy {
+        ChannelPrivacy::Invisible => "Invisible",
+        ChannelPrivacy::Locked => "Locked (and visible)",
+        ChannelPrivacy::Unlocked => "Unlocked (and visible)",
     };
     let permanence_text = voice_channel.permanence.read().clone().to_string();
+    let owner_id = *voice_channel.owner_id.read();
+    let mut manager_ids = voice_channel
+        .manager_ids
+        .read()
+        .iter()
+        .copied()
+        .collect::<Vec<Id<UserMarker>>>();
     let mut allow_list: Vec<Id<UserMarker>> = Vec::new();
     let mut deny_list: Vec<Id<UserMarker>> = Vec::new();
 
@@ -81,7 +91,9 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
         {
             continue;
         }
-        if permission_overwrite.id.eq(&interaction.user_id.cast()) {
+        if owner_id.is_some_and(|owner_id| permission_overwrite.id.eq(&owner_id.cast()))
+            || manager_ids.contains(&permission_overwrite.id.cast())
+        {
             continue;
         }
 
@@ -110,21 +122,65 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
     } else {
         format!("{} users have been denied.", deny_list.len())
     };
-    let bitrate_text = format!("{}kbps", voice_channel.bitrate.read().clone() / 1_000);
-    let owner_text = voice_channel
-        .owner_id
-        .read()
-        .map_or("No owner".to_owned(), |owner_id| format!("<@{owner_id}>"));
-    let slow_mode_text = match voice_channel.rate_limit_per_user.read().clone() {
+    let managers_text = if manager_ids.is_empty() {
+        "No manager has been added.".to_owned()
+    } else {
+        manager_ids.sort();
+
+        manager_ids
+            .into_iter()
+            .map(|manager_id| format!("<@{manager_id}>"))
+            .collect::<Vec<String>>()
+            .join(", ")
+    };
+    let bitrate_text = format!("{}kbps", *voice_channel.bitrate.read() / 1_000);
+    let owner_text = owner_id.map_or("No owner".to_owned(), |owner_id| format!("<@{owner_id}>"));
+    let slow_mode_text = match *voice_channel.rate_limit_per_user.read() {
         None | Some(0) => "No slow mode has been set.".to_owned(),
         Some(slow_mode) => humanize(slow_mode.into()),
     };
-    let user_limit_text = match voice_channel.user_limit.read().clone() {
+    let now = SystemTime::now();
+    let timed_permissions = context
+        .database
+        .voice_channel_timed_permissions(voice_channel.id)
+        .await?;
+    let timed_permissions_text = if timed_permissions.is_empty() {
+        "No timed permission has been set.".to_owned()
+    } else {
+        timed_permissions
+            .into_iter()
+            .map(|timed_permission| {
+                let permission_text = match timed_permission.kind {
+                    TimedPermissionKind::Allow => "allowed",
+                    TimedPermissionKind::Deny => "denied",
+                };
+                let remaining = timed_permission
+                    .expires_at
+                    .duration_since(now)
+                    .unwrap_or_default()
+                    .as_secs()
+                    .max(1);
+
+                format!(
+                    "<@{}> ({permission_text}, {} left)",
+                    timed_permission.member_id,
+                    humanize(remaining)
+                )
+            })
+            .collect::<Vec<String>>()
+            .join(", ")
+    };
+    let status_text = voice_channel
+        .status
+        .read()
+        .clone()
+        .unwrap_or_else(|| "No status has been set.".to_owned());
+    let user_limit_text = match *voice_channel.user_limit.read() {
         None | Some(0) => "No limit has been set.".to_owned(),
         Some(1) => "1 user".to_owned(),
         Some(user_limit) => format!("{user_limit} users"),
     };
-    let video_quality_mode_text = match voice_channel.video_quality_mode.read().clone() {
+    let video_quality_mode_text = match *voice_channel.video_quality_mode.read() {
         VideoQualityMode::Full => "720p",
         _ => "Auto",
     };
@@ -144,14 +200,17 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
         Some("us-west") => "US West",
         _ => "Automatic",
     };
-    let description = vec![
+    let description = [
         format!("**Allow list:** {allow_list_text}"),
         format!("**Bitrate:** {bitrate_text}"),
         format!("**Deny list:** {deny_list_text}"),
+        format!("**Managers:** {managers_text}"),
         format!("**Owner:** {owner_text}"),
         format!("**Permanence:** {permanence_text}"),
         format!("**Privacy:** {privacy_text}"),
         format!("**Slow mode:** {slow_mode_text}"),
+        format!("**Status:** {status_text}"),
+        format!("**Timed permissions:** {timed_permissions_text}"),
         format!("**User limit:** {user_limit_text}"),
         format!("**Video quality mode:** {video_quality_mode_text}"),
         format!("**Voice region:** {voice_region_text}"),
@@ -2,15 +2,84 @@ use std::sync::Arc;
 
 use eyre::Result;
 use twilight_model::{
-    application::interaction::application_command::CommandOptionValue,
+    application::{
+        command::{CommandOptionChoice, CommandOptionChoiceValue},
+        interaction::application_command::CommandOptionValue,
+    },
     channel::message::MessageFlags,
     http::interaction::{InteractionResponse, InteractionResponseType},
 };
 use twilight_util::builder::{embed::EmbedBuilder, InteractionResponseDataBuilder};
 
-use crate::structs::{context::Context, interaction::ApplicationCommandInteraction};
+use crate::{
+    structs::{
+        cache::CachedVoiceChannelUpdate, context::Context,
+        interaction::ApplicationCommandInteraction,
+    },
+    utilities::{audit::audit, constants::VOICE_REGION_OPTIONS},
+};
 
 pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteraction) -> Result<()> {
+    let region_value = match interaction
+        .data
+        .options
+        .iter()
+        .find(|&option| option.name.eq("region"))
+        .cloned()
+        .map(|option| option.value)
+    {
+        Some(CommandOptionValue::Focused(value, _)) => {
+            let lowercased_value = value.to_ascii_lowercase();
+            let current_rtc_region = context
+                .cache
+                .managed_voice_channel(interaction.guild.id, interaction.user_id)
+                .and_then(|voice_channel_id| context.cache.voice_channel(*voice_channel_id))
+                .map(|voice_channel| {
+                    voice_channel
+                        .rtc_region
+                        .read()
+                        .clone()
+                        .unwrap_or_else(|| "automatic".to_owned())
+                });
+            let mut choices = VOICE_REGION_OPTIONS
+                .into_iter()
+                .filter(|(name, _)| name.to_ascii_lowercase().contains(&lowercased_value))
+                .map(|(name, value)| {
+                    let name = if current_rtc_region.as_deref().eq(&Some(value)) {
+                        format!("{name} (current)")
+                    } else {
+                        name.to_owned()
+                    };
+
+                    CommandOptionChoice {
+                        name,
+                        name_localizations: None,
+                        value: CommandOptionChoiceValue::String(value.to_owned()),
+                    }
+                })
+                .collect::<Vec<CommandOptionChoice>>();
+
+            // The current region leads, so it's clear what a change would replace.
+            choices.sort_by_key(|choice| !choice.name.ends_with(" (current)"));
+
+            let data = InteractionResponseDataBuilder::new()
+                .choices(choices)
+                .build();
+            let interaction_response = InteractionResponse {
+                data: Some(data),
+                kind: InteractionResponseType::ApplicationCommandAutocompleteResult,
+            };
+
+            context
+                .interaction_client()
+                .create_response(interaction.id, &interaction.token, &interaction_response)
+                .await?;
+
+            return Ok(());
+        }
+        Some(CommandOptionValue::String(value)) => value,
+        _ => return Ok(()),
+    };
     let interaction_response_data = InteractionResponseDataBuilder::new()
         .flags(MessageFlags::EPHEMERAL)
         .build();
@@ -24,12 +93,9 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
         .create_response(interaction.id, &interaction.token, &interaction_response)
         .await?;
 
-    let Some(CommandOptionValue::String(rtc_region)) = interaction
-        .data
-        .options
+    let Some((rtc_region_text, rtc_region)) = VOICE_REGION_OPTIONS
         .into_iter()
-        .find(|option| option.name.eq("region"))
-        .map(|option| option.value)
+        .find(|(_, value)| value.eq(&region_value))
     else {
         let embed = EmbedBuilder::new()
             .color(0xF8F8FF)
@@ -46,11 +112,11 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
     };
     let Some(voice_channel_id) = context
         .cache
-        .voice_channel_owner(interaction.guild.id, interaction.user_id)
+        .managed_voice_channel(interaction.guild.id, interaction.user_id)
     else {
         let embed = EmbedBuilder::new()
             .color(0xF8F8FF)
-            .description("You do not own a voice channel.")
+            .description("You do not own or manage a voice channel.")
             .build();
 
         context
@@ -75,23 +141,7 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
 
         return Ok(());
     };
-    let rtc_region_text = match rtc_region.as_str() {
-        "brazil" => "Brazil",
-        "hongkong" => "Hong Kong",
-        "india" => "India",
-        "japan" => "Japan",
-        "rotterdam" => "Rotterdam",
-        "russia" => "Russia",
-        "singapore" => "Singapore",
-        "southafrica" => "South Africa",
-        "sydney" => "Sydney",
-        "us-central" => "US Central",
-        "us-east" => "US East",
-        "us-south" => "US South",
-        "us-west" => "US West",
-        _ => "Automatic",
-    };
-    let formatted_rtc_region = rtc_region.ne("automatic").then_some(rtc_region);
+    let formatted_rtc_region = rtc_region.ne("automatic").then(|| rtc_region.to_owned());
 
     if voice_channel.rtc_region.read().eq(&formatted_rtc_region) {
         let embed = EmbedBuilder::new()
@@ -108,6 +158,12 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
         return Ok(());
     }
 
+    let saved_settings = context
+        .cache
+        .update_saved_settings(voice_channel.id, |saved_settings| {
+            saved_settings.rtc_region = formatted_rtc_region.clone();
+        });
+
     if context
         .client
         .update_channel(voice_channel.id)
@@ -115,6 +171,14 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
         .await
         .is_err()
     {
+        context.cache.update_voice_channel(
+            voice_channel.id,
+            CachedVoiceChannelUpdate {
+                saved_settings: Some(saved_settings),
+                ..Default::default()
+            },
+        );
+
         let embed = EmbedBuilder::new()
             .color(0xF8F8FF)
             .description("I'm unable to set the voice region right now. Try again in 10 minutes.")
@@ -129,6 +193,23 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
         return Ok(());
     };
 
+    context
+        .database
+        .update_voice_channel_rtc_region(voice_channel.id, formatted_rtc_region.as_deref())
+        .await?;
+    audit(
+        &context,
+        &interaction.guild,
+        Some(interaction.user_id),
+        Some(voice_channel.id),
+        "/voice voice-region",
+        format!(
+            "<@{}> set the voice region of <#{voice_channel_id}> to **{rtc_region_text}**.",
+            interaction.user_id
+        ),
+    )
+    .await?;
+
     let embed = EmbedBuilder::new()
         .color(0xF8F8FF)
         .description(format!(
@@ -144,3 +225,69 @@ pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteracti
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use twilight_model::id::Id;
+
+    use crate::{
+        structs::{cache::CachedVoiceChannelUpdate, database::ChannelPrivacy},
+        testing::{
+            self,
+            mock_discord::MockDiscord,
+            payloads::{command, subcommand},
+            replay::replay,
+            seed_join_channel, VOICE_CHANNEL_ID,
+        },
+    };
+
+    #[tokio::test]
+    #[ignore = "needs a database, run with `cargo test -- --ignored`"]
+    async fn suggests_the_current_region_first() {
+        let mock_discord = MockDiscord::start().await;
+        let (context, _guard) = testing::context(&mock_discord).await;
+        let guild_id = Id::new(1001);
+
+        seed_join_channel(&context, guild_id, Id::new(1101), ChannelPrivacy::Unlocked).await;
+        replay(
+            &context,
+            include_str!("../../../testing/recordings/create.jsonl"),
+        )
+        .await
+        .unwrap();
+        // Discord reports the change with a `CHANNEL_UPDATE`, which is what updates the cache.
+        context.cache.update_voice_channel(
+            VOICE_CHANNEL_ID,
+            CachedVoiceChannelUpdate {
+                rtc_region: Some(Some("japan".to_owned())),
+                ..Default::default()
+            },
+        );
+        replay(
+            &context,
+            &command(
+                "suggest",
+                1001,
+                2001,
+                subcommand(
+                    "voice",
+                    "voice-region",
+                    json!([{ "focused": true, "name": "region", "type": 3, "value": "" }]),
+                ),
+            ),
+        )
+        .await
+        .unwrap();
+
+        let choices = mock_discord.autocomplete_choices("suggest");
+
+        assert_eq!(
+            choices[0],
+            ("Japan (current)".to_owned(), "japan".to_owned())
+        );
+        assert_eq!(choices.len(), 14);
+
+        context.database.remove_guild(guild_id).await.unwrap();
+    }
+}
@@ -0,0 +1,61 @@
+use std::sync::Arc;
+
+use eyre::Result;
+use twilight_util::builder::embed::EmbedBuilder;
+
+use super::voice_channel;
+use crate::{
+    structs::{context::Context, interaction::ApplicationCommandInteraction},
+    tasks::ownership::release_ownership,
+    utilities::audit::audit,
+};
+
+pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteraction) -> Result<()> {
+    let Some(voice_channel) = voice_channel(&context, &interaction).await? else {
+        return Ok(());
+    };
+    let channel_id = voice_channel.id;
+    let Some(owner_id) = *voice_channel.owner_id.read() else {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description("No change has been made.")
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    };
+
+    release_ownership(&context, &voice_channel).await?;
+    audit(
+        &context,
+        &interaction.guild,
+        Some(interaction.user_id),
+        Some(channel_id),
+        "/voice-admin clear-owner",
+        format!(
+            "<@{}> took <#{channel_id}> away from <@{owner_id}>.",
+            interaction.user_id
+        ),
+    )
+    .await?;
+
+    let embed = EmbedBuilder::new()
+        .color(0xF8F8FF)
+        .description(format!(
+            "<#{channel_id}> no longer has an owner, so anyone in it may `/voice claim` it."
+        ))
+        .build();
+
+    context
+        .interaction_client()
+        .update_response(&interaction.token)
+        .embeds(Some(&[embed]))
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,68 @@
+use std::sync::Arc;
+
+use eyre::Result;
+use twilight_util::builder::embed::EmbedBuilder;
+
+use super::voice_channel;
+use crate::{
+    structs::{context::Context, interaction::ApplicationCommandInteraction},
+    utilities::audit::audit,
+};
+
+pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteraction) -> Result<()> {
+    let Some(voice_channel) = voice_channel(&context, &interaction).await? else {
+        return Ok(());
+    };
+    let name = voice_channel.name.read().clone();
+
+    context.cache.insert_deleted_channel(voice_channel.id);
+
+    if context
+        .client
+        .delete_channel(voice_channel.id)
+        .await
+        .is_err()
+    {
+        context.cache.remove_deleted_channel(voice_channel.id);
+
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description(
+                "I'm unable to delete this voice channel right now. Try again in 10 minutes.",
+            )
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    }
+
+    audit(
+        &context,
+        &interaction.guild,
+        Some(interaction.user_id),
+        Some(voice_channel.id),
+        "/voice-admin delete",
+        format!("<@{}> force-deleted **{name}**.", interaction.user_id),
+    )
+    .await?;
+
+    if voice_channel.id.ne(&interaction.channel.id) {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description(format!("I have deleted **{name}**."))
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+    }
+
+    Ok(())
+}
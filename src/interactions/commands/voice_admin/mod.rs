@@ -0,0 +1,341 @@
+mod clear_owner;
+mod delete;
+mod reset_name;
+mod transfer;
+mod unlock;
+
+use std::{mem::replace, str::FromStr, sync::Arc};
+
+use eyre::Result;
+use twilight_model::{
+    application::{
+        command::{CommandOptionChoice, CommandOptionChoiceValue},
+        interaction::application_command::{CommandDataOption, CommandOptionValue},
+    },
+    channel::message::MessageFlags,
+    http::interaction::{InteractionResponse, InteractionResponseType},
+    id::{marker::ChannelMarker, Id},
+};
+use twilight_util::builder::{embed::EmbedBuilder, InteractionResponseDataBuilder};
+
+use crate::structs::{
+    cache::CachedVoiceChannel, context::Context, interaction::ApplicationCommandInteraction,
+};
+
+pub async fn run(
+    context: Arc<Context>,
+    mut interaction: ApplicationCommandInteraction,
+) -> Result<()> {
+    let Some(CommandDataOption {
+        name,
+        value: CommandOptionValue::SubCommand(options),
+    }) = interaction.data.options.clone().into_iter().next()
+    else {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description("I could not find a subcommand.")
+            .build();
+        let interaction_response_data = InteractionResponseDataBuilder::new()
+            .embeds(vec![embed])
+            .flags(MessageFlags::EPHEMERAL)
+            .build();
+        let interaction_response = InteractionResponse {
+            data: Some(interaction_response_data),
+            kind: InteractionResponseType::ChannelMessageWithSource,
+        };
+
+        context
+            .interaction_client()
+            .create_response(interaction.id, &interaction.token, &interaction_response)
+            .await?;
+
+        return Ok(());
+    };
+    let _ = replace(&mut interaction.data.options, options);
+
+    match name.as_str() {
+        "clear-owner" => clear_owner::run(context, interaction).await?,
+        "delete" => delete::run(context, interaction).await?,
+        "reset-name" => reset_name::run(context, interaction).await?,
+        "transfer" => transfer::run(context, interaction).await?,
+        "unlock" => unlock::run(context, interaction).await?,
+        _ => {
+            let embed = EmbedBuilder::new()
+                .color(0xF8F8FF)
+                .description(format!(
+                    "I don't have a subcommand with the name \"{name}\"."
+                ))
+                .build();
+            let interaction_response_data = InteractionResponseDataBuilder::new()
+                .embeds(vec![embed])
+                .flags(MessageFlags::EPHEMERAL)
+                .build();
+            let interaction_response = InteractionResponse {
+                data: Some(interaction_response_data),
+                kind: InteractionResponseType::ChannelMessageWithSource,
+            };
+
+            context
+                .interaction_client()
+                .create_response(interaction.id, &interaction.token, &interaction_response)
+                .await?;
+
+            return Ok(());
+        }
+    }
+
+    Ok(())
+}
+
+/// Answers the autocomplete of the `channel` option, or defers the reply and finds the voice
+/// channel it names, whoever owns it. `None` means there's nothing left to do.
+async fn voice_channel(
+    context: &Context,
+    interaction: &ApplicationCommandInteraction,
+) -> Result<Option<Arc<CachedVoiceChannel>>> {
+    let channel_value = match interaction
+        .data
+        .options
+        .iter()
+        .find(|&option| option.name.eq("channel"))
+        .cloned()
+        .map(|option| option.value)
+    {
+        Some(CommandOptionValue::Focused(value, _)) => {
+            let lowercased_value = value.to_lowercase();
+            let mut filtered_voice_channels = interaction
+                .guild
+                .voice_channel_ids
+                .read()
+                .clone()
+                .into_iter()
+                .filter_map(|channel_id| {
+                    let voice_channel = context.cache.voice_channel(channel_id)?;
+                    let name = voice_channel.name.read().clone();
+
+                    if !name.to_lowercase().contains(&lowercased_value) {
+                        return None;
+                    }
+
+                    Some((name, voice_channel.id.to_string()))
+                })
+                .collect::<Vec<(String, String)>>();
+
+            filtered_voice_channels.sort();
+
+            let choices = filtered_voice_channels
+                .into_iter()
+                .take(25)
+                .map(|voice_channel| CommandOptionChoice {
+                    name: voice_channel.0,
+                    name_localizations: None,
+                    value: CommandOptionChoiceValue::String(voice_channel.1),
+                })
+                .collect::<Vec<CommandOptionChoice>>();
+            let data = InteractionResponseDataBuilder::new()
+                .choices(choices)
+                .build();
+            let interaction_response = InteractionResponse {
+                data: Some(data),
+                kind: InteractionResponseType::ApplicationCommandAutocompleteResult,
+            };
+
+            context
+                .interaction_client()
+                .create_response(interaction.id, &interaction.token, &interaction_response)
+                .await?;
+
+            return Ok(None);
+        }
+        Some(CommandOptionValue::String(value)) => value,
+        _ => return Ok(None),
+    };
+    let interaction_response_data = InteractionResponseDataBuilder::new()
+        .flags(MessageFlags::EPHEMERAL)
+        .build();
+    let interaction_response = InteractionResponse {
+        data: Some(interaction_response_data),
+        kind: InteractionResponseType::DeferredChannelMessageWithSource,
+    };
+
+    context
+        .interaction_client()
+        .create_response(interaction.id, &interaction.token, &interaction_response)
+        .await?;
+
+    let Some(voice_channel) = Id::<ChannelMarker>::from_str(&channel_value)
+        .ok()
+        .filter(|channel_id| {
+            interaction
+                .guild
+                .voice_channel_ids
+                .read()
+                .contains(channel_id)
+        })
+        .and_then(|channel_id| context.cache.voice_channel(channel_id))
+    else {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description("I do not recognize this voice channel.")
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(None);
+    };
+
+    Ok(Some(voice_channel))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+    use twilight_model::id::Id;
+
+    use crate::{
+        structs::{cache::CachedVoiceChannelUpdate, database::ChannelPrivacy},
+        testing::{
+            self,
+            mock_discord::MockDiscord,
+            payloads::{command, subcommand},
+            replay::replay,
+            seed_join_channel,
+        },
+    };
+
+    fn voice_admin_command(token: &str, subcommand_name: &str, options: Value) -> String {
+        command(
+            token,
+            1001,
+            1,
+            subcommand("voice-admin", subcommand_name, options),
+        )
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a database, run with `cargo test -- --ignored`"]
+    async fn moderates_voice_channels_the_moderator_does_not_own() {
+        let mock_discord = MockDiscord::start().await;
+        let (context, _guard) = testing::context(&mock_discord).await;
+        let guild_id = Id::new(1001);
+        let channel = json!({ "name": "channel", "type": 3, "value": "5000" });
+
+        seed_join_channel(&context, guild_id, Id::new(1101), ChannelPrivacy::Locked).await;
+        replay(
+            &context,
+            include_str!("../../../testing/recordings/create.jsonl"),
+        )
+        .await
+        .unwrap();
+
+        // Names are matched regardless of case beyond ASCII too.
+        let rename = |name: &str| {
+            context.cache.update_voice_channel(
+                Id::new(5000),
+                CachedVoiceChannelUpdate {
+                    name: Some(name.to_owned()),
+                    ..Default::default()
+                },
+            );
+        };
+
+        rename("Salon de Élodie");
+        replay(
+            &context,
+            &voice_admin_command(
+                "suggest-accented",
+                "delete",
+                json!([{ "focused": true, "name": "channel", "type": 3, "value": "ÉLODIE" }]),
+            ),
+        )
+        .await
+        .unwrap();
+        rename("alex's voice");
+        replay(
+            &context,
+            &[
+                voice_admin_command(
+                    "suggest",
+                    "delete",
+                    json!([{ "focused": true, "name": "channel", "type": 3, "value": "ALEX" }]),
+                ),
+                voice_admin_command("unlock", "unlock", json!([channel])),
+                voice_admin_command("clear-owner", "clear-owner", json!([channel])),
+                voice_admin_command("reset-name", "reset-name", json!([channel])),
+                voice_admin_command(
+                    "transfer",
+                    "transfer",
+                    json!([channel, { "name": "member", "type": 6, "value": "2002" }]),
+                ),
+                voice_admin_command("renamed", "reset-name", json!([channel])),
+                voice_admin_command("delete", "delete", json!([channel])),
+            ]
+            .join("\n"),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            mock_discord.autocomplete_choices("suggest-accented"),
+            [("Salon de Élodie".to_owned(), "5000".to_owned())]
+        );
+        assert_eq!(
+            mock_discord.autocomplete_choices("suggest"),
+            [("alex's voice".to_owned(), "5000".to_owned())]
+        );
+        assert_eq!(
+            mock_discord.response_descriptions("unlock"),
+            ["<#5000> is now **unlocked (and visible)**."]
+        );
+        assert_eq!(
+            mock_discord.response_descriptions("clear-owner"),
+            ["<#5000> no longer has an owner, so anyone in it may `/voice claim` it."]
+        );
+        assert_eq!(
+            mock_discord.response_descriptions("reset-name"),
+            ["<#5000> has no owner to name it after."]
+        );
+        assert_eq!(
+            mock_discord.response_descriptions("transfer"),
+            ["<@2002> is now the owner of <#5000>."]
+        );
+
+        // The voice channel had no owner, so the new one gets a fresh overwrite.
+        let requests = mock_discord.requests_to("PATCH", "/channels/5000");
+
+        assert!(requests.iter().any(|request| {
+            request.body["permission_overwrites"]
+                .as_array()
+                .is_some_and(|overwrites| {
+                    overwrites
+                        .iter()
+                        .any(|overwrite| overwrite["id"].eq("2002") && overwrite["allow"].eq("0"))
+                })
+        }));
+        assert_eq!(requests.last().unwrap().body["name"], "member's voice");
+        assert_eq!(
+            mock_discord.response_descriptions("renamed"),
+            ["I've reset the name for <#5000>."]
+        );
+        assert_eq!(
+            mock_discord.response_descriptions("delete"),
+            ["I have deleted **alex's voice**."]
+        );
+        mock_discord.assert_requested("DELETE", "/channels/5000");
+        assert_eq!(
+            context
+                .database
+                .guild_audit_events(guild_id, Some(Id::new(1)), None, 10, 0)
+                .await
+                .unwrap()
+                .1,
+            5
+        );
+
+        context.database.remove_guild(guild_id).await.unwrap();
+    }
+}
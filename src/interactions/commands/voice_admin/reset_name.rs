@@ -0,0 +1,157 @@
+use std::{sync::Arc, time::SystemTime};
+
+use eyre::Result;
+use twilight_util::builder::embed::EmbedBuilder;
+
+use super::voice_channel;
+use crate::{
+    interactions::commands::voice::name::rename_retry_text,
+    structs::{
+        cache::CachedVoiceChannelUpdate, context::Context,
+        interaction::ApplicationCommandInteraction,
+    },
+    utilities::{
+        audit::audit,
+        name_filter::name_violation,
+        template::{default_name, render_name},
+    },
+};
+
+/// Renames the voice channel to what it would have started with, ignoring the owner's preset.
+pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteraction) -> Result<()> {
+    let Some(voice_channel) = voice_channel(&context, &interaction).await? else {
+        return Ok(());
+    };
+    let channel_id = voice_channel.id;
+    let Some(owner_id) = *voice_channel.owner_id.read() else {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description(format!("<#{channel_id}> has no owner to name it after."))
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    };
+    let owner = context
+        .client
+        .guild_member(interaction.guild.id, owner_id)
+        .await?
+        .model()
+        .await?;
+    let locale = interaction.guild.locale.read().clone();
+    let name = match interaction.guild.name_template.read().as_deref() {
+        Some(name_template) => render_name(name_template, &owner.user.name),
+        None => default_name(&locale, &owner.user.name),
+    };
+    let name = if name_violation(&interaction.guild, &name).is_some() {
+        default_name(&locale, &owner.user.name)
+    } else {
+        name
+    };
+
+    if voice_channel.name.read().eq(&name) {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description("No change has been made.")
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    }
+
+    let now = SystemTime::now();
+
+    if let Some(retry_after) = context.cache.rename_retry_after(channel_id, now) {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description(format!(
+                "You can rename <#{channel_id}> again in **{}**.",
+                rename_retry_text(retry_after)
+            ))
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    }
+
+    let saved_settings = context
+        .cache
+        .update_saved_settings(channel_id, |saved_settings| {
+            saved_settings.name = name.clone();
+        });
+
+    if context
+        .client
+        .update_channel(channel_id)
+        .name(&name)
+        .await
+        .is_err()
+    {
+        context.cache.update_voice_channel(
+            channel_id,
+            CachedVoiceChannelUpdate {
+                saved_settings: Some(saved_settings),
+                ..Default::default()
+            },
+        );
+
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description("I'm unable to set the name right now. Try again in 10 minutes.")
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    };
+
+    context
+        .database
+        .update_voice_channel_name(channel_id, &name)
+        .await?;
+    context.cache.insert_rename_time(channel_id, now);
+    audit(
+        &context,
+        &interaction.guild,
+        Some(interaction.user_id),
+        Some(channel_id),
+        "/voice-admin reset-name",
+        format!(
+            "<@{}> reset the name of <#{channel_id}> to **{name}**.",
+            interaction.user_id
+        ),
+    )
+    .await?;
+
+    let embed = EmbedBuilder::new()
+        .color(0xF8F8FF)
+        .description(format!("I've reset the name for <#{channel_id}>."))
+        .build();
+
+    context
+        .interaction_client()
+        .update_response(&interaction.token)
+        .embeds(Some(&[embed]))
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,129 @@
+use std::sync::Arc;
+
+use eyre::Result;
+use twilight_model::application::interaction::application_command::CommandOptionValue;
+use twilight_util::builder::embed::EmbedBuilder;
+
+use super::voice_channel;
+use crate::{
+    structs::{context::Context, interaction::ApplicationCommandInteraction},
+    tasks::ownership::transfer_ownership,
+    utilities::audit::audit,
+};
+
+pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteraction) -> Result<()> {
+    let Some(voice_channel) = voice_channel(&context, &interaction).await? else {
+        return Ok(());
+    };
+    let channel_id = voice_channel.id;
+    let Some(CommandOptionValue::User(member_id)) = interaction
+        .data
+        .options
+        .iter()
+        .find(|option| option.name.eq("member"))
+        .map(|option| option.value.clone())
+    else {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description("I could not find a valid **member** value.")
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    };
+
+    if context.application_id.eq(&member_id.cast())
+        || voice_channel
+            .owner_id
+            .read()
+            .is_some_and(|owner_id| owner_id.eq(&member_id))
+    {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description(format!(
+                "You may not transfer <#{channel_id}> to <@{member_id}>."
+            ))
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    }
+
+    if !context
+        .cache
+        .can_own_voice_channel(interaction.guild.id, member_id)
+    {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description(format!(
+                "<@{member_id}> already owns as many voice channels as this server allows."
+            ))
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    }
+
+    if transfer_ownership(&context, &voice_channel, member_id)
+        .await
+        .is_err()
+    {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description(
+                "I'm unable to transfer this voice channel right now. Try again in 10 minutes.",
+            )
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    };
+
+    audit(
+        &context,
+        &interaction.guild,
+        Some(interaction.user_id),
+        Some(channel_id),
+        "/voice-admin transfer",
+        format!(
+            "<@{}> force-transferred <#{channel_id}> to <@{member_id}>.",
+            interaction.user_id
+        ),
+    )
+    .await?;
+
+    let embed = EmbedBuilder::new()
+        .color(0xF8F8FF)
+        .description(format!(
+            "<@{member_id}> is now the owner of <#{channel_id}>."
+        ))
+        .build();
+
+    context
+        .interaction_client()
+        .update_response(&interaction.token)
+        .embeds(Some(&[embed]))
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,83 @@
+use std::sync::Arc;
+
+use eyre::Result;
+use twilight_util::builder::embed::EmbedBuilder;
+
+use super::voice_channel;
+use crate::{
+    interactions::commands::voice::privacy::set_privacy,
+    structs::{
+        context::Context, database::ChannelPrivacy, interaction::ApplicationCommandInteraction,
+    },
+    utilities::audit::audit,
+};
+
+pub async fn run(context: Arc<Context>, interaction: ApplicationCommandInteraction) -> Result<()> {
+    let Some(voice_channel) = voice_channel(&context, &interaction).await? else {
+        return Ok(());
+    };
+    let channel_id = voice_channel.id;
+
+    if voice_channel.privacy.read().eq(&ChannelPrivacy::Unlocked) {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description("No change has been made.")
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    }
+    if !set_privacy(
+        &context,
+        &interaction.guild,
+        &voice_channel,
+        ChannelPrivacy::Unlocked,
+    )
+    .await?
+    {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description(
+                "I'm unable to unlock this voice channel right now. Try again in 10 minutes.",
+            )
+            .build();
+
+        context
+            .interaction_client()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        return Ok(());
+    }
+
+    audit(
+        &context,
+        &interaction.guild,
+        Some(interaction.user_id),
+        Some(channel_id),
+        "/voice-admin unlock",
+        format!("<@{}> force-unlocked <#{channel_id}>.", interaction.user_id),
+    )
+    .await?;
+
+    let embed = EmbedBuilder::new()
+        .color(0xF8F8FF)
+        .description(format!(
+            "<#{channel_id}> is now **unlocked (and visible)**."
+        ))
+        .build();
+
+    context
+        .interaction_client()
+        .update_response(&interaction.token)
+        .embeds(Some(&[embed]))
+        .await?;
+
+    Ok(())
+}
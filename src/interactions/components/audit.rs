@@ -0,0 +1,167 @@
+use std::{sync::Arc, time::UNIX_EPOCH};
+
+use eyre::Result;
+use twilight_model::{
+    channel::message::{
+        component::{ActionRow, Button, ButtonStyle, Component},
+        Embed,
+    },
+    http::interaction::{InteractionResponse, InteractionResponseType},
+    id::{
+        marker::{ChannelMarker, GuildMarker, UserMarker},
+        Id,
+    },
+};
+use twilight_util::builder::{
+    embed::{EmbedBuilder, EmbedFooterBuilder},
+    InteractionResponseDataBuilder,
+};
+
+use crate::{
+    structs::{context::Context, interaction::MessageComponentInteraction},
+    utilities::constants::{AUDIT_PAGE_SIZE, EMBED_DESCRIPTION_LIMIT},
+};
+
+/// Answers the Previous or Next button of a `/join audit`, whose custom id is
+/// "audit:<page>:<user id or 0>:<channel id or 0>".
+pub async fn run(context: Arc<Context>, interaction: MessageComponentInteraction) -> Result<()> {
+    let mut custom_id = interaction.data.custom_id.split(':').skip(1);
+    let page = custom_id
+        .next()
+        .and_then(|page| page.parse::<i64>().ok())
+        .unwrap_or(1);
+    let user_id = custom_id
+        .next()
+        .and_then(|user_id| user_id.parse::<u64>().ok())
+        .and_then(Id::new_checked);
+    let channel_id = custom_id
+        .next()
+        .and_then(|channel_id| channel_id.parse::<u64>().ok())
+        .and_then(Id::new_checked);
+    let (embed, components) =
+        audit_page(&context, interaction.guild.id, user_id, channel_id, page).await?;
+    let interaction_response_data = InteractionResponseDataBuilder::new()
+        .components(components)
+        .embeds(vec![embed])
+        .build();
+    let interaction_response = InteractionResponse {
+        data: Some(interaction_response_data),
+        kind: InteractionResponseType::UpdateMessage,
+    };
+
+    context
+        .interaction_client()
+        .create_response(interaction.id, &interaction.token, &interaction_response)
+        .await?;
+
+    Ok(())
+}
+
+/// One page of the server's audit events, newest first, with buttons for the neighbouring pages.
+/// Pages past the last one show the last one.
+pub async fn audit_page(
+    context: &Context,
+    guild_id: Id<GuildMarker>,
+    user_id: Option<Id<UserMarker>>,
+    channel_id: Option<Id<ChannelMarker>>,
+    page: i64,
+) -> Result<(Embed, Vec<Component>)> {
+    let page = page.max(1);
+    let (mut audit_events, total) = context
+        .database
+        .guild_audit_events(
+            guild_id,
+            user_id,
+            channel_id,
+            AUDIT_PAGE_SIZE,
+            (page - 1) * AUDIT_PAGE_SIZE,
+        )
+        .await?;
+    let page_count = ((total + AUDIT_PAGE_SIZE - 1) / AUDIT_PAGE_SIZE).max(1);
+    let page = page.min(page_count);
+
+    if audit_events.is_empty() && total.gt(&0) {
+        (audit_events, _) = context
+            .database
+            .guild_audit_events(
+                guild_id,
+                user_id,
+                channel_id,
+                AUDIT_PAGE_SIZE,
+                (page - 1) * AUDIT_PAGE_SIZE,
+            )
+            .await?;
+    }
+
+    if audit_events.is_empty() {
+        let embed = EmbedBuilder::new()
+            .color(0xF8F8FF)
+            .description("No audit events have been recorded.")
+            .build();
+
+        return Ok((embed, Vec::new()));
+    }
+
+    // Descriptions can quote statuses, names and lists of members, so each line is cut short
+    // enough for a full page to fit in one embed.
+    let line_limit = (EMBED_DESCRIPTION_LIMIT + 1) / AUDIT_PAGE_SIZE as usize - 1;
+    let description = audit_events
+        .into_iter()
+        .map(|audit_event| {
+            let created_at = audit_event
+                .created_at
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs();
+            let line = format!(
+                "<t:{created_at}:f> `{}` {}",
+                audit_event.action, audit_event.description
+            );
+
+            if line.chars().count().gt(&line_limit) {
+                line.chars()
+                    .take(line_limit - 1)
+                    .chain(['…'])
+                    .collect::<String>()
+            } else {
+                line
+            }
+        })
+        .collect::<Vec<String>>()
+        .join("\n");
+    let embed = EmbedBuilder::new()
+        .color(0xF8F8FF)
+        .description(description)
+        .footer(EmbedFooterBuilder::new(format!(
+            "Page {page} of {page_count}"
+        )))
+        .title("Audit log")
+        .build();
+    let filter = format!(
+        "{}:{}",
+        user_id.map_or(0, |user_id| user_id.get()),
+        channel_id.map_or(0, |channel_id| channel_id.get())
+    );
+    let components = vec![Component::ActionRow(ActionRow {
+        components: vec![
+            Component::Button(Button {
+                custom_id: Some(format!("audit:{}:{filter}", page - 1)),
+                disabled: page.eq(&1),
+                emoji: None,
+                label: Some("Previous".to_owned()),
+                style: ButtonStyle::Secondary,
+                url: None,
+            }),
+            Component::Button(Button {
+                custom_id: Some(format!("audit:{}:{filter}", page + 1)),
+                disabled: page.eq(&page_count),
+                emoji: None,
+                label: Some("Next".to_owned()),
+                style: ButtonStyle::Secondary,
+                url: None,
+            }),
+        ],
+    })];
+
+    Ok((embed, components))
+}
@@ -0,0 +1,527 @@
+use std::{
+    str::FromStr,
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+use eyre::Result;
+use twilight_model::{
+    channel::{
+        message::{
+            component::{ActionRow, Button, ButtonStyle, Component},
+            AllowedMentions, MessageFlags,
+        },
+        permission_overwrite::PermissionOverwriteType as ChannelPermissionOverwriteType,
+    },
+    guild::Permissions,
+    http::{
+        interaction::{InteractionResponse, InteractionResponseType},
+        permission_overwrite::{
+            PermissionOverwrite as HttpPermissionOverwrite,
+            PermissionOverwriteType as HttpPermissionOverwriteType,
+        },
+    },
+    id::{marker::UserMarker, Id},
+};
+use twilight_util::builder::{embed::EmbedBuilder, InteractionResponseDataBuilder};
+
+use crate::{
+    structs::{
+        cache::CachedVoiceChannel, context::Context, interaction::MessageComponentInteraction,
+    },
+    utilities::{
+        audit::audit,
+        constants::{KNOCK_EXPIRY, KNOCK_RATE_LIMIT},
+        permission_overwrites::privacy_permissions,
+        time::humanize,
+    },
+};
+
+/// Answers the Accept or Decline button of a `/voice knock`, whose custom id is
+/// "knock:<accept|decline>:<user id>".
+pub async fn run(context: Arc<Context>, interaction: MessageComponentInteraction) -> Result<()> {
+    let mut custom_id = interaction.data.custom_id.split(':').skip(1);
+    let (Some(action), Some(user_id)) = (
+        custom_id.next().map(ToOwned::to_owned),
+        custom_id
+            .next()
+            .and_then(|user_id| Id::<UserMarker>::from_str(user_id).ok()),
+    ) else {
+        return respond(&context, &interaction, "I could not read this knock.").await;
+    };
+    let Some(voice_channel) = context.cache.voice_channel(interaction.channel.id) else {
+        return update_message(
+            &context,
+            &interaction,
+            format!("The voice channel <@{user_id}> knocked on no longer exists."),
+        )
+        .await;
+    };
+    let voice_channel_id = voice_channel.id;
+
+    if !voice_channel
+        .owner_id
+        .read()
+        .is_some_and(|owner_id| owner_id.eq(&interaction.user_id))
+        && !voice_channel
+            .manager_ids
+            .read()
+            .contains(&interaction.user_id)
+    {
+        return respond(
+            &context,
+            &interaction,
+            &format!("Only the owner or a manager of <#{voice_channel_id}> may answer this knock."),
+        )
+        .await;
+    }
+
+    let Some(knocked_at) = context.cache.remove_knock(voice_channel.id, user_id) else {
+        return update_message(
+            &context,
+            &interaction,
+            format!("The knock from <@{user_id}> has already been answered."),
+        )
+        .await;
+    };
+
+    if SystemTime::now()
+        .duration_since(knocked_at)
+        .unwrap_or_default()
+        .ge(&KNOCK_EXPIRY)
+    {
+        return update_message(
+            &context,
+            &interaction,
+            format!("The knock from <@{user_id}> has expired."),
+        )
+        .await;
+    }
+    if action.ne("accept") {
+        // Declining a member in the waiting room sends them on their way.
+        if context
+            .cache
+            .voice_state(interaction.guild.id, user_id)
+            .is_some_and(|channel_id| voice_channel.waiting_room_id.read().eq(&Some(*channel_id)))
+        {
+            _ = context
+                .client
+                .update_guild_member(interaction.guild.id, user_id)
+                .channel_id(None)
+                .await;
+        }
+
+        return update_message(
+            &context,
+            &interaction,
+            format!(
+                "<@{}> declined the knock from <@{user_id}>.",
+                interaction.user_id
+            ),
+        )
+        .await;
+    }
+
+    let (mut member_allow, mut member_deny) = voice_channel
+        .permission_overwrites
+        .read()
+        .clone()
+        .into_iter()
+        .find(|permission_overwrite| {
+            permission_overwrite
+                .kind
+                .eq(&ChannelPermissionOverwriteType::Member)
+                && permission_overwrite.id.eq(&user_id.cast())
+        })
+        .map_or(
+            (Permissions::empty(), Permissions::empty()),
+            |permission_overwrite| (permission_overwrite.allow, permission_overwrite.deny),
+        );
+    let permissions = privacy_permissions(voice_channel.kind, &voice_channel.privacy.read());
+
+    member_allow.remove(Permissions::CONNECT | Permissions::VIEW_CHANNEL);
+    member_deny.remove(Permissions::CONNECT | Permissions::VIEW_CHANNEL);
+    member_allow = member_allow.union(permissions);
+
+    if context
+        .client
+        .update_channel_permission(
+            voice_channel.id,
+            &HttpPermissionOverwrite {
+                allow: Some(member_allow),
+                deny: Some(member_deny),
+                id: user_id.cast(),
+                kind: HttpPermissionOverwriteType::Member,
+            },
+        )
+        .await
+        .is_err()
+    {
+        context
+            .cache
+            .insert_knock(voice_channel.id, user_id, knocked_at);
+
+        return respond(
+            &context,
+            &interaction,
+            &format!("I'm unable to let <@{user_id}> in right now. Try again in 10 minutes."),
+        )
+        .await;
+    }
+
+    // Members waiting in another voice channel are moved in. The rest may now join themselves.
+    if context
+        .cache
+        .voice_state(interaction.guild.id, user_id)
+        .is_some_and(|channel_id| (*channel_id).ne(&voice_channel.id))
+    {
+        _ = context
+            .client
+            .update_guild_member(interaction.guild.id, user_id)
+            .channel_id(Some(voice_channel.id))
+            .await;
+    }
+
+    audit(
+        &context,
+        &interaction.guild,
+        Some(interaction.user_id),
+        Some(voice_channel.id),
+        "knock accepted",
+        format!(
+            "<@{}> let <@{user_id}> into <#{voice_channel_id}>.",
+            interaction.user_id
+        ),
+    )
+    .await?;
+
+    update_message(
+        &context,
+        &interaction,
+        format!(
+            "<@{}> let <@{user_id}> into <#{voice_channel_id}>.",
+            interaction.user_id
+        ),
+    )
+    .await
+}
+
+/// Whether the member may join the voice channel without knocking.
+pub fn is_allowed(voice_channel: &CachedVoiceChannel, user_id: Id<UserMarker>) -> bool {
+    let privacy_permissions =
+        privacy_permissions(voice_channel.kind, &voice_channel.privacy.read());
+
+    privacy_permissions.is_empty()
+        || voice_channel.owner_id.read().eq(&Some(user_id))
+        || voice_channel.manager_ids.read().contains(&user_id)
+        || voice_channel
+            .permission_overwrites
+            .read()
+            .iter()
+            .any(|permission_overwrite| {
+                permission_overwrite
+                    .kind
+                    .eq(&ChannelPermissionOverwriteType::Member)
+                    && permission_overwrite.id.eq(&user_id.cast())
+                    && permission_overwrite.allow.contains(privacy_permissions)
+            })
+}
+
+/// How long the member must wait before knocking on the voice channel again, if at all.
+pub fn knock_retry_after(
+    voice_channel: &CachedVoiceChannel,
+    user_id: Id<UserMarker>,
+) -> Option<Duration> {
+    let knocked_at = voice_channel.last_knocks.read().get(&user_id).copied()?;
+    let elapsed = SystemTime::now()
+        .duration_since(knocked_at)
+        .unwrap_or_default();
+
+    KNOCK_RATE_LIMIT.checked_sub(elapsed)
+}
+
+/// Posts a knock from `user_id`, with Accept and Decline buttons, in the voice channel's text
+/// chat, where its owner and managers can answer it.
+pub async fn post_knock(
+    context: &Context,
+    voice_channel: &CachedVoiceChannel,
+    user_id: Id<UserMarker>,
+) -> Result<()> {
+    let voice_channel_id = voice_channel.id;
+    let embed = EmbedBuilder::new()
+        .color(0xF8F8FF)
+        .description(format!(
+            "<@{user_id}> is knocking on <#{voice_channel_id}>. This knock expires in {}.",
+            humanize(KNOCK_EXPIRY.as_secs())
+        ))
+        .build();
+    let embeds = [embed];
+    let components = [Component::ActionRow(ActionRow {
+        components: vec![
+            Component::Button(Button {
+                custom_id: Some(format!("knock:accept:{user_id}")),
+                disabled: false,
+                emoji: None,
+                label: Some("Accept".to_owned()),
+                style: ButtonStyle::Success,
+                url: None,
+            }),
+            Component::Button(Button {
+                custom_id: Some(format!("knock:decline:{user_id}")),
+                disabled: false,
+                emoji: None,
+                label: Some("Decline".to_owned()),
+                style: ButtonStyle::Danger,
+                url: None,
+            }),
+        ],
+    })];
+    let owner_id = *voice_channel.owner_id.read();
+    let allowed_mentions = AllowedMentions {
+        users: owner_id.into_iter().collect(),
+        ..Default::default()
+    };
+    let content = owner_id.map_or_else(String::new, |owner_id| format!("<@{owner_id}>"));
+
+    let mut message_request = context
+        .client
+        .create_message(voice_channel.id)
+        .allowed_mentions(Some(&allowed_mentions))
+        .components(&components)
+        .embeds(&embeds);
+
+    // An unowned voice channel has nobody to mention, but its managers may still answer.
+    if !content.is_empty() {
+        message_request = message_request.content(&content);
+    }
+
+    message_request.await?;
+    context
+        .cache
+        .insert_knock(voice_channel.id, user_id, SystemTime::now());
+
+    Ok(())
+}
+
+/// Replies to whoever pressed the button, leaving the knock as it was.
+async fn respond(
+    context: &Context,
+    interaction: &MessageComponentInteraction,
+    description: &str,
+) -> Result<()> {
+    let embed = EmbedBuilder::new()
+        .color(0xF8F8FF)
+        .description(description)
+        .build();
+    let interaction_response_data = InteractionResponseDataBuilder::new()
+        .embeds(vec![embed])
+        .flags(MessageFlags::EPHEMERAL)
+        .build();
+    let interaction_response = InteractionResponse {
+        data: Some(interaction_response_data),
+        kind: InteractionResponseType::ChannelMessageWithSource,
+    };
+
+    context
+        .interaction_client()
+        .create_response(interaction.id, &interaction.token, &interaction_response)
+        .await?;
+
+    Ok(())
+}
+
+/// Replaces the knock with its outcome and removes its buttons.
+async fn update_message(
+    context: &Context,
+    interaction: &MessageComponentInteraction,
+    description: String,
+) -> Result<()> {
+    let embed = EmbedBuilder::new()
+        .color(0xF8F8FF)
+        .description(description)
+        .build();
+    let interaction_response_data = InteractionResponseDataBuilder::new()
+        .components(Vec::new())
+        .embeds(vec![embed])
+        .build();
+    let interaction_response = InteractionResponse {
+        data: Some(interaction_response_data),
+        kind: InteractionResponseType::UpdateMessage,
+    };
+
+    context
+        .interaction_client()
+        .create_response(interaction.id, &interaction.token, &interaction_response)
+        .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, SystemTime};
+
+    use serde_json::json;
+    use twilight_model::id::Id;
+
+    use crate::{
+        structs::database::ChannelPrivacy,
+        testing::{
+            self,
+            mock_discord::MockDiscord,
+            payloads::{command, component, subcommand, voice_state_update},
+            replay::replay,
+            seed_join_channel, VOICE_CHANNEL_ID,
+        },
+    };
+
+    fn knock_command(token: &str, user_id: u64) -> String {
+        command(
+            token,
+            1001,
+            user_id,
+            subcommand(
+                "voice",
+                "knock",
+                json!([{ "name": "owner", "type": 6, "value": "2001" }]),
+            ),
+        )
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a database, run with `cargo test -- --ignored`"]
+    async fn accepting_lets_the_member_in() {
+        let mock_discord = MockDiscord::start().await;
+        let (context, _guard) = testing::context(&mock_discord).await;
+        let guild_id = Id::new(1001);
+
+        seed_join_channel(&context, guild_id, Id::new(1101), ChannelPrivacy::Locked).await;
+        replay(
+            &context,
+            include_str!("../../testing/recordings/create.jsonl"),
+        )
+        .await
+        .unwrap();
+        replay(
+            &context,
+            &[
+                voice_state_update(1001, 2002, "sam", Some(1200)),
+                knock_command("knock", 2002),
+                knock_command("knock-again", 2002),
+                component("stranger", 1001, 2003, 5000, "knock:accept:2002"),
+                component("accept", 1001, 2001, 5000, "knock:accept:2002"),
+                component("accept-again", 1001, 2001, 5000, "knock:accept:2002"),
+            ]
+            .join("\n"),
+        )
+        .await
+        .unwrap();
+
+        let knock = mock_discord.assert_requested("POST", "/channels/5000/messages");
+
+        assert_eq!(knock.body["content"], "<@2001>");
+        assert_eq!(
+            knock.body["components"][0]["components"][0]["custom_id"],
+            "knock:accept:2002"
+        );
+        assert!(mock_discord.response_descriptions("knock-again")[0]
+            .starts_with("You knocked on <#5000> recently."));
+        assert_eq!(
+            mock_discord.response_descriptions("stranger"),
+            ["Only the owner or a manager of <#5000> may answer this knock."]
+        );
+        assert_eq!(
+            mock_discord.response_descriptions("accept"),
+            ["<@2001> let <@2002> into <#5000>."]
+        );
+        assert_eq!(
+            mock_discord
+                .assert_requested("PUT", "/channels/5000/permissions/2002")
+                .body["allow"],
+            "1048576"
+        );
+        assert_eq!(
+            mock_discord
+                .assert_requested("PATCH", "/guilds/1001/members/2002")
+                .body["channel_id"],
+            "5000"
+        );
+        assert_eq!(
+            mock_discord.response_descriptions("accept-again"),
+            ["The knock from <@2002> has already been answered."]
+        );
+
+        context.database.remove_guild(guild_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a database, run with `cargo test -- --ignored`"]
+    async fn answered_knocks_keep_their_rate_limit() {
+        let mock_discord = MockDiscord::start().await;
+        let (context, _guard) = testing::context(&mock_discord).await;
+        let guild_id = Id::new(1001);
+
+        seed_join_channel(&context, guild_id, Id::new(1101), ChannelPrivacy::Locked).await;
+        replay(
+            &context,
+            include_str!("../../testing/recordings/create.jsonl"),
+        )
+        .await
+        .unwrap();
+        replay(
+            &context,
+            &[
+                knock_command("knock", 2002),
+                component("decline", 1001, 2001, 5000, "knock:decline:2002"),
+                knock_command("knock-again", 2002),
+            ]
+            .join("\n"),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            mock_discord.response_descriptions("decline"),
+            ["<@2001> declined the knock from <@2002>."]
+        );
+        assert!(mock_discord.response_descriptions("knock-again")[0]
+            .starts_with("You knocked on <#5000> recently."));
+
+        context.database.remove_guild(guild_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a database, run with `cargo test -- --ignored`"]
+    async fn knocks_expire() {
+        let mock_discord = MockDiscord::start().await;
+        let (context, _guard) = testing::context(&mock_discord).await;
+        let guild_id = Id::new(1001);
+
+        seed_join_channel(&context, guild_id, Id::new(1101), ChannelPrivacy::Locked).await;
+        replay(
+            &context,
+            include_str!("../../testing/recordings/create.jsonl"),
+        )
+        .await
+        .unwrap();
+        context.cache.insert_knock(
+            VOICE_CHANNEL_ID,
+            Id::new(2002),
+            SystemTime::now() - Duration::from_secs(3_600),
+        );
+        replay(
+            &context,
+            &component("decline", 1001, 2001, 5000, "knock:decline:2002"),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            mock_discord.response_descriptions("decline"),
+            ["The knock from <@2002> has expired."]
+        );
+        mock_discord.assert_not_requested("PUT", "/channels/5000/permissions/2002");
+
+        context.database.remove_guild(guild_id).await.unwrap();
+    }
+}
@@ -0,0 +1,385 @@
+use std::{str::FromStr, sync::Arc};
+
+use eyre::Result;
+use twilight_model::{
+    channel::permission_overwrite::PermissionOverwriteType as ChannelPermissionOverwriteType,
+    guild::Permissions,
+    http::{
+        interaction::{InteractionResponse, InteractionResponseType},
+        permission_overwrite::{
+            PermissionOverwrite as HttpPermissionOverwrite,
+            PermissionOverwriteType as HttpPermissionOverwriteType,
+        },
+    },
+    id::{marker::UserMarker, Id},
+};
+use twilight_util::builder::embed::EmbedBuilder;
+
+use crate::{
+    structs::{
+        cache::CachedVoiceChannel, context::Context, interaction::MessageComponentInteraction,
+    },
+    utilities::{
+        audit::audit,
+        bulk::{mentions, run_bulk, BulkOutcome},
+        permission_overwrites::privacy_permissions,
+    },
+};
+
+/// Answers the select menu of a `/voice members` subcommand, whose custom id is
+/// "members:<allow|deny|remove>", by applying the action to every member picked.
+pub async fn run(context: Arc<Context>, interaction: MessageComponentInteraction) -> Result<()> {
+    let interaction_response = InteractionResponse {
+        data: None,
+        kind: InteractionResponseType::DeferredUpdateMessage,
+    };
+
+    context
+        .interaction_client()
+        .create_response(interaction.id, &interaction.token, &interaction_response)
+        .await?;
+
+    let action = interaction
+        .data
+        .custom_id
+        .split(':')
+        .nth(1)
+        .unwrap_or_default()
+        .to_owned();
+    let Some(voice_channel) = context
+        .cache
+        .managed_voice_channel(interaction.guild.id, interaction.user_id)
+        .and_then(|voice_channel_id| context.cache.voice_channel(*voice_channel_id))
+    else {
+        return update_response(
+            &context,
+            &interaction,
+            "You do not own or manage a voice channel.",
+        )
+        .await;
+    };
+    let voice_channel_id = voice_channel.id;
+    let member_ids = interaction
+        .data
+        .values
+        .iter()
+        .filter_map(|value| Id::<UserMarker>::from_str(value).ok())
+        .collect::<Vec<Id<UserMarker>>>();
+    let (exempt_member_ids, member_ids): (Vec<Id<UserMarker>>, Vec<Id<UserMarker>>) =
+        member_ids.into_iter().partition(|&member_id| {
+            context.application_id.eq(&member_id.cast())
+                || voice_channel.owner_id.read().eq(&Some(member_id))
+                || (action.ne("allow") && voice_channel.manager_ids.read().contains(&member_id))
+        });
+    let (outcome, done, preposition) = match action.as_str() {
+        "allow" => (
+            run_bulk(member_ids, |member_id| {
+                allow(&context, &voice_channel, member_id)
+            })
+            .await,
+            "allowed",
+            "into",
+        ),
+        "deny" => (
+            run_bulk(member_ids, |member_id| {
+                deny(&context, &voice_channel, member_id)
+            })
+            .await,
+            "denied",
+            "from",
+        ),
+        "remove" => (
+            run_bulk(member_ids, |member_id| {
+                remove(&context, &voice_channel, member_id)
+            })
+            .await,
+            "removed",
+            "from",
+        ),
+        _ => {
+            return update_response(
+                &context,
+                &interaction,
+                "I don't recognize this interaction.",
+            )
+            .await
+        }
+    };
+    if !outcome.changed.is_empty() {
+        audit(
+            &context,
+            &interaction.guild,
+            Some(interaction.user_id),
+            Some(voice_channel.id),
+            &format!("/voice members {action}"),
+            format!(
+                "<@{}> {done} {} {preposition} <#{voice_channel_id}>.",
+                interaction.user_id,
+                mentions(&outcome.changed)
+            ),
+        )
+        .await?;
+    }
+
+    let mut description = outcome.describe(
+        |mentions| format!("I've {done} {mentions} {preposition} <#{voice_channel_id}>."),
+        |mentions| format!("I'm unable to {action} {mentions} right now. Try again in 10 minutes."),
+    );
+
+    // Owners, managers and I are left out, as with the single-member subcommands.
+    if !exempt_member_ids.is_empty() {
+        let exempt_outcome = BulkOutcome {
+            changed: exempt_member_ids,
+            failed: Vec::new(),
+        };
+        let exempt_text = exempt_outcome.describe(
+            |mentions| format!("{mentions} may not be {done} {preposition} <#{voice_channel_id}>."),
+            |_| String::new(),
+        );
+
+        description = if outcome.changed.is_empty() && outcome.failed.is_empty() {
+            exempt_text
+        } else {
+            format!("{description}\n{exempt_text}")
+        };
+    }
+
+    update_response(&context, &interaction, &description).await
+}
+
+/// Allows the member into the voice channel, as `/voice allow-member` does.
+async fn allow(
+    context: &Context,
+    voice_channel: &CachedVoiceChannel,
+    member_id: Id<UserMarker>,
+) -> Result<bool> {
+    let (mut member_allow, mut member_deny) = member_permissions(voice_channel, member_id);
+    let permissions = privacy_permissions(voice_channel.kind, &voice_channel.privacy.read());
+    let had_timed_permission = context
+        .database
+        .remove_timed_permission(voice_channel.id, member_id)
+        .await?;
+
+    if member_allow.contains(permissions) {
+        return Ok(had_timed_permission);
+    }
+
+    member_allow.remove(Permissions::CONNECT | Permissions::VIEW_CHANNEL);
+    member_deny.remove(Permissions::CONNECT | Permissions::VIEW_CHANNEL);
+    member_allow = member_allow.union(permissions);
+
+    context
+        .client
+        .update_channel_permission(
+            voice_channel.id,
+            &HttpPermissionOverwrite {
+                allow: Some(member_allow),
+                deny: Some(member_deny),
+                id: member_id.cast(),
+                kind: HttpPermissionOverwriteType::Member,
+            },
+        )
+        .await?;
+
+    Ok(true)
+}
+
+/// Denies the member from the voice channel, as `/voice deny-member` does.
+async fn deny(
+    context: &Context,
+    voice_channel: &CachedVoiceChannel,
+    member_id: Id<UserMarker>,
+) -> Result<bool> {
+    let (mut member_allow, mut member_deny) = member_permissions(voice_channel, member_id);
+    let had_timed_permission = context
+        .database
+        .remove_timed_permission(voice_channel.id, member_id)
+        .await?;
+
+    if member_deny.contains(Permissions::VIEW_CHANNEL) {
+        return Ok(had_timed_permission);
+    }
+
+    member_allow.remove(Permissions::CONNECT);
+    member_deny.remove(Permissions::CONNECT);
+    member_deny = member_deny.union(Permissions::VIEW_CHANNEL);
+
+    context
+        .client
+        .update_channel_permission(
+            voice_channel.id,
+            &HttpPermissionOverwrite {
+                allow: Some(member_allow),
+                deny: Some(member_deny),
+                id: member_id.cast(),
+                kind: HttpPermissionOverwriteType::Member,
+            },
+        )
+        .await?;
+
+    Ok(true)
+}
+
+/// Removes the member's permission to join the voice channel and disconnects them, as
+/// `/voice remove-member` does.
+async fn remove(
+    context: &Context,
+    voice_channel: &CachedVoiceChannel,
+    member_id: Id<UserMarker>,
+) -> Result<bool> {
+    let mut has_changed = context
+        .database
+        .remove_timed_permission(voice_channel.id, member_id)
+        .await?;
+    let does_user_permission_exist =
+        voice_channel
+            .permission_overwrites
+            .read()
+            .iter()
+            .any(|permission_overwrite| {
+                permission_overwrite
+                    .kind
+                    .eq(&ChannelPermissionOverwriteType::Member)
+                    && permission_overwrite.id.eq(&member_id.cast())
+            });
+
+    if does_user_permission_exist {
+        context
+            .client
+            .delete_channel_permission(voice_channel.id)
+            .member(member_id)
+            .await?;
+
+        has_changed = true;
+    }
+    if context
+        .cache
+        .voice_state(voice_channel.guild_id, member_id)
+        .is_some_and(|channel_id| voice_channel.id.eq(&*channel_id))
+    {
+        context
+            .client
+            .update_guild_member(voice_channel.guild_id, member_id)
+            .channel_id(None)
+            .await?;
+
+        has_changed = true;
+    }
+
+    Ok(has_changed)
+}
+
+/// The member's current overwrite on the voice channel, as `(allow, deny)`.
+fn member_permissions(
+    voice_channel: &CachedVoiceChannel,
+    member_id: Id<UserMarker>,
+) -> (Permissions, Permissions) {
+    voice_channel
+        .permission_overwrites
+        .read()
+        .iter()
+        .find(|permission_overwrite| {
+            permission_overwrite
+                .kind
+                .eq(&ChannelPermissionOverwriteType::Member)
+                && permission_overwrite.id.eq(&member_id.cast())
+        })
+        .map_or(
+            (Permissions::empty(), Permissions::empty()),
+            |permission_overwrite| (permission_overwrite.allow, permission_overwrite.deny),
+        )
+}
+
+/// Replaces the select menu with the outcome.
+async fn update_response(
+    context: &Context,
+    interaction: &MessageComponentInteraction,
+    description: &str,
+) -> Result<()> {
+    let embed = EmbedBuilder::new()
+        .color(0xF8F8FF)
+        .description(description)
+        .build();
+
+    context
+        .interaction_client()
+        .update_response(&interaction.token)
+        .components(Some(&[]))
+        .embeds(Some(&[embed]))
+        .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use twilight_model::id::Id;
+
+    use crate::{
+        structs::database::ChannelPrivacy,
+        testing::{
+            self,
+            mock_discord::MockDiscord,
+            payloads::{command, subcommand_group, user_select},
+            replay::replay,
+            seed_join_channel,
+        },
+    };
+
+    #[tokio::test]
+    #[ignore = "needs a database, run with `cargo test -- --ignored`"]
+    async fn denies_every_member_picked() {
+        let mock_discord = MockDiscord::start().await;
+        let (context, _guard) = testing::context(&mock_discord).await;
+        let guild_id = Id::new(1001);
+
+        seed_join_channel(&context, guild_id, Id::new(1101), ChannelPrivacy::Locked).await;
+        replay(
+            &context,
+            include_str!("../../testing/recordings/create.jsonl"),
+        )
+        .await
+        .unwrap();
+        mock_discord.fail("PUT", "/channels/5000/permissions/2003");
+        replay(
+            &context,
+            &[
+                command(
+                    "members",
+                    1001,
+                    2001,
+                    subcommand_group("voice", "members", "deny", json!([])),
+                ),
+                user_select(
+                    "pick",
+                    1001,
+                    2001,
+                    5000,
+                    "members:deny",
+                    &[2001, 2002, 2003, 2004],
+                ),
+            ]
+            .join("\n"),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            mock_discord.response_descriptions("members"),
+            ["Pick the members to deny from <#5000>."]
+        );
+        assert_eq!(
+            mock_discord
+                .assert_requested("PUT", "/channels/5000/permissions/2004")
+                .body["deny"],
+            "1024"
+        );
+        assert_eq!(
+            mock_discord.response_descriptions("pick"),
+            ["I've denied <@2002> and <@2004> from <#5000>.\nI'm unable to deny <@2003> right now. Try again in 10 minutes.\n<@2001> may not be denied from <#5000>."]
+        );
+
+        context.database.remove_guild(guild_id).await.unwrap();
+    }
+}
@@ -1,6 +1,9 @@
 mod events;
 mod interactions;
 mod structs;
+mod tasks;
+#[cfg(test)]
+mod testing;
 mod utilities;
 
 use std::sync::{
@@ -13,8 +16,9 @@ use eyre::Result;
 use structs::context::Context;
 use tokio::signal;
 
+use futures::StreamExt;
 use twilight_gateway::{
-    create_recommended, CloseFrame, Config as TwilightGatewayConfig, Event, Shard, StreamExt,
+    create_recommended, parse, CloseFrame, Config as TwilightGatewayConfig, Event, Message, Shard,
 };
 use twilight_http::Client;
 use utilities::constants::{DISCORD_TOKEN, INTENTS, WANTED_EVENT_TYPES};
@@ -22,18 +26,32 @@ use utilities::constants::{DISCORD_TOKEN, INTENTS, WANTED_EVENT_TYPES};
 static SHUTDOWN: AtomicBool = AtomicBool::new(false);
 
 async fn runner(mut shard: Shard, context: Arc<Context>) -> Result<()> {
-    while let Some(item) = shard.next_event(*WANTED_EVENT_TYPES).await {
-        match item {
-            Ok(Event::GatewayClose(_)) if SHUTDOWN.load(Ordering::Relaxed) => break,
-            Ok(event) => {
-                let event_context = Arc::clone(&context);
-
-                tokio::spawn(async move { handle_event(event_context, event).await.unwrap() })
+    while let Some(item) = shard.next().await {
+        let event = match item {
+            Ok(Message::Text(json)) => {
+                if let Some(recorder) = &context.recorder {
+                    _ = recorder.record(&json);
+                }
+
+                match parse(json, *WANTED_EVENT_TYPES) {
+                    Ok(Some(gateway_event)) => Event::from(gateway_event),
+                    _ => continue,
+                }
             }
+            Ok(Message::Close(frame)) => Event::GatewayClose(frame),
             Err(_source) => {
                 continue;
             }
         };
+
+        match event {
+            Event::GatewayClose(_) if SHUTDOWN.load(Ordering::Relaxed) => break,
+            event => {
+                let event_context = Arc::clone(&context);
+
+                tokio::spawn(async move { handle_event(event_context, event).await.unwrap() })
+            }
+        };
     }
 
     Ok(())
@@ -50,7 +68,7 @@ async fn main() -> Result<()> {
     let shard_count = shards.len();
     let mut senders = Vec::with_capacity(shard_count);
     let mut tasks = Vec::with_capacity(shard_count);
-    let context = Arc::new(Context::new(application_id, client));
+    let context = Arc::new(Context::new(application_id, client)?);
 
     context.database.create_tables().await?;
 
@@ -60,6 +78,11 @@ async fn main() -> Result<()> {
         .set_global_commands(&utilities::constants::COMMANDS)
         .await?;
 
+    tokio::spawn(tasks::ownership::run(Arc::clone(&context)));
+    tokio::spawn(tasks::reconcile::run(Arc::clone(&context)));
+    tokio::spawn(tasks::renames::run(Arc::clone(&context)));
+    tokio::spawn(tasks::timed_permissions::run(Arc::clone(&context)));
+
     for shard in shards {
         let shard_context = Arc::clone(&context);
 
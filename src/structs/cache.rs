@@ -1,9 +1,12 @@
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{HashMap, HashSet, VecDeque},
     sync::Arc,
+    time::{Duration, SystemTime},
 };
 
+use dashmap::{DashMap, DashSet};
 use parking_lot::RwLock;
+use regex::Regex;
 use twilight_model::{
     channel::{
         permission_overwrite::PermissionOverwrite as ChannelPermissionOverwrite, VideoQualityMode,
@@ -14,50 +17,127 @@ use twilight_model::{
     },
 };
 
-use super::database::ChannelPrivacy;
+use super::database::{
+    ChannelPrivacy, ExternalEditPolicy, OwnershipPolicy, VoiceChannelKind, VoiceChannelSettings,
+};
+use crate::utilities::constants::{
+    KNOCK_EXPIRY, KNOCK_RATE_LIMIT, RECENT_CONTACTS_LIMIT, RENAME_LIMIT, RENAME_WINDOW,
+};
 
+type GuildUserKey = (Id<GuildMarker>, Id<UserMarker>);
+
+// Every map is sharded, so a lookup or mutation only locks the shard holding its key. Entry
+// references must not be held while another map is locked, which is why methods clone the
+// `Arc` out of a map before touching the next one.
 pub struct Cache {
-    pub guilds: RwLock<HashMap<Id<GuildMarker>, Arc<CachedGuild>>>,
-    pub join_channels: RwLock<HashMap<Id<ChannelMarker>, Arc<CachedJoinChannel>>>,
-    pub unavailable_guilds: RwLock<HashSet<Id<GuildMarker>>>,
-    pub voice_channels: RwLock<HashMap<Id<ChannelMarker>, Arc<CachedVoiceChannel>>>,
-    pub voice_channel_owners:
-        RwLock<HashMap<(Id<GuildMarker>, Id<UserMarker>), Arc<Id<ChannelMarker>>>>,
-    pub voice_states: RwLock<HashMap<(Id<GuildMarker>, Id<UserMarker>), Arc<Id<ChannelMarker>>>>,
+    /// The members connected to each channel, kept alongside `voice_states` so a voice channel
+    /// can find who's already in it without going through every voice state.
+    pub channel_voice_states: DashMap<Id<ChannelMarker>, HashSet<Id<UserMarker>>>,
+    /// When each member last created a voice channel, for the server's creation cooldown.
+    pub creation_times: DashMap<GuildUserKey, SystemTime>,
+    /// Channels being deleted through a command, which audits the deletion itself.
+    pub deleted_channel_ids: DashSet<Id<ChannelMarker>>,
+    pub guilds: DashMap<Id<GuildMarker>, Arc<CachedGuild>>,
+    pub join_channels: DashMap<Id<ChannelMarker>, Arc<CachedJoinChannel>>,
+    /// The voice channels created from each join channel that still exist.
+    pub join_channel_voice_channels: DashMap<Id<ChannelMarker>, HashSet<Id<ChannelMarker>>>,
+    pub member_names: DashMap<GuildUserKey, Arc<String>>,
+    pub recent_contacts: DashMap<GuildUserKey, VecDeque<Id<UserMarker>>>,
+    pub unavailable_guilds: DashSet<Id<GuildMarker>>,
+    pub voice_channels: DashMap<Id<ChannelMarker>, Arc<CachedVoiceChannel>>,
+    /// The voice channels each member owns, oldest first.
+    pub voice_channel_owners: DashMap<GuildUserKey, Vec<Id<ChannelMarker>>>,
+    pub voice_states: DashMap<GuildUserKey, Arc<Id<ChannelMarker>>>,
+    /// The voice channel each waiting room belongs to.
+    pub waiting_rooms: DashMap<Id<ChannelMarker>, Arc<Id<ChannelMarker>>>,
 }
 
 pub struct CachedGuild {
+    /// Whether members turned away from a join channel are told why in a DM.
+    pub blocked_dms_enabled: RwLock<bool>,
+    /// Roles whose members may not create voice channels.
+    pub blocked_role_ids: RwLock<HashSet<Id<RoleMarker>>>,
+    /// Members who may not create voice channels.
+    pub blocked_user_ids: RwLock<HashSet<Id<UserMarker>>>,
     pub bot_role_id: Id<RoleMarker>,
+    /// How many seconds a member waits between creating voice channels.
+    pub creation_cooldown: RwLock<u32>,
+    /// What happens when a voice channel's overwrites are edited outside of Voz.
+    pub external_edit_policy: RwLock<ExternalEditPolicy>,
     pub id: Id<GuildMarker>,
     pub join_channel_ids: RwLock<HashSet<Id<ChannelMarker>>>,
+    /// The locale default voice channel names are written in.
+    pub locale: RwLock<String>,
+    pub log_channel_id: RwLock<Option<Id<ChannelMarker>>>,
+    pub max_voice_channels: RwLock<u8>,
     pub name: RwLock<String>,
+    /// Regular expressions voice channel names may not match.
+    pub name_filter_patterns: RwLock<Vec<Regex>>,
+    /// Lowercase words voice channel names may not contain.
+    pub name_filter_words: RwLock<HashSet<String>>,
+    /// The name new voice channels start with, where `{owner}` stands for the owner's name.
+    pub name_template: RwLock<Option<String>>,
+    pub presets_enabled: RwLock<bool>,
+    /// Whether voice channel names may only contain printable characters.
+    pub printable_names_only: RwLock<bool>,
     pub voice_channel_ids: RwLock<HashSet<Id<ChannelMarker>>>,
 }
 
 #[derive(Default)]
 pub struct CachedGuildUpdate {
+    pub blocked_dms_enabled: Option<bool>,
+    pub blocked_role_ids: Option<HashSet<Id<RoleMarker>>>,
+    pub blocked_user_ids: Option<HashSet<Id<UserMarker>>>,
+    pub creation_cooldown: Option<u32>,
+    pub external_edit_policy: Option<ExternalEditPolicy>,
+    pub locale: Option<String>,
+    pub log_channel_id: Option<Option<Id<ChannelMarker>>>,
+    pub max_voice_channels: Option<u8>,
     pub name: Option<String>,
+    pub name_filter_patterns: Option<Vec<Regex>>,
+    pub name_filter_words: Option<HashSet<String>>,
+    pub name_template: Option<Option<String>>,
+    pub presets_enabled: Option<bool>,
+    pub printable_names_only: Option<bool>,
 }
 
 pub struct CachedJoinChannel {
-    pub access_role_id: RwLock<Option<Id<RoleMarker>>>,
+    /// Roles whose members may use the join channel whatever its privacy level.
+    pub allowed_role_ids: RwLock<HashSet<Id<RoleMarker>>>,
+    /// Roles whose members are kept out of the join channel.
+    pub denied_role_ids: RwLock<HashSet<Id<RoleMarker>>>,
     pub id: Id<ChannelMarker>,
     pub guild_id: Id<GuildMarker>,
+    /// The kind of voice channel the join channel creates.
+    pub kind: VoiceChannelKind,
     pub name: RwLock<String>,
+    pub ownership_delay: RwLock<u32>,
+    pub ownership_policy: RwLock<OwnershipPolicy>,
     pub parent_id: RwLock<Option<Id<ChannelMarker>>>,
     pub permanence: RwLock<bool>,
     pub permission_overwrites: RwLock<Vec<ChannelPermissionOverwrite>>,
     pub privacy: RwLock<ChannelPrivacy>,
+    /// The status new voice channels start with, where `{owner}` stands for the owner's name.
+    pub status_template: RwLock<Option<String>>,
+    /// How many voice channels created from the join channel may exist at once.
+    pub voice_channel_limit: RwLock<Option<u16>>,
+    pub waiting_room: RwLock<bool>,
 }
 
 #[derive(Default)]
 pub struct CachedJoinChannelUpdate {
-    pub access_role_id: Option<Option<Id<RoleMarker>>>,
+    pub allowed_role_ids: Option<HashSet<Id<RoleMarker>>>,
+    pub denied_role_ids: Option<HashSet<Id<RoleMarker>>>,
     pub name: Option<String>,
+    pub ownership_delay: Option<u32>,
+    pub ownership_policy: Option<OwnershipPolicy>,
     pub parent_id: Option<Option<Id<ChannelMarker>>>,
     pub permanence: Option<bool>,
     pub permission_overwrites: Option<Vec<ChannelPermissionOverwrite>>,
     pub privacy: Option<ChannelPrivacy>,
+    pub status_template: Option<Option<String>>,
+    pub voice_channel_limit: Option<Option<u16>>,
+    pub waiting_room: Option<bool>,
 }
 
 pub struct CachedVoiceChannel {
@@ -65,15 +145,39 @@ pub struct CachedVoiceChannel {
     pub connected_user_ids: RwLock<HashSet<Id<UserMarker>>>,
     pub id: Id<ChannelMarker>,
     pub guild_id: Id<GuildMarker>,
+    /// The join channel the voice channel was created from, if it's still known.
+    pub join_channel_id: Option<Id<ChannelMarker>>,
+    /// When each connected member joined, so ownership can pass to whoever has stayed longest.
+    pub join_times: RwLock<HashMap<Id<UserMarker>, SystemTime>>,
+    pub kind: VoiceChannelKind,
+    /// When each member waiting on an answer knocked.
+    pub knocks: RwLock<HashMap<Id<UserMarker>, SystemTime>>,
+    /// When each member last used `/voice knock`, kept after an answer so they can't knock again
+    /// straight away.
+    pub last_knocks: RwLock<HashMap<Id<UserMarker>, SystemTime>>,
+    pub manager_ids: RwLock<HashSet<Id<UserMarker>>>,
     pub name: RwLock<String>,
     pub owner_id: RwLock<Option<Id<UserMarker>>>,
+    /// When the owner left, or `None` while they're connected (or there's no owner).
+    pub owner_left_at: RwLock<Option<SystemTime>>,
+    pub ownership_delay: u32,
+    pub ownership_policy: OwnershipPolicy,
     pub permanence: RwLock<bool>,
     pub permission_overwrites: RwLock<Vec<ChannelPermissionOverwrite>>,
     pub privacy: RwLock<ChannelPrivacy>,
+    /// The latest name asked for while renaming was rate limited, and who asked for it, or `None`
+    /// when it changes back a rename made outside of Voz.
+    pub queued_name: RwLock<Option<(Option<Id<UserMarker>>, String)>>,
     pub rate_limit_per_user: RwLock<Option<u16>>,
+    /// When I last renamed the voice channel, oldest first, for Discord's rename rate limit.
+    pub rename_times: RwLock<VecDeque<SystemTime>>,
     pub rtc_region: RwLock<Option<String>>,
+    /// The settings saved for the voice channel, which Voz's own edits change before they're sent.
+    pub saved_settings: RwLock<Option<VoiceChannelSettings>>,
+    pub status: RwLock<Option<String>>,
     pub user_limit: RwLock<Option<u32>>,
     pub video_quality_mode: RwLock<VideoQualityMode>,
+    pub waiting_room_id: RwLock<Option<Id<ChannelMarker>>>,
 }
 
 #[derive(Default)]
@@ -86,138 +190,554 @@ pub struct CachedVoiceChannelUpdate {
     pub privacy: Option<ChannelPrivacy>,
     pub rate_limit_per_user: Option<Option<u16>>,
     pub rtc_region: Option<Option<String>>,
+    pub saved_settings: Option<Option<VoiceChannelSettings>>,
+    pub status: Option<Option<String>>,
     pub user_limit: Option<Option<u32>>,
     pub video_quality_mode: Option<VideoQualityMode>,
 }
 
 impl Cache {
+    /// Whether a member owns fewer voice channels than their server allows.
+    pub fn can_own_voice_channel(
+        &self,
+        guild_id: Id<GuildMarker>,
+        user_id: Id<UserMarker>,
+    ) -> bool {
+        let Some(guild) = self.guild(guild_id) else {
+            return false;
+        };
+        let max_voice_channels = *guild.max_voice_channels.read();
+
+        self.owned_voice_channels(guild_id, user_id)
+            .len()
+            .lt(&usize::from(max_voice_channels))
+    }
+
+    /// How long the member must wait before creating another voice channel, if at all.
+    pub fn creation_retry_after(
+        &self,
+        guild_id: Id<GuildMarker>,
+        user_id: Id<UserMarker>,
+    ) -> Option<Duration> {
+        let creation_cooldown = *self.guild(guild_id)?.creation_cooldown.read();
+        let created_at = *self.creation_times.get(&(guild_id, user_id))?;
+        let elapsed = SystemTime::now()
+            .duration_since(created_at)
+            .unwrap_or_default();
+
+        Duration::from_secs(u64::from(creation_cooldown))
+            .checked_sub(elapsed)
+            .filter(|retry_after| !retry_after.is_zero())
+    }
+
     pub fn guild(&self, id: Id<GuildMarker>) -> Option<Arc<CachedGuild>> {
-        self.guilds.read().get(&id).cloned()
+        self.guilds.get(&id).map(|guild| Arc::clone(&guild))
+    }
+
+    pub fn guild_ids(&self) -> Vec<Id<GuildMarker>> {
+        self.guilds.iter().map(|guild| *guild.key()).collect()
     }
 
     pub fn insert_guild(&self, bot_role_id: Id<RoleMarker>, id: Id<GuildMarker>, name: String) {
-        self.guilds.write().insert(
+        self.guilds.insert(
             id,
             Arc::new(CachedGuild {
+                blocked_dms_enabled: RwLock::new(true),
+                blocked_role_ids: RwLock::new(HashSet::new()),
+                blocked_user_ids: RwLock::new(HashSet::new()),
                 bot_role_id,
+                creation_cooldown: RwLock::new(0),
+                external_edit_policy: RwLock::new(ExternalEditPolicy::Accept),
                 id,
                 join_channel_ids: RwLock::new(HashSet::new()),
+                locale: RwLock::new("en-US".to_owned()),
+                log_channel_id: RwLock::new(None),
+                max_voice_channels: RwLock::new(1),
                 name: RwLock::new(name),
+                name_filter_patterns: RwLock::new(Vec::new()),
+                name_filter_words: RwLock::new(HashSet::new()),
+                name_template: RwLock::new(None),
+                presets_enabled: RwLock::new(true),
+                printable_names_only: RwLock::new(false),
                 voice_channel_ids: RwLock::new(HashSet::new()),
             }),
         );
     }
 
+    pub fn insert_creation_time(
+        &self,
+        guild_id: Id<GuildMarker>,
+        user_id: Id<UserMarker>,
+        created_at: SystemTime,
+    ) {
+        self.creation_times.insert((guild_id, user_id), created_at);
+    }
+
+    pub fn insert_deleted_channel(&self, channel_id: Id<ChannelMarker>) {
+        self.deleted_channel_ids.insert(channel_id);
+    }
+
+    #[allow(clippy::too_many_arguments)]
     pub fn insert_join_channel(
         &self,
-        access_role_id: Option<Id<RoleMarker>>,
+        allowed_role_ids: HashSet<Id<RoleMarker>>,
+        denied_role_ids: HashSet<Id<RoleMarker>>,
         id: Id<ChannelMarker>,
         guild_id: Id<GuildMarker>,
+        kind: VoiceChannelKind,
         name: String,
+        ownership_delay: u32,
+        ownership_policy: OwnershipPolicy,
         parent_id: Option<Id<ChannelMarker>>,
         permanence: bool,
         permission_overwrites: Vec<ChannelPermissionOverwrite>,
         privacy: ChannelPrivacy,
+        status_template: Option<String>,
+        voice_channel_limit: Option<u16>,
+        waiting_room: bool,
     ) {
-        self.join_channels.write().insert(
+        self.join_channels.insert(
             id,
             Arc::new(CachedJoinChannel {
-                access_role_id: RwLock::new(access_role_id),
+                allowed_role_ids: RwLock::new(allowed_role_ids),
+                denied_role_ids: RwLock::new(denied_role_ids),
                 id,
                 guild_id,
+                kind,
                 name: RwLock::new(name),
+                ownership_delay: RwLock::new(ownership_delay),
+                ownership_policy: RwLock::new(ownership_policy),
                 parent_id: RwLock::new(parent_id),
                 permanence: RwLock::new(permanence),
                 permission_overwrites: RwLock::new(permission_overwrites),
                 privacy: RwLock::new(privacy),
+                status_template: RwLock::new(status_template),
+                voice_channel_limit: RwLock::new(voice_channel_limit),
+                waiting_room: RwLock::new(waiting_room),
             }),
         );
 
-        if let Some(guild) = self.guilds.write().get_mut(&guild_id) {
+        if let Some(guild) = self.guild(guild_id) {
             guild.join_channel_ids.write().insert(id);
         }
     }
 
+    /// Restores a persisted join time, provided the member is still connected to the channel.
+    pub fn insert_join_time(
+        &self,
+        channel_id: Id<ChannelMarker>,
+        user_id: Id<UserMarker>,
+        joined_at: SystemTime,
+    ) {
+        if let Some(voice_channel) = self.voice_channel(channel_id) {
+            if let Some(join_time) = voice_channel.join_times.write().get_mut(&user_id) {
+                *join_time = joined_at;
+            }
+        }
+    }
+
+    /// Records the member's knock, forgetting knocks that have expired and members who may knock
+    /// again.
+    pub fn insert_knock(
+        &self,
+        channel_id: Id<ChannelMarker>,
+        user_id: Id<UserMarker>,
+        knocked_at: SystemTime,
+    ) {
+        if let Some(voice_channel) = self.voice_channel(channel_id) {
+            let now = SystemTime::now();
+            let mut knocks = voice_channel.knocks.write();
+            let mut last_knocks = voice_channel.last_knocks.write();
+
+            knocks.retain(|_, knocked_at| {
+                now.duration_since(*knocked_at)
+                    .unwrap_or_default()
+                    .lt(&KNOCK_EXPIRY)
+            });
+            last_knocks.retain(|_, knocked_at| {
+                now.duration_since(*knocked_at)
+                    .unwrap_or_default()
+                    .lt(&KNOCK_RATE_LIMIT)
+            });
+            knocks.insert(user_id, knocked_at);
+            last_knocks
+                .entry(user_id)
+                .and_modify(|last_knocked_at| *last_knocked_at = knocked_at.max(*last_knocked_at))
+                .or_insert(knocked_at);
+        }
+    }
+
+    pub fn insert_member_name(
+        &self,
+        guild_id: Id<GuildMarker>,
+        user_id: Id<UserMarker>,
+        name: String,
+    ) {
+        self.member_names
+            .insert((guild_id, user_id), Arc::new(name));
+    }
+
+    /// Queues the name to apply once the voice channel may be renamed again, replacing any name
+    /// queued before it.
+    pub fn insert_queued_name(
+        &self,
+        channel_id: Id<ChannelMarker>,
+        user_id: Option<Id<UserMarker>>,
+        name: String,
+    ) {
+        if let Some(voice_channel) = self.voice_channel(channel_id) {
+            *voice_channel.queued_name.write() = Some((user_id, name));
+        }
+    }
+
+    /// Moves `contact_id` to the front of the member's recent contacts, dropping the oldest
+    /// contact once the list is full.
+    pub fn insert_recent_contact(
+        &self,
+        guild_id: Id<GuildMarker>,
+        user_id: Id<UserMarker>,
+        contact_id: Id<UserMarker>,
+    ) {
+        let mut recent_contacts = self.recent_contacts.entry((guild_id, user_id)).or_default();
+
+        recent_contacts.retain(|recent_contact_id| recent_contact_id.ne(&contact_id));
+        recent_contacts.push_front(contact_id);
+        recent_contacts.truncate(RECENT_CONTACTS_LIMIT);
+    }
+
+    pub fn insert_rename_time(&self, channel_id: Id<ChannelMarker>, renamed_at: SystemTime) {
+        if let Some(voice_channel) = self.voice_channel(channel_id) {
+            let mut rename_times = voice_channel.rename_times.write();
+
+            rename_times.push_back(renamed_at);
+
+            // Only the renames that can still count towards the limit are kept.
+            while rename_times.len().gt(&RENAME_LIMIT) {
+                rename_times.pop_front();
+            }
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
     pub fn insert_voice_channel(
         &self,
         bitrate: u32,
         id: Id<ChannelMarker>,
         guild_id: Id<GuildMarker>,
+        join_channel_id: Option<Id<ChannelMarker>>,
+        kind: VoiceChannelKind,
         name: String,
         owner_id: Option<Id<UserMarker>>,
+        ownership_delay: u32,
+        ownership_policy: OwnershipPolicy,
         permanence: bool,
         permission_overwrites: Vec<ChannelPermissionOverwrite>,
         privacy: ChannelPrivacy,
         rate_limit_per_user: Option<u16>,
         rtc_region: Option<String>,
+        saved_settings: Option<VoiceChannelSettings>,
+        status: Option<String>,
         user_limit: Option<u32>,
         video_quality_mode: VideoQualityMode,
     ) {
-        self.voice_channels.write().insert(
+        let connected_user_ids = self
+            .channel_voice_states
+            .get(&id)
+            .map(|user_ids| user_ids.clone())
+            .unwrap_or_default();
+        let now = SystemTime::now();
+        let join_times = connected_user_ids
+            .iter()
+            .map(|user_id| (*user_id, now))
+            .collect::<HashMap<Id<UserMarker>, SystemTime>>();
+        let owner_left_at = owner_id
+            .filter(|owner_id| !connected_user_ids.contains(owner_id))
+            .map(|_| now);
+
+        self.voice_channels.insert(
             id,
             Arc::new(CachedVoiceChannel {
                 bitrate: RwLock::new(bitrate),
-                connected_user_ids: RwLock::new(HashSet::new()),
+                connected_user_ids: RwLock::new(connected_user_ids),
                 id,
                 guild_id,
+                join_channel_id,
+                join_times: RwLock::new(join_times),
+                kind,
+                knocks: RwLock::new(HashMap::new()),
+                last_knocks: RwLock::new(HashMap::new()),
+                manager_ids: RwLock::new(HashSet::new()),
                 name: RwLock::new(name),
                 owner_id: RwLock::new(owner_id),
+                owner_left_at: RwLock::new(owner_left_at),
+                ownership_delay,
+                ownership_policy,
                 permanence: RwLock::new(permanence),
                 permission_overwrites: RwLock::new(permission_overwrites),
                 privacy: RwLock::new(privacy),
+                queued_name: RwLock::new(None),
                 rate_limit_per_user: RwLock::new(rate_limit_per_user),
+                rename_times: RwLock::new(VecDeque::new()),
                 rtc_region: RwLock::new(rtc_region),
+                saved_settings: RwLock::new(saved_settings),
+                status: RwLock::new(status),
                 user_limit: RwLock::new(user_limit),
                 video_quality_mode: RwLock::new(video_quality_mode),
+                waiting_room_id: RwLock::new(None),
             }),
         );
 
         if let Some(owner_id) = owner_id {
-            self.voice_channel_owners
-                .write()
-                .insert((guild_id, owner_id), Arc::new(id));
+            self.insert_voice_channel_owner(guild_id, owner_id, id);
+        }
+        if let Some(join_channel_id) = join_channel_id {
+            self.join_channel_voice_channels
+                .entry(join_channel_id)
+                .or_default()
+                .insert(id);
         }
-        if let Some(guild) = self.guilds.write().get_mut(&guild_id) {
+        if let Some(guild) = self.guild(guild_id) {
             guild.voice_channel_ids.write().insert(id);
         }
     }
 
-    pub fn insert_voice_state(
+    pub fn insert_voice_channel_manager(
+        &self,
+        channel_id: Id<ChannelMarker>,
+        user_id: Id<UserMarker>,
+    ) {
+        if let Some(voice_channel) = self.voice_channel(channel_id) {
+            voice_channel.manager_ids.write().insert(user_id);
+        }
+    }
+
+    fn insert_voice_channel_owner(
         &self,
         guild_id: Id<GuildMarker>,
         user_id: Id<UserMarker>,
         channel_id: Id<ChannelMarker>,
     ) {
-        self.voice_states
-            .write()
-            .insert((guild_id, user_id), Arc::new(channel_id));
+        let mut owned_channel_ids = self
+            .voice_channel_owners
+            .entry((guild_id, user_id))
+            .or_default();
 
-        if let Some(voice_channel) = self.voice_channels.write().get_mut(&channel_id) {
-            voice_channel.connected_user_ids.write().insert(user_id);
+        if !owned_channel_ids.contains(&channel_id) {
+            owned_channel_ids.push(channel_id);
         }
     }
 
+    pub fn insert_voice_state(
+        &self,
+        guild_id: Id<GuildMarker>,
+        user_id: Id<UserMarker>,
+        channel_id: Id<ChannelMarker>,
+    ) -> Option<Id<ChannelMarker>> {
+        let previous_channel_id = self
+            .voice_states
+            .insert((guild_id, user_id), Arc::new(channel_id))
+            .map(|previous_channel_id| *previous_channel_id);
+
+        if previous_channel_id.eq(&Some(channel_id)) {
+            return previous_channel_id;
+        }
+        if let Some(previous_channel_id) = previous_channel_id {
+            self.remove_channel_voice_state(previous_channel_id, user_id);
+        }
+
+        self.channel_voice_states
+            .entry(channel_id)
+            .or_default()
+            .insert(user_id);
+
+        if let Some(voice_channel) = previous_channel_id
+            .and_then(|previous_channel_id| self.voice_channel(previous_channel_id))
+        {
+            disconnect(&voice_channel, user_id);
+        }
+        if let Some(voice_channel) = self.voice_channel(channel_id) {
+            let contact_ids = {
+                let mut connected_user_ids = voice_channel.connected_user_ids.write();
+                let contact_ids = connected_user_ids.clone();
+
+                connected_user_ids.insert(user_id);
+
+                contact_ids
+            };
+
+            voice_channel
+                .join_times
+                .write()
+                .insert(user_id, SystemTime::now());
+
+            if voice_channel.owner_id.read().eq(&Some(user_id)) {
+                *voice_channel.owner_left_at.write() = None;
+            }
+
+            for contact_id in contact_ids {
+                self.insert_recent_contact(guild_id, user_id, contact_id);
+                self.insert_recent_contact(guild_id, contact_id, user_id);
+            }
+        }
+
+        previous_channel_id
+    }
+
     pub fn insert_unavailable_guilds(&self, ids: Vec<Id<GuildMarker>>) {
-        self.unavailable_guilds.write().extend(ids);
+        for id in ids {
+            self.unavailable_guilds.insert(id);
+        }
+    }
+
+    /// Links a waiting room to the voice channel its members are waiting to join.
+    pub fn insert_waiting_room(
+        &self,
+        channel_id: Id<ChannelMarker>,
+        waiting_room_id: Id<ChannelMarker>,
+    ) {
+        let Some(voice_channel) = self.voice_channel(channel_id) else {
+            return;
+        };
+
+        *voice_channel.waiting_room_id.write() = Some(waiting_room_id);
+
+        self.waiting_rooms
+            .insert(waiting_room_id, Arc::new(channel_id));
+    }
+
+    /// Whether the member, or any of their roles, is barred from creating voice channels.
+    pub fn is_blocked(
+        &self,
+        guild_id: Id<GuildMarker>,
+        user_id: Id<UserMarker>,
+        role_ids: &[Id<RoleMarker>],
+    ) -> bool {
+        let Some(guild) = self.guild(guild_id) else {
+            return false;
+        };
+
+        if guild.blocked_user_ids.read().contains(&user_id) {
+            return true;
+        }
+
+        let blocked_role_ids = guild.blocked_role_ids.read();
+
+        role_ids
+            .iter()
+            .any(|role_id| blocked_role_ids.contains(role_id))
     }
 
     pub fn join_channel(&self, id: Id<ChannelMarker>) -> Option<Arc<CachedJoinChannel>> {
-        self.join_channels.read().get(&id).cloned()
+        self.join_channels
+            .get(&id)
+            .map(|join_channel| Arc::clone(&join_channel))
+    }
+
+    /// How many voice channels created from the join channel still exist.
+    pub fn join_channel_voice_channel_count(&self, join_channel_id: Id<ChannelMarker>) -> usize {
+        self.join_channel_voice_channels
+            .get(&join_channel_id)
+            .map_or(0, |voice_channel_ids| voice_channel_ids.len())
+    }
+
+    /// The voice channel a member owns or, failing that, manages. A member managing several
+    /// voice channels gets the one they're connected to, if any.
+    pub fn managed_voice_channel(
+        &self,
+        guild_id: Id<GuildMarker>,
+        user_id: Id<UserMarker>,
+    ) -> Option<Arc<Id<ChannelMarker>>> {
+        if let Some(voice_channel_id) = self.voice_channel_owner(guild_id, user_id) {
+            return Some(voice_channel_id);
+        }
+
+        let is_manager = |channel_id: Id<ChannelMarker>| {
+            self.voice_channel(channel_id)
+                .is_some_and(|voice_channel| voice_channel.manager_ids.read().contains(&user_id))
+        };
+
+        if let Some(channel_id) = self
+            .voice_state(guild_id, user_id)
+            .filter(|channel_id| is_manager(**channel_id))
+        {
+            return Some(channel_id);
+        }
+
+        let voice_channel_ids = self.guild(guild_id)?.voice_channel_ids.read().clone();
+
+        voice_channel_ids
+            .into_iter()
+            .find(|channel_id| is_manager(*channel_id))
+            .map(Arc::new)
+    }
+
+    pub fn member_name(
+        &self,
+        guild_id: Id<GuildMarker>,
+        user_id: Id<UserMarker>,
+    ) -> Option<Arc<String>> {
+        self.member_names
+            .get(&(guild_id, user_id))
+            .map(|name| Arc::clone(&name))
+    }
+
+    /// Every voice channel a member owns, oldest first.
+    pub fn owned_voice_channels(
+        &self,
+        guild_id: Id<GuildMarker>,
+        user_id: Id<UserMarker>,
+    ) -> Vec<Id<ChannelMarker>> {
+        self.voice_channel_owners
+            .get(&(guild_id, user_id))
+            .map(|owned_channel_ids| owned_channel_ids.clone())
+            .unwrap_or_default()
     }
 
     pub fn new() -> Self {
         Self {
-            guilds: RwLock::new(HashMap::new()),
-            join_channels: RwLock::new(HashMap::new()),
-            unavailable_guilds: RwLock::new(HashSet::new()),
-            voice_channels: RwLock::new(HashMap::new()),
-            voice_channel_owners: RwLock::new(HashMap::new()),
-            voice_states: RwLock::new(HashMap::new()),
+            channel_voice_states: DashMap::new(),
+            creation_times: DashMap::new(),
+            deleted_channel_ids: DashSet::new(),
+            guilds: DashMap::new(),
+            join_channels: DashMap::new(),
+            join_channel_voice_channels: DashMap::new(),
+            member_names: DashMap::new(),
+            recent_contacts: DashMap::new(),
+            unavailable_guilds: DashSet::new(),
+            voice_channels: DashMap::new(),
+            voice_channel_owners: DashMap::new(),
+            voice_states: DashMap::new(),
+            waiting_rooms: DashMap::new(),
         }
     }
 
+    pub fn recent_contacts(
+        &self,
+        guild_id: Id<GuildMarker>,
+        user_id: Id<UserMarker>,
+    ) -> Vec<Id<UserMarker>> {
+        self.recent_contacts
+            .get(&(guild_id, user_id))
+            .map(|recent_contacts| recent_contacts.iter().copied().collect())
+            .unwrap_or_default()
+    }
+
+    fn remove_channel_voice_state(&self, channel_id: Id<ChannelMarker>, user_id: Id<UserMarker>) {
+        self.channel_voice_states
+            .remove_if_mut(&channel_id, |_, user_ids| {
+                user_ids.remove(&user_id);
+
+                user_ids.is_empty()
+            });
+    }
+
+    /// Forgets a channel being deleted through a command, returning whether it was one.
+    pub fn remove_deleted_channel(&self, channel_id: Id<ChannelMarker>) -> bool {
+        self.deleted_channel_ids.remove(&channel_id).is_some()
+    }
+
     pub fn remove_guild(&self, id: Id<GuildMarker>) {
-        let Some(guild) = self.guilds.write().remove(&id) else {
+        let Some((_, guild)) = self.guilds.remove(&id) else {
             return;
         };
 
@@ -228,65 +748,224 @@ impl Cache {
         for voice_channel_id in guild.voice_channel_ids.read().clone().into_iter() {
             self.remove_voice_channel(voice_channel_id);
         }
+
+        self.creation_times
+            .retain(|(guild_id, _), _| guild_id.ne(&id));
+        self.member_names
+            .retain(|(guild_id, _), _| guild_id.ne(&id));
+        self.recent_contacts
+            .retain(|(guild_id, _), _| guild_id.ne(&id));
     }
 
     pub fn remove_join_channel(&self, id: Id<ChannelMarker>) {
-        let Some(join_channel) = self.join_channels.write().remove(&id) else {
+        let Some((_, join_channel)) = self.join_channels.remove(&id) else {
             return;
         };
 
-        if let Some(guild) = self.guilds.write().get_mut(&join_channel.guild_id) {
+        if let Some(guild) = self.guild(join_channel.guild_id) {
             guild.join_channel_ids.write().remove(&id);
         }
     }
 
+    /// Forgets the member's knock once it's answered, returning when they knocked. They still may
+    /// not knock again until `KNOCK_RATE_LIMIT` has passed.
+    pub fn remove_knock(
+        &self,
+        channel_id: Id<ChannelMarker>,
+        user_id: Id<UserMarker>,
+    ) -> Option<SystemTime> {
+        self.voice_channel(channel_id)
+            .and_then(|voice_channel| voice_channel.knocks.write().remove(&user_id))
+    }
+
+    pub fn remove_member(&self, guild_id: Id<GuildMarker>, user_id: Id<UserMarker>) {
+        self.creation_times.remove(&(guild_id, user_id));
+        self.member_names.remove(&(guild_id, user_id));
+        self.recent_contacts.remove(&(guild_id, user_id));
+    }
+
+    /// Takes the name queued for the voice channel, along with who asked for it.
+    pub fn remove_queued_name(
+        &self,
+        channel_id: Id<ChannelMarker>,
+    ) -> Option<(Option<Id<UserMarker>>, String)> {
+        self.voice_channel(channel_id)
+            .and_then(|voice_channel| voice_channel.queued_name.write().take())
+    }
+
     pub fn remove_voice_channel(&self, id: Id<ChannelMarker>) {
-        let Some(voice_channel) = self.voice_channels.write().remove(&id) else {
+        let Some((_, voice_channel)) = self.voice_channels.remove(&id) else {
             return;
         };
 
-        if let Some(guild) = self.guilds.write().get_mut(&voice_channel.guild_id) {
+        if let Some(guild) = self.guild(voice_channel.guild_id) {
             guild.voice_channel_ids.write().remove(&id);
         }
-        if let Some(owner_id) = voice_channel.owner_id.read().clone() {
-            self.voice_channel_owners
-                .write()
-                .remove(&(voice_channel.guild_id, owner_id));
+        if let Some(owner_id) = *voice_channel.owner_id.read() {
+            self.remove_voice_channel_owner(voice_channel.guild_id, owner_id, id);
+        }
+        if let Some(join_channel_id) = voice_channel.join_channel_id {
+            self.join_channel_voice_channels.remove_if_mut(
+                &join_channel_id,
+                |_, voice_channel_ids| {
+                    voice_channel_ids.remove(&id);
+
+                    voice_channel_ids.is_empty()
+                },
+            );
         }
 
         for user_id in voice_channel.connected_user_ids.read().clone().into_iter() {
-            self.voice_states
-                .write()
-                .remove(&(voice_channel.guild_id, user_id));
+            self.voice_states.remove(&(voice_channel.guild_id, user_id));
+        }
+
+        self.channel_voice_states.remove(&id);
+
+        let waiting_room_id = *voice_channel.waiting_room_id.read();
+
+        if let Some(waiting_room_id) = waiting_room_id {
+            self.waiting_rooms.remove(&waiting_room_id);
         }
     }
 
-    pub fn remove_voice_state(&self, guild_id: Id<GuildMarker>, user_id: Id<UserMarker>) {
-        let Some(channel_id) = self.voice_states.write().remove(&(guild_id, user_id)) else {
-            return;
-        };
+    pub fn remove_voice_channel_manager(
+        &self,
+        channel_id: Id<ChannelMarker>,
+        user_id: Id<UserMarker>,
+    ) {
+        if let Some(voice_channel) = self.voice_channel(channel_id) {
+            voice_channel.manager_ids.write().remove(&user_id);
+        }
+    }
 
-        if let Some(voice_channel) = self.voice_channels.write().get_mut(&channel_id) {
-            voice_channel.connected_user_ids.write().remove(&user_id);
+    fn remove_voice_channel_owner(
+        &self,
+        guild_id: Id<GuildMarker>,
+        user_id: Id<UserMarker>,
+        channel_id: Id<ChannelMarker>,
+    ) {
+        self.voice_channel_owners
+            .remove_if_mut(&(guild_id, user_id), |_, owned_channel_ids| {
+                owned_channel_ids.retain(|owned_channel_id| owned_channel_id.ne(&channel_id));
+
+                owned_channel_ids.is_empty()
+            });
+    }
+
+    pub fn remove_voice_state(
+        &self,
+        guild_id: Id<GuildMarker>,
+        user_id: Id<UserMarker>,
+    ) -> Option<Id<ChannelMarker>> {
+        let (_, channel_id) = self.voice_states.remove(&(guild_id, user_id))?;
+
+        self.remove_channel_voice_state(*channel_id, user_id);
+
+        if let Some(voice_channel) = self.voice_channel(*channel_id) {
+            disconnect(&voice_channel, user_id);
         };
+
+        Some(*channel_id)
+    }
+
+    /// Unlinks a waiting room from its voice channel, returning the voice channel's id.
+    pub fn remove_waiting_room(&self, id: Id<ChannelMarker>) -> Option<Id<ChannelMarker>> {
+        let (_, channel_id) = self.waiting_rooms.remove(&id)?;
+
+        if let Some(voice_channel) = self.voice_channel(*channel_id) {
+            *voice_channel.waiting_room_id.write() = None;
+        }
+
+        Some(*channel_id)
+    }
+
+    /// How long until the voice channel may be renamed at `now` without going over Discord's
+    /// rename rate limit, if it can't be renamed already.
+    pub fn rename_retry_after(
+        &self,
+        channel_id: Id<ChannelMarker>,
+        now: SystemTime,
+    ) -> Option<Duration> {
+        let voice_channel = self.voice_channel(channel_id)?;
+        let rename_times = voice_channel.rename_times.read();
+
+        if rename_times.len().lt(&RENAME_LIMIT) {
+            return None;
+        }
+
+        let elapsed = now
+            .duration_since(*rename_times.front()?)
+            .unwrap_or_default();
+
+        RENAME_WINDOW
+            .checked_sub(elapsed)
+            .filter(|retry_after| !retry_after.is_zero())
     }
 
     pub fn update_guild(&self, id: Id<GuildMarker>, update: CachedGuildUpdate) {
-        if let Some(guild) = self.guilds.write().get_mut(&id) {
+        if let Some(guild) = self.guild(id) {
+            if let Some(blocked_dms_enabled) = update.blocked_dms_enabled {
+                *guild.blocked_dms_enabled.write() = blocked_dms_enabled;
+            }
+            if let Some(blocked_role_ids) = update.blocked_role_ids {
+                *guild.blocked_role_ids.write() = blocked_role_ids;
+            }
+            if let Some(blocked_user_ids) = update.blocked_user_ids {
+                *guild.blocked_user_ids.write() = blocked_user_ids;
+            }
+            if let Some(creation_cooldown) = update.creation_cooldown {
+                *guild.creation_cooldown.write() = creation_cooldown;
+            }
+            if let Some(external_edit_policy) = update.external_edit_policy {
+                *guild.external_edit_policy.write() = external_edit_policy;
+            }
+            if let Some(locale) = update.locale {
+                *guild.locale.write() = locale;
+            }
+            if let Some(log_channel_id) = update.log_channel_id {
+                *guild.log_channel_id.write() = log_channel_id;
+            }
+            if let Some(max_voice_channels) = update.max_voice_channels {
+                *guild.max_voice_channels.write() = max_voice_channels;
+            }
             if let Some(name) = update.name {
                 *guild.name.write() = name;
             }
+            if let Some(name_filter_patterns) = update.name_filter_patterns {
+                *guild.name_filter_patterns.write() = name_filter_patterns;
+            }
+            if let Some(name_filter_words) = update.name_filter_words {
+                *guild.name_filter_words.write() = name_filter_words;
+            }
+            if let Some(name_template) = update.name_template {
+                *guild.name_template.write() = name_template;
+            }
+            if let Some(presets_enabled) = update.presets_enabled {
+                *guild.presets_enabled.write() = presets_enabled;
+            }
+            if let Some(printable_names_only) = update.printable_names_only {
+                *guild.printable_names_only.write() = printable_names_only;
+            }
         }
     }
 
     pub fn update_join_channel(&self, id: Id<ChannelMarker>, update: CachedJoinChannelUpdate) {
-        if let Some(join_channel) = self.join_channels.write().get_mut(&id) {
-            if let Some(access_role_id) = update.access_role_id {
-                *join_channel.access_role_id.write() = access_role_id;
+        if let Some(join_channel) = self.join_channel(id) {
+            if let Some(allowed_role_ids) = update.allowed_role_ids {
+                *join_channel.allowed_role_ids.write() = allowed_role_ids;
+            }
+            if let Some(denied_role_ids) = update.denied_role_ids {
+                *join_channel.denied_role_ids.write() = denied_role_ids;
             }
             if let Some(name) = update.name {
                 *join_channel.name.write() = name;
             }
+            if let Some(ownership_delay) = update.ownership_delay {
+                *join_channel.ownership_delay.write() = ownership_delay;
+            }
+            if let Some(ownership_policy) = update.ownership_policy {
+                *join_channel.ownership_policy.write() = ownership_policy;
+            }
             if let Some(parent_id) = update.parent_id {
                 *join_channel.parent_id.write() = parent_id;
             }
@@ -299,11 +978,39 @@ impl Cache {
             if let Some(privacy) = update.privacy {
                 *join_channel.privacy.write() = privacy;
             }
+            if let Some(status_template) = update.status_template {
+                *join_channel.status_template.write() = status_template;
+            }
+            if let Some(voice_channel_limit) = update.voice_channel_limit {
+                *join_channel.voice_channel_limit.write() = voice_channel_limit;
+            }
+            if let Some(waiting_room) = update.waiting_room {
+                *join_channel.waiting_room.write() = waiting_room;
+            }
+        }
+    }
+
+    /// Edits the settings saved for a voice channel before Voz sends the same edit, so the
+    /// `CHANNEL_UPDATE` it causes isn't mistaken for an edit made outside of Voz. The settings are
+    /// returned as they were, to be put back if Discord refuses the edit.
+    pub fn update_saved_settings(
+        &self,
+        channel_id: Id<ChannelMarker>,
+        edit: impl FnOnce(&mut VoiceChannelSettings),
+    ) -> Option<VoiceChannelSettings> {
+        let voice_channel = self.voice_channel(channel_id)?;
+        let mut saved_settings = voice_channel.saved_settings.write();
+        let previous_saved_settings = saved_settings.clone();
+
+        if let Some(saved_settings) = saved_settings.as_mut() {
+            edit(saved_settings);
         }
+
+        previous_saved_settings
     }
 
     pub fn update_voice_channel(&self, id: Id<ChannelMarker>, update: CachedVoiceChannelUpdate) {
-        if let Some(voice_channel) = self.voice_channels.write().get_mut(&id) {
+        if let Some(voice_channel) = self.voice_channel(id) {
             if let Some(bitrate) = update.bitrate {
                 *voice_channel.bitrate.write() = bitrate;
             }
@@ -311,18 +1018,24 @@ impl Cache {
                 *voice_channel.name.write() = name;
             }
             if let Some(owner_id) = update.owner_id {
-                if let Some(current_owner_id) = voice_channel.owner_id.read().clone() {
-                    self.voice_channel_owners
-                        .write()
-                        .remove(&(voice_channel.guild_id, current_owner_id));
+                if let Some(current_owner_id) = *voice_channel.owner_id.read() {
+                    self.remove_voice_channel_owner(
+                        voice_channel.guild_id,
+                        current_owner_id,
+                        voice_channel.id,
+                    );
                 }
 
                 *voice_channel.owner_id.write() = owner_id;
+                *voice_channel.owner_left_at.write() = owner_id
+                    .filter(|owner_id| !voice_channel.connected_user_ids.read().contains(owner_id))
+                    .map(|_| SystemTime::now());
 
                 if let Some(new_owner_id) = owner_id {
-                    self.voice_channel_owners.write().insert(
-                        (voice_channel.guild_id, new_owner_id),
-                        Arc::new(voice_channel.id),
+                    self.insert_voice_channel_owner(
+                        voice_channel.guild_id,
+                        new_owner_id,
+                        voice_channel.id,
                     );
                 }
             }
@@ -341,6 +1054,12 @@ impl Cache {
             if let Some(rtc_region) = update.rtc_region {
                 *voice_channel.rtc_region.write() = rtc_region;
             }
+            if let Some(saved_settings) = update.saved_settings {
+                *voice_channel.saved_settings.write() = saved_settings;
+            }
+            if let Some(status) = update.status {
+                *voice_channel.status.write() = status;
+            }
             if let Some(user_limit) = update.user_limit {
                 *voice_channel.user_limit.write() = user_limit;
             }
@@ -350,19 +1069,41 @@ impl Cache {
         }
     }
 
+    pub fn update_voice_state(
+        &self,
+        guild_id: Id<GuildMarker>,
+        user_id: Id<UserMarker>,
+        channel_id: Option<Id<ChannelMarker>>,
+    ) -> Option<Id<ChannelMarker>> {
+        match channel_id {
+            Some(channel_id) => self.insert_voice_state(guild_id, user_id, channel_id),
+            None => self.remove_voice_state(guild_id, user_id),
+        }
+    }
+
     pub fn voice_channel(&self, id: Id<ChannelMarker>) -> Option<Arc<CachedVoiceChannel>> {
-        self.voice_channels.read().get(&id).cloned()
+        self.voice_channels
+            .get(&id)
+            .map(|voice_channel| Arc::clone(&voice_channel))
     }
 
+    /// The voice channel a member owns. A member owning several voice channels gets the one
+    /// they're connected to or, failing that, the newest.
     pub fn voice_channel_owner(
         &self,
         guild_id: Id<GuildMarker>,
         user_id: Id<UserMarker>,
     ) -> Option<Arc<Id<ChannelMarker>>> {
-        self.voice_channel_owners
-            .read()
-            .get(&(guild_id, user_id))
-            .cloned()
+        let owned_channel_ids = self.owned_voice_channels(guild_id, user_id);
+
+        if let Some(channel_id) = self
+            .voice_state(guild_id, user_id)
+            .filter(|channel_id| owned_channel_ids.contains(channel_id))
+        {
+            return Some(channel_id);
+        }
+
+        owned_channel_ids.last().copied().map(Arc::new)
     }
 
     pub fn voice_state(
@@ -370,6 +1111,459 @@ impl Cache {
         guild_id: Id<GuildMarker>,
         user_id: Id<UserMarker>,
     ) -> Option<Arc<Id<ChannelMarker>>> {
-        self.voice_states.read().get(&(guild_id, user_id)).cloned()
+        self.voice_states
+            .get(&(guild_id, user_id))
+            .map(|channel_id| Arc::clone(&channel_id))
+    }
+
+    /// The voice channel a waiting room belongs to.
+    pub fn waiting_room(&self, id: Id<ChannelMarker>) -> Option<Arc<Id<ChannelMarker>>> {
+        self.waiting_rooms
+            .get(&id)
+            .map(|channel_id| Arc::clone(&channel_id))
+    }
+}
+
+fn disconnect(voice_channel: &CachedVoiceChannel, user_id: Id<UserMarker>) {
+    voice_channel.connected_user_ids.write().remove(&user_id);
+    voice_channel.join_times.write().remove(&user_id);
+
+    if voice_channel.owner_id.read().eq(&Some(user_id)) {
+        *voice_channel.owner_left_at.write() = Some(SystemTime::now());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashSet,
+        thread,
+        time::{Duration, Instant, SystemTime},
+    };
+
+    use twilight_model::{
+        channel::VideoQualityMode,
+        id::{
+            marker::{ChannelMarker, GuildMarker, RoleMarker, UserMarker},
+            Id,
+        },
+    };
+
+    use super::Cache;
+    use crate::{
+        structs::database::{ChannelPrivacy, OwnershipPolicy, VoiceChannelKind},
+        utilities::constants::RECENT_CONTACTS_LIMIT,
+    };
+
+    const GUILD_ID: Id<GuildMarker> = Id::new(1);
+    const JOIN_CHANNEL_ID: Id<ChannelMarker> = Id::new(10);
+    const VOICE_CHANNEL_ID: Id<ChannelMarker> = Id::new(20);
+    const OTHER_VOICE_CHANNEL_ID: Id<ChannelMarker> = Id::new(21);
+    const UNMANAGED_CHANNEL_ID: Id<ChannelMarker> = Id::new(30);
+    const OWNER_ID: Id<UserMarker> = Id::new(100);
+    const MEMBER_ID: Id<UserMarker> = Id::new(101);
+
+    fn cache() -> Cache {
+        let cache = Cache::new();
+
+        cache.insert_guild(Id::<RoleMarker>::new(2), GUILD_ID, "guild".to_owned());
+        cache.insert_join_channel(
+            HashSet::new(),
+            HashSet::new(),
+            JOIN_CHANNEL_ID,
+            GUILD_ID,
+            VoiceChannelKind::Voice,
+            "join".to_owned(),
+            300,
+            OwnershipPolicy::Keep,
+            None,
+            false,
+            Vec::new(),
+            ChannelPrivacy::Unlocked,
+            None,
+            None,
+            false,
+        );
+        insert_voice_channel(&cache, VOICE_CHANNEL_ID, Some(OWNER_ID));
+        insert_voice_channel(&cache, OTHER_VOICE_CHANNEL_ID, None);
+
+        cache
+    }
+
+    fn insert_voice_channel(
+        cache: &Cache,
+        id: Id<ChannelMarker>,
+        owner_id: Option<Id<UserMarker>>,
+    ) {
+        cache.insert_voice_channel(
+            64_000,
+            id,
+            GUILD_ID,
+            Some(JOIN_CHANNEL_ID),
+            VoiceChannelKind::Voice,
+            "voice".to_owned(),
+            owner_id,
+            300,
+            OwnershipPolicy::Keep,
+            false,
+            Vec::new(),
+            ChannelPrivacy::Unlocked,
+            None,
+            None,
+            None,
+            None,
+            None,
+            VideoQualityMode::Auto,
+        );
+    }
+
+    fn replay(
+        cache: &Cache,
+        sequence: &[(Id<UserMarker>, Option<Id<ChannelMarker>>)],
+    ) -> Vec<Option<Id<ChannelMarker>>> {
+        sequence
+            .iter()
+            .map(|(user_id, channel_id)| cache.update_voice_state(GUILD_ID, *user_id, *channel_id))
+            .collect()
+    }
+
+    fn connected_user_ids(cache: &Cache, channel_id: Id<ChannelMarker>) -> Vec<Id<UserMarker>> {
+        let mut user_ids = cache
+            .voice_channel(channel_id)
+            .unwrap()
+            .connected_user_ids
+            .read()
+            .iter()
+            .copied()
+            .collect::<Vec<Id<UserMarker>>>();
+
+        user_ids.sort();
+
+        user_ids
+    }
+
+    #[test]
+    fn join_and_leave_managed_channel() {
+        let cache = cache();
+        let previous_channel_ids = replay(
+            &cache,
+            &[(OWNER_ID, Some(VOICE_CHANNEL_ID)), (OWNER_ID, None)],
+        );
+
+        assert_eq!(previous_channel_ids, vec![None, Some(VOICE_CHANNEL_ID)]);
+        assert!(connected_user_ids(&cache, VOICE_CHANNEL_ID).is_empty());
+        assert!(cache.voice_state(GUILD_ID, OWNER_ID).is_none());
+    }
+
+    #[test]
+    fn repeated_state_for_same_channel_keeps_member_connected() {
+        let cache = cache();
+        let previous_channel_ids = replay(
+            &cache,
+            &[
+                (OWNER_ID, Some(VOICE_CHANNEL_ID)),
+                (OWNER_ID, Some(VOICE_CHANNEL_ID)),
+            ],
+        );
+
+        assert_eq!(previous_channel_ids, vec![None, Some(VOICE_CHANNEL_ID)]);
+        assert_eq!(connected_user_ids(&cache, VOICE_CHANNEL_ID), vec![OWNER_ID]);
+    }
+
+    #[test]
+    fn move_from_managed_to_unmanaged_channel() {
+        let cache = cache();
+
+        replay(
+            &cache,
+            &[
+                (OWNER_ID, Some(VOICE_CHANNEL_ID)),
+                (MEMBER_ID, Some(VOICE_CHANNEL_ID)),
+                (OWNER_ID, Some(UNMANAGED_CHANNEL_ID)),
+            ],
+        );
+
+        assert_eq!(
+            connected_user_ids(&cache, VOICE_CHANNEL_ID),
+            vec![MEMBER_ID]
+        );
+        assert_eq!(
+            cache.voice_state(GUILD_ID, OWNER_ID).as_deref(),
+            Some(&UNMANAGED_CHANNEL_ID)
+        );
+    }
+
+    #[test]
+    fn move_between_managed_channels() {
+        let cache = cache();
+
+        replay(
+            &cache,
+            &[
+                (MEMBER_ID, Some(VOICE_CHANNEL_ID)),
+                (MEMBER_ID, Some(OTHER_VOICE_CHANNEL_ID)),
+            ],
+        );
+
+        assert!(connected_user_ids(&cache, VOICE_CHANNEL_ID).is_empty());
+        assert_eq!(
+            connected_user_ids(&cache, OTHER_VOICE_CHANNEL_ID),
+            vec![MEMBER_ID]
+        );
+    }
+
+    #[test]
+    fn join_channel_and_unmanaged_channel_states_are_tracked() {
+        let cache = cache();
+        let previous_channel_ids = replay(
+            &cache,
+            &[
+                (MEMBER_ID, Some(UNMANAGED_CHANNEL_ID)),
+                (MEMBER_ID, Some(JOIN_CHANNEL_ID)),
+            ],
+        );
+
+        assert_eq!(previous_channel_ids, vec![None, Some(UNMANAGED_CHANNEL_ID)]);
+        assert_eq!(
+            cache.voice_state(GUILD_ID, MEMBER_ID).as_deref(),
+            Some(&JOIN_CHANNEL_ID)
+        );
+    }
+
+    #[test]
+    fn voice_channel_inserted_after_members_connected() {
+        let cache = cache();
+        let channel_id = Id::new(40);
+
+        replay(
+            &cache,
+            &[
+                (OWNER_ID, Some(channel_id)),
+                (MEMBER_ID, Some(channel_id)),
+                (MEMBER_ID, Some(UNMANAGED_CHANNEL_ID)),
+                (MEMBER_ID, Some(channel_id)),
+                (OWNER_ID, None),
+            ],
+        );
+        insert_voice_channel(&cache, channel_id, Some(OWNER_ID));
+
+        assert_eq!(connected_user_ids(&cache, channel_id), vec![MEMBER_ID]);
+        assert!(cache
+            .channel_voice_states
+            .get(&UNMANAGED_CHANNEL_ID)
+            .is_none());
+    }
+
+    #[test]
+    fn voice_channels_are_counted_per_join_channel() {
+        let cache = cache();
+
+        assert_eq!(cache.join_channel_voice_channel_count(JOIN_CHANNEL_ID), 2);
+
+        cache.remove_voice_channel(VOICE_CHANNEL_ID);
+
+        assert_eq!(cache.join_channel_voice_channel_count(JOIN_CHANNEL_ID), 1);
+
+        cache.remove_voice_channel(OTHER_VOICE_CHANNEL_ID);
+
+        assert_eq!(cache.join_channel_voice_channel_count(JOIN_CHANNEL_ID), 0);
+        assert!(cache.join_channel_voice_channels.is_empty());
+    }
+
+    #[test]
+    fn owner_departures_and_join_times_are_tracked() {
+        let cache = cache();
+
+        replay(
+            &cache,
+            &[
+                (OWNER_ID, Some(VOICE_CHANNEL_ID)),
+                (MEMBER_ID, Some(VOICE_CHANNEL_ID)),
+            ],
+        );
+
+        let voice_channel = cache.voice_channel(VOICE_CHANNEL_ID).unwrap();
+
+        assert!(voice_channel.owner_left_at.read().is_none());
+        assert_eq!(voice_channel.join_times.read().len(), 2);
+
+        replay(&cache, &[(OWNER_ID, Some(UNMANAGED_CHANNEL_ID))]);
+
+        assert!(voice_channel.owner_left_at.read().is_some());
+        assert!(!voice_channel.join_times.read().contains_key(&OWNER_ID));
+
+        replay(&cache, &[(OWNER_ID, Some(VOICE_CHANNEL_ID))]);
+
+        assert!(voice_channel.owner_left_at.read().is_none());
+    }
+
+    #[test]
+    fn members_sharing_a_voice_channel_become_recent_contacts() {
+        let cache = cache();
+
+        cache.insert_voice_state(GUILD_ID, OWNER_ID, VOICE_CHANNEL_ID);
+        cache.insert_voice_state(GUILD_ID, MEMBER_ID, VOICE_CHANNEL_ID);
+
+        for user_id in 200..(200 + RECENT_CONTACTS_LIMIT as u64) {
+            cache.insert_voice_state(GUILD_ID, Id::new(user_id), VOICE_CHANNEL_ID);
+        }
+
+        let latest_user_id = Id::new(199 + RECENT_CONTACTS_LIMIT as u64);
+        let recent_contacts = cache.recent_contacts(GUILD_ID, OWNER_ID);
+
+        assert_eq!(recent_contacts.len(), RECENT_CONTACTS_LIMIT);
+        assert_eq!(recent_contacts[0], latest_user_id);
+        assert!(!recent_contacts.contains(&MEMBER_ID));
+        assert_eq!(
+            cache.recent_contacts(GUILD_ID, MEMBER_ID)[0],
+            latest_user_id
+        );
+
+        // Unmanaged channels aren't tracked.
+        cache.insert_voice_state(GUILD_ID, Id::new(300), UNMANAGED_CHANNEL_ID);
+        cache.insert_voice_state(GUILD_ID, Id::new(301), UNMANAGED_CHANNEL_ID);
+
+        assert!(cache.recent_contacts(GUILD_ID, Id::new(300)).is_empty());
+    }
+
+    #[test]
+    fn removed_voice_channel_clears_voice_states() {
+        let cache = cache();
+
+        replay(
+            &cache,
+            &[
+                (OWNER_ID, Some(VOICE_CHANNEL_ID)),
+                (MEMBER_ID, Some(JOIN_CHANNEL_ID)),
+            ],
+        );
+        cache.remove_voice_channel(VOICE_CHANNEL_ID);
+
+        assert!(cache.voice_state(GUILD_ID, OWNER_ID).is_none());
+        assert!(cache.voice_channel_owner(GUILD_ID, OWNER_ID).is_none());
+        assert_eq!(
+            cache.voice_state(GUILD_ID, MEMBER_ID).as_deref(),
+            Some(&JOIN_CHANNEL_ID)
+        );
+    }
+
+    #[test]
+    fn renames_are_rate_limited() {
+        let cache = cache();
+        let now = SystemTime::now();
+
+        assert_eq!(cache.rename_retry_after(VOICE_CHANNEL_ID, now), None);
+
+        cache.insert_rename_time(VOICE_CHANNEL_ID, now - Duration::from_secs(180));
+
+        assert_eq!(cache.rename_retry_after(VOICE_CHANNEL_ID, now), None);
+
+        cache.insert_rename_time(VOICE_CHANNEL_ID, now - Duration::from_secs(60));
+
+        assert_eq!(
+            cache.rename_retry_after(VOICE_CHANNEL_ID, now),
+            Some(Duration::from_secs(420))
+        );
+        assert_eq!(
+            cache.rename_retry_after(VOICE_CHANNEL_ID, now + Duration::from_secs(420)),
+            None
+        );
+
+        // The oldest rename stops counting once a third is made.
+        cache.insert_rename_time(VOICE_CHANNEL_ID, now + Duration::from_secs(420));
+
+        assert_eq!(
+            cache.rename_retry_after(VOICE_CHANNEL_ID, now + Duration::from_secs(420)),
+            Some(Duration::from_secs(120))
+        );
+        assert_eq!(cache.rename_retry_after(OTHER_VOICE_CHANNEL_ID, now), None);
+    }
+
+    #[test]
+    #[ignore = "benchmark, run with `cargo test --release -- --ignored --nocapture`"]
+    fn voice_state_update_bursts() {
+        const GUILD_COUNT: u64 = 5_000;
+        const CHANNELS_PER_GUILD: u64 = 4;
+        const EVENTS_PER_THREAD: u64 = 200_000;
+
+        let cache = Cache::new();
+
+        for guild_id in 1..=GUILD_COUNT {
+            let guild_id = Id::new(guild_id);
+
+            cache.insert_guild(Id::new(1), guild_id, "guild".to_owned());
+
+            for channel_index in 0..CHANNELS_PER_GUILD {
+                cache.insert_voice_channel(
+                    64_000,
+                    Id::new(guild_id.get() * CHANNELS_PER_GUILD + channel_index),
+                    guild_id,
+                    None,
+                    VoiceChannelKind::Voice,
+                    "voice".to_owned(),
+                    None,
+                    300,
+                    OwnershipPolicy::Keep,
+                    false,
+                    Vec::new(),
+                    ChannelPrivacy::Unlocked,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    VideoQualityMode::Auto,
+                );
+            }
+        }
+
+        for thread_count in [1, 2, 4, 8] {
+            let started_at = Instant::now();
+            let mut latencies = thread::scope(|scope| {
+                let handles = (0..thread_count)
+                    .map(|thread_index| {
+                        let cache = &cache;
+
+                        scope.spawn(move || {
+                            let mut latencies = Vec::with_capacity(EVENTS_PER_THREAD as usize);
+
+                            for event_index in 0..EVENTS_PER_THREAD {
+                                let seed = event_index * 7_919 + thread_index * 104_729;
+                                let guild_id = Id::new(seed % GUILD_COUNT + 1);
+                                let user_id = Id::new(seed % 64 + 1);
+                                let channel_id = (event_index % 5 != 0).then(|| {
+                                    Id::new(
+                                        guild_id.get() * CHANNELS_PER_GUILD
+                                            + seed % CHANNELS_PER_GUILD,
+                                    )
+                                });
+                                let event_started_at = Instant::now();
+
+                                cache.update_voice_state(guild_id, user_id, channel_id);
+                                latencies.push(event_started_at.elapsed());
+                            }
+
+                            latencies
+                        })
+                    })
+                    .collect::<Vec<_>>();
+
+                handles
+                    .into_iter()
+                    .flat_map(|handle| handle.join().unwrap())
+                    .collect::<Vec<Duration>>()
+            });
+            let elapsed = started_at.elapsed();
+
+            latencies.sort();
+
+            println!(
+                "{thread_count} thread(s): {:.0} events/s, p50 {:?}, p99 {:?}, max {:?}",
+                latencies.len() as f64 / elapsed.as_secs_f64(),
+                latencies[latencies.len() / 2],
+                latencies[latencies.len() * 99 / 100],
+                latencies[latencies.len() - 1],
+            );
+        }
     }
 }
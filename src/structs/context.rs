@@ -1,28 +1,36 @@
 use std::sync::Arc;
 
+use eyre::Result;
+
 use twilight_http::{client::InteractionClient, Client};
 use twilight_model::id::{marker::ApplicationMarker, Id};
 
-use super::{cache::Cache, database::Database};
+use super::{cache::Cache, database::Database, recorder::EventRecorder};
+use crate::utilities::constants::EVENT_RECORDING_PATH;
 
 pub struct Context {
     pub application_id: Id<ApplicationMarker>,
     pub cache: Cache,
     pub client: Arc<Client>,
     pub database: Database,
+    pub recorder: Option<EventRecorder>,
 }
 
 impl Context {
-    pub fn interaction_client(&self) -> InteractionClient {
+    pub fn interaction_client(&self) -> InteractionClient<'_> {
         self.client.interaction(self.application_id)
     }
 
-    pub fn new(application_id: Id<ApplicationMarker>, client: Client) -> Self {
-        Self {
+    pub fn new(application_id: Id<ApplicationMarker>, client: Client) -> Result<Self> {
+        Ok(Self {
             application_id,
             cache: Cache::new(),
             client: Arc::new(client),
             database: Database::new(),
-        }
+            recorder: EVENT_RECORDING_PATH
+                .as_deref()
+                .map(EventRecorder::new)
+                .transpose()?,
+        })
     }
 }
@@ -1,4 +1,4 @@
-use std::str::FromStr;
+use std::{str::FromStr, time::SystemTime};
 
 use deadpool_postgres::{Manager, ManagerConfig, Pool, RecyclingMethod};
 use eyre::{Result, WrapErr};
@@ -6,12 +6,15 @@ use tokio_postgres::{
     types::{FromSql, ToSql},
     Config, NoTls, Row,
 };
-use twilight_model::id::{
-    marker::{ChannelMarker, GuildMarker, RoleMarker, UserMarker},
-    Id,
+use twilight_model::{
+    channel::{Channel, VideoQualityMode},
+    id::{
+        marker::{ChannelMarker, GuildMarker, RoleMarker, UserMarker},
+        Id,
+    },
 };
 
-use crate::utilities::constants::DATABASE_URL;
+use crate::utilities::{constants::DATABASE_URL, time::snowflake_at};
 
 #[derive(Clone, Debug, Eq, FromSql, PartialEq, ToSql)]
 #[postgres(name = "channel_privacy")]
@@ -24,17 +27,162 @@ pub enum ChannelPrivacy {
     Unlocked,
 }
 
+/// What happens when a voice channel's overwrites, name, bitrate, user limit or region are edited
+/// outside of Voz so that they no longer match what it saved.
+#[derive(Clone, Copy, Debug, Eq, FromSql, PartialEq, ToSql)]
+#[postgres(name = "external_edit_policy")]
+pub enum ExternalEditPolicy {
+    /// The edited privacy level and settings are adopted.
+    #[postgres(name = "accept")]
+    Accept,
+    /// The edit is kept but recorded as an audit event.
+    #[postgres(name = "audit")]
+    Audit,
+    /// The saved privacy level and settings are put back.
+    #[postgres(name = "revert")]
+    Revert,
+}
+
+/// Whether a join channel role lets its members create voice channels or keeps them out.
+#[derive(Clone, Copy, Debug, Eq, FromSql, PartialEq, ToSql)]
+#[postgres(name = "join_channel_role_kind")]
+pub enum JoinChannelRoleKind {
+    #[postgres(name = "allow")]
+    Allow,
+    #[postgres(name = "deny")]
+    Deny,
+}
+
+/// Whether a name filter entry is a word matched anywhere in a name or a regular expression.
+#[derive(Clone, Copy, Debug, Eq, FromSql, PartialEq, ToSql)]
+#[postgres(name = "name_filter_kind")]
+pub enum NameFilterKind {
+    #[postgres(name = "pattern")]
+    Pattern,
+    #[postgres(name = "word")]
+    Word,
+}
+
+/// What happens to a voice channel once its owner has left it for the join channel's ownership
+/// delay.
+#[derive(Clone, Copy, Debug, Eq, FromSql, PartialEq, ToSql)]
+#[postgres(name = "ownership_policy")]
+pub enum OwnershipPolicy {
+    #[postgres(name = "claimable")]
+    Claimable,
+    #[postgres(name = "keep")]
+    Keep,
+    #[postgres(name = "transfer")]
+    Transfer,
+}
+
+/// Whether a member has `/voice trust`ed or `/voice block`ed another member.
+#[derive(Clone, Copy, Debug, Eq, FromSql, PartialEq, ToSql)]
+#[postgres(name = "relationship_kind")]
+pub enum RelationshipKind {
+    #[postgres(name = "blocked")]
+    Blocked,
+    #[postgres(name = "trusted")]
+    Trusted,
+}
+
+/// Whether a timed permission let a member in or kept them out.
+#[derive(Clone, Copy, Debug, Eq, FromSql, PartialEq, ToSql)]
+#[postgres(name = "timed_permission_kind")]
+pub enum TimedPermissionKind {
+    #[postgres(name = "allow")]
+    Allow,
+    #[postgres(name = "deny")]
+    Deny,
+}
+
+/// Whether a voice channel is an ordinary voice channel or a stage channel, where the owner
+/// moderates and members listen until invited to speak.
+#[derive(Clone, Copy, Debug, Eq, FromSql, PartialEq, ToSql)]
+#[postgres(name = "voice_channel_kind")]
+pub enum VoiceChannelKind {
+    #[postgres(name = "stage")]
+    Stage,
+    #[postgres(name = "voice")]
+    Voice,
+}
+
+pub struct AuditEvent {
+    pub action: String,
+    pub created_at: SystemTime,
+    pub description: String,
+}
+
 pub struct Database {
     pub pool: Pool,
 }
 
+pub struct GuildSettings {
+    pub blocked_dms_enabled: bool,
+    /// How many seconds a member waits between creating voice channels.
+    pub creation_cooldown: u32,
+    pub external_edit_policy: ExternalEditPolicy,
+    pub locale: String,
+    pub log_channel_id: Option<Id<ChannelMarker>>,
+    pub max_voice_channels: u8,
+    pub name_template: Option<String>,
+    pub presets_enabled: bool,
+    /// Whether voice channel names may only contain printable characters.
+    pub printable_names_only: bool,
+}
+
 pub struct JoinChannel {
     pub id: Id<ChannelMarker>,
     pub guild_id: Id<GuildMarker>,
-    pub access_role_id: Option<Id<RoleMarker>>,
+    pub allowed_role_ids: Vec<Id<RoleMarker>>,
+    pub denied_role_ids: Vec<Id<RoleMarker>>,
     pub parent_id: Option<Id<ChannelMarker>>,
     pub permanence: bool,
     pub privacy: ChannelPrivacy,
+    pub ownership_policy: OwnershipPolicy,
+    pub ownership_delay: u32,
+    pub waiting_room: bool,
+    pub status_template: Option<String>,
+    pub voice_channel_limit: Option<u16>,
+    /// The kind of voice channel the join channel creates.
+    pub kind: VoiceChannelKind,
+}
+
+/// A word or pattern that voice channel names in a guild may not contain.
+pub struct NameFilter {
+    pub kind: NameFilterKind,
+    pub value: String,
+}
+
+/// A member's `/voice allow-member` or `/voice deny-member` that lasts until `expires_at`.
+pub struct TimedPermission {
+    pub channel_id: Id<ChannelMarker>,
+    pub member_id: Id<UserMarker>,
+    pub kind: TimedPermissionKind,
+    pub expires_at: SystemTime,
+}
+
+/// The settings a member saved with `/voice preset save`, applied to each voice channel they
+/// create.
+pub struct UserPreset {
+    pub guild_id: Id<GuildMarker>,
+    pub user_id: Id<UserMarker>,
+    pub name: String,
+    pub bitrate: u32,
+    pub user_limit: Option<u32>,
+    pub rate_limit_per_user: Option<u16>,
+    pub rtc_region: Option<String>,
+    pub video_quality_mode: VideoQualityMode,
+    pub privacy: ChannelPrivacy,
+    pub allowed_user_ids: Vec<Id<UserMarker>>,
+    pub denied_user_ids: Vec<Id<UserMarker>>,
+}
+
+/// A member on another member's trusted or blocked list, applied to every voice channel the
+/// latter owns.
+pub struct UserRelationship {
+    pub member_id: Id<UserMarker>,
+    pub kind: RelationshipKind,
 }
 
 pub struct VoiceChannel {
@@ -43,6 +191,34 @@ pub struct VoiceChannel {
     pub owner_id: Option<Id<UserMarker>>,
     pub permanence: bool,
     pub privacy: ChannelPrivacy,
+    pub ownership_policy: OwnershipPolicy,
+    pub ownership_delay: u32,
+    pub waiting_room_id: Option<Id<ChannelMarker>>,
+    pub status: Option<String>,
+    pub join_channel_id: Option<Id<ChannelMarker>>,
+    pub kind: VoiceChannelKind,
+    /// `None` for voice channels saved before their settings were, until they're next seen.
+    pub settings: Option<VoiceChannelSettings>,
+}
+
+/// The settings of a voice channel as Voz last set them, so edits made outside of Voz stand out.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VoiceChannelSettings {
+    pub name: String,
+    pub bitrate: u32,
+    pub user_limit: Option<u32>,
+    pub rtc_region: Option<String>,
+}
+
+pub struct VoiceChannelManager {
+    pub channel_id: Id<ChannelMarker>,
+    pub user_id: Id<UserMarker>,
+}
+
+pub struct VoiceChannelMember {
+    pub channel_id: Id<ChannelMarker>,
+    pub user_id: Id<UserMarker>,
+    pub joined_at: SystemTime,
 }
 
 impl Database {
@@ -63,14 +239,98 @@ impl Database {
                 when duplicate_object then null;
             end $$;
 
+            -- ownership_policy enum
+            do $$
+            begin
+                create type ownership_policy as enum (
+                    'claimable',
+                    'keep',
+                    'transfer'
+                );
+            exception
+                when duplicate_object then null;
+            end $$;
+
+            -- relationship_kind enum
+            do $$
+            begin
+                create type relationship_kind as enum (
+                    'blocked',
+                    'trusted'
+                );
+            exception
+                when duplicate_object then null;
+            end $$;
+
+            -- external_edit_policy enum
+            do $$
+            begin
+                create type external_edit_policy as enum (
+                    'accept',
+                    'audit',
+                    'revert'
+                );
+            exception
+                when duplicate_object then null;
+            end $$;
+
+            -- join_channel_role_kind enum
+            do $$
+            begin
+                create type join_channel_role_kind as enum (
+                    'allow',
+                    'deny'
+                );
+            exception
+                when duplicate_object then null;
+            end $$;
+
+            -- name_filter_kind enum
+            do $$
+            begin
+                create type name_filter_kind as enum (
+                    'pattern',
+                    'word'
+                );
+            exception
+                when duplicate_object then null;
+            end $$;
+
+            -- timed_permission_kind enum
+            do $$
+            begin
+                create type timed_permission_kind as enum (
+                    'allow',
+                    'deny'
+                );
+            exception
+                when duplicate_object then null;
+            end $$;
+
+            -- voice_channel_kind enum
+            do $$
+            begin
+                create type voice_channel_kind as enum (
+                    'stage',
+                    'voice'
+                );
+            exception
+                when duplicate_object then null;
+            end $$;
+
             -- join_channel table
             create table if not exists public.join_channel (
                 id int8 primary key,
                 guild_id int8 not null,
-                access_role_id int8,
                 parent_id int8,
                 permanence boolean not null default false,
-                privacy channel_privacy not null default 'unlocked'
+                privacy channel_privacy not null default 'unlocked',
+                ownership_policy ownership_policy not null default 'keep',
+                ownership_delay int4 not null default 300,
+                waiting_room boolean not null default false,
+                status_template text,
+                voice_channel_limit int2,
+                kind voice_channel_kind not null default 'voice'
             );
 
             -- voice_channel table
@@ -79,12 +339,219 @@ impl Database {
                 guild_id int8 not null,
                 owner_id int8,
                 permanence boolean not null,
-                privacy channel_privacy not null
+                privacy channel_privacy not null,
+                ownership_policy ownership_policy not null default 'keep',
+                ownership_delay int4 not null default 300,
+                waiting_room_id int8,
+                status text,
+                join_channel_id int8,
+                kind voice_channel_kind not null default 'voice',
+                name text,
+                bitrate int4,
+                user_limit int4,
+                rtc_region text
+            );
+
+            -- Tables created before ownership policies existed
+            alter table join_channel
+                add column if not exists ownership_policy ownership_policy not null default 'keep',
+                add column if not exists ownership_delay int4 not null default 300;
+            alter table voice_channel
+                add column if not exists ownership_policy ownership_policy not null default 'keep',
+                add column if not exists ownership_delay int4 not null default 300;
+
+            -- Tables created before waiting rooms existed
+            alter table join_channel
+                add column if not exists waiting_room boolean not null default false;
+            alter table voice_channel
+                add column if not exists waiting_room_id int8;
+
+            -- Tables created before voice channel statuses existed
+            alter table join_channel
+                add column if not exists status_template text;
+            alter table voice_channel
+                add column if not exists status text;
+
+            -- Tables created before voice channel limits existed
+            alter table join_channel
+                add column if not exists voice_channel_limit int2;
+            alter table voice_channel
+                add column if not exists join_channel_id int8;
+
+            -- Tables created before stage channels existed
+            alter table join_channel
+                add column if not exists kind voice_channel_kind not null default 'voice';
+            alter table voice_channel
+                add column if not exists kind voice_channel_kind not null default 'voice';
+
+            -- Tables created before voice channel settings were saved
+            alter table voice_channel
+                add column if not exists name text,
+                add column if not exists bitrate int4,
+                add column if not exists user_limit int4,
+                add column if not exists rtc_region text;
+
+            -- join_channel_role table
+            create table if not exists public.join_channel_role (
+                channel_id int8 not null references join_channel(id) on delete cascade,
+                role_id int8 not null,
+                kind join_channel_role_kind not null,
+                primary key (channel_id, role_id)
+            );
+
+            -- Join channels created before join channel roles existed keep their access role
+            do $$
+            begin
+                if exists (
+                    select
+                        1
+                    from
+                        information_schema.columns
+                    where
+                        table_name = 'join_channel'
+                        and column_name = 'access_role_id'
+                ) then
+                    insert into
+                        join_channel_role
+                    select
+                        id,
+                        access_role_id,
+                        'allow'
+                    from
+                        join_channel
+                    where
+                        access_role_id is not null
+                    on conflict
+                    do nothing;
+
+                    alter table join_channel
+                        drop column access_role_id;
+                end if;
+            end $$;
+
+            -- voice_channel_manager table
+            create table if not exists public.voice_channel_manager (
+                channel_id int8 not null references voice_channel(id) on delete cascade,
+                user_id int8 not null,
+                primary key (channel_id, user_id)
+            );
+
+            -- voice_channel_member table
+            create table if not exists public.voice_channel_member (
+                channel_id int8 not null references voice_channel(id) on delete cascade,
+                user_id int8 not null,
+                joined_at timestamptz not null,
+                primary key (channel_id, user_id)
+            );
+
+            -- timed_permission table
+            create table if not exists public.timed_permission (
+                channel_id int8 not null references voice_channel(id) on delete cascade,
+                member_id int8 not null,
+                kind timed_permission_kind not null,
+                expires_at timestamptz not null,
+                primary key (channel_id, member_id)
+            );
+
+            -- guild_settings table
+            create table if not exists public.guild_settings (
+                guild_id int8 primary key,
+                presets_enabled boolean not null default true,
+                log_channel_id int8,
+                max_voice_channels int2 not null default 1,
+                name_template text,
+                locale text not null default 'en-US',
+                blocked_dms_enabled boolean not null default true,
+                creation_cooldown int4 not null default 0,
+                printable_names_only boolean not null default false,
+                external_edit_policy external_edit_policy not null default 'accept'
+            );
+
+            -- Tables created before guild-wide settings existed
+            alter table guild_settings
+                add column if not exists log_channel_id int8,
+                add column if not exists max_voice_channels int2 not null default 1,
+                add column if not exists name_template text,
+                add column if not exists locale text not null default 'en-US';
+
+            -- Tables created before blocklists existed
+            alter table guild_settings
+                add column if not exists blocked_dms_enabled boolean not null default true;
+
+            -- Tables created before creation cooldowns existed
+            alter table guild_settings
+                add column if not exists creation_cooldown int4 not null default 0;
+
+            -- Tables created before name filters existed
+            alter table guild_settings
+                add column if not exists printable_names_only boolean not null default false;
+
+            -- Tables created before external edit policies existed
+            alter table guild_settings
+                add column if not exists external_edit_policy external_edit_policy not null default 'accept';
+
+            -- user_preset table
+            create table if not exists public.user_preset (
+                guild_id int8 not null,
+                user_id int8 not null,
+                name text not null,
+                bitrate int4 not null,
+                user_limit int4,
+                rate_limit_per_user int4,
+                rtc_region text,
+                video_quality_mode int2 not null,
+                privacy channel_privacy not null,
+                allowed_user_ids int8[] not null default '{}',
+                denied_user_ids int8[] not null default '{}',
+                primary key (guild_id, user_id)
+            );
+
+            -- user_relationship table
+            create table if not exists public.user_relationship (
+                guild_id int8 not null,
+                user_id int8 not null,
+                member_id int8 not null,
+                kind relationship_kind not null,
+                primary key (guild_id, user_id, member_id)
+            );
+
+            -- blocked_member table
+            create table if not exists public.blocked_member (
+                guild_id int8 not null,
+                user_id int8 not null,
+                primary key (guild_id, user_id)
             );
 
+            -- blocked_role table
+            create table if not exists public.blocked_role (
+                guild_id int8 not null,
+                role_id int8 not null,
+                primary key (guild_id, role_id)
+            );
+
+            -- name_filter table
+            create table if not exists public.name_filter (
+                guild_id int8 not null,
+                kind name_filter_kind not null,
+                value text not null,
+                primary key (guild_id, kind, value)
+            );
+
+            -- audit_event table
+            create table if not exists public.audit_event (
+                id bigserial primary key,
+                guild_id int8 not null,
+                channel_id int8,
+                user_id int8,
+                action text not null,
+                description text not null,
+                created_at timestamptz not null default now()
+            );
+
+            create index if not exists audit_event_guild_id_idx on audit_event(guild_id, id);
             create index if not exists join_channel_guild_id_idx on join_channel(guild_id);
-            create index if not exists join_channel_access_role_id_idx on join_channel(access_role_id);
             create index if not exists voice_channel_guild_id_idx on voice_channel(guild_id);
+            create index if not exists timed_permission_expires_at_idx on timed_permission(expires_at);
         ";
 
         client
@@ -95,17 +562,161 @@ impl Database {
         Ok(())
     }
 
+    /// Every timed permission that has expired by `now`, soonest first.
+    pub async fn due_timed_permissions(&self, now: SystemTime) -> Result<Vec<TimedPermission>> {
+        let client = self.pool.get().await?;
+        let rows = client
+            .query(
+                "
+                    select
+                        *
+                    from
+                        timed_permission
+                    where
+                        expires_at <= $1
+                    order by
+                        expires_at;
+                ",
+                &[&now],
+            )
+            .await
+            .wrap_err("I'm unable to run the \"due_timed_permissions\" endpoint.")?;
+
+        Ok(rows.into_iter().map(TimedPermission::from).collect())
+    }
+
+    /// One page of a server's audit events, newest first, along with how many match in total.
+    pub async fn guild_audit_events(
+        &self,
+        guild_id: Id<GuildMarker>,
+        user_id: Option<Id<UserMarker>>,
+        channel_id: Option<Id<ChannelMarker>>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<AuditEvent>, i64)> {
+        let client = self.pool.get().await?;
+        let guild_id = guild_id.get() as i64;
+        let user_id = user_id.map(|id| id.get() as i64);
+        let channel_id = channel_id.map(|id| id.get() as i64);
+        let rows = client
+            .query(
+                "
+                    select
+                        *
+                    from
+                        audit_event
+                    where
+                        guild_id = $1
+                        and ($2::int8 is null or user_id = $2)
+                        and ($3::int8 is null or channel_id = $3)
+                    order by
+                        id desc
+                    limit
+                        $4
+                    offset
+                        $5;
+                ",
+                &[&guild_id, &user_id, &channel_id, &limit, &offset],
+            )
+            .await
+            .wrap_err(
+                "I'm unable to run the first query of the \"guild_audit_events\" endpoint.",
+            )?;
+        let total = client
+            .query_one(
+                "
+                    select
+                        count(*)
+                    from
+                        audit_event
+                    where
+                        guild_id = $1
+                        and ($2::int8 is null or user_id = $2)
+                        and ($3::int8 is null or channel_id = $3);
+                ",
+                &[&guild_id, &user_id, &channel_id],
+            )
+            .await
+            .wrap_err("I'm unable to run the second query of the \"guild_audit_events\" endpoint.")?
+            .get::<_, i64>(0);
+
+        Ok((rows.into_iter().map(AuditEvent::from).collect(), total))
+    }
+
+    pub async fn guild_blocked_members(
+        &self,
+        guild_id: Id<GuildMarker>,
+    ) -> Result<Vec<Id<UserMarker>>> {
+        let client = self.pool.get().await?;
+        let rows = client
+            .query(
+                "
+                    select
+                        user_id
+                    from
+                        blocked_member
+                    where
+                        guild_id = $1;
+                ",
+                &[&(guild_id.get() as i64)],
+            )
+            .await
+            .wrap_err("I'm unable to run the \"guild_blocked_members\" endpoint.")?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| Id::new(row.get::<_, i64>("user_id") as u64))
+            .collect())
+    }
+
+    pub async fn guild_blocked_roles(
+        &self,
+        guild_id: Id<GuildMarker>,
+    ) -> Result<Vec<Id<RoleMarker>>> {
+        let client = self.pool.get().await?;
+        let rows = client
+            .query(
+                "
+                    select
+                        role_id
+                    from
+                        blocked_role
+                    where
+                        guild_id = $1;
+                ",
+                &[&(guild_id.get() as i64)],
+            )
+            .await
+            .wrap_err("I'm unable to run the \"guild_blocked_roles\" endpoint.")?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| Id::new(row.get::<_, i64>("role_id") as u64))
+            .collect())
+    }
+
     pub async fn guild_join_channels(&self, guild_id: Id<GuildMarker>) -> Result<Vec<JoinChannel>> {
         let client = self.pool.get().await?;
         let rows_result = client
             .query(
                 "
                     select
-                        *
+                        join_channel.*,
+                        coalesce(
+                            array_agg(join_channel_role.role_id) filter (where join_channel_role.kind = 'allow'),
+                            '{}'
+                        ) as allowed_role_ids,
+                        coalesce(
+                            array_agg(join_channel_role.role_id) filter (where join_channel_role.kind = 'deny'),
+                            '{}'
+                        ) as denied_role_ids
                     from
                         join_channel
+                        left join join_channel_role on join_channel_role.channel_id = join_channel.id
                     where
-                        guild_id = $1;
+                        join_channel.guild_id = $1
+                    group by
+                        join_channel.id;
                 ",
                 &[&(guild_id.get() as i64)],
             )
@@ -114,12 +725,57 @@ impl Database {
         let join_channels = rows_result
             .unwrap_or_default()
             .into_iter()
-            .map(|row| JoinChannel::from(row))
+            .map(JoinChannel::from)
             .collect::<Vec<JoinChannel>>();
 
         Ok(join_channels)
     }
 
+    pub async fn guild_name_filters(&self, guild_id: Id<GuildMarker>) -> Result<Vec<NameFilter>> {
+        let client = self.pool.get().await?;
+        let rows = client
+            .query(
+                "
+                    select
+                        kind,
+                        value
+                    from
+                        name_filter
+                    where
+                        guild_id = $1
+                    order by
+                        kind,
+                        value;
+                ",
+                &[&(guild_id.get() as i64)],
+            )
+            .await
+            .wrap_err("I'm unable to run the \"guild_name_filters\" endpoint.")?;
+
+        Ok(rows.into_iter().map(NameFilter::from).collect())
+    }
+
+    pub async fn guild_settings(&self, guild_id: Id<GuildMarker>) -> Result<GuildSettings> {
+        let client = self.pool.get().await?;
+        let row = client
+            .query_opt(
+                "
+                    select
+                        *
+                    from
+                        guild_settings
+                    where
+                        guild_id = $1;
+                ",
+                &[&(guild_id.get() as i64)],
+            )
+            .await
+            .wrap_err("I'm unable to run the \"guild_settings\" endpoint.")?;
+        let guild_settings = row.map_or_else(GuildSettings::default, GuildSettings::from);
+
+        Ok(guild_settings)
+    }
+
     pub async fn guild_voice_channels(
         &self,
         guild_id: Id<GuildMarker>,
@@ -142,40 +798,180 @@ impl Database {
         let voice_channels = rows_result
             .unwrap_or_default()
             .into_iter()
-            .map(|row| VoiceChannel::from(row))
+            .map(VoiceChannel::from)
             .collect::<Vec<VoiceChannel>>();
 
         Ok(voice_channels)
     }
 
-    pub async fn insert_join_channel(
+    pub async fn guild_voice_channel_managers(
         &self,
-        id: Id<ChannelMarker>,
         guild_id: Id<GuildMarker>,
-        access_role_id: Option<Id<RoleMarker>>,
-        parent_id: Option<Id<ChannelMarker>>,
-        permanence: bool,
-        privacy: ChannelPrivacy,
-    ) -> Result<()> {
+    ) -> Result<Vec<VoiceChannelManager>> {
         let client = self.pool.get().await?;
-
-        client
-            .execute(
+        let rows_result = client
+            .query(
                 "
-                    insert into
-                        join_channel
-                    values
-                        ($1, $2, $3, $4, $5, $6)
-                    on conflict
-                    do nothing;
-                ",
-                &[
+                    select
+                        voice_channel_manager.*
+                    from
+                        voice_channel_manager
+                        inner join voice_channel on voice_channel.id = voice_channel_manager.channel_id
+                    where
+                        voice_channel.guild_id = $1;
+                ",
+                &[&(guild_id.get() as i64)],
+            )
+            .await
+            .wrap_err("I'm unable to run the \"guild_voice_channel_managers\" endpoint.");
+        let voice_channel_managers = rows_result
+            .unwrap_or_default()
+            .into_iter()
+            .map(VoiceChannelManager::from)
+            .collect::<Vec<VoiceChannelManager>>();
+
+        Ok(voice_channel_managers)
+    }
+
+    pub async fn guild_voice_channel_members(
+        &self,
+        guild_id: Id<GuildMarker>,
+    ) -> Result<Vec<VoiceChannelMember>> {
+        let client = self.pool.get().await?;
+        let rows_result = client
+            .query(
+                "
+                    select
+                        voice_channel_member.*
+                    from
+                        voice_channel_member
+                        inner join voice_channel on voice_channel.id = voice_channel_member.channel_id
+                    where
+                        voice_channel.guild_id = $1;
+                ",
+                &[&(guild_id.get() as i64)],
+            )
+            .await
+            .wrap_err("I'm unable to run the \"guild_voice_channel_members\" endpoint.");
+        let voice_channel_members = rows_result
+            .unwrap_or_default()
+            .into_iter()
+            .map(VoiceChannelMember::from)
+            .collect::<Vec<VoiceChannelMember>>();
+
+        Ok(voice_channel_members)
+    }
+
+    pub async fn insert_audit_event(
+        &self,
+        guild_id: Id<GuildMarker>,
+        channel_id: Option<Id<ChannelMarker>>,
+        user_id: Option<Id<UserMarker>>,
+        action: &str,
+        description: &str,
+    ) -> Result<()> {
+        let client = self.pool.get().await?;
+
+        client
+            .execute(
+                "
+                    insert into
+                        audit_event (guild_id, channel_id, user_id, action, description)
+                    values
+                        ($1, $2, $3, $4, $5);
+                ",
+                &[
+                    &(guild_id.get() as i64),
+                    &channel_id.map(|id| id.get() as i64),
+                    &user_id.map(|id| id.get() as i64),
+                    &action,
+                    &description,
+                ],
+            )
+            .await
+            .wrap_err("I'm unable to run the \"insert_audit_event\" endpoint.")?;
+
+        Ok(())
+    }
+
+    pub async fn insert_blocked_member(
+        &self,
+        guild_id: Id<GuildMarker>,
+        user_id: Id<UserMarker>,
+    ) -> Result<()> {
+        let client = self.pool.get().await?;
+
+        client
+            .execute(
+                "
+                    insert into
+                        blocked_member
+                    values
+                        ($1, $2)
+                    on conflict
+                    do nothing;
+                ",
+                &[&(guild_id.get() as i64), &(user_id.get() as i64)],
+            )
+            .await
+            .wrap_err("I'm unable to run the \"insert_blocked_member\" endpoint.")?;
+
+        Ok(())
+    }
+
+    pub async fn insert_blocked_role(
+        &self,
+        guild_id: Id<GuildMarker>,
+        role_id: Id<RoleMarker>,
+    ) -> Result<()> {
+        let client = self.pool.get().await?;
+
+        client
+            .execute(
+                "
+                    insert into
+                        blocked_role
+                    values
+                        ($1, $2)
+                    on conflict
+                    do nothing;
+                ",
+                &[&(guild_id.get() as i64), &(role_id.get() as i64)],
+            )
+            .await
+            .wrap_err("I'm unable to run the \"insert_blocked_role\" endpoint.")?;
+
+        Ok(())
+    }
+
+    pub async fn insert_join_channel(
+        &self,
+        id: Id<ChannelMarker>,
+        guild_id: Id<GuildMarker>,
+        parent_id: Option<Id<ChannelMarker>>,
+        permanence: bool,
+        privacy: ChannelPrivacy,
+        kind: VoiceChannelKind,
+    ) -> Result<()> {
+        let client = self.pool.get().await?;
+
+        client
+            .execute(
+                "
+                    insert into
+                        join_channel (id, guild_id, parent_id, permanence, privacy, kind)
+                    values
+                        ($1, $2, $3, $4, $5, $6)
+                    on conflict
+                    do nothing;
+                ",
+                &[
                     &(id.get() as i64),
                     &(guild_id.get() as i64),
-                    &access_role_id.map(|access_role_id| access_role_id.get() as i64),
                     &parent_id.map(|parent_id| parent_id.get() as i64),
                     &permanence,
                     &privacy,
+                    &kind,
                 ],
             )
             .await
@@ -184,6 +980,180 @@ impl Database {
         Ok(())
     }
 
+    pub async fn insert_join_channel_role(
+        &self,
+        channel_id: Id<ChannelMarker>,
+        role_id: Id<RoleMarker>,
+        kind: JoinChannelRoleKind,
+    ) -> Result<()> {
+        let client = self.pool.get().await?;
+
+        client
+            .execute(
+                "
+                    insert into
+                        join_channel_role
+                    values
+                        ($1, $2, $3)
+                    on conflict (channel_id, role_id)
+                    do update set
+                        kind = excluded.kind;
+                ",
+                &[&(channel_id.get() as i64), &(role_id.get() as i64), &kind],
+            )
+            .await
+            .wrap_err("I'm unable to run the \"insert_join_channel_role\" endpoint.")?;
+
+        Ok(())
+    }
+
+    pub async fn insert_name_filter(
+        &self,
+        guild_id: Id<GuildMarker>,
+        kind: NameFilterKind,
+        value: &str,
+    ) -> Result<()> {
+        let client = self.pool.get().await?;
+
+        client
+            .execute(
+                "
+                    insert into
+                        name_filter
+                    values
+                        ($1, $2, $3)
+                    on conflict
+                    do nothing;
+                ",
+                &[&(guild_id.get() as i64), &kind, &value],
+            )
+            .await
+            .wrap_err("I'm unable to run the \"insert_name_filter\" endpoint.")?;
+
+        Ok(())
+    }
+
+    pub async fn insert_timed_permission(
+        &self,
+        channel_id: Id<ChannelMarker>,
+        member_id: Id<UserMarker>,
+        kind: TimedPermissionKind,
+        expires_at: SystemTime,
+    ) -> Result<()> {
+        let client = self.pool.get().await?;
+
+        client
+            .execute(
+                "
+                    insert into
+                        timed_permission
+                    values
+                        ($1, $2, $3, $4)
+                    on conflict (channel_id, member_id)
+                    do update set
+                        kind = excluded.kind,
+                        expires_at = excluded.expires_at;
+                ",
+                &[
+                    &(channel_id.get() as i64),
+                    &(member_id.get() as i64),
+                    &kind,
+                    &expires_at,
+                ],
+            )
+            .await
+            .wrap_err("I'm unable to run the \"insert_timed_permission\" endpoint.")?;
+
+        Ok(())
+    }
+
+    pub async fn insert_user_preset(&self, user_preset: &UserPreset) -> Result<()> {
+        let client = self.pool.get().await?;
+
+        client
+            .execute(
+                "
+                    insert into
+                        user_preset
+                    values
+                        ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+                    on conflict (guild_id, user_id)
+                    do update set
+                        name = excluded.name,
+                        bitrate = excluded.bitrate,
+                        user_limit = excluded.user_limit,
+                        rate_limit_per_user = excluded.rate_limit_per_user,
+                        rtc_region = excluded.rtc_region,
+                        video_quality_mode = excluded.video_quality_mode,
+                        privacy = excluded.privacy,
+                        allowed_user_ids = excluded.allowed_user_ids,
+                        denied_user_ids = excluded.denied_user_ids;
+                ",
+                &[
+                    &(user_preset.guild_id.get() as i64),
+                    &(user_preset.user_id.get() as i64),
+                    &user_preset.name,
+                    &(user_preset.bitrate as i32),
+                    &user_preset.user_limit.map(|user_limit| user_limit as i32),
+                    &user_preset
+                        .rate_limit_per_user
+                        .map(|rate_limit_per_user| rate_limit_per_user as i32),
+                    &user_preset.rtc_region,
+                    &(u8::from(user_preset.video_quality_mode) as i16),
+                    &user_preset.privacy,
+                    &user_preset
+                        .allowed_user_ids
+                        .iter()
+                        .map(|user_id| user_id.get() as i64)
+                        .collect::<Vec<i64>>(),
+                    &user_preset
+                        .denied_user_ids
+                        .iter()
+                        .map(|user_id| user_id.get() as i64)
+                        .collect::<Vec<i64>>(),
+                ],
+            )
+            .await
+            .wrap_err("I'm unable to run the \"insert_user_preset\" endpoint.")?;
+
+        Ok(())
+    }
+
+    /// Puts `member_id` on the member's `kind` list, moving them off the other list if needed.
+    pub async fn insert_user_relationship(
+        &self,
+        guild_id: Id<GuildMarker>,
+        user_id: Id<UserMarker>,
+        member_id: Id<UserMarker>,
+        kind: RelationshipKind,
+    ) -> Result<()> {
+        let client = self.pool.get().await?;
+
+        client
+            .execute(
+                "
+                    insert into
+                        user_relationship
+                    values
+                        ($1, $2, $3, $4)
+                    on conflict (guild_id, user_id, member_id)
+                    do update set
+                        kind = excluded.kind;
+                ",
+                &[
+                    &(guild_id.get() as i64),
+                    &(user_id.get() as i64),
+                    &(member_id.get() as i64),
+                    &kind,
+                ],
+            )
+            .await
+            .wrap_err("I'm unable to run the \"insert_user_relationship\" endpoint.")?;
+
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
     pub async fn insert_voice_channel(
         &self,
         id: Id<ChannelMarker>,
@@ -191,6 +1161,11 @@ impl Database {
         owner_id: Option<Id<UserMarker>>,
         permanence: bool,
         privacy: ChannelPrivacy,
+        ownership_policy: OwnershipPolicy,
+        ownership_delay: u32,
+        join_channel_id: Option<Id<ChannelMarker>>,
+        kind: VoiceChannelKind,
+        settings: &VoiceChannelSettings,
     ) -> Result<()> {
         let client = self.pool.get().await?;
 
@@ -200,7 +1175,7 @@ impl Database {
                     insert into
                         voice_channel
                     values
-                        ($1, $2, $3, $4, $5)
+                        ($1, $2, $3, $4, $5, $6, $7, null, null, $8, $9, $10, $11, $12, $13)
                     on conflict
                     do nothing;
                 ",
@@ -210,6 +1185,14 @@ impl Database {
                     &owner_id.map(|owner_id| owner_id.get() as i64),
                     &permanence,
                     &privacy,
+                    &ownership_policy,
+                    &(ownership_delay as i32),
+                    &join_channel_id.map(|join_channel_id| join_channel_id.get() as i64),
+                    &kind,
+                    &settings.name,
+                    &(settings.bitrate as i32),
+                    &settings.user_limit.map(|user_limit| user_limit as i32),
+                    &settings.rtc_region,
                 ],
             )
             .await
@@ -218,6 +1201,62 @@ impl Database {
         Ok(())
     }
 
+    pub async fn insert_voice_channel_manager(
+        &self,
+        channel_id: Id<ChannelMarker>,
+        user_id: Id<UserMarker>,
+    ) -> Result<()> {
+        let client = self.pool.get().await?;
+
+        client
+            .execute(
+                "
+                    insert into
+                        voice_channel_manager
+                    values
+                        ($1, $2)
+                    on conflict
+                    do nothing;
+                ",
+                &[&(channel_id.get() as i64), &(user_id.get() as i64)],
+            )
+            .await
+            .wrap_err("I'm unable to run the \"insert_voice_channel_manager\" endpoint.")?;
+
+        Ok(())
+    }
+
+    pub async fn insert_voice_channel_member(
+        &self,
+        channel_id: Id<ChannelMarker>,
+        user_id: Id<UserMarker>,
+        joined_at: SystemTime,
+    ) -> Result<()> {
+        let client = self.pool.get().await?;
+
+        client
+            .execute(
+                "
+                    insert into
+                        voice_channel_member
+                    values
+                        ($1, $2, $3)
+                    on conflict (channel_id, user_id)
+                    do update set
+                        joined_at = excluded.joined_at;
+                ",
+                &[
+                    &(channel_id.get() as i64),
+                    &(user_id.get() as i64),
+                    &joined_at,
+                ],
+            )
+            .await
+            .wrap_err("I'm unable to run the \"insert_voice_channel_member\" endpoint.")?;
+
+        Ok(())
+    }
+
     pub fn new() -> Self {
         Self {
             pool: Pool::builder(Manager::from_config(
@@ -234,134 +1273,814 @@ impl Database {
         }
     }
 
-    pub async fn remove_guild(&self, id: Id<GuildMarker>) -> Result<()> {
-        let mut client = self.pool.get().await?;
-        let transaction = client.transaction().await?;
-        let params: &[&(dyn ToSql + Sync)] = &[&(id.get() as i64)];
+    pub async fn remove_blocked_member(
+        &self,
+        guild_id: Id<GuildMarker>,
+        user_id: Id<UserMarker>,
+    ) -> Result<()> {
+        let client = self.pool.get().await?;
 
-        transaction
+        client
             .execute(
                 "
                     delete from
-                        join_channel
+                        blocked_member
                     where
-                        guild_id = $1;
+                        guild_id = $1
+                        and user_id = $2;
                 ",
-                params,
+                &[&(guild_id.get() as i64), &(user_id.get() as i64)],
             )
             .await
-            .wrap_err("I'm unable to run the first query of the \"remove_guild\" endpoint.")?;
-        transaction
-            .execute(
-                "
-                    delete from
-                        voice_channel
-                    where
-                        guild_id = $1;
-                ",
-                params,
+            .wrap_err("I'm unable to run the \"remove_blocked_member\" endpoint.")?;
+
+        Ok(())
+    }
+
+    pub async fn remove_blocked_role(
+        &self,
+        guild_id: Id<GuildMarker>,
+        role_id: Id<RoleMarker>,
+    ) -> Result<()> {
+        let client = self.pool.get().await?;
+
+        client
+            .execute(
+                "
+                    delete from
+                        blocked_role
+                    where
+                        guild_id = $1
+                        and role_id = $2;
+                ",
+                &[&(guild_id.get() as i64), &(role_id.get() as i64)],
+            )
+            .await
+            .wrap_err("I'm unable to run the \"remove_blocked_role\" endpoint.")?;
+
+        Ok(())
+    }
+
+    pub async fn remove_guild(&self, id: Id<GuildMarker>) -> Result<()> {
+        let mut client = self.pool.get().await?;
+        let transaction = client.transaction().await?;
+        let params: &[&(dyn ToSql + Sync)] = &[&(id.get() as i64)];
+
+        transaction
+            .execute(
+                "
+                    delete from
+                        join_channel
+                    where
+                        guild_id = $1;
+                ",
+                params,
+            )
+            .await
+            .wrap_err("I'm unable to run the first query of the \"remove_guild\" endpoint.")?;
+        transaction
+            .execute(
+                "
+                    delete from
+                        voice_channel
+                    where
+                        guild_id = $1;
+                ",
+                params,
             )
             .await
             .wrap_err("I'm unable to run the second query of the \"remove_guild\" endpoint.")?;
+        transaction
+            .execute(
+                "
+                    delete from
+                        guild_settings
+                    where
+                        guild_id = $1;
+                ",
+                params,
+            )
+            .await
+            .wrap_err("I'm unable to run the third query of the \"remove_guild\" endpoint.")?;
+        transaction
+            .execute(
+                "
+                    delete from
+                        user_preset
+                    where
+                        guild_id = $1;
+                ",
+                params,
+            )
+            .await
+            .wrap_err("I'm unable to run the fourth query of the \"remove_guild\" endpoint.")?;
+        transaction
+            .execute(
+                "
+                    delete from
+                        user_relationship
+                    where
+                        guild_id = $1;
+                ",
+                params,
+            )
+            .await
+            .wrap_err("I'm unable to run the fifth query of the \"remove_guild\" endpoint.")?;
+        transaction
+            .execute(
+                "
+                    delete from
+                        audit_event
+                    where
+                        guild_id = $1;
+                ",
+                params,
+            )
+            .await
+            .wrap_err("I'm unable to run the sixth query of the \"remove_guild\" endpoint.")?;
+        transaction
+            .execute(
+                "
+                    delete from
+                        blocked_member
+                    where
+                        guild_id = $1;
+                ",
+                params,
+            )
+            .await
+            .wrap_err("I'm unable to run the seventh query of the \"remove_guild\" endpoint.")?;
+        transaction
+            .execute(
+                "
+                    delete from
+                        blocked_role
+                    where
+                        guild_id = $1;
+                ",
+                params,
+            )
+            .await
+            .wrap_err("I'm unable to run the eighth query of the \"remove_guild\" endpoint.")?;
+        transaction
+            .execute(
+                "
+                    delete from
+                        name_filter
+                    where
+                        guild_id = $1;
+                ",
+                params,
+            )
+            .await
+            .wrap_err("I'm unable to run the ninth query of the \"remove_guild\" endpoint.")?;
+
+        transaction.commit().await?;
+
+        Ok(())
+    }
+
+    pub async fn remove_join_channel(&self, id: Id<ChannelMarker>) -> Result<()> {
+        let client = self.pool.get().await?;
+
+        client
+            .execute(
+                "
+                    delete from
+                        join_channel
+                    where
+                        id = $1;
+                ",
+                &[&(id.get() as i64)],
+            )
+            .await
+            .wrap_err("I'm unable to run the \"remove_join_channel\" endpoint.")?;
+
+        Ok(())
+    }
+
+    pub async fn remove_join_channel_role(
+        &self,
+        channel_id: Id<ChannelMarker>,
+        role_id: Id<RoleMarker>,
+    ) -> Result<()> {
+        let client = self.pool.get().await?;
+
+        client
+            .execute(
+                "
+                    delete from
+                        join_channel_role
+                    where
+                        channel_id = $1
+                        and role_id = $2;
+                ",
+                &[&(channel_id.get() as i64), &(role_id.get() as i64)],
+            )
+            .await
+            .wrap_err("I'm unable to run the \"remove_join_channel_role\" endpoint.")?;
+
+        Ok(())
+    }
+
+    pub async fn remove_name_filter(
+        &self,
+        guild_id: Id<GuildMarker>,
+        kind: NameFilterKind,
+        value: &str,
+    ) -> Result<bool> {
+        let client = self.pool.get().await?;
+        let removed_rows = client
+            .execute(
+                "
+                    delete from
+                        name_filter
+                    where
+                        guild_id = $1
+                        and kind = $2
+                        and value = $3;
+                ",
+                &[&(guild_id.get() as i64), &kind, &value],
+            )
+            .await
+            .wrap_err("I'm unable to run the \"remove_name_filter\" endpoint.")?;
+
+        Ok(removed_rows.gt(&0))
+    }
+
+    pub async fn remove_timed_permission(
+        &self,
+        channel_id: Id<ChannelMarker>,
+        member_id: Id<UserMarker>,
+    ) -> Result<bool> {
+        let client = self.pool.get().await?;
+        let removed_rows = client
+            .execute(
+                "
+                    delete from
+                        timed_permission
+                    where
+                        channel_id = $1
+                        and member_id = $2;
+                ",
+                &[&(channel_id.get() as i64), &(member_id.get() as i64)],
+            )
+            .await
+            .wrap_err("I'm unable to run the \"remove_timed_permission\" endpoint.")?;
+
+        Ok(removed_rows.gt(&0))
+    }
+
+    /// Removes the guild's join and voice channels missing from `channel_ids`, a snapshot of its
+    /// channels taken at `snapshot_time`. Channels created since then can't be in the snapshot,
+    /// so they're kept.
+    pub async fn remove_unknown_channels(
+        &self,
+        channel_ids: Vec<Id<ChannelMarker>>,
+        guild_id: Id<GuildMarker>,
+        snapshot_time: SystemTime,
+    ) -> Result<u64> {
+        let mut client = self.pool.get().await?;
+        let transaction = client.transaction().await?;
+        let params: &[&(dyn ToSql + Sync)] = &[
+            &channel_ids
+                .into_iter()
+                .map(|id| id.get() as i64)
+                .collect::<Vec<i64>>(),
+            &(guild_id.get() as i64),
+            &(snowflake_at(snapshot_time) as i64),
+        ];
+
+        let removed_join_channels = transaction
+            .execute(
+                "
+                    delete from
+                        join_channel
+                    where
+                        not(id = any($1::int8[]))
+                        and guild_id = $2
+                        and id < $3;
+                ",
+                params,
+            )
+            .await
+            .wrap_err(
+                "I'm unable to run the first query of the \"remove_unknown_channels\" endpoint.",
+            )?;
+        let removed_voice_channels = transaction
+            .execute(
+                "
+                    delete from
+                        voice_channel
+                    where
+                        not(id = any($1::int8[]))
+                        and guild_id = $2
+                        and id < $3;
+                ",
+                params,
+            )
+            .await
+            .wrap_err(
+                "I'm unable to run the second query of the \"remove_unknown_channels\" endpoint.",
+            )?;
+
+        // A waiting room deleted while I was offline leaves its voice channel without one.
+        transaction
+            .execute(
+                "
+                    update
+                        voice_channel
+                    set
+                        waiting_room_id = null
+                    where
+                        not(waiting_room_id = any($1::int8[]))
+                        and guild_id = $2
+                        and waiting_room_id < $3;
+                ",
+                params,
+            )
+            .await
+            .wrap_err(
+                "I'm unable to run the third query of the \"remove_unknown_channels\" endpoint.",
+            )?;
+
+        transaction.commit().await?;
+
+        Ok(removed_join_channels + removed_voice_channels)
+    }
+
+    /// Removes the member's preset, returning whether there was one.
+    pub async fn remove_user_preset(
+        &self,
+        guild_id: Id<GuildMarker>,
+        user_id: Id<UserMarker>,
+    ) -> Result<bool> {
+        let client = self.pool.get().await?;
+        let removed_rows = client
+            .execute(
+                "
+                    delete from
+                        user_preset
+                    where
+                        guild_id = $1
+                        and user_id = $2;
+                ",
+                &[&(guild_id.get() as i64), &(user_id.get() as i64)],
+            )
+            .await
+            .wrap_err("I'm unable to run the \"remove_user_preset\" endpoint.")?;
+
+        Ok(removed_rows.gt(&0))
+    }
+
+    /// Takes `member_id` off the member's `kind` list, returning whether they were on it.
+    pub async fn remove_user_relationship(
+        &self,
+        guild_id: Id<GuildMarker>,
+        user_id: Id<UserMarker>,
+        member_id: Id<UserMarker>,
+        kind: RelationshipKind,
+    ) -> Result<bool> {
+        let client = self.pool.get().await?;
+        let removed_rows = client
+            .execute(
+                "
+                    delete from
+                        user_relationship
+                    where
+                        guild_id = $1
+                        and user_id = $2
+                        and member_id = $3
+                        and kind = $4;
+                ",
+                &[
+                    &(guild_id.get() as i64),
+                    &(user_id.get() as i64),
+                    &(member_id.get() as i64),
+                    &kind,
+                ],
+            )
+            .await
+            .wrap_err("I'm unable to run the \"remove_user_relationship\" endpoint.")?;
+
+        Ok(removed_rows.gt(&0))
+    }
+
+    pub async fn remove_voice_channel(&self, id: Id<ChannelMarker>) -> Result<()> {
+        let client = self.pool.get().await?;
+
+        client
+            .execute(
+                "
+                    delete from
+                        voice_channel
+                    where
+                        id = $1;
+                ",
+                &[&(id.get() as i64)],
+            )
+            .await
+            .wrap_err("I'm unable to run the \"remove_voice_channel\" endpoint.")?;
+
+        Ok(())
+    }
+
+    pub async fn remove_voice_channel_manager(
+        &self,
+        channel_id: Id<ChannelMarker>,
+        user_id: Id<UserMarker>,
+    ) -> Result<()> {
+        let client = self.pool.get().await?;
+
+        client
+            .execute(
+                "
+                    delete from
+                        voice_channel_manager
+                    where
+                        channel_id = $1
+                        and user_id = $2;
+                ",
+                &[&(channel_id.get() as i64), &(user_id.get() as i64)],
+            )
+            .await
+            .wrap_err("I'm unable to run the \"remove_voice_channel_manager\" endpoint.")?;
+
+        Ok(())
+    }
+
+    pub async fn remove_voice_channel_member(
+        &self,
+        channel_id: Id<ChannelMarker>,
+        user_id: Id<UserMarker>,
+    ) -> Result<()> {
+        let client = self.pool.get().await?;
+
+        client
+            .execute(
+                "
+                    delete from
+                        voice_channel_member
+                    where
+                        channel_id = $1
+                        and user_id = $2;
+                ",
+                &[&(channel_id.get() as i64), &(user_id.get() as i64)],
+            )
+            .await
+            .wrap_err("I'm unable to run the \"remove_voice_channel_member\" endpoint.")?;
+
+        Ok(())
+    }
+
+    /// Replaces every stored join time in the guild with `voice_channel_members`, which is what
+    /// I see connected once a guild becomes available.
+    pub async fn replace_guild_voice_channel_members(
+        &self,
+        guild_id: Id<GuildMarker>,
+        voice_channel_members: Vec<VoiceChannelMember>,
+    ) -> Result<()> {
+        let mut client = self.pool.get().await?;
+        let transaction = client.transaction().await?;
+        let (channel_ids, (user_ids, joined_ats)): (Vec<i64>, (Vec<i64>, Vec<SystemTime>)) =
+            voice_channel_members
+                .into_iter()
+                .map(|voice_channel_member| {
+                    (
+                        voice_channel_member.channel_id.get() as i64,
+                        (
+                            voice_channel_member.user_id.get() as i64,
+                            voice_channel_member.joined_at,
+                        ),
+                    )
+                })
+                .unzip();
+
+        transaction
+            .execute(
+                "
+                    delete from
+                        voice_channel_member
+                    using
+                        voice_channel
+                    where
+                        voice_channel.id = voice_channel_member.channel_id
+                        and voice_channel.guild_id = $1;
+                ",
+                &[&(guild_id.get() as i64)],
+            )
+            .await
+            .wrap_err(
+                "I'm unable to run the first query of the \"replace_guild_voice_channel_members\" endpoint.",
+            )?;
+        transaction
+            .execute(
+                "
+                    insert into
+                        voice_channel_member
+                    select
+                        *
+                    from
+                        unnest($1::int8[], $2::int8[], $3::timestamptz[])
+                    on conflict
+                    do nothing;
+                ",
+                &[&channel_ids, &user_ids, &joined_ats],
+            )
+            .await
+            .wrap_err(
+                "I'm unable to run the second query of the \"replace_guild_voice_channel_members\" endpoint.",
+            )?;
 
         transaction.commit().await?;
 
         Ok(())
     }
 
-    pub async fn remove_join_channel(&self, id: Id<ChannelMarker>) -> Result<()> {
+    pub async fn update_guild_settings(
+        &self,
+        guild_id: Id<GuildMarker>,
+        guild_settings: &GuildSettings,
+    ) -> Result<()> {
+        let client = self.pool.get().await?;
+
+        client
+            .execute(
+                "
+                    insert into
+                        guild_settings
+                    values
+                        ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+                    on conflict (guild_id)
+                    do update set
+                        presets_enabled = excluded.presets_enabled,
+                        log_channel_id = excluded.log_channel_id,
+                        max_voice_channels = excluded.max_voice_channels,
+                        name_template = excluded.name_template,
+                        locale = excluded.locale,
+                        blocked_dms_enabled = excluded.blocked_dms_enabled,
+                        creation_cooldown = excluded.creation_cooldown,
+                        printable_names_only = excluded.printable_names_only,
+                        external_edit_policy = excluded.external_edit_policy;
+                ",
+                &[
+                    &(guild_id.get() as i64),
+                    &guild_settings.presets_enabled,
+                    &guild_settings.log_channel_id.map(|id| id.get() as i64),
+                    &(guild_settings.max_voice_channels as i16),
+                    &guild_settings.name_template,
+                    &guild_settings.locale,
+                    &guild_settings.blocked_dms_enabled,
+                    &(guild_settings.creation_cooldown as i32),
+                    &guild_settings.printable_names_only,
+                    &guild_settings.external_edit_policy,
+                ],
+            )
+            .await
+            .wrap_err("I'm unable to run the \"update_guild_settings\" endpoint.")?;
+
+        Ok(())
+    }
+
+    pub async fn update_join_channel_ownership(
+        &self,
+        id: Id<ChannelMarker>,
+        ownership_policy: OwnershipPolicy,
+        ownership_delay: u32,
+    ) -> Result<()> {
+        let client = self.pool.get().await?;
+
+        client
+            .execute(
+                "
+                    update
+                        join_channel
+                    set
+                        ownership_policy = $2,
+                        ownership_delay = $3
+                    where
+                        id = $1;
+                ",
+                &[
+                    &(id.get() as i64),
+                    &ownership_policy,
+                    &(ownership_delay as i32),
+                ],
+            )
+            .await
+            .wrap_err("I'm unable to run the \"update_join_channel_ownership\" endpoint.")?;
+
+        Ok(())
+    }
+
+    pub async fn update_join_channel_parent_id(
+        &self,
+        id: Id<ChannelMarker>,
+        parent_id: Option<Id<ChannelMarker>>,
+    ) -> Result<()> {
+        let client = self.pool.get().await?;
+
+        client
+            .execute(
+                "
+                    update
+                        join_channel
+                    set
+                        parent_id = $2
+                    where
+                        id = $1;
+                ",
+                &[
+                    &(id.get() as i64),
+                    &parent_id.map(|parent_id| parent_id.get() as i64),
+                ],
+            )
+            .await
+            .wrap_err("I'm unable to run the \"update_join_channel_parent_id\" endpoint.")?;
+
+        Ok(())
+    }
+
+    pub async fn update_join_channel_permanence(
+        &self,
+        id: Id<ChannelMarker>,
+        permanence: bool,
+    ) -> Result<()> {
+        let client = self.pool.get().await?;
+
+        client
+            .execute(
+                "
+                    update
+                        join_channel
+                    set
+                        permanence = $2
+                    where
+                        id = $1;
+                ",
+                &[&(id.get() as i64), &permanence],
+            )
+            .await
+            .wrap_err("I'm unable to run the \"update_join_channel_permanence\" endpoint.")?;
+
+        Ok(())
+    }
+
+    pub async fn update_join_channel_privacy(
+        &self,
+        id: Id<ChannelMarker>,
+        privacy: ChannelPrivacy,
+    ) -> Result<()> {
+        let client = self.pool.get().await?;
+
+        client
+            .execute(
+                "
+                    update
+                        join_channel
+                    set
+                        privacy = $2
+                    where
+                        id = $1;
+                ",
+                &[&(id.get() as i64), &privacy],
+            )
+            .await
+            .wrap_err("I'm unable to run the \"update_join_channel_privacy\" endpoint.")?;
+
+        Ok(())
+    }
+
+    pub async fn update_join_channel_status_template(
+        &self,
+        id: Id<ChannelMarker>,
+        status_template: Option<&str>,
+    ) -> Result<()> {
+        let client = self.pool.get().await?;
+
+        client
+            .execute(
+                "
+                    update
+                        join_channel
+                    set
+                        status_template = $2
+                    where
+                        id = $1;
+                ",
+                &[&(id.get() as i64), &status_template],
+            )
+            .await
+            .wrap_err("I'm unable to run the \"update_join_channel_status_template\" endpoint.")?;
+
+        Ok(())
+    }
+
+    pub async fn update_join_channel_waiting_room(
+        &self,
+        id: Id<ChannelMarker>,
+        waiting_room: bool,
+    ) -> Result<()> {
         let client = self.pool.get().await?;
 
         client
             .execute(
                 "
-                    delete from
+                    update
                         join_channel
+                    set
+                        waiting_room = $2
                     where
                         id = $1;
                 ",
-                &[&(id.get() as i64)],
+                &[&(id.get() as i64), &waiting_room],
             )
             .await
-            .wrap_err("I'm unable to run the \"remove_join_channel\" endpoint.")?;
+            .wrap_err("I'm unable to run the \"update_join_channel_waiting_room\" endpoint.")?;
 
         Ok(())
     }
 
-    pub async fn remove_unknown_channels(
+    pub async fn update_join_channel_voice_channel_limit(
         &self,
-        channel_ids: Vec<Id<ChannelMarker>>,
-        guild_id: Id<GuildMarker>,
+        id: Id<ChannelMarker>,
+        voice_channel_limit: Option<u16>,
     ) -> Result<()> {
-        let mut client = self.pool.get().await?;
-        let transaction = client.transaction().await?;
-        let params: &[&(dyn ToSql + Sync)] = &[
-            &channel_ids
-                .into_iter()
-                .map(|id| id.get() as i64)
-                .collect::<Vec<i64>>(),
-            &(guild_id.get() as i64),
-        ];
+        let client = self.pool.get().await?;
 
-        transaction
+        client
             .execute(
                 "
-                    delete from
+                    update
                         join_channel
+                    set
+                        voice_channel_limit = $2
                     where
-                        not(id = any($1::int8[]))
-                        and guild_id = $2;
+                        id = $1;
                 ",
-                params,
+                &[
+                    &(id.get() as i64),
+                    &voice_channel_limit.map(|voice_channel_limit| voice_channel_limit as i16),
+                ],
             )
             .await
             .wrap_err(
-                "I'm unable to run the first query of the \"remove_unknown_channels\" endpoint.",
+                "I'm unable to run the \"update_join_channel_voice_channel_limit\" endpoint.",
             )?;
-        transaction
+
+        Ok(())
+    }
+
+    pub async fn update_voice_channel_bitrate(
+        &self,
+        id: Id<ChannelMarker>,
+        bitrate: u32,
+    ) -> Result<()> {
+        let client = self.pool.get().await?;
+
+        client
             .execute(
                 "
-                    delete from
+                    update
                         voice_channel
+                    set
+                        bitrate = $2
                     where
-                        not(id = any($1::int8[]))
-                        and guild_id = $2;
+                        id = $1;
                 ",
-                params,
+                &[&(id.get() as i64), &(bitrate as i32)],
             )
             .await
-            .wrap_err(
-                "I'm unable to run the second query of the \"remove_unknown_channels\" endpoint.",
-            )?;
-
-        transaction.commit().await?;
+            .wrap_err("I'm unable to run the \"update_voice_channel_bitrate\" endpoint.")?;
 
         Ok(())
     }
 
-    pub async fn remove_voice_channel(&self, id: Id<ChannelMarker>) -> Result<()> {
+    pub async fn update_voice_channel_name(&self, id: Id<ChannelMarker>, name: &str) -> Result<()> {
         let client = self.pool.get().await?;
 
         client
             .execute(
                 "
-                    delete from
+                    update
                         voice_channel
+                    set
+                        name = $2
                     where
                         id = $1;
                 ",
-                &[&(id.get() as i64)],
+                &[&(id.get() as i64), &name],
             )
             .await
-            .wrap_err("I'm unable to run the \"remove_voice_channel\" endpoint.")?;
+            .wrap_err("I'm unable to run the \"update_voice_channel_name\" endpoint.")?;
 
         Ok(())
     }
 
-    pub async fn update_join_channel_access_role_id(
+    pub async fn update_voice_channel_owner_id(
         &self,
         id: Id<ChannelMarker>,
-        access_role_id: Option<Id<RoleMarker>>,
+        owner_id: Option<Id<UserMarker>>,
     ) -> Result<()> {
         let client = self.pool.get().await?;
 
@@ -369,27 +2088,27 @@ impl Database {
             .execute(
                 "
                     update
-                        join_channel
+                        voice_channel
                     set
-                        access_role_id = $2
+                        owner_id = $2
                     where
                         id = $1;
                 ",
                 &[
                     &(id.get() as i64),
-                    &access_role_id.map(|access_role_id| access_role_id.get() as i64),
+                    &owner_id.map(|owner_id| owner_id.get() as i64),
                 ],
             )
             .await
-            .wrap_err("I'm unable to run the \"update_join_channel_access_role_id\" endpoint.")?;
+            .wrap_err("I'm unable to run the \"update_voice_channel_owner_id\" endpoint.")?;
 
         Ok(())
     }
 
-    pub async fn update_join_channel_parent_id(
+    pub async fn update_voice_channel_permanence(
         &self,
         id: Id<ChannelMarker>,
-        parent_id: Option<Id<ChannelMarker>>,
+        permanence: bool,
     ) -> Result<()> {
         let client = self.pool.get().await?;
 
@@ -397,27 +2116,24 @@ impl Database {
             .execute(
                 "
                     update
-                        join_channel
+                        voice_channel
                     set
-                        parent_id = $2
+                        permanence = $2
                     where
                         id = $1;
                 ",
-                &[
-                    &(id.get() as i64),
-                    &parent_id.map(|parent_id| parent_id.get() as i64),
-                ],
+                &[&(id.get() as i64), &permanence],
             )
             .await
-            .wrap_err("I'm unable to run the \"update_join_channel_parent_id\" endpoint.")?;
+            .wrap_err("I'm unable to run the \"update_voice_channel_permanence\" endpoint.")?;
 
         Ok(())
     }
 
-    pub async fn update_join_channel_permanence(
+    pub async fn update_voice_channel_privacy(
         &self,
         id: Id<ChannelMarker>,
-        permanence: bool,
+        privacy: ChannelPrivacy,
     ) -> Result<()> {
         let client = self.pool.get().await?;
 
@@ -425,24 +2141,24 @@ impl Database {
             .execute(
                 "
                     update
-                        join_channel
+                        voice_channel
                     set
-                        permanence = $2
+                        privacy = $2
                     where
                         id = $1;
                 ",
-                &[&(id.get() as i64), &permanence],
+                &[&(id.get() as i64), &privacy],
             )
             .await
-            .wrap_err("I'm unable to run the \"update_join_channel_permanence\" endpoint.")?;
+            .wrap_err("I'm unable to run the \"update_voice_channel_privacy\" endpoint.")?;
 
         Ok(())
     }
 
-    pub async fn update_join_channel_privacy(
+    pub async fn update_voice_channel_rtc_region(
         &self,
         id: Id<ChannelMarker>,
-        privacy: ChannelPrivacy,
+        rtc_region: Option<&str>,
     ) -> Result<()> {
         let client = self.pool.get().await?;
 
@@ -450,24 +2166,24 @@ impl Database {
             .execute(
                 "
                     update
-                        join_channel
+                        voice_channel
                     set
-                        privacy = $2
+                        rtc_region = $2
                     where
                         id = $1;
                 ",
-                &[&(id.get() as i64), &privacy],
+                &[&(id.get() as i64), &rtc_region],
             )
             .await
-            .wrap_err("I'm unable to run the \"update_join_channel_privacy\" endpoint.")?;
+            .wrap_err("I'm unable to run the \"update_voice_channel_rtc_region\" endpoint.")?;
 
         Ok(())
     }
 
-    pub async fn update_voice_channel_owner_id(
+    pub async fn update_voice_channel_settings(
         &self,
         id: Id<ChannelMarker>,
-        owner_id: Option<Id<UserMarker>>,
+        settings: &VoiceChannelSettings,
     ) -> Result<()> {
         let client = self.pool.get().await?;
 
@@ -477,25 +2193,31 @@ impl Database {
                     update
                         voice_channel
                     set
-                        owner_id = $2
+                        name = $2,
+                        bitrate = $3,
+                        user_limit = $4,
+                        rtc_region = $5
                     where
                         id = $1;
                 ",
                 &[
                     &(id.get() as i64),
-                    &owner_id.map(|owner_id| owner_id.get() as i64),
+                    &settings.name,
+                    &(settings.bitrate as i32),
+                    &settings.user_limit.map(|user_limit| user_limit as i32),
+                    &settings.rtc_region,
                 ],
             )
             .await
-            .wrap_err("I'm unable to run the \"update_voice_channel_owner_id\" endpoint.")?;
+            .wrap_err("I'm unable to run the \"update_voice_channel_settings\" endpoint.")?;
 
         Ok(())
     }
 
-    pub async fn update_voice_channel_permanence(
+    pub async fn update_voice_channel_status(
         &self,
         id: Id<ChannelMarker>,
-        permanence: bool,
+        status: Option<&str>,
     ) -> Result<()> {
         let client = self.pool.get().await?;
 
@@ -505,22 +2227,22 @@ impl Database {
                     update
                         voice_channel
                     set
-                        permanence = $2
+                        status = $2
                     where
                         id = $1;
                 ",
-                &[&(id.get() as i64), &permanence],
+                &[&(id.get() as i64), &status],
             )
             .await
-            .wrap_err("I'm unable to run the \"update_voice_channel_permanence\" endpoint.")?;
+            .wrap_err("I'm unable to run the \"update_voice_channel_status\" endpoint.")?;
 
         Ok(())
     }
 
-    pub async fn update_voice_channel_privacy(
+    pub async fn update_voice_channel_user_limit(
         &self,
         id: Id<ChannelMarker>,
-        privacy: ChannelPrivacy,
+        user_limit: Option<u32>,
     ) -> Result<()> {
         let client = self.pool.get().await?;
 
@@ -530,17 +2252,169 @@ impl Database {
                     update
                         voice_channel
                     set
-                        privacy = $2
+                        user_limit = $2
                     where
                         id = $1;
                 ",
-                &[&(id.get() as i64), &privacy],
+                &[
+                    &(id.get() as i64),
+                    &user_limit.map(|user_limit| user_limit as i32),
+                ],
             )
             .await
-            .wrap_err("I'm unable to run the \"update_voice_channel_privacy\" endpoint.")?;
+            .wrap_err("I'm unable to run the \"update_voice_channel_user_limit\" endpoint.")?;
+
+        Ok(())
+    }
+
+    pub async fn update_voice_channel_waiting_room_id(
+        &self,
+        id: Id<ChannelMarker>,
+        waiting_room_id: Option<Id<ChannelMarker>>,
+    ) -> Result<()> {
+        let client = self.pool.get().await?;
+
+        client
+            .execute(
+                "
+                    update
+                        voice_channel
+                    set
+                        waiting_room_id = $2
+                    where
+                        id = $1;
+                ",
+                &[
+                    &(id.get() as i64),
+                    &waiting_room_id.map(|waiting_room_id| waiting_room_id.get() as i64),
+                ],
+            )
+            .await
+            .wrap_err("I'm unable to run the \"update_voice_channel_waiting_room_id\" endpoint.")?;
 
         Ok(())
     }
+
+    pub async fn user_preset(
+        &self,
+        guild_id: Id<GuildMarker>,
+        user_id: Id<UserMarker>,
+    ) -> Result<Option<UserPreset>> {
+        let client = self.pool.get().await?;
+        let row = client
+            .query_opt(
+                "
+                    select
+                        *
+                    from
+                        user_preset
+                    where
+                        guild_id = $1
+                        and user_id = $2;
+                ",
+                &[&(guild_id.get() as i64), &(user_id.get() as i64)],
+            )
+            .await
+            .wrap_err("I'm unable to run the \"user_preset\" endpoint.")?;
+
+        Ok(row.map(UserPreset::from))
+    }
+
+    pub async fn user_relationships(
+        &self,
+        guild_id: Id<GuildMarker>,
+        user_id: Id<UserMarker>,
+    ) -> Result<Vec<UserRelationship>> {
+        let client = self.pool.get().await?;
+        let rows = client
+            .query(
+                "
+                    select
+                        *
+                    from
+                        user_relationship
+                    where
+                        guild_id = $1
+                        and user_id = $2
+                    order by
+                        member_id;
+                ",
+                &[&(guild_id.get() as i64), &(user_id.get() as i64)],
+            )
+            .await
+            .wrap_err("I'm unable to run the \"user_relationships\" endpoint.")?;
+
+        Ok(rows.into_iter().map(UserRelationship::from).collect())
+    }
+
+    pub async fn voice_channel_timed_permissions(
+        &self,
+        channel_id: Id<ChannelMarker>,
+    ) -> Result<Vec<TimedPermission>> {
+        let client = self.pool.get().await?;
+        let rows = client
+            .query(
+                "
+                    select
+                        *
+                    from
+                        timed_permission
+                    where
+                        channel_id = $1
+                    order by
+                        expires_at;
+                ",
+                &[&(channel_id.get() as i64)],
+            )
+            .await
+            .wrap_err("I'm unable to run the \"voice_channel_timed_permissions\" endpoint.")?;
+
+        Ok(rows.into_iter().map(TimedPermission::from).collect())
+    }
+}
+
+impl Default for GuildSettings {
+    fn default() -> Self {
+        Self {
+            blocked_dms_enabled: true,
+            creation_cooldown: 0,
+            external_edit_policy: ExternalEditPolicy::Accept,
+            locale: "en-US".to_owned(),
+            log_channel_id: None,
+            max_voice_channels: 1,
+            name_template: None,
+            presets_enabled: true,
+            printable_names_only: false,
+        }
+    }
+}
+
+impl From<Row> for AuditEvent {
+    fn from(row: Row) -> Self {
+        Self {
+            action: row.get::<_, String>("action"),
+            created_at: row.get::<_, SystemTime>("created_at"),
+            description: row.get::<_, String>("description"),
+        }
+    }
+}
+
+impl From<Row> for GuildSettings {
+    fn from(row: Row) -> Self {
+        Self {
+            blocked_dms_enabled: row.get::<_, bool>("blocked_dms_enabled"),
+            creation_cooldown: row.get::<_, i32>("creation_cooldown") as u32,
+            external_edit_policy: row.get::<_, ExternalEditPolicy>("external_edit_policy"),
+            locale: row.get::<_, String>("locale"),
+            log_channel_id: row
+                .get::<_, Option<i64>>("log_channel_id")
+                .map(|id| Id::new(id as u64)),
+            max_voice_channels: row.get::<_, i16>("max_voice_channels") as u8,
+            name_template: row.get::<_, Option<String>>("name_template"),
+            presets_enabled: row.get::<_, bool>("presets_enabled"),
+            printable_names_only: row.get::<_, bool>("printable_names_only"),
+        }
+    }
 }
 
 impl From<Row> for JoinChannel {
@@ -548,14 +2422,90 @@ impl From<Row> for JoinChannel {
         Self {
             id: Id::new(row.get::<_, i64>("id") as u64),
             guild_id: Id::new(row.get::<_, i64>("guild_id") as u64),
-            access_role_id: row
-                .get::<_, Option<i64>>("access_role_id")
-                .map(|id| Id::new(id as u64)),
+            allowed_role_ids: row
+                .get::<_, Vec<i64>>("allowed_role_ids")
+                .into_iter()
+                .map(|id| Id::new(id as u64))
+                .collect(),
+            denied_role_ids: row
+                .get::<_, Vec<i64>>("denied_role_ids")
+                .into_iter()
+                .map(|id| Id::new(id as u64))
+                .collect(),
             parent_id: row
                 .get::<_, Option<i64>>("parent_id")
                 .map(|id| Id::new(id as u64)),
             permanence: row.get::<_, bool>("permanence"),
             privacy: row.get::<_, ChannelPrivacy>("privacy"),
+            ownership_policy: row.get::<_, OwnershipPolicy>("ownership_policy"),
+            ownership_delay: row.get::<_, i32>("ownership_delay") as u32,
+            waiting_room: row.get::<_, bool>("waiting_room"),
+            status_template: row.get::<_, Option<String>>("status_template"),
+            voice_channel_limit: row
+                .get::<_, Option<i16>>("voice_channel_limit")
+                .map(|voice_channel_limit| voice_channel_limit as u16),
+            kind: row.get::<_, VoiceChannelKind>("kind"),
+        }
+    }
+}
+
+impl From<Row> for NameFilter {
+    fn from(row: Row) -> Self {
+        Self {
+            kind: row.get::<_, NameFilterKind>("kind"),
+            value: row.get::<_, String>("value"),
+        }
+    }
+}
+
+impl From<Row> for TimedPermission {
+    fn from(row: Row) -> Self {
+        Self {
+            channel_id: Id::new(row.get::<_, i64>("channel_id") as u64),
+            member_id: Id::new(row.get::<_, i64>("member_id") as u64),
+            kind: row.get::<_, TimedPermissionKind>("kind"),
+            expires_at: row.get::<_, SystemTime>("expires_at"),
+        }
+    }
+}
+
+impl From<Row> for UserPreset {
+    fn from(row: Row) -> Self {
+        Self {
+            guild_id: Id::new(row.get::<_, i64>("guild_id") as u64),
+            user_id: Id::new(row.get::<_, i64>("user_id") as u64),
+            name: row.get::<_, String>("name"),
+            bitrate: row.get::<_, i32>("bitrate") as u32,
+            user_limit: row
+                .get::<_, Option<i32>>("user_limit")
+                .map(|user_limit| user_limit as u32),
+            rate_limit_per_user: row
+                .get::<_, Option<i32>>("rate_limit_per_user")
+                .map(|rate_limit_per_user| rate_limit_per_user as u16),
+            rtc_region: row.get::<_, Option<String>>("rtc_region"),
+            video_quality_mode: VideoQualityMode::from(
+                row.get::<_, i16>("video_quality_mode") as u8
+            ),
+            privacy: row.get::<_, ChannelPrivacy>("privacy"),
+            allowed_user_ids: row
+                .get::<_, Vec<i64>>("allowed_user_ids")
+                .into_iter()
+                .map(|id| Id::new(id as u64))
+                .collect(),
+            denied_user_ids: row
+                .get::<_, Vec<i64>>("denied_user_ids")
+                .into_iter()
+                .map(|id| Id::new(id as u64))
+                .collect(),
+        }
+    }
+}
+
+impl From<Row> for UserRelationship {
+    fn from(row: Row) -> Self {
+        Self {
+            member_id: Id::new(row.get::<_, i64>("member_id") as u64),
+            kind: row.get::<_, RelationshipKind>("kind"),
         }
     }
 }
@@ -570,6 +2520,57 @@ impl From<Row> for VoiceChannel {
                 .map(|id| Id::new(id as u64)),
             permanence: row.get::<_, bool>("permanence"),
             privacy: row.get::<_, ChannelPrivacy>("privacy"),
+            ownership_policy: row.get::<_, OwnershipPolicy>("ownership_policy"),
+            ownership_delay: row.get::<_, i32>("ownership_delay") as u32,
+            waiting_room_id: row
+                .get::<_, Option<i64>>("waiting_room_id")
+                .map(|id| Id::new(id as u64)),
+            status: row.get::<_, Option<String>>("status"),
+            join_channel_id: row
+                .get::<_, Option<i64>>("join_channel_id")
+                .map(|id| Id::new(id as u64)),
+            kind: row.get::<_, VoiceChannelKind>("kind"),
+            settings: row
+                .get::<_, Option<String>>("name")
+                .map(|name| VoiceChannelSettings {
+                    name,
+                    bitrate: row.get::<_, Option<i32>>("bitrate").unwrap_or(64_000) as u32,
+                    user_limit: row
+                        .get::<_, Option<i32>>("user_limit")
+                        .map(|user_limit| user_limit as u32),
+                    rtc_region: row.get::<_, Option<String>>("rtc_region"),
+                }),
+        }
+    }
+}
+
+impl From<Row> for VoiceChannelManager {
+    fn from(row: Row) -> Self {
+        Self {
+            channel_id: Id::new(row.get::<_, i64>("channel_id") as u64),
+            user_id: Id::new(row.get::<_, i64>("user_id") as u64),
+        }
+    }
+}
+
+impl From<Row> for VoiceChannelMember {
+    fn from(row: Row) -> Self {
+        Self {
+            channel_id: Id::new(row.get::<_, i64>("channel_id") as u64),
+            user_id: Id::new(row.get::<_, i64>("user_id") as u64),
+            joined_at: row.get::<_, SystemTime>("joined_at"),
+        }
+    }
+}
+
+impl From<&Channel> for VoiceChannelSettings {
+    fn from(channel: &Channel) -> Self {
+        Self {
+            name: channel.name.clone().unwrap_or_default(),
+            bitrate: channel.bitrate.unwrap_or(64_000),
+            // Discord reports no limit as a limit of 0.
+            user_limit: channel.user_limit.filter(|user_limit| user_limit.ne(&0)),
+            rtc_region: channel.rtc_region.clone(),
         }
     }
 }
@@ -1,7 +1,9 @@
 use std::sync::Arc;
 
 use twilight_model::{
-    application::interaction::application_command::CommandData,
+    application::interaction::{
+        application_command::CommandData, message_component::MessageComponentInteractionData,
+    },
     channel::Channel,
     id::{
         marker::{InteractionMarker, UserMarker},
@@ -19,3 +21,12 @@ pub struct ApplicationCommandInteraction {
     pub token: String,
     pub user_id: Id<UserMarker>,
 }
+
+pub struct MessageComponentInteraction {
+    pub channel: Channel,
+    pub data: Box<MessageComponentInteractionData>,
+    pub guild: Arc<CachedGuild>,
+    pub id: Id<InteractionMarker>,
+    pub token: String,
+    pub user_id: Id<UserMarker>,
+}
@@ -0,0 +1,49 @@
+use std::{
+    fs::OpenOptions,
+    io::{BufWriter, Write},
+    sync::mpsc::{self, Sender},
+    thread,
+};
+
+use eyre::{Result, WrapErr};
+
+/// Appends raw gateway payloads to a recording. Payloads are handed to a writer thread, so the
+/// shard loops never wait on the file.
+pub struct EventRecorder {
+    sender: Sender<String>,
+}
+
+impl EventRecorder {
+    pub fn new(path: &str) -> Result<Self> {
+        let file = OpenOptions::new()
+            .append(true)
+            .create(true)
+            .open(path)
+            .wrap_err_with(|| format!("I'm unable to open \"{path}\" for recording."))?;
+        let (sender, receiver) = mpsc::channel::<String>();
+
+        thread::spawn(move || {
+            let mut writer = BufWriter::new(file);
+
+            for json in receiver {
+                // Each payload is flushed as it's written, so a recording cut short by a crash
+                // still ends on a whole line.
+                if writeln!(writer, "{json}")
+                    .and_then(|_| writer.flush())
+                    .is_err()
+                {
+                    break;
+                }
+            }
+        });
+
+        Ok(Self { sender })
+    }
+
+    /// Queues one raw gateway payload as a line of the recording.
+    pub fn record(&self, json: &str) -> Result<()> {
+        self.sender
+            .send(json.to_owned())
+            .wrap_err("I'm unable to record the event, as the recording was closed.")
+    }
+}
@@ -0,0 +1,4 @@
+pub mod ownership;
+pub mod reconcile;
+pub mod renames;
+pub mod timed_permissions;
@@ -0,0 +1,420 @@
+use std::{
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+use eyre::Result;
+use tokio::time::{interval, MissedTickBehavior};
+use twilight_model::{
+    channel::permission_overwrite::{
+        PermissionOverwrite as ChannelPermissionOverwrite,
+        PermissionOverwriteType as ChannelPermissionOverwriteType,
+    },
+    guild::Permissions,
+    http::permission_overwrite::{
+        PermissionOverwrite as HttpPermissionOverwrite,
+        PermissionOverwriteType as HttpPermissionOverwriteType,
+    },
+    id::{marker::UserMarker, Id},
+};
+
+use crate::{
+    structs::{
+        cache::{CachedVoiceChannel, CachedVoiceChannelUpdate},
+        context::Context,
+        database::{OwnershipPolicy, VoiceChannelKind},
+    },
+    utilities::{
+        audit::audit,
+        constants::OWNERSHIP_INTERVAL,
+        permission_overwrites::{owner_permissions, privacy_permissions},
+    },
+};
+
+pub async fn run(context: Arc<Context>) {
+    let mut ownership_interval = interval(OWNERSHIP_INTERVAL);
+
+    ownership_interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    loop {
+        ownership_interval.tick().await;
+
+        hand_off(&context, SystemTime::now()).await;
+    }
+}
+
+/// Applies the ownership policy of every voice channel whose owner has been gone for at least
+/// its ownership delay by `now`, returning how many voice channels changed hands.
+pub async fn hand_off(context: &Arc<Context>, now: SystemTime) -> u64 {
+    let voice_channels = context
+        .cache
+        .voice_channels
+        .iter()
+        .map(|voice_channel| Arc::clone(&voice_channel))
+        .collect::<Vec<Arc<CachedVoiceChannel>>>();
+    let mut handed_off = 0;
+
+    for voice_channel in voice_channels {
+        let Some(owner_left_at) = *voice_channel.owner_left_at.read() else {
+            continue;
+        };
+
+        if now
+            .duration_since(owner_left_at)
+            .unwrap_or_default()
+            .lt(&Duration::from_secs(voice_channel.ownership_delay.into()))
+        {
+            continue;
+        }
+
+        let (result, notice, description) = match voice_channel.ownership_policy {
+            OwnershipPolicy::Claimable => (
+                release_ownership(context, &voice_channel).await,
+                "This voice channel's owner has left, so anyone here may now `/voice claim` it."
+                    .to_owned(),
+                format!("<#{}> has been left without an owner.", voice_channel.id),
+            ),
+            OwnershipPolicy::Keep => continue,
+            OwnershipPolicy::Transfer => {
+                let longest_connected_user_id = voice_channel
+                    .join_times
+                    .read()
+                    .iter()
+                    .filter(|(user_id, _)| {
+                        context
+                            .cache
+                            .can_own_voice_channel(voice_channel.guild_id, **user_id)
+                    })
+                    .min_by_key(|(user_id, joined_at)| (**joined_at, **user_id))
+                    .map(|(user_id, _)| *user_id);
+                let Some(user_id) = longest_connected_user_id else {
+                    continue;
+                };
+
+                (
+                    transfer_ownership(context, &voice_channel, user_id).await,
+                    format!("This voice channel's owner has left, so <@{user_id}> owns it now."),
+                    format!("<#{}> has been handed to <@{user_id}>.", voice_channel.id),
+                )
+            }
+        };
+
+        if result.is_err() {
+            continue;
+        }
+
+        // Not every voice channel has a text chat I may post in, and the hand-off stands
+        // regardless.
+        _ = context
+            .client
+            .create_message(voice_channel.id)
+            .content(&notice)
+            .await;
+
+        if let Some(guild) = context.cache.guild(voice_channel.guild_id) {
+            _ = audit(
+                context,
+                &guild,
+                None,
+                Some(voice_channel.id),
+                "ownership hand-off",
+                description,
+            )
+            .await;
+        }
+
+        handed_off += 1;
+    }
+
+    handed_off
+}
+
+/// Leaves the voice channel without an owner, so anyone connected can `/voice claim` it.
+pub async fn release_ownership(
+    context: &Arc<Context>,
+    voice_channel: &CachedVoiceChannel,
+) -> Result<()> {
+    let owner_id = *voice_channel.owner_id.read();
+
+    // The owner of a stage stops moderating it along with owning it.
+    if let Some(owner_id) = owner_id.filter(|_| voice_channel.kind.eq(&VoiceChannelKind::Stage)) {
+        let privacy_permissions =
+            privacy_permissions(voice_channel.kind, &voice_channel.privacy.read());
+
+        context
+            .client
+            .update_channel_permission(
+                voice_channel.id,
+                &HttpPermissionOverwrite {
+                    allow: Some(privacy_permissions),
+                    deny: None,
+                    id: owner_id.cast(),
+                    kind: HttpPermissionOverwriteType::Member,
+                },
+            )
+            .await?;
+    }
+
+    context
+        .database
+        .update_voice_channel_owner_id(voice_channel.id, None)
+        .await?;
+    context.cache.update_voice_channel(
+        voice_channel.id,
+        CachedVoiceChannelUpdate {
+            owner_id: Some(None),
+            ..Default::default()
+        },
+    );
+
+    Ok(())
+}
+
+/// Hands the voice channel to `member_id`, who takes over the current owner's permission
+/// overwrite (or gets a fresh one, if the voice channel has no owner).
+pub async fn transfer_ownership(
+    context: &Arc<Context>,
+    voice_channel: &CachedVoiceChannel,
+    member_id: Id<UserMarker>,
+) -> Result<()> {
+    let owner_id = *voice_channel.owner_id.read();
+    let mut permission_overwrites = voice_channel.permission_overwrites.read().clone();
+
+    // Managers and allowed members already have an overwrite, which the owner's replaces.
+    permission_overwrites.retain(|permission_overwrite| {
+        permission_overwrite.id.ne(&member_id.cast())
+            || permission_overwrite
+                .kind
+                .ne(&ChannelPermissionOverwriteType::Member)
+    });
+
+    for permission_overwrite in permission_overwrites.iter_mut() {
+        if owner_id.is_some_and(|owner_id| permission_overwrite.id.eq(&owner_id.cast()))
+            && permission_overwrite
+                .kind
+                .eq(&ChannelPermissionOverwriteType::Member)
+        {
+            permission_overwrite.id = member_id.cast();
+        }
+    }
+
+    if owner_id.is_none() {
+        permission_overwrites.push(ChannelPermissionOverwrite {
+            allow: owner_permissions(voice_channel.kind, &voice_channel.privacy.read()),
+            deny: Permissions::empty(),
+            id: member_id.cast(),
+            kind: ChannelPermissionOverwriteType::Member,
+        });
+    }
+
+    context
+        .client
+        .update_channel(voice_channel.id)
+        .permission_overwrites(&permission_overwrites)
+        .await?;
+    context
+        .database
+        .update_voice_channel_owner_id(voice_channel.id, Some(member_id))
+        .await?;
+    context
+        .database
+        .remove_voice_channel_manager(voice_channel.id, member_id)
+        .await?;
+    context
+        .cache
+        .remove_voice_channel_manager(voice_channel.id, member_id);
+    context.cache.update_voice_channel(
+        voice_channel.id,
+        CachedVoiceChannelUpdate {
+            owner_id: Some(Some(member_id)),
+            ..Default::default()
+        },
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::Arc,
+        time::{Duration, SystemTime},
+    };
+
+    use serde_json::json;
+    use twilight_model::id::{
+        marker::{GuildMarker, UserMarker},
+        Id,
+    };
+
+    use super::hand_off;
+    use crate::{
+        structs::{
+            context::Context,
+            database::{ChannelPrivacy, OwnershipPolicy},
+        },
+        testing::{
+            self,
+            mock_discord::MockDiscord,
+            payloads::{command, subcommand, voice_state_update},
+            replay::replay,
+            seed_join_channel, VOICE_CHANNEL_ID,
+        },
+    };
+
+    const GUILD_ID: Id<GuildMarker> = Id::new(1001);
+
+    /// Alex creates a voice channel under `ownership_policy`, Sam then Kim join it, and Alex
+    /// leaves.
+    async fn abandon(context: &Arc<Context>, ownership_policy: OwnershipPolicy) {
+        seed_join_channel(context, GUILD_ID, Id::new(1101), ChannelPrivacy::Unlocked).await;
+        context
+            .database
+            .update_join_channel_ownership(Id::new(1101), ownership_policy, 60)
+            .await
+            .unwrap();
+        replay(context, include_str!("../testing/recordings/create.jsonl"))
+            .await
+            .unwrap();
+        replay(
+            context,
+            &[
+                voice_state_update(1001, 2002, "sam", Some(5000)),
+                voice_state_update(1001, 2003, "kim", Some(5000)),
+                voice_state_update(1001, 2001, "alex", None),
+            ]
+            .join("\n"),
+        )
+        .await
+        .unwrap();
+    }
+
+    fn an_hour_from_now() -> SystemTime {
+        SystemTime::now() + Duration::from_secs(3_600)
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a database, run with `cargo test -- --ignored`"]
+    async fn transfers_to_the_longest_connected_member() {
+        let mock_discord = MockDiscord::start().await;
+        let (context, _guard) = testing::context(&mock_discord).await;
+        let user_id = Id::<UserMarker>::new(2002);
+
+        abandon(&context, OwnershipPolicy::Transfer).await;
+
+        assert_eq!(hand_off(&context, SystemTime::now()).await, 0);
+        assert_eq!(hand_off(&context, an_hour_from_now()).await, 1);
+
+        let voice_channel = context.cache.voice_channel(VOICE_CHANNEL_ID).unwrap();
+
+        assert_eq!(*voice_channel.owner_id.read(), Some(user_id));
+        assert!(voice_channel.owner_left_at.read().is_none());
+        assert!(mock_discord
+            .assert_requested("PATCH", "/channels/5000")
+            .body["permission_overwrites"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|permission_overwrite| permission_overwrite["id"].eq("2002")));
+        assert_eq!(
+            mock_discord
+                .assert_requested("POST", "/channels/5000/messages")
+                .body["content"],
+            "This voice channel's owner has left, so <@2002> owns it now."
+        );
+        assert_eq!(
+            context
+                .database
+                .guild_voice_channels(GUILD_ID)
+                .await
+                .unwrap()[0]
+                .owner_id,
+            Some(user_id)
+        );
+
+        context.database.remove_guild(GUILD_ID).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a database, run with `cargo test -- --ignored`"]
+    async fn join_times_are_persisted() {
+        let mock_discord = MockDiscord::start().await;
+        let (context, _guard) = testing::context(&mock_discord).await;
+
+        abandon(&context, OwnershipPolicy::Transfer).await;
+
+        let mut user_ids = context
+            .database
+            .guild_voice_channel_members(GUILD_ID)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|voice_channel_member| voice_channel_member.user_id.get())
+            .collect::<Vec<u64>>();
+
+        user_ids.sort();
+
+        assert_eq!(user_ids, [2002, 2003]);
+
+        context.database.remove_guild(GUILD_ID).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a database, run with `cargo test -- --ignored`"]
+    async fn keeps_the_owner_by_default() {
+        let mock_discord = MockDiscord::start().await;
+        let (context, _guard) = testing::context(&mock_discord).await;
+
+        abandon(&context, OwnershipPolicy::Keep).await;
+
+        assert_eq!(hand_off(&context, an_hour_from_now()).await, 0);
+        assert_eq!(
+            *context
+                .cache
+                .voice_channel(VOICE_CHANNEL_ID)
+                .unwrap()
+                .owner_id
+                .read(),
+            Some(Id::new(2001))
+        );
+
+        context.database.remove_guild(GUILD_ID).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a database, run with `cargo test -- --ignored`"]
+    async fn claimable_voice_channels_wait_out_the_delay() {
+        let mock_discord = MockDiscord::start().await;
+        let (context, _guard) = testing::context(&mock_discord).await;
+        let claim_command =
+            |token: &str| command(token, 1001, 2003, subcommand("voice", "claim", json!([])));
+
+        abandon(&context, OwnershipPolicy::Claimable).await;
+        replay(&context, &claim_command("early")).await.unwrap();
+
+        assert!(mock_discord.response_descriptions("early")[0]
+            .starts_with("This voice channel's owner has left. You may claim it in "));
+
+        assert_eq!(hand_off(&context, an_hour_from_now()).await, 1);
+        assert!(context
+            .cache
+            .voice_channel(VOICE_CHANNEL_ID)
+            .unwrap()
+            .owner_id
+            .read()
+            .is_none());
+
+        replay(&context, &claim_command("claim")).await.unwrap();
+
+        assert_eq!(
+            mock_discord.response_descriptions("claim"),
+            ["You are now the owner of <#5000>."]
+        );
+        assert_eq!(
+            context.cache.voice_channel_owner(GUILD_ID, Id::new(2003)),
+            Some(Arc::new(VOICE_CHANNEL_ID))
+        );
+
+        context.database.remove_guild(GUILD_ID).await.unwrap();
+    }
+}
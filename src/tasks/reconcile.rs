@@ -0,0 +1,468 @@
+use std::{collections::HashMap, ops::AddAssign, sync::Arc, time::SystemTime};
+
+use eyre::Result;
+use futures::future::join_all;
+use tokio::time::{interval, MissedTickBehavior};
+use twilight_model::{
+    channel::{Channel, ChannelType, VideoQualityMode},
+    id::{
+        marker::{ChannelMarker, GuildMarker},
+        Id,
+    },
+};
+
+use crate::{
+    structs::{
+        cache::{CachedJoinChannelUpdate, CachedVoiceChannelUpdate},
+        context::Context,
+        database::VoiceChannelSettings,
+    },
+    utilities::constants::RECONCILE_INTERVAL,
+};
+
+#[derive(Default)]
+pub struct ReconcileReport {
+    pub failed_guilds: u64,
+    pub reconciled_guilds: u64,
+    pub removed_join_channels: u64,
+    pub removed_unknown_rows: u64,
+    pub removed_voice_channels: u64,
+    pub restored_join_channels: u64,
+    pub restored_voice_channels: u64,
+    pub updated_join_channels: u64,
+    pub updated_voice_channels: u64,
+}
+
+impl AddAssign for ReconcileReport {
+    fn add_assign(&mut self, other: Self) {
+        self.failed_guilds += other.failed_guilds;
+        self.reconciled_guilds += other.reconciled_guilds;
+        self.removed_join_channels += other.removed_join_channels;
+        self.removed_unknown_rows += other.removed_unknown_rows;
+        self.removed_voice_channels += other.removed_voice_channels;
+        self.restored_join_channels += other.restored_join_channels;
+        self.restored_voice_channels += other.restored_voice_channels;
+        self.updated_join_channels += other.updated_join_channels;
+        self.updated_voice_channels += other.updated_voice_channels;
+    }
+}
+
+pub async fn run(context: Arc<Context>) {
+    let mut reconcile_interval = interval(RECONCILE_INTERVAL);
+
+    reconcile_interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    // The first tick completes immediately, and every guild is already synced by its
+    // `GuildCreate` event at that point.
+    reconcile_interval.tick().await;
+
+    loop {
+        reconcile_interval.tick().await;
+
+        let guild_ids = context.cache.guild_ids();
+        let guild_reports = join_all(
+            guild_ids
+                .into_iter()
+                .map(|guild_id| reconcile_guild(Arc::clone(&context), guild_id)),
+        )
+        .await;
+        let mut report = ReconcileReport::default();
+
+        for guild_report in guild_reports {
+            match guild_report {
+                Ok(guild_report) => report += guild_report,
+                Err(_) => report.failed_guilds += 1,
+            }
+        }
+
+        println!(
+            "Reconciled {} guild(s) ({} failed): {} join channel(s) removed, {} restored and {} updated; {} voice channel(s) removed, {} restored and {} updated; {} unknown row(s) removed.",
+            report.reconciled_guilds,
+            report.failed_guilds,
+            report.removed_join_channels,
+            report.restored_join_channels,
+            report.updated_join_channels,
+            report.removed_voice_channels,
+            report.restored_voice_channels,
+            report.updated_voice_channels,
+            report.removed_unknown_rows,
+        );
+    }
+}
+
+pub async fn reconcile_guild(
+    context: Arc<Context>,
+    guild_id: Id<GuildMarker>,
+) -> Result<ReconcileReport> {
+    let mut report = ReconcileReport {
+        reconciled_guilds: 1,
+        ..Default::default()
+    };
+    let Some(guild) = context.cache.guild(guild_id) else {
+        return Ok(report);
+    };
+    // Voice channels are created on Discord, then stored, then cached. Reading in the opposite
+    // order before taking the snapshot means a cached channel is always stored, and a stored one
+    // is always in the snapshot, even if a member creates one meanwhile.
+    let cached_join_channel_ids = guild.join_channel_ids.read().clone();
+    let cached_voice_channel_ids = guild.voice_channel_ids.read().clone();
+    let join_channels = context
+        .database
+        .guild_join_channels(guild_id)
+        .await?
+        .into_iter()
+        .map(|join_channel| (join_channel.id, join_channel))
+        .collect::<HashMap<_, _>>();
+    let voice_channels = context
+        .database
+        .guild_voice_channels(guild_id)
+        .await?
+        .into_iter()
+        .map(|voice_channel| (voice_channel.id, voice_channel))
+        .collect::<HashMap<_, _>>();
+    let snapshot_time = SystemTime::now();
+    let guild_voice_channels = context
+        .client
+        .guild_channels(guild_id)
+        .await?
+        .models()
+        .await?
+        .into_iter()
+        .filter(|channel| {
+            matches!(
+                channel.kind,
+                ChannelType::GuildStageVoice | ChannelType::GuildVoice
+            )
+        })
+        .map(|channel| (channel.id, channel))
+        .collect::<HashMap<Id<ChannelMarker>, Channel>>();
+
+    for channel_id in cached_join_channel_ids.iter().copied() {
+        let Some(join_channel) = context.cache.join_channel(channel_id) else {
+            continue;
+        };
+        let Some(channel) = guild_voice_channels
+            .get(&channel_id)
+            .filter(|_| join_channels.contains_key(&channel_id))
+        else {
+            context.database.remove_join_channel(channel_id).await?;
+            context.cache.remove_join_channel(channel_id);
+
+            report.removed_join_channels += 1;
+
+            continue;
+        };
+        let mut update = CachedJoinChannelUpdate::default();
+        let name = channel.name.clone().unwrap_or_default();
+        let permission_overwrites = channel.permission_overwrites.clone().unwrap_or_default();
+
+        if join_channel.name.read().ne(&name) {
+            update.name = Some(name);
+        }
+        if join_channel
+            .permission_overwrites
+            .read()
+            .ne(&permission_overwrites)
+        {
+            update.permission_overwrites = Some(permission_overwrites);
+        }
+        if update.name.is_some() || update.permission_overwrites.is_some() {
+            context.cache.update_join_channel(channel_id, update);
+
+            report.updated_join_channels += 1;
+        }
+    }
+    for channel_id in cached_voice_channel_ids.iter().copied() {
+        let Some(voice_channel) = context.cache.voice_channel(channel_id) else {
+            continue;
+        };
+        let Some(channel) = guild_voice_channels
+            .get(&channel_id)
+            .filter(|_| voice_channels.contains_key(&channel_id))
+        else {
+            context.database.remove_voice_channel(channel_id).await?;
+            context.cache.remove_voice_channel(channel_id);
+
+            report.removed_voice_channels += 1;
+
+            continue;
+        };
+        let mut has_changed = false;
+        let mut update = CachedVoiceChannelUpdate::default();
+        let bitrate = channel.bitrate.unwrap_or(64_000);
+        let name = channel.name.clone().unwrap_or_default();
+        let permission_overwrites = channel.permission_overwrites.clone().unwrap_or_default();
+        let video_quality_mode = channel.video_quality_mode.unwrap_or(VideoQualityMode::Auto);
+
+        if voice_channel.bitrate.read().ne(&bitrate) {
+            update.bitrate = Some(bitrate);
+            has_changed = true;
+        }
+        if voice_channel.name.read().ne(&name) {
+            update.name = Some(name);
+            has_changed = true;
+        }
+        if voice_channel
+            .permission_overwrites
+            .read()
+            .ne(&permission_overwrites)
+        {
+            update.permission_overwrites = Some(permission_overwrites);
+            has_changed = true;
+        }
+        if voice_channel
+            .rate_limit_per_user
+            .read()
+            .ne(&channel.rate_limit_per_user)
+        {
+            update.rate_limit_per_user = Some(channel.rate_limit_per_user);
+            has_changed = true;
+        }
+        if voice_channel.rtc_region.read().ne(&channel.rtc_region) {
+            update.rtc_region = Some(channel.rtc_region.clone());
+            has_changed = true;
+        }
+        if voice_channel.user_limit.read().ne(&channel.user_limit) {
+            update.user_limit = Some(channel.user_limit);
+            has_changed = true;
+        }
+        if voice_channel
+            .video_quality_mode
+            .read()
+            .ne(&video_quality_mode)
+        {
+            update.video_quality_mode = Some(video_quality_mode);
+            has_changed = true;
+        }
+        if has_changed {
+            context.cache.update_voice_channel(channel_id, update);
+
+            report.updated_voice_channels += 1;
+        }
+    }
+    for join_channel in join_channels.into_values() {
+        // A join channel created since the IDs were read is already cached.
+        if cached_join_channel_ids.contains(&join_channel.id)
+            || context.cache.join_channel(join_channel.id).is_some()
+        {
+            continue;
+        }
+
+        let Some(channel) = guild_voice_channels.get(&join_channel.id) else {
+            continue;
+        };
+
+        context.cache.insert_join_channel(
+            join_channel.allowed_role_ids.into_iter().collect(),
+            join_channel.denied_role_ids.into_iter().collect(),
+            join_channel.id,
+            join_channel.guild_id,
+            join_channel.kind,
+            channel.name.clone().unwrap_or_default(),
+            join_channel.ownership_delay,
+            join_channel.ownership_policy,
+            join_channel.parent_id,
+            join_channel.permanence,
+            channel.permission_overwrites.clone().unwrap_or_default(),
+            join_channel.privacy,
+            join_channel.status_template,
+            join_channel.voice_channel_limit,
+            join_channel.waiting_room,
+        );
+
+        report.restored_join_channels += 1;
+    }
+    for voice_channel in voice_channels.into_values() {
+        if cached_voice_channel_ids.contains(&voice_channel.id)
+            || context.cache.voice_channel(voice_channel.id).is_some()
+        {
+            continue;
+        }
+
+        let Some(channel) = guild_voice_channels.get(&voice_channel.id) else {
+            continue;
+        };
+
+        let saved_settings = match voice_channel.settings {
+            Some(saved_settings) => saved_settings,
+            None => {
+                let saved_settings = VoiceChannelSettings::from(channel);
+
+                context
+                    .database
+                    .update_voice_channel_settings(voice_channel.id, &saved_settings)
+                    .await?;
+
+                saved_settings
+            }
+        };
+
+        context.cache.insert_voice_channel(
+            channel.bitrate.unwrap_or(64_000),
+            voice_channel.id,
+            voice_channel.guild_id,
+            voice_channel.join_channel_id,
+            voice_channel.kind,
+            channel.name.clone().unwrap_or_default(),
+            voice_channel.owner_id,
+            voice_channel.ownership_delay,
+            voice_channel.ownership_policy,
+            voice_channel.permanence,
+            channel.permission_overwrites.clone().unwrap_or_default(),
+            voice_channel.privacy,
+            channel.rate_limit_per_user,
+            channel.rtc_region.clone(),
+            Some(saved_settings),
+            voice_channel.status,
+            channel.user_limit,
+            channel.video_quality_mode.unwrap_or(VideoQualityMode::Auto),
+        );
+
+        if let Some(waiting_room_id) = voice_channel.waiting_room_id {
+            context
+                .cache
+                .insert_waiting_room(voice_channel.id, waiting_room_id);
+        }
+
+        report.restored_voice_channels += 1;
+    }
+
+    if report.restored_voice_channels.gt(&0) {
+        for voice_channel_manager in context
+            .database
+            .guild_voice_channel_managers(guild_id)
+            .await?
+        {
+            context.cache.insert_voice_channel_manager(
+                voice_channel_manager.channel_id,
+                voice_channel_manager.user_id,
+            );
+        }
+    }
+
+    report.removed_unknown_rows = context
+        .database
+        .remove_unknown_channels(
+            guild_voice_channels.keys().copied().collect(),
+            guild_id,
+            snapshot_time,
+        )
+        .await?;
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::Arc,
+        time::{Duration, SystemTime},
+    };
+
+    use twilight_model::id::Id;
+
+    use super::reconcile_guild;
+    use crate::{
+        structs::database::{
+            ChannelPrivacy, OwnershipPolicy, VoiceChannelKind, VoiceChannelSettings,
+        },
+        testing::{
+            self, mock_discord::MockDiscord, replay::replay, seed_join_channel, VOICE_CHANNEL_ID,
+        },
+        utilities::time::snowflake_at,
+    };
+
+    #[tokio::test]
+    #[ignore = "needs a database, run with `cargo test -- --ignored`"]
+    async fn picks_up_changes_made_while_events_were_missed() {
+        let mock_discord = MockDiscord::start().await;
+        let (context, _guard) = testing::context(&mock_discord).await;
+        let guild_id = Id::new(1001);
+
+        seed_join_channel(&context, guild_id, Id::new(1101), ChannelPrivacy::Unlocked).await;
+        replay(&context, include_str!("../testing/recordings/create.jsonl"))
+            .await
+            .unwrap();
+
+        let mut voice_channel = mock_discord.channel(5000).unwrap();
+
+        voice_channel["name"] = "renamed".into();
+        mock_discord.insert_channel(voice_channel);
+
+        let report = reconcile_guild(Arc::clone(&context), guild_id)
+            .await
+            .unwrap();
+
+        // The join channel was never created on the mock, so it's gone as far as Discord knows.
+        assert_eq!(report.removed_join_channels, 1);
+        assert_eq!(report.updated_voice_channels, 1);
+        assert!(context.cache.join_channel(Id::new(1101)).is_none());
+        assert_eq!(
+            context
+                .cache
+                .voice_channel(VOICE_CHANNEL_ID)
+                .unwrap()
+                .name
+                .read()
+                .as_str(),
+            "renamed"
+        );
+
+        context.database.remove_guild(guild_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a database, run with `cargo test -- --ignored`"]
+    async fn keeps_voice_channels_newer_than_the_snapshot() {
+        let mock_discord = MockDiscord::start().await;
+        let (context, _guard) = testing::context(&mock_discord).await;
+        let guild_id = Id::new(1001);
+        // A voice channel stored while the snapshot was on its way, which it can't include.
+        let voice_channel_id = Id::new(snowflake_at(SystemTime::now() + Duration::from_secs(60)));
+
+        seed_join_channel(&context, guild_id, Id::new(1101), ChannelPrivacy::Unlocked).await;
+        replay(&context, include_str!("../testing/recordings/create.jsonl"))
+            .await
+            .unwrap();
+        context
+            .database
+            .insert_voice_channel(
+                voice_channel_id,
+                guild_id,
+                Some(Id::new(2002)),
+                false,
+                ChannelPrivacy::Unlocked,
+                OwnershipPolicy::Keep,
+                0,
+                Some(Id::new(1101)),
+                VoiceChannelKind::Voice,
+                &VoiceChannelSettings {
+                    name: "sam's voice".to_owned(),
+                    bitrate: 64_000,
+                    user_limit: None,
+                    rtc_region: None,
+                },
+            )
+            .await
+            .unwrap();
+
+        reconcile_guild(Arc::clone(&context), guild_id)
+            .await
+            .unwrap();
+
+        assert!(context
+            .database
+            .guild_voice_channels(guild_id)
+            .await
+            .unwrap()
+            .iter()
+            .any(|voice_channel| voice_channel.id.eq(&voice_channel_id)));
+
+        context
+            .database
+            .remove_voice_channel(voice_channel_id)
+            .await
+            .unwrap();
+        context.database.remove_guild(guild_id).await.unwrap();
+    }
+}
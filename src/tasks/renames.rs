@@ -0,0 +1,286 @@
+use std::{sync::Arc, time::SystemTime};
+
+use tokio::time::{interval, MissedTickBehavior};
+use twilight_http::{api_error::ApiError, error::ErrorType};
+
+use crate::{
+    structs::{
+        cache::{CachedVoiceChannel, CachedVoiceChannelUpdate},
+        context::Context,
+    },
+    utilities::{audit::audit, constants::RENAME_INTERVAL, name_filter::name_violation},
+};
+
+pub async fn run(context: Arc<Context>) {
+    let mut rename_interval = interval(RENAME_INTERVAL);
+
+    rename_interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    loop {
+        rename_interval.tick().await;
+
+        apply_queued_names(&context, SystemTime::now()).await;
+    }
+}
+
+/// Renames every voice channel with a queued name that Discord allows to be renamed by `now`,
+/// returning how many were renamed. A queued name is dropped once the name filter catches it,
+/// the member who asked for it no longer owns or manages the voice channel, or Discord refuses it
+/// for anything but a rate limit.
+pub async fn apply_queued_names(context: &Arc<Context>, now: SystemTime) -> u64 {
+    let voice_channels = context
+        .cache
+        .voice_channels
+        .iter()
+        .filter(|voice_channel| voice_channel.queued_name.read().is_some())
+        .map(|voice_channel| Arc::clone(&voice_channel))
+        .collect::<Vec<Arc<CachedVoiceChannel>>>();
+    let mut renamed = 0;
+
+    for voice_channel in voice_channels {
+        if context
+            .cache
+            .rename_retry_after(voice_channel.id, now)
+            .is_some()
+        {
+            continue;
+        }
+
+        let Some(guild) = context.cache.guild(voice_channel.guild_id) else {
+            continue;
+        };
+        let Some((user_id, name)) = context.cache.remove_queued_name(voice_channel.id) else {
+            continue;
+        };
+
+        if name_violation(&guild, &name).is_some() {
+            continue;
+        }
+        if user_id.is_some_and(|user_id| {
+            voice_channel.owner_id.read().ne(&Some(user_id))
+                && !voice_channel.manager_ids.read().contains(&user_id)
+        }) {
+            continue;
+        }
+
+        let saved_settings =
+            context
+                .cache
+                .update_saved_settings(voice_channel.id, |saved_settings| {
+                    saved_settings.name = name.clone();
+                });
+
+        if let Err(error) = context
+            .client
+            .update_channel(voice_channel.id)
+            .name(&name)
+            .await
+        {
+            context.cache.update_voice_channel(
+                voice_channel.id,
+                CachedVoiceChannelUpdate {
+                    saved_settings: Some(saved_settings),
+                    ..Default::default()
+                },
+            );
+
+            // A rate limited name stays queued for the next run. Anything else, like missing
+            // permissions, would only fail again.
+            if matches!(
+                error.kind(),
+                ErrorType::Response {
+                    error: ApiError::Ratelimited(_),
+                    ..
+                }
+            ) {
+                context
+                    .cache
+                    .insert_queued_name(voice_channel.id, user_id, name);
+            }
+
+            continue;
+        }
+
+        context.cache.insert_rename_time(voice_channel.id, now);
+        _ = context
+            .database
+            .update_voice_channel_name(voice_channel.id, &name)
+            .await;
+        let (action, description) = match user_id {
+            Some(user_id) => (
+                "/voice name",
+                format!(
+                    "<@{user_id}> renamed <#{}> to **{name}**.",
+                    voice_channel.id
+                ),
+            ),
+            None => (
+                "external edit",
+                format!(
+                    "I changed the name of <#{}> back to **{name}**.",
+                    voice_channel.id
+                ),
+            ),
+        };
+
+        _ = audit(
+            context,
+            &guild,
+            user_id,
+            Some(voice_channel.id),
+            action,
+            description,
+        )
+        .await;
+
+        renamed += 1;
+    }
+
+    renamed
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, SystemTime};
+
+    use serde_json::json;
+    use twilight_model::id::Id;
+
+    use super::apply_queued_names;
+    use crate::{
+        structs::{cache::CachedVoiceChannelUpdate, database::ChannelPrivacy},
+        testing::{
+            self,
+            mock_discord::MockDiscord,
+            payloads::{command, subcommand},
+            replay::replay,
+            seed_join_channel,
+        },
+    };
+
+    fn name_command(token: &str, name: &str, is_queued: bool) -> String {
+        command(
+            token,
+            1001,
+            2001,
+            subcommand(
+                "voice",
+                "name",
+                json!([
+                    { "name": "name", "type": 3, "value": name },
+                    { "name": "queue", "type": 5, "value": is_queued },
+                ]),
+            ),
+        )
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a database, run with `cargo test -- --ignored`"]
+    async fn queued_names_wait_for_the_rate_limit() {
+        let mock_discord = MockDiscord::start().await;
+        let (context, _guard) = testing::context(&mock_discord).await;
+        let guild_id = Id::new(1001);
+
+        seed_join_channel(&context, guild_id, Id::new(1101), ChannelPrivacy::Unlocked).await;
+        replay(&context, include_str!("../testing/recordings/create.jsonl"))
+            .await
+            .unwrap();
+        replay(
+            &context,
+            &[
+                name_command("first", "study hall", false),
+                name_command("second", "library", false),
+                name_command("third", "quiet room", false),
+                name_command("fourth", "quiet room", true),
+            ]
+            .join("\n"),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(mock_discord.channel(5000).unwrap()["name"], "library");
+        assert_eq!(
+            mock_discord.response_descriptions("third"),
+            ["You can rename <#5000> again in **10m**. Use the **queue** option to have me rename it then."]
+        );
+        assert_eq!(
+            mock_discord.response_descriptions("fourth"),
+            ["You can rename <#5000> again in **10m**, so I'll name it **quiet room** then."]
+        );
+        assert_eq!(apply_queued_names(&context, SystemTime::now()).await, 0);
+        assert_eq!(
+            apply_queued_names(&context, SystemTime::now() + Duration::from_secs(600)).await,
+            1
+        );
+        assert_eq!(mock_discord.channel(5000).unwrap()["name"], "quiet room");
+        assert!(context
+            .cache
+            .voice_channel(Id::new(5000))
+            .unwrap()
+            .queued_name
+            .read()
+            .is_none());
+
+        context.database.remove_guild(guild_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a database, run with `cargo test -- --ignored`"]
+    async fn queued_names_are_dropped_when_they_cant_apply() {
+        let mock_discord = MockDiscord::start().await;
+        let (context, _guard) = testing::context(&mock_discord).await;
+        let guild_id = Id::new(1001);
+        let set_owner = |owner_id: u64| {
+            context.cache.update_voice_channel(
+                Id::new(5000),
+                CachedVoiceChannelUpdate {
+                    owner_id: Some(Some(Id::new(owner_id))),
+                    ..Default::default()
+                },
+            );
+        };
+        let queued_name = || {
+            context
+                .cache
+                .voice_channel(Id::new(5000))
+                .unwrap()
+                .queued_name
+                .read()
+                .clone()
+        };
+
+        seed_join_channel(&context, guild_id, Id::new(1101), ChannelPrivacy::Unlocked).await;
+        replay(&context, include_str!("../testing/recordings/create.jsonl"))
+            .await
+            .unwrap();
+        replay(
+            &context,
+            &[
+                name_command("first", "study hall", false),
+                name_command("second", "library", false),
+                name_command("queued", "quiet room", true),
+            ]
+            .join("\n"),
+        )
+        .await
+        .unwrap();
+        set_owner(2002);
+
+        let later = SystemTime::now() + Duration::from_secs(600);
+
+        assert_eq!(apply_queued_names(&context, later).await, 0);
+        assert_eq!(queued_name(), None);
+
+        set_owner(2001);
+        replay(&context, &name_command("queued-again", "quiet room", true))
+            .await
+            .unwrap();
+        mock_discord.fail("PATCH", "/channels/5000");
+
+        assert_eq!(apply_queued_names(&context, later).await, 0);
+        assert_eq!(queued_name(), None);
+        assert_eq!(mock_discord.channel(5000).unwrap()["name"], "library");
+
+        context.database.remove_guild(guild_id).await.unwrap();
+    }
+}
@@ -0,0 +1,305 @@
+use std::{sync::Arc, time::SystemTime};
+
+use eyre::Result;
+use tokio::time::{interval, MissedTickBehavior};
+use twilight_model::{
+    channel::{
+        message::AllowedMentions,
+        permission_overwrite::PermissionOverwriteType as ChannelPermissionOverwriteType,
+    },
+    guild::Permissions,
+    http::permission_overwrite::{
+        PermissionOverwrite as HttpPermissionOverwrite,
+        PermissionOverwriteType as HttpPermissionOverwriteType,
+    },
+};
+
+use crate::{
+    structs::{
+        cache::CachedVoiceChannel,
+        context::Context,
+        database::{TimedPermission, TimedPermissionKind},
+    },
+    utilities::constants::TIMED_PERMISSION_INTERVAL,
+};
+
+pub async fn run(context: Arc<Context>) {
+    let mut timed_permission_interval = interval(TIMED_PERMISSION_INTERVAL);
+
+    timed_permission_interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    loop {
+        timed_permission_interval.tick().await;
+
+        expire(&context, SystemTime::now()).await;
+    }
+}
+
+/// Reverts every timed permission that has expired by `now` and lets the voice channel's owner
+/// know, returning how many expired. Expiries are read from the database, so those that fell due
+/// while I was offline are handled on the first run.
+pub async fn expire(context: &Arc<Context>, now: SystemTime) -> u64 {
+    let Ok(timed_permissions) = context.database.due_timed_permissions(now).await else {
+        return 0;
+    };
+    let mut expired = 0;
+
+    for timed_permission in timed_permissions {
+        // The guild may be unavailable, in which case the permission expires once it's back.
+        let Some(voice_channel) = context.cache.voice_channel(timed_permission.channel_id) else {
+            continue;
+        };
+
+        if revert(context, &voice_channel, &timed_permission)
+            .await
+            .is_err()
+        {
+            continue;
+        }
+
+        let member_id = timed_permission.member_id;
+        let permission_text = match timed_permission.kind {
+            TimedPermissionKind::Allow => "allowed",
+            TimedPermissionKind::Deny => "denied",
+        };
+        let owner_id = *voice_channel.owner_id.read();
+        let notice = match owner_id {
+            Some(owner_id) => format!(
+                "<@{owner_id}>, <@{member_id}> is no longer {permission_text} permission in this voice channel."
+            ),
+            None => format!(
+                "<@{member_id}> is no longer {permission_text} permission in this voice channel."
+            ),
+        };
+        let allowed_mentions = AllowedMentions {
+            users: owner_id.into_iter().collect(),
+            ..Default::default()
+        };
+
+        // Not every voice channel has a text chat I may post in, and the expiry stands
+        // regardless.
+        _ = context
+            .client
+            .create_message(voice_channel.id)
+            .allowed_mentions(Some(&allowed_mentions))
+            .content(&notice)
+            .await;
+
+        expired += 1;
+    }
+
+    expired
+}
+
+/// Takes back what the timed permission gave the member, leaving the rest of their overwrite as
+/// it is. The owner's and managers' overwrites are never touched.
+async fn revert(
+    context: &Context,
+    voice_channel: &CachedVoiceChannel,
+    timed_permission: &TimedPermission,
+) -> Result<()> {
+    let member_id = timed_permission.member_id;
+    let is_exempt = voice_channel.owner_id.read().eq(&Some(member_id))
+        || voice_channel.manager_ids.read().contains(&member_id);
+    let permission_overwrite = voice_channel
+        .permission_overwrites
+        .read()
+        .iter()
+        .find(|permission_overwrite| {
+            permission_overwrite
+                .kind
+                .eq(&ChannelPermissionOverwriteType::Member)
+                && permission_overwrite.id.eq(&member_id.cast())
+        })
+        .cloned();
+
+    if let (false, Some(permission_overwrite)) = (is_exempt, permission_overwrite) {
+        let (mut member_allow, mut member_deny) =
+            (permission_overwrite.allow, permission_overwrite.deny);
+
+        match timed_permission.kind {
+            TimedPermissionKind::Allow => {
+                member_allow.remove(Permissions::CONNECT | Permissions::VIEW_CHANNEL)
+            }
+            TimedPermissionKind::Deny => {
+                member_deny.remove(Permissions::CONNECT | Permissions::VIEW_CHANNEL)
+            }
+        }
+
+        if member_allow.is_empty() && member_deny.is_empty() {
+            context
+                .client
+                .delete_channel_permission(voice_channel.id)
+                .member(member_id)
+                .await?;
+        } else {
+            context
+                .client
+                .update_channel_permission(
+                    voice_channel.id,
+                    &HttpPermissionOverwrite {
+                        allow: Some(member_allow),
+                        deny: Some(member_deny),
+                        id: member_id.cast(),
+                        kind: HttpPermissionOverwriteType::Member,
+                    },
+                )
+                .await?;
+        }
+    }
+
+    context
+        .database
+        .remove_timed_permission(voice_channel.id, member_id)
+        .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, SystemTime};
+
+    use serde_json::{json, Value};
+    use twilight_model::id::Id;
+
+    use super::expire;
+    use crate::{
+        structs::database::ChannelPrivacy,
+        testing::{
+            self,
+            mock_discord::MockDiscord,
+            payloads::{channel_update, command, subcommand},
+            replay::replay,
+            seed_join_channel,
+        },
+    };
+
+    fn allow_command(token: &str, duration: &str) -> String {
+        command(
+            token,
+            1001,
+            2001,
+            subcommand(
+                "voice",
+                "allow-member",
+                json!([
+                    { "name": "member", "type": 3, "value": "2002" },
+                    { "name": "duration", "type": 3, "value": duration },
+                ]),
+            ),
+        )
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a database, run with `cargo test -- --ignored`"]
+    async fn timed_allows_expire_and_notify_the_owner() {
+        let mock_discord = MockDiscord::start().await;
+        let (context, _guard) = testing::context(&mock_discord).await;
+        let guild_id = Id::new(1001);
+
+        seed_join_channel(&context, guild_id, Id::new(1101), ChannelPrivacy::Locked).await;
+        replay(&context, include_str!("../testing/recordings/create.jsonl"))
+            .await
+            .unwrap();
+        replay(
+            &context,
+            &[
+                allow_command("forever", "forever"),
+                allow_command("allow", "30m"),
+            ]
+            .join("\n"),
+        )
+        .await
+        .unwrap();
+        replay(
+            &context,
+            &channel_update(mock_discord.channel(5000).unwrap()),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            mock_discord.response_descriptions("forever"),
+            ["I could not find a valid **duration** value."]
+        );
+        assert_eq!(
+            mock_discord.response_descriptions("allow"),
+            ["<@2002> is now allowed permission in this voice channel for **30m**."]
+        );
+        assert_eq!(expire(&context, SystemTime::now()).await, 0);
+        assert_eq!(
+            expire(&context, SystemTime::now() + Duration::from_secs(1_801)).await,
+            1
+        );
+        mock_discord.assert_requested("DELETE", "/channels/5000/permissions/2002");
+        assert_eq!(
+            mock_discord
+                .assert_requested("POST", "/channels/5000/messages")
+                .body["content"],
+            "<@2001>, <@2002> is no longer allowed permission in this voice channel."
+        );
+        assert_eq!(
+            expire(&context, SystemTime::now() + Duration::from_secs(1_801)).await,
+            0
+        );
+
+        context.database.remove_guild(guild_id).await.unwrap();
+    }
+    #[tokio::test]
+    #[ignore = "needs a database, run with `cargo test -- --ignored`"]
+    async fn timed_denies_leave_permanent_denies_alone() {
+        let mock_discord = MockDiscord::start().await;
+        let (context, _guard) = testing::context(&mock_discord).await;
+        let guild_id = Id::new(1001);
+        let deny_command = |token: &str, options: Value| {
+            command(
+                token,
+                1001,
+                2001,
+                subcommand("voice", "deny-member", options),
+            )
+        };
+
+        seed_join_channel(&context, guild_id, Id::new(1101), ChannelPrivacy::Unlocked).await;
+        replay(&context, include_str!("../testing/recordings/create.jsonl"))
+            .await
+            .unwrap();
+        replay(
+            &context,
+            &deny_command(
+                "deny",
+                json!([{ "name": "member", "type": 6, "value": "2002" }]),
+            ),
+        )
+        .await
+        .unwrap();
+        replay(
+            &context,
+            &[
+                channel_update(mock_discord.channel(5000).unwrap()),
+                deny_command(
+                    "timed",
+                    json!([
+                        { "name": "member", "type": 6, "value": "2002" },
+                        { "name": "duration", "type": 3, "value": "30m" },
+                    ]),
+                ),
+            ]
+            .join("\n"),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            mock_discord.response_descriptions("timed"),
+            ["<@2002> is already denied permission in this voice channel."]
+        );
+        assert_eq!(
+            expire(&context, SystemTime::now() + Duration::from_secs(1_801)).await,
+            0
+        );
+
+        context.database.remove_guild(guild_id).await.unwrap();
+    }
+}
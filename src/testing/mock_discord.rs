@@ -0,0 +1,542 @@
+use std::{collections::HashMap, io, net::SocketAddr, sync::Arc};
+
+use parking_lot::Mutex;
+use serde_json::{json, Value};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader},
+    net::{TcpListener, TcpStream},
+};
+use twilight_http::Client;
+
+use super::APPLICATION_ID;
+
+/// The first ID handed out for resources the mock creates.
+pub const FIRST_MOCK_ID: u64 = 5_000;
+
+/// An in-process stand-in for the parts of the Discord REST API I use. Channels are kept in
+/// memory so that reads, updates and permission changes behave like the real thing.
+pub struct MockDiscord {
+    pub address: SocketAddr,
+    pub state: Arc<Mutex<MockDiscordState>>,
+}
+
+pub struct MockDiscordState {
+    pub channels: HashMap<u64, Value>,
+    pub failures: Vec<(String, String)>,
+    pub member_roles: HashMap<u64, Vec<u64>>,
+    pub next_id: u64,
+    pub requests: Vec<MockRequest>,
+    /// Live stage instances, by the ID of their stage channel.
+    pub stage_instances: HashMap<u64, Value>,
+}
+
+#[derive(Clone, Debug)]
+pub struct MockRequest {
+    pub body: Value,
+    pub method: String,
+    pub path: String,
+}
+
+impl MockDiscord {
+    /// Panics unless `method path` was requested, and returns the latest matching request.
+    pub fn assert_requested(&self, method: &str, path: &str) -> MockRequest {
+        let requests = self.requests();
+
+        requests
+            .iter()
+            .rev()
+            .find(|request| request.method.eq(method) && request.path.eq(path))
+            .cloned()
+            .unwrap_or_else(|| {
+                panic!("\"{method} {path}\" was never requested. Requests: {requests:#?}")
+            })
+    }
+
+    /// Panics if `method path` was requested.
+    pub fn assert_not_requested(&self, method: &str, path: &str) {
+        let requests = self.requests_to(method, path);
+
+        assert!(
+            requests.is_empty(),
+            "\"{method} {path}\" was requested: {requests:#?}"
+        );
+    }
+
+    /// The `(name, value)` pairs of every autocomplete result sent for an interaction.
+    pub fn autocomplete_choices(&self, token: &str) -> Vec<(String, String)> {
+        self.requests()
+            .into_iter()
+            .filter(|request| {
+                matches!(
+                    split_path(&request.path).as_slice(),
+                    ["interactions", _, response_token, "callback"] if response_token.eq(&token)
+                ) && request.body["type"].eq(&8)
+            })
+            .flat_map(|request| {
+                request.body["data"]["choices"]
+                    .as_array()
+                    .cloned()
+                    .unwrap_or_default()
+            })
+            .map(|choice| {
+                (
+                    choice["name"].as_str().unwrap_or_default().to_owned(),
+                    choice["value"].as_str().unwrap_or_default().to_owned(),
+                )
+            })
+            .collect()
+    }
+
+    pub fn channel(&self, id: u64) -> Option<Value> {
+        self.state.lock().channels.get(&id).cloned()
+    }
+
+    /// A client whose requests go to this mock over plain HTTP.
+    pub fn client(&self) -> Client {
+        Client::builder()
+            .proxy(self.address.to_string(), true)
+            .ratelimiter(None)
+            .token("mock".to_owned())
+            .build()
+    }
+
+    /// Makes every later `method path` request fail with "Missing Permissions".
+    pub fn fail(&self, method: &str, path: &str) {
+        self.state
+            .lock()
+            .failures
+            .push((method.to_owned(), path.to_owned()));
+    }
+
+    pub fn insert_channel(&self, channel: Value) {
+        let id = channel["id"].as_str().unwrap().parse().unwrap();
+
+        self.state.lock().channels.insert(id, channel);
+    }
+
+    /// Gives the member the roles returned when they're looked up or edited.
+    pub fn insert_member_roles(&self, user_id: u64, role_ids: &[u64]) {
+        self.state
+            .lock()
+            .member_roles
+            .insert(user_id, role_ids.to_vec());
+    }
+
+    pub fn requests(&self) -> Vec<MockRequest> {
+        self.state.lock().requests.clone()
+    }
+
+    pub fn requests_to(&self, method: &str, path: &str) -> Vec<MockRequest> {
+        self.requests()
+            .into_iter()
+            .filter(|request| request.method.eq(method) && request.path.eq(path))
+            .collect()
+    }
+
+    /// The embed descriptions sent for an interaction, whether as its initial response or as an
+    /// edit of it, in the order they were sent.
+    pub fn response_descriptions(&self, token: &str) -> Vec<String> {
+        self.requests()
+            .into_iter()
+            .filter_map(|request| {
+                let segments = split_path(&request.path);
+
+                match (request.method.as_str(), segments.as_slice()) {
+                    ("POST", ["interactions", _, response_token, "callback"])
+                        if response_token.eq(&token) =>
+                    {
+                        Some(request.body["data"]["embeds"].clone())
+                    }
+                    ("PATCH", ["webhooks", _, response_token, "messages", "@original"])
+                        if response_token.eq(&token) =>
+                    {
+                        Some(request.body["embeds"].clone())
+                    }
+                    _ => None,
+                }
+            })
+            .flat_map(|embeds| embeds.as_array().cloned().unwrap_or_default())
+            .filter_map(|embed| embed["description"].as_str().map(ToOwned::to_owned))
+            .collect()
+    }
+
+    pub fn stage_instance(&self, channel_id: u64) -> Option<Value> {
+        self.state.lock().stage_instances.get(&channel_id).cloned()
+    }
+
+    pub async fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let state = Arc::new(Mutex::new(MockDiscordState {
+            channels: HashMap::new(),
+            failures: Vec::new(),
+            member_roles: HashMap::new(),
+            next_id: FIRST_MOCK_ID,
+            requests: Vec::new(),
+            stage_instances: HashMap::new(),
+        }));
+        let listener_state = Arc::clone(&state);
+
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                tokio::spawn(serve(stream, Arc::clone(&listener_state)));
+            }
+        });
+
+        Self { address, state }
+    }
+}
+
+impl MockDiscordState {
+    fn respond(&mut self, method: &str, path: &str, body: Value) -> (u16, Option<Value>) {
+        self.requests.push(MockRequest {
+            body: body.clone(),
+            method: method.to_owned(),
+            path: path.to_owned(),
+        });
+
+        if self
+            .failures
+            .iter()
+            .any(|(failed_method, failed_path)| failed_method.eq(method) && failed_path.eq(path))
+        {
+            return error(403, 50_013, "Missing Permissions");
+        }
+
+        match (method, split_path(path).as_slice()) {
+            ("GET", ["applications", "@me"]) => (
+                200,
+                Some(json!({
+                    "bot_public": true,
+                    "bot_require_code_grant": false,
+                    "cover_image": null,
+                    "description": "",
+                    "flags": 0,
+                    "guild_id": null,
+                    "icon": null,
+                    "id": APPLICATION_ID.to_string(),
+                    "name": "voz",
+                    "owner": null,
+                    "primary_sku_id": null,
+                    "slug": null,
+                    "team": null,
+                    "verify_key": "",
+                })),
+            ),
+            ("GET", ["guilds", guild_id, "channels"]) => {
+                let channels = self
+                    .channels
+                    .values()
+                    .filter(|channel| channel["guild_id"].eq(guild_id))
+                    .cloned()
+                    .collect::<Vec<Value>>();
+
+                (200, Some(Value::Array(channels)))
+            }
+            ("POST", ["guilds", guild_id, "channels"]) => {
+                // Stage channels are capped at 64kbps whatever the server's boost level.
+                if body["type"].eq(&13)
+                    && body["bitrate"]
+                        .as_u64()
+                        .is_some_and(|bitrate| bitrate > 64_000)
+                {
+                    return error(400, 50_035, "Invalid Form Body");
+                }
+
+                let id = self.next_id;
+                let channel = json!({
+                    "bitrate": body.get("bitrate").cloned().unwrap_or(json!(64_000)),
+                    "guild_id": guild_id,
+                    "id": id.to_string(),
+                    "name": body["name"],
+                    "parent_id": body.get("parent_id").cloned().unwrap_or(Value::Null),
+                    "permission_overwrites": body
+                        .get("permission_overwrites")
+                        .cloned()
+                        .unwrap_or(json!([])),
+                    "rate_limit_per_user": 0,
+                    "rtc_region": null,
+                    "type": body.get("type").cloned().unwrap_or(json!(2)),
+                    "user_limit": 0,
+                    "video_quality_mode": 1,
+                });
+
+                self.next_id += 1;
+                self.channels.insert(id, channel.clone());
+
+                (200, Some(channel))
+            }
+            ("GET" | "PATCH", ["guilds", guild_id, "members", user_id]) => {
+                let role_ids = user_id
+                    .parse::<u64>()
+                    .ok()
+                    .and_then(|user_id| self.member_roles.get(&user_id))
+                    .cloned()
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|role_id| role_id.to_string())
+                    .collect::<Vec<String>>();
+
+                (
+                    200,
+                    Some(json!({
+                        "avatar": null,
+                        "communication_disabled_until": null,
+                        "deaf": false,
+                        "flags": 0,
+                        "guild_id": guild_id,
+                        "joined_at": "2024-01-01T00:00:00.000000+00:00",
+                        "mute": false,
+                        "nick": null,
+                        "pending": false,
+                        "roles": role_ids,
+                        "user": {
+                            "avatar": null,
+                            "discriminator": "0",
+                            "global_name": null,
+                            "id": user_id,
+                            "username": "member",
+                        },
+                    })),
+                )
+            }
+            ("PATCH", ["guilds", _, "voice-states", _]) => (204, None),
+            ("POST", ["stage-instances"]) => {
+                let Some(channel_id) = body["channel_id"]
+                    .as_str()
+                    .and_then(|channel_id| channel_id.parse::<u64>().ok())
+                    .filter(|channel_id| self.channels.contains_key(channel_id))
+                else {
+                    return error(404, 10_003, "Unknown Channel");
+                };
+                let id = self.next_id;
+                let stage_instance = json!({
+                    "channel_id": channel_id.to_string(),
+                    "guild_id": self.channels[&channel_id]["guild_id"],
+                    "guild_scheduled_event_id": null,
+                    "id": id.to_string(),
+                    "privacy_level": 2,
+                    "topic": body["topic"],
+                });
+
+                self.next_id += 1;
+                self.stage_instances
+                    .insert(channel_id, stage_instance.clone());
+
+                (200, Some(stage_instance))
+            }
+            ("PATCH", ["stage-instances", channel_id]) => {
+                let Some(stage_instance) = channel_id
+                    .parse()
+                    .ok()
+                    .and_then(|id: u64| self.stage_instances.get_mut(&id))
+                else {
+                    return error(404, 10_067, "Unknown Stage Instance");
+                };
+
+                if let Some(topic) = body.get("topic") {
+                    stage_instance["topic"] = topic.clone();
+                }
+
+                (200, Some(stage_instance.clone()))
+            }
+            ("PATCH", ["channels", channel_id]) => {
+                let Some(channel) = self.channel_mut(channel_id) else {
+                    return error(404, 10_003, "Unknown Channel");
+                };
+
+                if let (Some(channel), Some(changes)) = (channel.as_object_mut(), body.as_object())
+                {
+                    for (key, value) in changes {
+                        channel.insert(key.clone(), value.clone());
+                    }
+                }
+
+                (200, Some(channel.clone()))
+            }
+            ("DELETE", ["channels", channel_id]) => {
+                match channel_id
+                    .parse()
+                    .ok()
+                    .and_then(|id: u64| self.channels.remove(&id))
+                {
+                    Some(channel) => (200, Some(channel)),
+                    None => error(404, 10_003, "Unknown Channel"),
+                }
+            }
+            ("PUT", ["channels", channel_id, "permissions", overwrite_id]) => {
+                let Some(channel) = self.channel_mut(channel_id) else {
+                    return error(404, 10_003, "Unknown Channel");
+                };
+                let permission_overwrite = json!({
+                    "allow": body.get("allow").cloned().unwrap_or(json!("0")),
+                    "deny": body.get("deny").cloned().unwrap_or(json!("0")),
+                    "id": overwrite_id,
+                    "type": body["type"],
+                });
+                let permission_overwrites =
+                    channel["permission_overwrites"].as_array_mut().unwrap();
+
+                permission_overwrites.retain(|existing| existing["id"].ne(overwrite_id));
+                permission_overwrites.push(permission_overwrite);
+
+                (204, None)
+            }
+            ("PUT", ["channels", channel_id, "voice-status"]) => {
+                let Some(channel) = self.channel_mut(channel_id) else {
+                    return error(404, 10_003, "Unknown Channel");
+                };
+
+                channel["status"] = body.get("status").cloned().unwrap_or(Value::Null);
+
+                (204, None)
+            }
+            ("DELETE", ["channels", channel_id, "permissions", overwrite_id]) => {
+                let Some(channel) = self.channel_mut(channel_id) else {
+                    return error(404, 10_003, "Unknown Channel");
+                };
+
+                channel["permission_overwrites"]
+                    .as_array_mut()
+                    .unwrap()
+                    .retain(|existing| existing["id"].ne(overwrite_id));
+
+                (204, None)
+            }
+            ("POST", ["users", "@me", "channels"]) => {
+                let id = self.next_id;
+
+                self.next_id += 1;
+
+                (
+                    200,
+                    Some(json!({
+                        "id": id.to_string(),
+                        "last_message_id": null,
+                        "recipients": [{
+                            "avatar": null,
+                            "discriminator": "0",
+                            "global_name": null,
+                            "id": body["recipient_id"],
+                            "username": "member",
+                        }],
+                        "type": 1,
+                    })),
+                )
+            }
+            ("POST", ["interactions", _, _, "callback"]) => (204, None),
+            ("POST", ["channels", channel_id, "messages"]) => {
+                let id = self.next_id;
+
+                self.next_id += 1;
+
+                (200, Some(message(channel_id, &id.to_string(), 0, 0, &body)))
+            }
+            ("PATCH", ["webhooks", _, _, "messages", "@original"]) => {
+                (200, Some(message("1", "1", 20, 64, &body)))
+            }
+            _ => error(404, 0, "404: Not Found"),
+        }
+    }
+
+    fn channel_mut(&mut self, channel_id: &str) -> Option<&mut Value> {
+        channel_id
+            .parse()
+            .ok()
+            .and_then(|id: u64| self.channels.get_mut(&id))
+    }
+}
+
+fn message(channel_id: &str, id: &str, kind: u8, flags: u64, body: &Value) -> Value {
+    json!({
+        "application_id": APPLICATION_ID.to_string(),
+        "attachments": [],
+        "author": {
+            "avatar": null,
+            "bot": true,
+            "discriminator": "0",
+            "id": APPLICATION_ID.to_string(),
+            "username": "voz",
+        },
+        "channel_id": channel_id,
+        "components": body.get("components").cloned().unwrap_or(json!([])),
+        "content": body.get("content").cloned().unwrap_or(json!("")),
+        "edited_timestamp": null,
+        "embeds": body.get("embeds").cloned().unwrap_or(json!([])),
+        "flags": flags,
+        "id": id,
+        "mention_everyone": false,
+        "mention_roles": [],
+        "mentions": [],
+        "pinned": false,
+        "timestamp": "2024-01-01T00:00:00.000000+00:00",
+        "tts": false,
+        "type": kind,
+    })
+}
+
+fn error(status: u16, code: u64, message: &str) -> (u16, Option<Value>) {
+    (status, Some(json!({ "code": code, "message": message })))
+}
+
+fn split_path(path: &str) -> Vec<&str> {
+    path.trim_matches('/').split('/').collect()
+}
+
+async fn serve(stream: TcpStream, state: Arc<Mutex<MockDiscordState>>) -> io::Result<()> {
+    let mut reader = BufReader::new(stream);
+
+    loop {
+        let mut request_line = String::new();
+
+        if reader.read_line(&mut request_line).await? == 0 {
+            return Ok(());
+        }
+
+        let mut request_line_parts = request_line.split_whitespace();
+        let method = request_line_parts.next().unwrap_or_default().to_owned();
+        let target = request_line_parts.next().unwrap_or_default();
+        let path = target
+            .split('?')
+            .next()
+            .unwrap_or_default()
+            .trim_start_matches("/api/v10")
+            .to_owned();
+        let mut content_length = 0;
+
+        loop {
+            let mut header = String::new();
+
+            reader.read_line(&mut header).await?;
+
+            if header.trim().is_empty() {
+                break;
+            }
+            if let Some((name, value)) = header.split_once(':') {
+                if name.eq_ignore_ascii_case("content-length") {
+                    content_length = value.trim().parse().unwrap_or_default();
+                }
+            }
+        }
+
+        let mut body = vec![0; content_length];
+
+        reader.read_exact(&mut body).await?;
+
+        let body = serde_json::from_slice(&body).unwrap_or(Value::Null);
+        let (status, response_body) = state.lock().respond(&method, &path, body);
+        let response = match response_body {
+            Some(response_body) => {
+                let response_body = response_body.to_string();
+
+                format!(
+                    "HTTP/1.1 {status} Mock\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\r\n{response_body}",
+                    response_body.len()
+                )
+            }
+            None => format!("HTTP/1.1 {status} Mock\r\ncontent-length: 0\r\n\r\n"),
+        };
+
+        reader.get_mut().write_all(response.as_bytes()).await?;
+    }
+}
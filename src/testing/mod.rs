@@ -0,0 +1,73 @@
+pub mod mock_discord;
+pub mod payloads;
+pub mod replay;
+
+use std::{env, sync::Arc};
+
+use tokio::sync::{Mutex, MutexGuard, OnceCell};
+use twilight_model::id::{
+    marker::{ApplicationMarker, ChannelMarker, GuildMarker},
+    Id,
+};
+
+use self::mock_discord::{MockDiscord, FIRST_MOCK_ID};
+use crate::structs::{
+    context::Context,
+    database::{ChannelPrivacy, VoiceChannelKind},
+};
+
+pub const APPLICATION_ID: Id<ApplicationMarker> = Id::new(900);
+/// The voice channel a recording's first member gets, as it's the first channel the mock creates.
+pub const VOICE_CHANNEL_ID: Id<ChannelMarker> = Id::new(FIRST_MOCK_ID);
+
+// Mocks hand out the same channel IDs, so tests sharing the database take turns.
+static DATABASE: Mutex<()> = Mutex::const_new(());
+static TABLES: OnceCell<()> = OnceCell::const_new();
+
+/// A context backed by the mock and the database at `DATABASE_URL`. Tests that need it are
+/// ignored by default, so a missing database fails them rather than letting them pass unrun. The
+/// guard must be held for as long as the test touches the database.
+pub async fn context(mock_discord: &MockDiscord) -> (Arc<Context>, MutexGuard<'static, ()>) {
+    _ = dotenvy::dotenv();
+
+    assert!(
+        env::var("DATABASE_URL").is_ok_and(|url| !url.is_empty()),
+        "\"DATABASE_URL\" must be set to run tests that need a database."
+    );
+
+    let guard = DATABASE.lock().await;
+    let context = Arc::new(Context::new(APPLICATION_ID, mock_discord.client()).unwrap());
+
+    TABLES
+        .get_or_init(|| async { context.database.create_tables().await.unwrap() })
+        .await;
+
+    (context, guard)
+}
+
+/// Clears whatever an earlier run left for the guild and stores its join channel.
+pub async fn seed_join_channel(
+    context: &Arc<Context>,
+    guild_id: Id<GuildMarker>,
+    join_channel_id: Id<ChannelMarker>,
+    privacy: ChannelPrivacy,
+) {
+    context.database.remove_guild(guild_id).await.unwrap();
+    context
+        .database
+        .remove_voice_channel(VOICE_CHANNEL_ID)
+        .await
+        .unwrap();
+    context
+        .database
+        .insert_join_channel(
+            join_channel_id,
+            guild_id,
+            None,
+            false,
+            privacy,
+            VoiceChannelKind::Voice,
+        )
+        .await
+        .unwrap();
+}
@@ -0,0 +1,223 @@
+use serde_json::{json, Value};
+
+use super::APPLICATION_ID;
+
+/// A `CHANNEL_DELETE` gateway payload for `channel`, as Discord sends once the bot deletes it.
+pub fn channel_delete(channel: Value) -> String {
+    json!({
+        "op": 0,
+        "s": 1,
+        "t": "CHANNEL_DELETE",
+        "d": channel,
+    })
+    .to_string()
+}
+
+/// A `CHANNEL_UPDATE` gateway payload echoing `channel`, as Discord sends after every edit the
+/// bot makes.
+pub fn channel_update(channel: Value) -> String {
+    json!({
+        "op": 0,
+        "s": 1,
+        "t": "CHANNEL_UPDATE",
+        "d": channel,
+    })
+    .to_string()
+}
+
+/// An `INTERACTION_CREATE` gateway payload, ready to be replayed, for a command used by
+/// `user_id` in a voice channel's text chat.
+pub fn command(token: &str, guild_id: u64, user_id: u64, data: Value) -> String {
+    let channel = json!({
+        "guild_id": guild_id.to_string(),
+        "id": "1",
+        "name": "text",
+        "type": 0,
+    });
+
+    interaction_create(token, guild_id, user_id, channel, 2, data)
+}
+
+/// An `INTERACTION_CREATE` gateway payload for `user_id` pressing the button with `custom_id` on
+/// a message in the text chat of `channel_id`.
+pub fn component(
+    token: &str,
+    guild_id: u64,
+    user_id: u64,
+    channel_id: u64,
+    custom_id: &str,
+) -> String {
+    let channel = json!({
+        "guild_id": guild_id.to_string(),
+        "id": channel_id.to_string(),
+        "name": "voice",
+        "type": 2,
+    });
+    let data = json!({
+        "component_type": 2,
+        "custom_id": custom_id,
+    });
+
+    interaction_create(token, guild_id, user_id, channel, 3, data)
+}
+
+fn interaction_create(
+    token: &str,
+    guild_id: u64,
+    user_id: u64,
+    channel: Value,
+    kind: u8,
+    data: Value,
+) -> String {
+    json!({
+        "op": 0,
+        "s": 1,
+        "t": "INTERACTION_CREATE",
+        "d": {
+            "application_id": APPLICATION_ID.to_string(),
+            "channel": channel,
+            "data": data,
+            "guild_id": guild_id.to_string(),
+            "id": "1",
+            "member": {
+                "communication_disabled_until": null,
+                "deaf": false,
+                "flags": 0,
+                "joined_at": "2024-01-01T00:00:00.000000+00:00",
+                "mute": false,
+                "nick": null,
+                "permissions": "0",
+                "roles": [],
+                "user": {
+                    "avatar": null,
+                    "discriminator": "0",
+                    "global_name": null,
+                    "id": user_id.to_string(),
+                    "username": "member",
+                },
+            },
+            "token": token,
+            "type": kind,
+            "version": 1,
+        },
+    })
+    .to_string()
+}
+
+/// A `GUILD_ROLE_DELETE` gateway payload, as Discord sends once a role is deleted.
+pub fn role_delete(guild_id: u64, role_id: u64) -> String {
+    json!({
+        "op": 0,
+        "s": 1,
+        "t": "GUILD_ROLE_DELETE",
+        "d": {
+            "guild_id": guild_id.to_string(),
+            "role_id": role_id.to_string(),
+        },
+    })
+    .to_string()
+}
+
+/// Command data for `/<command> <subcommand>` with the given options.
+pub fn subcommand(command: &str, subcommand: &str, options: Value) -> Value {
+    json!({
+        "id": "1",
+        "name": command,
+        "options": [{
+            "name": subcommand,
+            "options": options,
+            "type": 1,
+        }],
+        "type": 1,
+    })
+}
+
+/// Command data for `/<command> <group> <subcommand>` with the given options.
+pub fn subcommand_group(command: &str, group: &str, subcommand: &str, options: Value) -> Value {
+    json!({
+        "id": "1",
+        "name": command,
+        "options": [{
+            "name": group,
+            "options": [{
+                "name": subcommand,
+                "options": options,
+                "type": 1,
+            }],
+            "type": 2,
+        }],
+        "type": 1,
+    })
+}
+
+/// An `INTERACTION_CREATE` gateway payload for members picked from a user select menu.
+pub fn user_select(
+    token: &str,
+    guild_id: u64,
+    user_id: u64,
+    channel_id: u64,
+    custom_id: &str,
+    member_ids: &[u64],
+) -> String {
+    let channel = json!({
+        "guild_id": guild_id.to_string(),
+        "id": channel_id.to_string(),
+        "name": "voice",
+        "type": 2,
+    });
+    let data = json!({
+        "component_type": 5,
+        "custom_id": custom_id,
+        "values": member_ids
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<String>>(),
+    });
+
+    interaction_create(token, guild_id, user_id, channel, 3, data)
+}
+
+/// A `VOICE_STATE_UPDATE` gateway payload for a member moving to `channel_id`, or leaving voice
+/// when it's `None`.
+pub fn voice_state_update(
+    guild_id: u64,
+    user_id: u64,
+    username: &str,
+    channel_id: Option<u64>,
+) -> String {
+    json!({
+        "op": 0,
+        "s": 1,
+        "t": "VOICE_STATE_UPDATE",
+        "d": {
+            "channel_id": channel_id.map(|channel_id| channel_id.to_string()),
+            "deaf": false,
+            "guild_id": guild_id.to_string(),
+            "member": {
+                "communication_disabled_until": null,
+                "deaf": false,
+                "flags": 0,
+                "joined_at": "2024-01-01T00:00:00.000000+00:00",
+                "mute": false,
+                "nick": null,
+                "roles": [],
+                "user": {
+                    "avatar": null,
+                    "discriminator": "0",
+                    "global_name": null,
+                    "id": user_id.to_string(),
+                    "username": username,
+                },
+            },
+            "mute": false,
+            "request_to_speak_timestamp": null,
+            "self_deaf": false,
+            "self_mute": false,
+            "self_video": false,
+            "session_id": "session",
+            "suppress": false,
+            "user_id": user_id.to_string(),
+        },
+    })
+    .to_string()
+}
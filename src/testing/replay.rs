@@ -0,0 +1,22 @@
+use std::sync::Arc;
+
+use eyre::Result;
+use twilight_gateway::{parse, Event};
+
+use crate::{
+    events::handle_event, structs::context::Context, utilities::constants::WANTED_EVENT_TYPES,
+};
+
+/// Feeds every line of a recording made with `EVENT_RECORDING_PATH` through `handle_event`, in
+/// order and one at a time.
+pub async fn replay(context: &Arc<Context>, recording: &str) -> Result<()> {
+    for json in recording.lines().filter(|json| !json.trim().is_empty()) {
+        let Some(gateway_event) = parse(json.to_owned(), *WANTED_EVENT_TYPES)? else {
+            continue;
+        };
+
+        handle_event(Arc::clone(context), Event::from(gateway_event)).await?;
+    }
+
+    Ok(())
+}
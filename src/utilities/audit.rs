@@ -0,0 +1,55 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use eyre::Result;
+use twilight_model::{
+    id::{
+        marker::{ChannelMarker, UserMarker},
+        Id,
+    },
+    util::Timestamp,
+};
+use twilight_util::builder::embed::{EmbedBuilder, EmbedFooterBuilder};
+
+use crate::structs::{cache::CachedGuild, context::Context};
+
+/// Records something done to a join or voice channel, and posts it to the server's log channel
+/// if it has one. A log channel I can't post in is no reason to stop whatever is being audited,
+/// so posting failures are ignored.
+pub async fn audit(
+    context: &Context,
+    guild: &CachedGuild,
+    user_id: Option<Id<UserMarker>>,
+    channel_id: Option<Id<ChannelMarker>>,
+    action: &str,
+    description: String,
+) -> Result<()> {
+    context
+        .database
+        .insert_audit_event(guild.id, channel_id, user_id, action, &description)
+        .await?;
+
+    let Some(log_channel_id) = *guild.log_channel_id.read() else {
+        return Ok(());
+    };
+    let mut embed = EmbedBuilder::new()
+        .color(0xF8F8FF)
+        .description(description)
+        .footer(EmbedFooterBuilder::new(action));
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+
+    if let Ok(timestamp) = Timestamp::from_secs(now as i64) {
+        embed = embed.timestamp(timestamp);
+    }
+
+    _ = context
+        .client
+        .create_message(log_channel_id)
+        .embeds(&[embed.build()])
+        .await;
+
+    Ok(())
+}
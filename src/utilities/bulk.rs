@@ -0,0 +1,156 @@
+use std::{future::Future, time::Duration};
+
+use eyre::{Report, Result};
+use tokio::time::sleep;
+use twilight_http::{api_error::ApiError, error::ErrorType};
+use twilight_model::id::{marker::UserMarker, Id};
+
+use super::{
+    constants::{BULK_RETRY_LIMIT, LISTED_MEMBER_LIMIT},
+    text::list_text,
+};
+
+/// Which members a bulk action changed, and which it failed for.
+#[derive(Default)]
+pub struct BulkOutcome {
+    pub changed: Vec<Id<UserMarker>>,
+    pub failed: Vec<Id<UserMarker>>,
+}
+
+impl BulkOutcome {
+    /// One line for the members that changed and one for those that failed, each given the
+    /// members as a list of mentions.
+    pub fn describe(
+        &self,
+        changed_text: impl FnOnce(String) -> String,
+        failed_text: impl FnOnce(String) -> String,
+    ) -> String {
+        let mut lines = Vec::new();
+
+        if !self.changed.is_empty() {
+            lines.push(changed_text(mentions(&self.changed)));
+        }
+        if !self.failed.is_empty() {
+            lines.push(failed_text(mentions(&self.failed)));
+        }
+        if lines.is_empty() {
+            return "No change has been made.".to_owned();
+        }
+
+        lines.join("\n")
+    }
+}
+
+/// Runs `action` for one member at a time, so the HTTP client's ratelimiter can pace the
+/// requests. A member still ratelimited is retried once after the wait Discord asks for, unless
+/// that wait is longer than [`BULK_RETRY_LIMIT`]. `action` returns whether the member changed.
+pub async fn run_bulk<F, Fut>(
+    member_ids: impl IntoIterator<Item = Id<UserMarker>>,
+    mut action: F,
+) -> BulkOutcome
+where
+    F: FnMut(Id<UserMarker>) -> Fut,
+    Fut: Future<Output = Result<bool>>,
+{
+    let mut outcome = BulkOutcome::default();
+
+    for member_id in member_ids {
+        let mut result = action(member_id).await;
+
+        if let Some(retry_after) = result.as_ref().err().and_then(retry_after) {
+            sleep(retry_after).await;
+
+            result = action(member_id).await;
+        }
+
+        match result {
+            Ok(true) => outcome.changed.push(member_id),
+            Ok(false) => {}
+            Err(_) => outcome.failed.push(member_id),
+        }
+    }
+
+    outcome
+}
+
+/// Mentions joined as "a, b and c", with members past [`LISTED_MEMBER_LIMIT`] counted instead.
+pub fn mentions(member_ids: &[Id<UserMarker>]) -> String {
+    let mut mentions = member_ids
+        .iter()
+        .take(LISTED_MEMBER_LIMIT)
+        .map(|member_id| format!("<@{member_id}>"))
+        .collect::<Vec<String>>();
+
+    if member_ids.len().gt(&LISTED_MEMBER_LIMIT) {
+        mentions.push(format!("{} more", member_ids.len() - LISTED_MEMBER_LIMIT));
+    }
+
+    list_text(mentions)
+}
+
+fn retry_after(report: &Report) -> Option<Duration> {
+    let ErrorType::Response {
+        error: ApiError::Ratelimited(ratelimited),
+        ..
+    } = report.downcast_ref::<twilight_http::Error>()?.kind()
+    else {
+        return None;
+    };
+    let retry_after = Duration::from_secs_f64(ratelimited.retry_after.max(0.0));
+
+    retry_after.le(&BULK_RETRY_LIMIT).then_some(retry_after)
+}
+
+#[cfg(test)]
+mod tests {
+    use twilight_model::id::Id;
+
+    use super::{mentions, BulkOutcome};
+    use crate::utilities::constants::{EMBED_DESCRIPTION_LIMIT, LISTED_MEMBER_LIMIT};
+
+    #[test]
+    fn describes_changed_and_failed_members() {
+        let outcome = BulkOutcome {
+            changed: vec![Id::new(1), Id::new(2), Id::new(3)],
+            failed: vec![Id::new(4)],
+        };
+
+        assert_eq!(
+            outcome.describe(
+                |mentions| format!("Changed {mentions}."),
+                |mentions| format!("Failed {mentions}.")
+            ),
+            "Changed <@1>, <@2> and <@3>.\nFailed <@4>."
+        );
+        assert_eq!(
+            BulkOutcome::default().describe(|_| String::new(), |_| String::new()),
+            "No change has been made."
+        );
+    }
+
+    #[test]
+    fn long_lists_are_cut_short() {
+        let member_ids = (1..=1_000)
+            .map(|member_id| Id::new(u64::MAX - member_id))
+            .collect::<Vec<_>>();
+        let outcome = BulkOutcome {
+            changed: member_ids.clone(),
+            failed: member_ids.clone(),
+        };
+
+        assert!(mentions(&member_ids).ends_with(&format!(
+            "and {} more",
+            member_ids.len() - LISTED_MEMBER_LIMIT
+        )));
+        assert!(outcome
+            .describe(
+                |mentions| format!("I've disconnected {mentions} from <#5000>."),
+                |mentions| format!(
+                    "I'm unable to disconnect {mentions} right now. Try again in 10 minutes."
+                ),
+            )
+            .chars()
+            .count()
+            .lt(&EMBED_DESCRIPTION_LIMIT));
+    }
+}
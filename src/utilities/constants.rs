@@ -1,4 +1,4 @@
-use std::{env, sync::LazyLock};
+use std::{env, sync::LazyLock, time::Duration};
 
 use eyre::WrapErr;
 use twilight_gateway::{EventTypeFlags, Intents};
@@ -9,24 +9,93 @@ use twilight_model::{
 };
 use twilight_util::builder::command::{
     BooleanBuilder, ChannelBuilder, CommandBuilder, IntegerBuilder, RoleBuilder, StringBuilder,
-    SubCommandBuilder, UserBuilder,
+    SubCommandBuilder, SubCommandGroupBuilder, UserBuilder,
 };
 
+/// How many audit events `/join audit` shows per page.
+pub const AUDIT_PAGE_SIZE: i64 = 10;
+
+/// The longest a bulk member action waits out a ratelimit before giving up on a member.
+pub const BULK_RETRY_LIMIT: Duration = Duration::from_secs(10);
+
+/// The most characters a channel's name may hold.
+pub const CHANNEL_NAME_LIMIT: usize = 100;
+
+#[cfg_attr(not(feature = "set-global-commands"), allow(dead_code))]
 pub static COMMANDS: LazyLock<Vec<Command>> = LazyLock::new(|| {
     vec![
         CommandBuilder::new("join", "Modify join channels", CommandType::ChatInput)
             .default_member_permissions(Permissions::ADMINISTRATOR)
             .option(
-                SubCommandBuilder::new("access-role", "Modify the access role for a join channel")
+                SubCommandGroupBuilder::new("access-role", "Modify the roles that may or may not use a join channel")
+                    .subcommands(vec![
+                        SubCommandBuilder::new("add", "Let a role's members use a join channel, or keep them out")
+                            .option(
+                                StringBuilder::new("channel", "The join channel")
+                                    .autocomplete(true)
+                                    .required(true)
+                                    .build(),
+                            )
+                            .option(
+                                RoleBuilder::new("role", "The role")
+                                    .required(true)
+                                    .build(),
+                            )
+                            .option(
+                                StringBuilder::new("kind", "Whether to allow or deny the role (allow, if left out)")
+                                    .choices(vec![("Allow", "allow"), ("Deny", "deny")])
+                                    .build(),
+                            ),
+                        SubCommandBuilder::new("list", "List the roles that may or may not use a join channel")
+                            .option(
+                                StringBuilder::new("channel", "The join channel")
+                                    .autocomplete(true)
+                                    .required(true)
+                                    .build(),
+                            ),
+                        SubCommandBuilder::new("remove", "Stop a role from being allowed or denied")
+                            .option(
+                                StringBuilder::new("channel", "The join channel")
+                                    .autocomplete(true)
+                                    .required(true)
+                                    .build(),
+                            )
+                            .option(
+                                RoleBuilder::new("role", "The role")
+                                    .required(true)
+                                    .build(),
+                            ),
+                    ])
+                    .build(),
+            )
+            .option(
+                SubCommandBuilder::new("audit", "View the changes made to voice and join channels")
+                    .option(UserBuilder::new("member", "Only show changes made by this member").build())
                     .option(
-                        StringBuilder::new("channel", "The join channel")
-                            .autocomplete(true)
-                            .required(true)
+                        ChannelBuilder::new("channel", "Only show changes made to this channel")
+                            .channel_types(vec![ChannelType::GuildStageVoice, ChannelType::GuildVoice])
                             .build(),
                     )
-                    .option(
-                        RoleBuilder::new("role", "The role to let access the join channel").build(),
-                    )
+                    .option(IntegerBuilder::new("page", "The page to start on").min_value(1).build())
+                    .build(),
+            )
+            .option(
+                SubCommandGroupBuilder::new("block", "Stop members from creating voice channels")
+                    .subcommands(vec![
+                        SubCommandBuilder::new("list", "View the blocked members and roles"),
+                        SubCommandBuilder::new("member", "Stop a member from creating voice channels")
+                            .option(
+                                UserBuilder::new("member", "The member")
+                                    .required(true)
+                                    .build(),
+                            ),
+                        SubCommandBuilder::new("role", "Stop a role's members from creating voice channels")
+                            .option(
+                                RoleBuilder::new("role", "The role")
+                                    .required(true)
+                                    .build(),
+                            ),
+                    ])
                     .build(),
             )
             .option(
@@ -57,8 +126,18 @@ pub static COMMANDS: LazyLock<Vec<Command>> = LazyLock::new(|| {
                             .build(),
                     )
                     .option(
-                        RoleBuilder::new("access-role", "The role to let access the join channel")
-                            .build(),
+                        StringBuilder::new(
+                            "access-roles",
+                            "Roles to let access the join channel, such as @Members @Guests",
+                        )
+                        .build(),
+                    )
+                    .option(
+                        StringBuilder::new(
+                            "deny-roles",
+                            "Roles to keep out of the join channel, such as @Muted",
+                        )
+                        .build(),
                     )
                     .option(
                         ChannelBuilder::new(
@@ -87,6 +166,14 @@ pub static COMMANDS: LazyLock<Vec<Command>> = LazyLock::new(|| {
                         ])
                         .build(),
                     )
+                    .option(
+                        StringBuilder::new(
+                            "kind",
+                            "The kind of channel to create (voice channels, if left out)",
+                        )
+                        .choices(vec![("Stage channels", "stage"), ("Voice channels", "voice")])
+                        .build(),
+                    )
                     .build(),
             )
             .option(
@@ -104,6 +191,36 @@ pub static COMMANDS: LazyLock<Vec<Command>> = LazyLock::new(|| {
                     )
                     .build(),
             )
+            .option(
+                SubCommandBuilder::new(
+                    "ownership",
+                    "Modify what happens to voice channels when their owner leaves",
+                )
+                .option(
+                    StringBuilder::new("channel", "The join channel")
+                        .autocomplete(true)
+                        .required(true)
+                        .build(),
+                )
+                .option(
+                    StringBuilder::new("policy", "What happens once the owner leaves")
+                        .choices(vec![
+                            ("Keep the owner", "keep"),
+                            ("Make the voice channel claimable", "claimable"),
+                            ("Transfer to the longest-connected member", "transfer"),
+                        ])
+                        .required(true)
+                        .build(),
+                )
+                .option(
+                    StringBuilder::new(
+                        "delay",
+                        "How long to wait for the owner to return, such as \"5m\" (5 minutes by default)",
+                    )
+                    .build(),
+                )
+                .build(),
+            )
             .option(
                 SubCommandBuilder::new(
                     "permanence",
@@ -155,6 +272,42 @@ pub static COMMANDS: LazyLock<Vec<Command>> = LazyLock::new(|| {
                     )
                     .build(),
             )
+            .option(
+                SubCommandBuilder::new(
+                    "status",
+                    "Modify the status new voice channels from a join channel start with",
+                )
+                .option(
+                    StringBuilder::new("channel", "The join channel")
+                        .autocomplete(true)
+                        .required(true)
+                        .build(),
+                )
+                .option(
+                    StringBuilder::new("template", "The status, where {owner} stands for the owner's name (none by default)")
+                        .max_length(500)
+                        .build(),
+                )
+                .build(),
+            )
+            .option(
+                SubCommandGroupBuilder::new("unblock", "Let blocked members create voice channels again")
+                    .subcommands(vec![
+                        SubCommandBuilder::new("member", "Let a member create voice channels again")
+                            .option(
+                                UserBuilder::new("member", "The member")
+                                    .required(true)
+                                    .build(),
+                            ),
+                        SubCommandBuilder::new("role", "Let a role's members create voice channels again")
+                            .option(
+                                RoleBuilder::new("role", "The role")
+                                    .required(true)
+                                    .build(),
+                            ),
+                    ])
+                    .build(),
+            )
             .option(
                 SubCommandBuilder::new("view", "View the current settings of a join channel")
                     .option(
@@ -165,6 +318,153 @@ pub static COMMANDS: LazyLock<Vec<Command>> = LazyLock::new(|| {
                     )
                     .build(),
             )
+            .option(
+                SubCommandBuilder::new(
+                    "voice-channel-limit",
+                    "Modify how many voice channels a join channel may have at once",
+                )
+                .option(
+                    StringBuilder::new("channel", "The join channel")
+                        .autocomplete(true)
+                        .required(true)
+                        .build(),
+                )
+                .option(
+                    IntegerBuilder::new("limit", "The most voice channels at once (no limit, if left out)")
+                        .min_value(1)
+                        .max_value(MAX_JOIN_CHANNEL_VOICE_CHANNELS)
+                        .build(),
+                )
+                .build(),
+            )
+            .option(
+                SubCommandBuilder::new(
+                    "waiting-room",
+                    "Turn waiting rooms on or off for a join channel",
+                )
+                .option(
+                    StringBuilder::new("channel", "The join channel")
+                        .autocomplete(true)
+                        .required(true)
+                        .build(),
+                )
+                .option(
+                    BooleanBuilder::new("enabled", "Should locked voice channels have a waiting room?")
+                        .required(true)
+                        .build(),
+                )
+                .build(),
+            )
+            .build(),
+        CommandBuilder::new("settings", "Modify the settings of this server", CommandType::ChatInput)
+            .default_member_permissions(Permissions::ADMINISTRATOR)
+            .option(
+                SubCommandGroupBuilder::new("name-filter", "Modify the words and patterns voice channel names may not use")
+                    .subcommands(vec![
+                        SubCommandBuilder::new("add", "Stop voice channel names from using a word or pattern")
+                            .option(
+                                StringBuilder::new("kind", "Whether the value is a word or a regular expression")
+                                    .choices(vec![("Word", "word"), ("Pattern", "pattern")])
+                                    .required(true)
+                                    .build(),
+                            )
+                            .option(
+                                StringBuilder::new("value", "The word or pattern (both ignore case)")
+                                    .max_length(100)
+                                    .required(true)
+                                    .build(),
+                            ),
+                        SubCommandBuilder::new("list", "View the filtered words and patterns"),
+                        SubCommandBuilder::new("remove", "Let voice channel names use a word or pattern again")
+                            .option(
+                                StringBuilder::new("kind", "Whether the value is a word or a regular expression")
+                                    .choices(vec![("Word", "word"), ("Pattern", "pattern")])
+                                    .required(true)
+                                    .build(),
+                            )
+                            .option(
+                                StringBuilder::new("value", "The word or pattern")
+                                    .autocomplete(true)
+                                    .required(true)
+                                    .build(),
+                            ),
+                    ])
+                    .build(),
+            )
+            .option(
+                SubCommandBuilder::new("reset", "Reset a setting to its default")
+                    .option(
+                        StringBuilder::new("setting", "The setting")
+                            .choices(vec![
+                                ("Blocked DMs", "blocked-dms"),
+                                ("Creation cooldown", "creation-cooldown"),
+                                ("External edits", "external-edits"),
+                                ("Locale", "locale"),
+                                ("Log channel", "log-channel"),
+                                ("Maximum voice channels", "max-voice-channels"),
+                                ("Naming template", "name-template"),
+                                ("Presets", "presets"),
+                                ("Printable names only", "printable-names"),
+                            ])
+                            .required(true)
+                            .build(),
+                    )
+                    .build(),
+            )
+            .option(
+                SubCommandBuilder::new("set", "Modify one or more settings")
+                    .option(
+                        BooleanBuilder::new("blocked-dms", "Should blocked members be told why in a DM?")
+                            .build(),
+                    )
+                    .option(
+                        StringBuilder::new(
+                            "creation-cooldown",
+                            "How long members wait between creating voice channels, such as \"30s\" (0 turns it off)",
+                        )
+                        .build(),
+                    )
+                    .option(
+                        StringBuilder::new("external-edits", "What happens when a voice channel is edited outside of Voz")
+                            .choices(vec![
+                                ("Accept", "accept"),
+                                ("Audit only", "audit"),
+                                ("Revert", "revert"),
+                            ])
+                            .build(),
+                    )
+                    .option(
+                        StringBuilder::new("locale", "The locale default voice channel names are written in")
+                            .choices(LOCALE_OPTIONS.to_vec())
+                            .build(),
+                    )
+                    .option(
+                        ChannelBuilder::new("log-channel", "The channel I log voice channel activity in")
+                            .channel_types(vec![ChannelType::GuildText])
+                            .build(),
+                    )
+                    .option(
+                        IntegerBuilder::new("max-voice-channels", "How many voice channels a member may own at once")
+                            .min_value(1)
+                            .max_value(MAX_VOICE_CHANNELS)
+                            .build(),
+                    )
+                    .option(
+                        StringBuilder::new("name-template", "The name new voice channels start with, where {owner} stands for the owner's name")
+                            .max_length(CHANNEL_NAME_LIMIT as u16)
+                            .build(),
+                    )
+                    .option(
+                        BooleanBuilder::new("presets", "Should voice channels start with their owner's preset?")
+                            .build(),
+                    )
+                    .option(
+                        BooleanBuilder::new("printable-names", "Should voice channel names only contain printable characters?")
+                            .build(),
+                    )
+                    .build(),
+            )
+            .option(SubCommandBuilder::new("view", "View the settings of this server").build())
             .build(),
         CommandBuilder::new(
             "voice",
@@ -174,10 +474,16 @@ pub static COMMANDS: LazyLock<Vec<Command>> = LazyLock::new(|| {
         .option(
             SubCommandBuilder::new("allow-member", "Allow a member permission to join your voice channel")
                 .option(
-                    UserBuilder::new("member", "The member")
+                    StringBuilder::new("member", "The member")
+                        .autocomplete(true)
                         .required(true)
                         .build(),
                 )
+                .option(
+                    StringBuilder::new("duration", "How long, such as 30m or 2h (until changed, if left out)")
+                        .max_length(20)
+                        .build(),
+                )
                 .build(),
         )
         .option(
@@ -191,6 +497,26 @@ pub static COMMANDS: LazyLock<Vec<Command>> = LazyLock::new(|| {
                 )
                 .build(),
         )
+        .option(
+            SubCommandGroupBuilder::new("block", "Modify who is kept out of every voice channel you own")
+                .subcommands(vec![
+                    SubCommandBuilder::new("add", "Block a member from every voice channel you own")
+                        .option(
+                            UserBuilder::new("member", "The member")
+                                .required(true)
+                                .build(),
+                        ),
+                    SubCommandBuilder::new("list", "List the members you have blocked"),
+                    SubCommandBuilder::new("remove", "Unblock a member")
+                        .option(
+                            StringBuilder::new("member", "The member")
+                                .autocomplete(true)
+                                .required(true)
+                                .build(),
+                        ),
+                ])
+                .build(),
+        )
         .option(SubCommandBuilder::new("claim", "Claim an unowned voice channel").build())
         .option(SubCommandBuilder::new("delete", "Delete your voice channel").build())
         .option(
@@ -200,6 +526,56 @@ pub static COMMANDS: LazyLock<Vec<Command>> = LazyLock::new(|| {
                         .required(true)
                         .build(),
                 )
+                .option(
+                    StringBuilder::new("duration", "How long, such as 30m or 2h (until changed, if left out)")
+                        .max_length(20)
+                        .build(),
+                )
+                .build(),
+        )
+        .option(
+            SubCommandBuilder::new(
+                "kick-all",
+                "Disconnect everyone but the owner and managers from your voice channel",
+            )
+            .build(),
+        )
+        .option(
+            SubCommandBuilder::new("knock", "Ask the owner of a voice channel to let you in")
+                .option(
+                    UserBuilder::new("owner", "The owner of the voice channel")
+                        .required(true)
+                        .build(),
+                )
+                .build(),
+        )
+        .option(
+            SubCommandGroupBuilder::new("managers", "Modify who helps manage your voice channel")
+                .subcommands(vec![
+                    SubCommandBuilder::new("add", "Let a member manage your voice channel")
+                        .option(
+                            UserBuilder::new("member", "The member")
+                                .required(true)
+                                .build(),
+                        ),
+                    SubCommandBuilder::new("list", "List the managers of your voice channel"),
+                    SubCommandBuilder::new("remove", "Stop a member from managing your voice channel")
+                        .option(
+                            StringBuilder::new("member", "The member")
+                                .autocomplete(true)
+                                .required(true)
+                                .build(),
+                        ),
+                ])
+                .build(),
+        )
+        .option(
+            SubCommandGroupBuilder::new("members", "Pick several members at once")
+                .subcommands(vec![
+                    SubCommandBuilder::new("allow", "Allow members permission to join your voice channel"),
+                    SubCommandBuilder::new("deny", "Deny members permission to join your voice channel"),
+                    SubCommandBuilder::new("remove", "Remove members' permission to join your voice channel (and disconnect them)"),
+                ])
                 .build(),
         )
         .option(
@@ -211,6 +587,10 @@ pub static COMMANDS: LazyLock<Vec<Command>> = LazyLock::new(|| {
                         .required(true)
                         .build(),
                 )
+                .option(
+                    BooleanBuilder::new("queue", "If Discord won't allow a rename yet, should I rename it once it does?")
+                        .build(),
+                )
                 .build(),
         )
         .option(
@@ -222,6 +602,15 @@ pub static COMMANDS: LazyLock<Vec<Command>> = LazyLock::new(|| {
                 )
                 .build(),
         )
+        .option(
+            SubCommandGroupBuilder::new("preset", "Modify your voice channel preset")
+                .subcommands(vec![
+                    SubCommandBuilder::new("clear", "Clear your preset"),
+                    SubCommandBuilder::new("load", "Apply your preset to your voice channel"),
+                    SubCommandBuilder::new("save", "Save the settings of your voice channel as your preset"),
+                ])
+                .build(),
+        )
         .option(
             SubCommandBuilder::new("privacy", "Modify the privacy level of your voice channel")
                 .option(
@@ -239,7 +628,17 @@ pub static COMMANDS: LazyLock<Vec<Command>> = LazyLock::new(|| {
         .option(
             SubCommandBuilder::new("remove-member", "Remove a member's permission to join your voice channel (and disconnect the member)")
                 .option(
-                    UserBuilder::new("member", "The member")
+                    StringBuilder::new("member", "The member")
+                        .autocomplete(true)
+                        .required(true)
+                        .build(),
+                )
+                .build(),
+        )
+        .option(
+            SubCommandBuilder::new("remove-role", "Remove a role's permission to join your voice channel (and disconnect its members)")
+                .option(
+                    RoleBuilder::new("role", "The role")
                         .required(true)
                         .build(),
                 )
@@ -255,15 +654,70 @@ pub static COMMANDS: LazyLock<Vec<Command>> = LazyLock::new(|| {
                 )
                 .build(),
         )
+        .option(
+            SubCommandGroupBuilder::new("stage", "Run your stage channel")
+                .subcommands(vec![
+                    SubCommandBuilder::new("invite", "Invite a member of the audience to speak")
+                        .option(
+                            StringBuilder::new("member", "The member")
+                                .autocomplete(true)
+                                .required(true)
+                                .build(),
+                        ),
+                    SubCommandBuilder::new("topic", "Set the topic of your stage channel (and start the stage)")
+                        .option(
+                            StringBuilder::new("text", "The topic")
+                                .max_length(120)
+                                .required(true)
+                                .build(),
+                        ),
+                ])
+                .build(),
+        )
+        .option(
+            SubCommandGroupBuilder::new("status", "Modify the status of your voice channel")
+                .subcommands(vec![
+                    SubCommandBuilder::new("clear", "Clear the status of your voice channel"),
+                    SubCommandBuilder::new("set", "Set the status of your voice channel")
+                        .option(
+                            StringBuilder::new("text", "The status")
+                                .max_length(500)
+                                .required(true)
+                                .build(),
+                        ),
+                ])
+                .build(),
+        )
         .option(
             SubCommandBuilder::new("transfer", "Transfer ownership of your voice channel to another member")
                 .option(
-                    UserBuilder::new("member", "The member")
+                    StringBuilder::new("member", "The member")
+                        .autocomplete(true)
                         .required(true)
                         .build(),
                 )
                 .build(),
         )
+        .option(
+            SubCommandGroupBuilder::new("trust", "Modify who may join every voice channel you own")
+                .subcommands(vec![
+                    SubCommandBuilder::new("add", "Trust a member to join every voice channel you own")
+                        .option(
+                            UserBuilder::new("member", "The member")
+                                .required(true)
+                                .build(),
+                        ),
+                    SubCommandBuilder::new("list", "List the members you have trusted"),
+                    SubCommandBuilder::new("remove", "Stop trusting a member")
+                        .option(
+                            StringBuilder::new("member", "The member")
+                                .autocomplete(true)
+                                .required(true)
+                                .build(),
+                        ),
+                ])
+                .build(),
+        )
         .option(
             SubCommandBuilder::new("user-limit", "Modify the user limit of your voice channel")
                 .option(
@@ -290,28 +744,71 @@ pub static COMMANDS: LazyLock<Vec<Command>> = LazyLock::new(|| {
             SubCommandBuilder::new("voice-region", "Modify the voice region of your voice channel")
                 .option(
                     StringBuilder::new("region", "The voice region")
-                        .choices(vec![
-                            ("Automatic", "automatic"),
-                            ("Brazil", "brazil"),
-                            ("Hong Kong", "hongkong"),
-                            ("India", "india"),
-                            ("Japan", "japan"),
-                            ("Rotterdam", "rotterdam"),
-                            ("Russia", "russia"),
-                            ("Singapore", "singapore"),
-                            ("South Africa", "southafrica"),
-                            ("Sydney", "sydney"),
-                            ("US Central", "us-central"),
-                            ("US East", "us-east"),
-                            ("US South", "us-south"),
-                            ("US West", "us-west"),
-                        ])
+                        .autocomplete(true)
                         .required(true)
                         .build(),
                 )
                 .build(),
         )
         .build(),
+        CommandBuilder::new("voice-admin", "Moderate any voice channel", CommandType::ChatInput)
+            .default_member_permissions(Permissions::MANAGE_CHANNELS)
+            .option(
+                SubCommandBuilder::new("clear-owner", "Leave a voice channel without an owner, so it may be claimed")
+                    .option(
+                        StringBuilder::new("channel", "The voice channel")
+                            .autocomplete(true)
+                            .required(true)
+                            .build(),
+                    )
+                    .build(),
+            )
+            .option(
+                SubCommandBuilder::new("delete", "Delete a voice channel")
+                    .option(
+                        StringBuilder::new("channel", "The voice channel")
+                            .autocomplete(true)
+                            .required(true)
+                            .build(),
+                    )
+                    .build(),
+            )
+            .option(
+                SubCommandBuilder::new("reset-name", "Reset the name of a voice channel")
+                    .option(
+                        StringBuilder::new("channel", "The voice channel")
+                            .autocomplete(true)
+                            .required(true)
+                            .build(),
+                    )
+                    .build(),
+            )
+            .option(
+                SubCommandBuilder::new("transfer", "Transfer a voice channel to another member")
+                    .option(
+                        StringBuilder::new("channel", "The voice channel")
+                            .autocomplete(true)
+                            .required(true)
+                            .build(),
+                    )
+                    .option(
+                        UserBuilder::new("member", "The member")
+                            .required(true)
+                            .build(),
+                    )
+                    .build(),
+            )
+            .option(
+                SubCommandBuilder::new("unlock", "Unlock (and make visible) a voice channel")
+                    .option(
+                        StringBuilder::new("channel", "The voice channel")
+                            .autocomplete(true)
+                            .required(true)
+                            .build(),
+                    )
+                    .build(),
+            )
+            .build(),
     ]
 });
 
@@ -321,15 +818,81 @@ pub static DATABASE_URL: LazyLock<String> = LazyLock::new(|| {
         .unwrap()
 });
 
+/// How long, in seconds, a join channel waits for an absent owner unless told otherwise.
+pub const DEFAULT_OWNERSHIP_DELAY: u32 = 300;
+
 pub static DISCORD_TOKEN: LazyLock<String> = LazyLock::new(|| {
     env::var("DISCORD_TOKEN")
         .wrap_err("Environment variable \"DISCORD_TOKEN\" is not set.")
         .unwrap()
 });
 
+/// The most characters an embed's description may hold.
+pub const EMBED_DESCRIPTION_LIMIT: usize = 4_096;
+
+pub static EVENT_RECORDING_PATH: LazyLock<Option<String>> = LazyLock::new(|| {
+    env::var("EVENT_RECORDING_PATH")
+        .ok()
+        .filter(|path| !path.is_empty())
+});
+
 pub static INTENTS: LazyLock<Intents> =
     LazyLock::new(|| Intents::GUILDS | Intents::GUILD_MEMBERS | Intents::GUILD_VOICE_STATES);
 
+/// How long an owner has to answer a `/voice knock`.
+pub const KNOCK_EXPIRY: Duration = Duration::from_secs(300);
+
+/// How long a member waits between knocks on the same voice channel.
+pub const KNOCK_RATE_LIMIT: Duration = Duration::from_secs(60);
+
+/// The most members a reply lists, with the rest counted as "and N more" to keep it within
+/// [`EMBED_DESCRIPTION_LIMIT`].
+pub const LISTED_MEMBER_LIMIT: usize = 50;
+
+/// The locales default voice channel names may be written in.
+pub const LOCALE_OPTIONS: [(&str, &str); 6] = [
+    ("Deutsch", "de"),
+    ("English (UK)", "en-GB"),
+    ("English (US)", "en-US"),
+    ("Español", "es-ES"),
+    ("Français", "fr"),
+    ("Português (Brasil)", "pt-BR"),
+];
+
+/// The longest a server may make members wait between creating voice channels, in seconds.
+pub const MAX_CREATION_COOLDOWN: u64 = 3_600;
+
+/// The most voice channels a join channel may be limited to at once.
+pub const MAX_JOIN_CHANNEL_VOICE_CHANNELS: i64 = 100;
+
+/// The longest slow mode Discord allows, in seconds.
+pub const MAX_SLOW_MODE: u64 = 21_600;
+
+/// The longest a join channel may wait for an absent owner, in seconds.
+pub const MAX_OWNERSHIP_DELAY: u64 = 86_400;
+
+/// The longest a timed `/voice allow-member` or `/voice deny-member` may last, in seconds.
+pub const MAX_TIMED_PERMISSION: u64 = 604_800;
+
+/// The most voice channels a server may let one member own at once.
+pub const MAX_VOICE_CHANNELS: i64 = 10;
+
+/// How often absent owners are checked against their voice channel's ownership policy.
+pub const OWNERSHIP_INTERVAL: Duration = Duration::from_secs(30);
+
+/// How many members I remember someone recently sharing a voice channel with.
+pub const RECENT_CONTACTS_LIMIT: usize = 25;
+
+pub const RECONCILE_INTERVAL: Duration = Duration::from_secs(900);
+
+/// How often queued voice channel names are checked against the rename rate limit.
+pub const RENAME_INTERVAL: Duration = Duration::from_secs(15);
+
+/// How many times Discord lets a channel be renamed within `RENAME_WINDOW`.
+pub const RENAME_LIMIT: usize = 2;
+
+pub const RENAME_WINDOW: Duration = Duration::from_secs(600);
+
 pub static SLOW_MODE_OPTIONS: LazyLock<Vec<[String; 2]>> = LazyLock::new(|| {
     let mut choices = vec![["Off".to_owned(), "0".to_owned()]];
 
@@ -366,6 +929,35 @@ pub static SLOW_MODE_OPTIONS: LazyLock<Vec<[String; 2]>> = LazyLock::new(|| {
     choices
 });
 
+/// What Discord counts as moderating a stage channel, which the owner of one is allowed.
+///
+/// Discord won't let anyone without `MANAGE_CHANNELS` moderate a stage, so a stage channel's
+/// owner can also rename it and edit its overwrites by hand. Those edits are always held to the
+/// name filter and the saved privacy level, whatever the server's external edit policy.
+pub const STAGE_MODERATOR_PERMISSIONS: Permissions = Permissions::MANAGE_CHANNELS
+    .union(Permissions::MOVE_MEMBERS)
+    .union(Permissions::MUTE_MEMBERS);
+
+/// How often timed permissions are checked for expiry.
+pub const TIMED_PERMISSION_INTERVAL: Duration = Duration::from_secs(15);
+
+pub const VOICE_REGION_OPTIONS: [(&str, &str); 14] = [
+    ("Automatic", "automatic"),
+    ("Brazil", "brazil"),
+    ("Hong Kong", "hongkong"),
+    ("India", "india"),
+    ("Japan", "japan"),
+    ("Rotterdam", "rotterdam"),
+    ("Russia", "russia"),
+    ("Singapore", "singapore"),
+    ("South Africa", "southafrica"),
+    ("Sydney", "sydney"),
+    ("US Central", "us-central"),
+    ("US East", "us-east"),
+    ("US South", "us-south"),
+    ("US West", "us-west"),
+];
+
 pub static WANTED_EVENT_TYPES: LazyLock<EventTypeFlags> = LazyLock::new(|| {
     EventTypeFlags::CHANNEL_DELETE
         | EventTypeFlags::CHANNEL_UPDATE
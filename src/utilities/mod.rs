@@ -1,2 +1,11 @@
+pub mod audit;
+pub mod bulk;
 pub mod constants;
+pub mod name_filter;
+pub mod permission_overwrites;
+pub mod stage;
+pub mod template;
+pub mod text;
 pub mod time;
+pub mod voice_status;
+pub mod waiting_room;
@@ -0,0 +1,107 @@
+use regex::{Error, Regex, RegexBuilder};
+
+use crate::structs::cache::CachedGuild;
+
+/// Compiles a name filter pattern, which matches regardless of case like filtered words do.
+pub fn compile_pattern(pattern: &str) -> Result<Regex, Error> {
+    RegexBuilder::new(pattern).case_insensitive(true).build()
+}
+
+/// Why `name` may not be used for a voice channel in `guild`, if it may not.
+pub fn name_violation(guild: &CachedGuild, name: &str) -> Option<&'static str> {
+    let lowercase_name = name.to_lowercase();
+
+    if guild
+        .name_filter_words
+        .read()
+        .iter()
+        .any(|word| lowercase_name.contains(word.as_str()))
+    {
+        return Some("contains a word that isn't allowed here");
+    }
+    if guild
+        .name_filter_patterns
+        .read()
+        .iter()
+        .any(|pattern| pattern.is_match(name))
+    {
+        return Some("matches a pattern that isn't allowed here");
+    }
+    if *guild.printable_names_only.read() && !name.chars().all(is_printable) {
+        return Some("contains characters that aren't printable");
+    }
+
+    None
+}
+
+/// Whether a character shows up as itself, rather than controlling or decorating its neighbours.
+fn is_printable(character: char) -> bool {
+    !character.is_control()
+        && !matches!(
+            character,
+            // Combining marks, which stack on the character before them
+            '\u{0300}'..='\u{036F}'
+                | '\u{1AB0}'..='\u{1AFF}'
+                | '\u{1DC0}'..='\u{1DFF}'
+                | '\u{20D0}'..='\u{20FF}'
+                | '\u{FE20}'..='\u{FE2F}'
+                // Invisible formatting characters, such as zero-width spaces and direction overrides
+                | '\u{00AD}'
+                | '\u{200B}'..='\u{200F}'
+                | '\u{202A}'..='\u{202E}'
+                | '\u{2060}'..='\u{206F}'
+                | '\u{FEFF}'
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use twilight_model::id::Id;
+
+    use super::{compile_pattern, name_violation};
+    use crate::structs::cache::{Cache, CachedGuildUpdate};
+
+    #[test]
+    fn names_are_checked_against_the_filter() {
+        let cache = Cache::new();
+        let guild_id = Id::new(1001);
+
+        cache.insert_guild(Id::new(3000), guild_id, "guild".to_owned());
+
+        let guild = cache.guild(guild_id).unwrap();
+
+        assert_eq!(name_violation(&guild, "Z\u{0335}algo"), None);
+
+        cache.update_guild(
+            guild_id,
+            CachedGuildUpdate {
+                name_filter_patterns: Some(vec![compile_pattern(r"^free\s+nitro").unwrap()]),
+                name_filter_words: Some(HashSet::from(["heck".to_owned()])),
+                printable_names_only: Some(true),
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(name_violation(&guild, "alex's voice"), None);
+        assert_eq!(name_violation(&guild, "Café ☕"), None);
+        assert_eq!(
+            name_violation(&guild, "What the HECK"),
+            Some("contains a word that isn't allowed here")
+        );
+        assert_eq!(
+            name_violation(&guild, "FREE  nitro here"),
+            Some("matches a pattern that isn't allowed here")
+        );
+        assert_eq!(
+            name_violation(&guild, "Z\u{0335}algo"),
+            Some("contains characters that aren't printable")
+        );
+        assert_eq!(
+            name_violation(&guild, "hidden\u{200B}text"),
+            Some("contains characters that aren't printable")
+        );
+        assert!(compile_pattern("(unclosed").is_err());
+    }
+}
@@ -0,0 +1,197 @@
+use std::collections::HashSet;
+
+use twilight_model::{
+    channel::permission_overwrite::{
+        PermissionOverwrite as ChannelPermissionOverwrite,
+        PermissionOverwriteType as ChannelPermissionOverwriteType,
+    },
+    guild::Permissions,
+    id::{
+        marker::{GuildMarker, RoleMarker, UserMarker},
+        Id,
+    },
+};
+
+use crate::{
+    structs::database::{ChannelPrivacy, RelationshipKind, UserRelationship, VoiceChannelKind},
+    utilities::constants::STAGE_MODERATOR_PERMISSIONS,
+};
+
+/// Rebuilds the role overwrites of a join channel: allowed roles get `privacy_permissions`,
+/// denied roles may not connect and `previous_role_ids` that are neither lose their overwrite.
+pub fn apply_join_channel_roles(
+    permission_overwrites: &mut Vec<ChannelPermissionOverwrite>,
+    previous_role_ids: &HashSet<Id<RoleMarker>>,
+    allowed_role_ids: &HashSet<Id<RoleMarker>>,
+    denied_role_ids: &HashSet<Id<RoleMarker>>,
+    privacy_permissions: Permissions,
+) {
+    permission_overwrites.retain(|permission_overwrite| {
+        permission_overwrite
+            .kind
+            .ne(&ChannelPermissionOverwriteType::Role)
+            || !previous_role_ids
+                .iter()
+                .chain(allowed_role_ids)
+                .chain(denied_role_ids)
+                .any(|role_id| permission_overwrite.id.eq(&role_id.cast()))
+    });
+
+    let mut role_overwrites = allowed_role_ids
+        .iter()
+        .map(|role_id| (*role_id, privacy_permissions, Permissions::empty()))
+        .chain(
+            denied_role_ids
+                .iter()
+                .map(|role_id| (*role_id, Permissions::empty(), Permissions::CONNECT)),
+        )
+        .collect::<Vec<_>>();
+
+    role_overwrites.sort_by_key(|(role_id, _, _)| *role_id);
+
+    for (role_id, allow, deny) in role_overwrites {
+        permission_overwrites.push(ChannelPermissionOverwrite {
+            allow,
+            deny,
+            id: role_id.cast(),
+            kind: ChannelPermissionOverwriteType::Role,
+        });
+    }
+}
+
+/// Rewrites the member overwrites for the owner's trusted and blocked lists: trusted members get
+/// `privacy_permissions` and blocked members may not view the voice channel. Members for whom
+/// `is_exempt` holds, such as the owner and managers, keep their overwrite.
+pub fn apply_relationships(
+    permission_overwrites: &mut Vec<ChannelPermissionOverwrite>,
+    user_relationships: &[UserRelationship],
+    privacy_permissions: Permissions,
+    is_exempt: impl Fn(Id<UserMarker>) -> bool,
+) {
+    for user_relationship in user_relationships {
+        let member_id = user_relationship.member_id;
+
+        if is_exempt(member_id) {
+            continue;
+        }
+
+        let (allow, deny) = match user_relationship.kind {
+            RelationshipKind::Blocked => (Permissions::empty(), Permissions::VIEW_CHANNEL),
+            RelationshipKind::Trusted => (privacy_permissions, Permissions::empty()),
+        };
+
+        permission_overwrites.retain(|permission_overwrite| {
+            permission_overwrite.id.ne(&member_id.cast())
+                || permission_overwrite
+                    .kind
+                    .ne(&ChannelPermissionOverwriteType::Member)
+        });
+        permission_overwrites.push(ChannelPermissionOverwrite {
+            allow,
+            deny,
+            id: member_id.cast(),
+            kind: ChannelPermissionOverwriteType::Member,
+        });
+    }
+}
+
+/// What the owner's overwrite allows: the privacy permissions, and moderating a stage.
+pub fn owner_permissions(kind: VoiceChannelKind, privacy: &ChannelPrivacy) -> Permissions {
+    match kind {
+        VoiceChannelKind::Stage => privacy_permissions(kind, privacy) | STAGE_MODERATOR_PERMISSIONS,
+        VoiceChannelKind::Voice => privacy_permissions(kind, privacy),
+    }
+}
+
+/// The privacy level a voice channel's overwrites describe, read from what the `@everyone`
+/// overwrite denies.
+pub fn overwrite_privacy(
+    guild_id: Id<GuildMarker>,
+    permission_overwrites: &[ChannelPermissionOverwrite],
+) -> ChannelPrivacy {
+    let deny = permission_overwrites
+        .iter()
+        .find(|permission_overwrite| {
+            permission_overwrite
+                .kind
+                .eq(&ChannelPermissionOverwriteType::Role)
+                && permission_overwrite.id.eq(&guild_id.cast())
+        })
+        .map_or(Permissions::empty(), |permission_overwrite| {
+            permission_overwrite.deny
+        });
+
+    if deny.contains(Permissions::VIEW_CHANNEL) {
+        ChannelPrivacy::Invisible
+    } else if deny.intersects(Permissions::CONNECT | Permissions::REQUEST_TO_SPEAK) {
+        ChannelPrivacy::Locked
+    } else {
+        ChannelPrivacy::Unlocked
+    }
+}
+
+/// The permissions a privacy level withholds from everyone else: viewing the voice channel when
+/// it's invisible, and connecting to it (or, on a stage, asking to speak) when it's locked.
+pub fn privacy_permissions(kind: VoiceChannelKind, privacy: &ChannelPrivacy) -> Permissions {
+    match (privacy, kind) {
+        (ChannelPrivacy::Invisible, _) => Permissions::VIEW_CHANNEL,
+        (ChannelPrivacy::Locked, VoiceChannelKind::Stage) => Permissions::REQUEST_TO_SPEAK,
+        (ChannelPrivacy::Locked, VoiceChannelKind::Voice) => Permissions::CONNECT,
+        (ChannelPrivacy::Unlocked, _) => Permissions::empty(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use twilight_model::{
+        channel::permission_overwrite::{
+            PermissionOverwrite as ChannelPermissionOverwrite,
+            PermissionOverwriteType as ChannelPermissionOverwriteType,
+        },
+        guild::Permissions,
+        id::Id,
+    };
+
+    use super::overwrite_privacy;
+    use crate::structs::database::ChannelPrivacy;
+
+    #[test]
+    fn privacy_follows_the_everyone_overwrite() {
+        let guild_id = Id::new(1001);
+        let everyone = |deny| ChannelPermissionOverwrite {
+            allow: Permissions::empty(),
+            deny,
+            id: guild_id.cast(),
+            kind: ChannelPermissionOverwriteType::Role,
+        };
+
+        assert_eq!(overwrite_privacy(guild_id, &[]), ChannelPrivacy::Unlocked);
+        assert_eq!(
+            overwrite_privacy(guild_id, &[everyone(Permissions::CONNECT)]),
+            ChannelPrivacy::Locked
+        );
+        // A locked stage lets everyone listen, but not ask to speak.
+        assert_eq!(
+            overwrite_privacy(guild_id, &[everyone(Permissions::REQUEST_TO_SPEAK)]),
+            ChannelPrivacy::Locked
+        );
+        assert_eq!(
+            overwrite_privacy(
+                guild_id,
+                &[everyone(Permissions::VIEW_CHANNEL | Permissions::CONNECT)]
+            ),
+            ChannelPrivacy::Invisible
+        );
+        // A member overwrite with the guild's id isn't the `@everyone` overwrite.
+        assert_eq!(
+            overwrite_privacy(
+                guild_id,
+                &[ChannelPermissionOverwrite {
+                    kind: ChannelPermissionOverwriteType::Member,
+                    ..everyone(Permissions::CONNECT)
+                }]
+            ),
+            ChannelPrivacy::Unlocked
+        );
+    }
+}
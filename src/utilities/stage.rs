@@ -0,0 +1,32 @@
+use eyre::Result;
+use twilight_http::{
+    request::{Method, RequestBuilder},
+    response::marker::EmptyBody,
+    routing::Path,
+    Client,
+};
+use twilight_model::id::{
+    marker::{ChannelMarker, GuildMarker, UserMarker},
+    Id,
+};
+
+/// Invites a member of a stage's audience to speak. Twilight can only move members back to the
+/// audience, so the request (and its small JSON body) is built by hand.
+pub async fn invite_to_speak(
+    client: &Client,
+    guild_id: Id<GuildMarker>,
+    user_id: Id<UserMarker>,
+    channel_id: Id<ChannelMarker>,
+) -> Result<()> {
+    let request = RequestBuilder::raw(
+        Method::Patch,
+        Path::GuildsIdVoiceStates(guild_id.get()),
+        format!("guilds/{guild_id}/voice-states/{user_id}"),
+    )
+    .body(format!(r#"{{"channel_id":"{channel_id}","suppress":false}}"#).into_bytes())
+    .build()?;
+
+    client.request::<EmptyBody>(request).await?;
+
+    Ok(())
+}
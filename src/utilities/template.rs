@@ -0,0 +1,85 @@
+use super::constants::CHANNEL_NAME_LIMIT;
+
+/// The name a voice channel starts with when neither a preset nor the server names it.
+pub fn default_name(locale: &str, owner: &str) -> String {
+    match locale {
+        "de" => format!("Kanal von {owner}"),
+        "es-ES" | "pt-BR" => format!("Canal de {owner}"),
+        "fr" => format!("Salon de {owner}"),
+        _ if owner.ends_with("s") => format!("{owner}' voice"),
+        _ => format!("{owner}'s voice"),
+    }
+}
+
+/// Fills in a template set by a server, where `{owner}` stands for the owner's name.
+pub fn render(template: &str, owner: &str) -> String {
+    template.replace("{owner}", owner)
+}
+
+/// Fills in a name template, cut short where the owner's name would run past what Discord allows.
+pub fn render_name(template: &str, owner: &str) -> String {
+    render(template, owner)
+        .chars()
+        .take(CHANNEL_NAME_LIMIT)
+        .collect()
+}
+
+/// The name of the waiting room next to a voice channel, whose name is cut short to leave room for
+/// the suffix.
+pub fn waiting_room_name(locale: &str, name: &str) -> String {
+    let suffix = match locale {
+        "de" => " (Warteraum)",
+        "es-ES" | "pt-BR" => " (sala de espera)",
+        "fr" => " (salle d'attente)",
+        _ => " (waiting)",
+    };
+    let name = name
+        .chars()
+        .take(CHANNEL_NAME_LIMIT - suffix.chars().count())
+        .collect::<String>();
+
+    format!("{}{suffix}", name.trim_end())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{default_name, render, render_name, waiting_room_name};
+
+    #[test]
+    fn render_replaces_every_placeholder() {
+        assert_eq!(render("{owner} & {owner}", "alex"), "alex & alex");
+        assert_eq!(render("Study hall", "alex"), "Study hall");
+    }
+
+    #[test]
+    fn rendered_names_fit_discord() {
+        let name_template = format!("{} {{owner}}", "a".repeat(90));
+
+        assert_eq!(render_name(&name_template, "alex").chars().count(), 95);
+        assert_eq!(
+            render_name(&name_template, "alexandra_the_great")
+                .chars()
+                .count(),
+            100
+        );
+    }
+
+    #[test]
+    fn names_follow_the_locale() {
+        assert_eq!(default_name("en-US", "alex"), "alex's voice");
+        assert_eq!(default_name("en-GB", "james"), "james' voice");
+        assert_eq!(default_name("fr", "alex"), "Salon de alex");
+        assert_eq!(
+            waiting_room_name("de", "Kanal von alex"),
+            "Kanal von alex (Warteraum)"
+        );
+    }
+
+    #[test]
+    fn waiting_room_names_fit_discord() {
+        let name = waiting_room_name("en-US", &"a".repeat(100));
+
+        assert_eq!(name.chars().count(), 100);
+        assert!(name.ends_with("a (waiting)"));
+    }
+}
@@ -0,0 +1,28 @@
+/// Items joined as "a, b and c".
+pub fn list_text(items: impl IntoIterator<Item = String>) -> String {
+    let mut items = items.into_iter().collect::<Vec<String>>();
+    let Some(last_item) = items.pop() else {
+        return String::new();
+    };
+
+    if items.is_empty() {
+        return last_item;
+    }
+
+    format!("{} and {last_item}", items.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::list_text;
+
+    #[test]
+    fn lists_read_as_a_sentence() {
+        assert_eq!(list_text(Vec::new()), "");
+        assert_eq!(list_text(["a".to_owned()]), "a");
+        assert_eq!(
+            list_text(["a".to_owned(), "b".to_owned(), "c".to_owned()]),
+            "a, b and c"
+        );
+    }
+}
@@ -1,8 +1,13 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Milliseconds from the Unix epoch to the first second of 2015, where Discord's snowflakes start.
+const DISCORD_EPOCH: u64 = 1_420_070_400_000;
+
 pub fn humanize(mut seconds: u64) -> String {
     let hours = seconds / 3_600;
-    seconds = seconds % 3_600;
+    seconds %= 3_600;
     let minutes = seconds / 60;
-    seconds = seconds % 60;
+    seconds %= 60;
 
     let parts = vec![(hours, "h"), (minutes, "m"), (seconds, "s")];
     let duration: String = parts
@@ -16,3 +21,96 @@ pub fn humanize(mut seconds: u64) -> String {
 
     duration
 }
+
+/// The lowest snowflake Discord could hand out at `time`, so anything with a lower ID was
+/// created before then.
+pub fn snowflake_at(time: SystemTime) -> u64 {
+    let milliseconds = time
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_millis() as u64);
+
+    milliseconds.saturating_sub(DISCORD_EPOCH) << 22
+}
+
+/// Reads a duration typed as free text, such as "90", "90s", "2m", "1h 30m" or "2 minutes", into
+/// seconds. A bare number is read as seconds.
+pub fn parse(text: &str) -> Option<u64> {
+    let text = text.trim().to_ascii_lowercase();
+
+    if text.is_empty() {
+        return None;
+    }
+    if let Ok(seconds) = text.parse::<u64>() {
+        return Some(seconds);
+    }
+
+    let mut seconds = 0u64;
+    let mut characters = text.chars().filter(|c| !c.is_whitespace()).peekable();
+
+    while characters.peek().is_some() {
+        let mut value = String::new();
+        let mut unit = String::new();
+
+        while let Some(c) = characters.next_if(char::is_ascii_digit) {
+            value.push(c);
+        }
+        while let Some(c) = characters.next_if(char::is_ascii_alphabetic) {
+            unit.push(c);
+        }
+
+        let multiplier = match unit.as_str() {
+            "s" | "sec" | "secs" | "second" | "seconds" => 1,
+            "m" | "min" | "mins" | "minute" | "minutes" => 60,
+            "h" | "hr" | "hrs" | "hour" | "hours" => 3_600,
+            _ => return None,
+        };
+
+        seconds = seconds.checked_add(value.parse::<u64>().ok()?.checked_mul(multiplier)?)?;
+    }
+
+    Some(seconds)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use super::{parse, snowflake_at};
+
+    #[test]
+    fn parses_bare_numbers_as_seconds() {
+        assert_eq!(parse("90"), Some(90));
+        assert_eq!(parse(" 0 "), Some(0));
+    }
+
+    #[test]
+    fn parses_units() {
+        assert_eq!(parse("90s"), Some(90));
+        assert_eq!(parse("2m"), Some(120));
+        assert_eq!(parse("2 Minutes"), Some(120));
+        assert_eq!(parse("1h"), Some(3_600));
+        assert_eq!(parse("1h 30m"), Some(5_400));
+        assert_eq!(parse("1m30s"), Some(90));
+    }
+
+    #[test]
+    fn snowflakes_start_at_their_timestamp() {
+        // The example snowflake from Discord's documentation.
+        let snowflake = 175_928_847_299_117_063;
+        let time = UNIX_EPOCH + Duration::from_millis(1_462_015_105_796);
+
+        assert!(snowflake_at(time) <= snowflake);
+        assert!(snowflake < snowflake_at(time + Duration::from_millis(1)));
+        assert_eq!(snowflake_at(UNIX_EPOCH), 0);
+    }
+
+    #[test]
+    fn rejects_anything_else() {
+        assert_eq!(parse(""), None);
+        assert_eq!(parse("soon"), None);
+        assert_eq!(parse("m"), None);
+        assert_eq!(parse("5 days"), None);
+        assert_eq!(parse("-5s"), None);
+        assert_eq!(parse("99999999999999999999h"), None);
+    }
+}
@@ -0,0 +1,30 @@
+use std::collections::HashMap;
+
+use eyre::Result;
+use twilight_http::{
+    request::{Method, RequestBuilder},
+    response::marker::EmptyBody,
+    routing::Path,
+    Client,
+};
+use twilight_model::id::{marker::ChannelMarker, Id};
+
+/// Sets (or, given `None`, clears) the status shown under a voice channel. Twilight has no
+/// endpoint for this yet, so the request is built by hand.
+pub async fn set_voice_status(
+    client: &Client,
+    channel_id: Id<ChannelMarker>,
+    status: Option<&str>,
+) -> Result<()> {
+    let request = RequestBuilder::raw(
+        Method::Put,
+        Path::ChannelsId(channel_id.get()),
+        format!("channels/{channel_id}/voice-status"),
+    )
+    .json(&HashMap::from([("status", status)]))
+    .build()?;
+
+    client.request::<EmptyBody>(request).await?;
+
+    Ok(())
+}
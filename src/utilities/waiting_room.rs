@@ -0,0 +1,101 @@
+use eyre::Result;
+use twilight_model::{
+    channel::{
+        permission_overwrite::{
+            PermissionOverwrite as ChannelPermissionOverwrite,
+            PermissionOverwriteType as ChannelPermissionOverwriteType,
+        },
+        ChannelType,
+    },
+    guild::Permissions,
+    id::{marker::ChannelMarker, Id},
+};
+
+use super::template::waiting_room_name;
+use crate::structs::{
+    cache::{CachedGuild, CachedVoiceChannel},
+    context::Context,
+    database::ChannelPrivacy,
+};
+
+/// Creates a waiting room next to a locked voice channel, where members can wait to be let in.
+pub async fn create_waiting_room(
+    context: &Context,
+    guild: &CachedGuild,
+    parent_id: Option<Id<ChannelMarker>>,
+    voice_channel_id: Id<ChannelMarker>,
+    name: &str,
+) -> Result<()> {
+    let waiting_room_name = waiting_room_name(&guild.locale.read(), name);
+    let permission_overwrites = [ChannelPermissionOverwrite {
+        allow: Permissions::empty(),
+        deny: Permissions::SPEAK | Permissions::STREAM,
+        id: guild.id.cast(),
+        kind: ChannelPermissionOverwriteType::Role,
+    }];
+    let mut waiting_room_request = context
+        .client
+        .create_guild_channel(guild.id, &waiting_room_name)
+        .kind(ChannelType::GuildVoice)
+        .permission_overwrites(&permission_overwrites);
+
+    if let Some(parent_id) = parent_id {
+        waiting_room_request = waiting_room_request.parent_id(parent_id);
+    }
+
+    // Members may still `/voice knock` if the category has no room left for a waiting room.
+    let Ok(waiting_room_response) = waiting_room_request.await else {
+        return Ok(());
+    };
+    let Ok(waiting_room) = waiting_room_response.model().await else {
+        return Ok(());
+    };
+
+    context
+        .database
+        .update_voice_channel_waiting_room_id(voice_channel_id, Some(waiting_room.id))
+        .await?;
+    context
+        .cache
+        .insert_waiting_room(voice_channel_id, waiting_room.id);
+
+    Ok(())
+}
+
+/// Deletes the waiting room of a voice channel that's no longer locked. Members still waiting are
+/// moved in if it's been unlocked, rather than disconnected along with the waiting room.
+pub async fn delete_waiting_room(
+    context: &Context,
+    voice_channel: &CachedVoiceChannel,
+) -> Result<()> {
+    let Some(waiting_room_id) = *voice_channel.waiting_room_id.read() else {
+        return Ok(());
+    };
+
+    context.cache.remove_waiting_room(waiting_room_id);
+    context
+        .database
+        .update_voice_channel_waiting_room_id(voice_channel.id, None)
+        .await?;
+
+    if voice_channel.privacy.read().eq(&ChannelPrivacy::Unlocked) {
+        let waiting_user_ids = context
+            .cache
+            .channel_voice_states
+            .get(&waiting_room_id)
+            .map(|user_ids| user_ids.clone())
+            .unwrap_or_default();
+
+        for user_id in waiting_user_ids {
+            _ = context
+                .client
+                .update_guild_member(voice_channel.guild_id, user_id)
+                .channel_id(Some(voice_channel.id))
+                .await;
+        }
+    }
+
+    _ = context.client.delete_channel(waiting_room_id).await;
+
+    Ok(())
+}